@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -7,7 +8,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use pin_project::pin_project;
 use sha2::Digest;
-use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, Time};
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, NanoSeconds, Time};
 use sov_rollup_interface::maybestd::sync::Arc;
 use sov_rollup_interface::services::da::{DaService, SlotData};
 use tokio::sync::{broadcast, RwLock, RwLockWriteGuard};
@@ -70,6 +71,10 @@ pub struct MockDaService {
     finalized_header_sender: broadcast::Sender<MockBlockHeader>,
     wait_attempts: usize,
     planned_fork: Arc<Mutex<Option<PlannedFork>>>,
+    /// Seconds added to the time of the new blocks, see [`MockDaService::increase_time`].
+    time_offset: Arc<AtomicU64>,
+    /// Height above which blocks aren't finalized, see [`MockDaService::hold_finality`].
+    finality_hold: Arc<Mutex<Option<u64>>>,
 }
 
 impl MockDaService {
@@ -95,6 +100,8 @@ impl MockDaService {
             finalized_header_sender: tx,
             wait_attempts: 100_0000,
             planned_fork: Arc::new(Mutex::new(None)),
+            time_offset: Arc::new(AtomicU64::new(0)),
+            finality_hold: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -130,6 +137,82 @@ impl MockDaService {
         );
     }
 
+    /// Submits a blob in a new block, and returns the header of the block.
+    pub async fn submit_blob(&self, blob: &[u8]) -> anyhow::Result<MockBlockHeader> {
+        let mut blocks = self.blocks.write().await;
+        self.add_blob(blob, Default::default(), &mut blocks).await
+    }
+
+    /// Produces a new block without blobs, and returns its header.
+    pub async fn produce_empty_block(&self) -> anyhow::Result<MockBlockHeader> {
+        let mut blocks = self.blocks.write().await;
+        let empty_hash = hash_to_array(&[]);
+        self.add_block(vec![], empty_hash, empty_hash, &mut blocks)
+            .await
+    }
+
+    /// Moves the time of the new blocks forward by `seconds`, and returns the total offset
+    /// from the current time.
+    pub fn increase_time(&self, seconds: u64) -> u64 {
+        self.time_offset.fetch_add(seconds, Ordering::SeqCst) + seconds
+    }
+
+    /// The number of seconds added to the time of the new blocks.
+    pub fn time_offset(&self) -> u64 {
+        self.time_offset.load(Ordering::SeqCst)
+    }
+
+    /// Sets the number of seconds added to the time of the new blocks.
+    pub fn set_time_offset(&self, seconds: u64) {
+        self.time_offset.store(seconds, Ordering::SeqCst);
+    }
+
+    /// Keeps the blocks above `height` unfinalized, whatever the finality of the service,
+    /// so that they can be replaced with [`MockDaService::rewind_to`]. With `None`, the blocks
+    /// are finalized again, and the ones held so far are finalized at once.
+    pub async fn hold_finality(&self, height: Option<u64>) {
+        let blocks = self.blocks.write().await;
+        let finalized_before = self.get_last_finalized_height(&blocks).await;
+        *self.finality_hold.lock().unwrap() = height;
+        let finalized_after = self.get_last_finalized_height(&blocks).await;
+
+        for height in finalized_before + 1..=finalized_after {
+            let header = blocks[height as usize - 1].header().clone();
+            self.finalized_header_sender.send(header).unwrap();
+        }
+    }
+
+    /// Replaces the non finalized blocks above `height` with empty blocks, and returns the header
+    /// of the last one. One more block than the replaced ones is produced, so that a rollup which
+    /// has processed them notices the fork, and executes the empty blocks on the state it
+    /// had at `height`.
+    pub async fn rewind_to(&self, height: u64) -> anyhow::Result<MockBlockHeader> {
+        let mut blocks = self.blocks.write().await;
+        let last_finalized_height = self.get_last_finalized_height(&blocks).await;
+        if last_finalized_height > height {
+            anyhow::bail!(
+                "Cannot rewind to height {}, last finalized height is {}",
+                height,
+                last_finalized_height
+            );
+        }
+        let head_height = blocks.len() as u64;
+        if height > head_height {
+            anyhow::bail!("Cannot rewind to height {height}, the head is at height {head_height}");
+        }
+        blocks.retain(|b| b.header().height <= height);
+
+        let empty_hash = hash_to_array(&[]);
+        let mut header = None;
+        for _ in height..=head_height {
+            header = Some(
+                self.add_block(vec![], empty_hash, empty_hash, &mut blocks)
+                    .await?,
+            );
+        }
+        Ok(header.expect("At least one block is produced"))
+    }
+
     /// Rewrites existing non finalized blocks with given blocks
     /// New blobs will be added **after** specified height,
     /// meaning that first blob will be in the block of height + 1.
@@ -177,10 +260,18 @@ impl MockDaService {
         &self,
         blocks: &RwLockWriteGuard<'_, VecDeque<MockBlock>>,
     ) -> u64 {
-        blocks
-            .len()
+        self.finalized_height(blocks.len())
+    }
+
+    /// The height of the last finalized block, when `blocks_len` blocks have been produced.
+    fn finalized_height(&self, blocks_len: usize) -> u64 {
+        let height = blocks_len
             .checked_sub(self.blocks_to_finality as usize)
-            .unwrap_or_default() as u64
+            .unwrap_or_default() as u64;
+        match *self.finality_hold.lock().unwrap() {
+            Some(hold) => height.min(hold),
+            None => height,
+        }
     }
 
     async fn add_blob(
@@ -188,16 +279,9 @@ impl MockDaService {
         blob: &[u8],
         zkp_proof: Vec<u8>,
         blocks: &mut RwLockWriteGuard<'_, VecDeque<MockBlock>>,
-    ) -> anyhow::Result<u64> {
-        let (previous_block_hash, height) = match blocks.iter().last().map(|b| b.header().clone()) {
-            None => (GENESIS_HEADER.hash(), GENESIS_HEADER.height() + 1),
-            Some(block_header) => (block_header.hash(), block_header.height + 1),
-        };
-
+    ) -> anyhow::Result<MockBlockHeader> {
         let data_hash = hash_to_array(blob);
         let proof_hash = hash_to_array(&zkp_proof);
-        // Hash only from single blob
-        let block_hash = block_hash(height, data_hash, proof_hash, previous_block_hash.into());
 
         let blob = MockBlob::new_with_zkp_proof(
             blob.to_vec(),
@@ -205,29 +289,54 @@ impl MockDaService {
             self.sequencer_da_address,
             data_hash,
         );
+        self.add_block(vec![blob], data_hash, proof_hash, blocks)
+            .await
+    }
+
+    async fn add_block(
+        &self,
+        blobs: Vec<MockBlob>,
+        data_hash: [u8; 32],
+        proof_hash: [u8; 32],
+        blocks: &mut RwLockWriteGuard<'_, VecDeque<MockBlock>>,
+    ) -> anyhow::Result<MockBlockHeader> {
+        let (previous_block_hash, height) = match blocks.iter().last().map(|b| b.header().clone()) {
+            None => (GENESIS_HEADER.hash(), GENESIS_HEADER.height() + 1),
+            Some(block_header) => (block_header.hash(), block_header.height + 1),
+        };
+
+        // Hash only from single blob
+        let block_hash = block_hash(height, data_hash, proof_hash, previous_block_hash.into());
+
+        let now = Time::now();
+        let time_offset = self.time_offset.load(Ordering::SeqCst) as i64;
         let header = MockBlockHeader {
             prev_hash: previous_block_hash,
             hash: block_hash,
             height,
-            time: Time::now(),
+            time: Time::new(
+                now.secs() + time_offset,
+                NanoSeconds::new(now.subsec_nanos())
+                    .unwrap_or_else(|_| panic!("The current time has too many nanoseconds")),
+            ),
         };
         let block = MockBlock {
-            header,
+            header: header.clone(),
             validity_cond: Default::default(),
-            blobs: vec![blob],
+            blobs,
         };
         blocks.push_back(block);
 
         // Enough blocks to finalize block
-        if blocks.len() > self.blocks_to_finality as usize {
-            let next_index_to_finalize = blocks.len() - self.blocks_to_finality as usize - 1;
-            let next_finalized_header = blocks[next_index_to_finalize].header().clone();
+        let finalized_height = self.finalized_height(blocks.len());
+        if finalized_height > self.finalized_height(blocks.len() - 1) {
+            let next_finalized_header = blocks[finalized_height as usize - 1].header().clone();
             self.finalized_header_sender
                 .send(next_finalized_header)
                 .unwrap();
         }
 
-        Ok(height)
+        Ok(header)
     }
 
     /// Executes planned fork if it is planned at given height
@@ -315,14 +424,13 @@ impl DaService for MockDaService {
     async fn get_last_finalized_block_header(
         &self,
     ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
-        let blocks_len = { self.blocks.read().await.len() };
-        if blocks_len < self.blocks_to_finality as usize + 1 {
+        let blocks = self.blocks.read().await;
+        let finalized_height = self.finalized_height(blocks.len());
+        if finalized_height == 0 {
             return Ok(GENESIS_HEADER);
         }
 
-        let blocks = self.blocks.read().await;
-        let index = blocks_len - self.blocks_to_finality as usize - 1;
-        Ok(blocks[index].header().clone())
+        Ok(blocks[finalized_height as usize - 1].header().clone())
     }
 
     async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
@@ -346,7 +454,13 @@ impl DaService for MockDaService {
         &self,
         block: &Self::FilteredBlock,
     ) -> Vec<<Self::Spec as DaSpec>::BlobTransaction> {
-        block.blobs.clone()
+        // Aggregated proofs are not rollup transactions
+        block
+            .blobs
+            .iter()
+            .filter(|blob| blob.zk_proofs_data.is_empty())
+            .cloned()
+            .collect()
     }
 
     async fn get_extraction_proof(
@@ -361,15 +475,16 @@ impl DaService for MockDaService {
     }
 
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
-        let mut blocks = self.blocks.write().await;
-        let _ = self.add_blob(blob, Default::default(), &mut blocks).await?;
+        let _ = self.submit_blob(blob).await?;
         Ok(())
     }
 
     async fn send_aggregated_zk_proof(&self, proof: &[u8]) -> Result<u64, Self::Error> {
         let mut blocks = self.blocks.write().await;
-        self.add_blob(Default::default(), proof.to_vec(), &mut blocks)
-            .await
+        let header = self
+            .add_blob(Default::default(), proof.to_vec(), &mut blocks)
+            .await?;
+        Ok(header.height)
     }
 
     async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
@@ -627,6 +742,28 @@ mod tests {
         let proofs = da.get_aggregated_proofs_at(height).await?;
 
         assert_eq!(vec![aggregated_proof_data], proofs);
+
+        // The proof is not a rollup transaction
+        let block = da.get_block_at(height).await?;
+        assert!(da.extract_relevant_blobs(&block).is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_empty_blocks_and_time_offset() -> Result<(), anyhow::Error> {
+        let da = MockDaService::new(MockAddress::new([1; 32]));
+        let first = da.submit_blob(&[1, 2, 3]).await?;
+        let empty = da.produce_empty_block().await?;
+
+        assert_eq!(first.height() + 1, empty.height());
+        assert_eq!(first.hash(), empty.prev_hash());
+        let block = da.get_block_at(empty.height()).await?;
+        assert!(da.extract_relevant_blobs(&block).is_empty());
+
+        assert_eq!(3600, da.increase_time(3600));
+        assert_eq!(7200, da.increase_time(3600));
+        let later = da.produce_empty_block().await?;
+        assert!(later.time().secs() >= empty.time().secs() + 7200);
         Ok(())
     }
 
@@ -794,6 +931,39 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_held_blocks_are_rewound() {
+        let mut da = MockDaService::new(MockAddress::new([1; 32]));
+        da.wait_attempts = 2;
+
+        da.send_transaction(&[1, 1, 1, 1]).await.unwrap();
+        da.hold_finality(Some(1)).await;
+        da.send_transaction(&[2, 2, 2, 2]).await.unwrap();
+        da.send_transaction(&[3, 3, 3, 3]).await.unwrap();
+        let block_1 = da.get_block_at(1).await.unwrap();
+        let finalized_header = da.get_last_finalized_block_header().await.unwrap();
+        assert_eq!(block_1.header(), &finalized_header);
+
+        // The held blocks are replaced with one more empty block.
+        let head = da.rewind_to(1).await.unwrap();
+        assert_eq!(4, head.height());
+        let block_2 = da.get_block_at(2).await.unwrap();
+        assert!(da.extract_relevant_blobs(&block_2).is_empty());
+        assert_consecutive_blocks(&block_1, &block_2);
+        let block_3 = da.get_block_at(3).await.unwrap();
+        assert_consecutive_blocks(&block_2, &block_3);
+
+        let result = da.rewind_to(0).await;
+        assert_eq!(
+            "Cannot rewind to height 0, last finalized height is 1",
+            result.unwrap_err().to_string()
+        );
+
+        // Once released, the held blocks are finalized.
+        da.hold_finality(None).await;
+        assert_eq!(head, da.get_last_finalized_block_header().await.unwrap());
+    }
+
     fn assert_consecutive_blocks(block1: &MockBlock, block2: &MockBlock) {
         assert_eq!(block2.header().prev_hash(), block1.header().hash())
     }
@@ -8,8 +8,11 @@ use std::sync::{Arc, Condvar, Mutex};
 use anyhow::ensure;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::da::BlockHeaderTrait;
-use sov_rollup_interface::zk::{Matches, StateTransitionData, ValidityCondition};
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::zk::{
+    AggregatedStateTransition, ExecutionStats, Matches, Proof, ProofWithStats, StateTransition,
+    StateTransitionData, ValidityCondition,
+};
 
 /// A mock commitment to a particular zkVM program.
 #[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
@@ -92,12 +95,26 @@ impl Notifier {
     }
 }
 
+/// A guest program which the [`MockZkvm`] runs natively.
+pub type MockGuestProgram = Arc<dyn Fn(MockZkGuest) -> anyhow::Result<()> + Send + Sync>;
+
 /// A mock implementing the zkVM trait.
+///
+/// By default, the mock doesn't run any guest program: its proofs expose the first hint
+/// provided by the host instead, and their output is derived from it. A mock created with
+/// [`MockZkvm::with_program`] runs the given program over all the hints, and its proofs
+/// expose the outputs committed by the program.
+///
+/// The proofs are [`MockProof`]s for the code commitment of the mock
+/// (see [`MockZkvm::with_code_commitment`]), so the [`MockZkGuest`] only verifies them against
+/// this commitment.
 #[derive(Clone)]
 pub struct MockZkvm<ValidityCond> {
     worker_thread_notifier: Notifier,
     committed_data: VecDeque<Vec<u8>>,
     validity_condition: ValidityCond,
+    program: Option<MockGuestProgram>,
+    code_commitment: MockCodeCommitment,
 }
 
 impl<ValidityCond> MockZkvm<ValidityCond> {
@@ -107,6 +124,22 @@ impl<ValidityCond> MockZkvm<ValidityCond> {
             worker_thread_notifier: Default::default(),
             committed_data: Default::default(),
             validity_condition,
+            program: None,
+            code_commitment: MockCodeCommitment([0; 32]),
+        }
+    }
+
+    /// Sets the commitment to the program proven by the mock, which is `[0; 32]` by default.
+    pub fn with_code_commitment(mut self, code_commitment: MockCodeCommitment) -> Self {
+        self.code_commitment = code_commitment;
+        self
+    }
+
+    /// Creates a new MockZkvm which runs the `program` to generate proofs.
+    pub fn with_program(validity_condition: ValidityCond, program: MockGuestProgram) -> Self {
+        Self {
+            program: Some(program),
+            ..Self::new(validity_condition)
         }
     }
 
@@ -126,13 +159,7 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::Zkvm for MockZkv
         serialized_proof: &'a [u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<&'a [u8], Self::Error> {
-        let proof = MockProof::decode(serialized_proof)?;
-        anyhow::ensure!(
-            proof.program_id.matches(code_commitment),
-            "Proof failed to verify against requested code commitment"
-        );
-        anyhow::ensure!(proof.is_valid, "Proof is not valid");
-        Ok(proof.log)
+        verify_mock_proof(serialized_proof, code_commitment)
     }
 
     fn verify_and_extract_output<
@@ -145,6 +172,17 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::Zkvm for MockZkv
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(bincode::deserialize(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
 }
 
 impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
@@ -154,23 +192,40 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
 
     fn add_hint<T: Serialize>(&mut self, item: T) {
         let hint = bincode::serialize(&item).unwrap();
-        let proof_info = ProofInfo {
-            hint,
-            validity_condition: self.validity_condition,
-        };
-
-        let data = bincode::serialize(&proof_info).unwrap();
-        self.committed_data.push_back(data)
+        self.committed_data.push_back(hint)
     }
 
     fn simulate_with_hints(&mut self) -> Self::Guest {
-        MockZkGuest {}
+        MockZkGuest::new(std::mem::take(&mut self.committed_data))
     }
 
-    fn run(&mut self, _with_proof: bool) -> Result<sov_rollup_interface::zk::Proof, anyhow::Error> {
+    fn run(&mut self, _with_proof: bool) -> Result<ProofWithStats, anyhow::Error> {
         self.worker_thread_notifier.wait();
-        let data = self.committed_data.pop_front().unwrap_or_default();
-        Ok(sov_rollup_interface::zk::Proof::PublicInput(data))
+        let start = std::time::Instant::now();
+        let public_input = match &self.program {
+            Some(program) => {
+                let guest = MockZkGuest::new(std::mem::take(&mut self.committed_data));
+                program(guest.clone())?;
+                MockPublicInput::Output(guest.committed_output())
+            }
+            None => MockPublicInput::Hint(ProofInfo {
+                hint: self.committed_data.pop_front().unwrap_or_default(),
+                validity_condition: self.validity_condition,
+            }),
+        };
+        let proof = MockProof {
+            program_id: self.code_commitment.clone(),
+            is_valid: true,
+            log: &bincode::serialize(&public_input)?,
+        };
+        // The mock doesn't execute cycles.
+        Ok(ProofWithStats {
+            proof: Proof::PublicInput(proof.encode_to_vec()),
+            stats: ExecutionStats {
+                wall_time: start.elapsed(),
+                ..Default::default()
+            },
+        })
     }
 
     fn extract_output<
@@ -179,27 +234,41 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
     >(
         proof: &sov_rollup_interface::zk::Proof,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
-        match proof {
-            sov_rollup_interface::zk::Proof::PublicInput(pub_input) => {
-                let data: ProofInfo<Da::ValidityCondition> = bincode::deserialize(pub_input)?;
-                let st: StateTransitionData<Root, (), Da> = bincode::deserialize(&data.hint)?;
-
-                Ok(sov_rollup_interface::zk::StateTransition {
-                    initial_state_root: st.initial_state_root,
-                    final_state_root: st.final_state_root,
-                    slot_hash: st.da_block_header.hash(),
-                    validity_condition: data.validity_condition,
-                })
-            }
-            sov_rollup_interface::zk::Proof::Full(_) => {
-                panic!("Mock DA doesn't generate real proofs")
-            }
-        }
+        extract_state_transition(public_input(proof)?)
+    }
+
+    fn extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        proof: &sov_rollup_interface::zk::Proof,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        extract_committed_output(public_input(proof)?)
     }
 }
 
-/// A mock implementing the Guest.
-pub struct MockZkGuest {}
+/// A mock implementing the Guest. It reads the hints provided by the host, and records
+/// the outputs committed by the program.
+#[derive(Clone, Default)]
+pub struct MockZkGuest {
+    hints: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    output: Arc<Mutex<Vec<u8>>>,
+}
+
+impl MockZkGuest {
+    /// Creates a guest which reads the given serialized hints, in order.
+    pub fn new(hints: VecDeque<Vec<u8>>) -> Self {
+        Self {
+            hints: Arc::new(Mutex::new(hints)),
+            output: Default::default(),
+        }
+    }
+
+    /// Returns the serialized outputs committed so far.
+    pub fn committed_output(&self) -> Vec<u8> {
+        self.output.lock().unwrap().clone()
+    }
+}
 
 impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     type CodeCommitment = MockCodeCommitment;
@@ -207,30 +276,103 @@ impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     type Error = anyhow::Error;
 
     fn verify<'a>(
-        _serialized_proof: &'a [u8],
-        _code_commitment: &Self::CodeCommitment,
+        serialized_proof: &'a [u8],
+        code_commitment: &Self::CodeCommitment,
     ) -> Result<&'a [u8], Self::Error> {
-        unimplemented!()
+        verify_mock_proof(serialized_proof, code_commitment)
     }
 
+    /// Verifies a proof generated by the [`MockZkvm`] against the code commitment of the mock,
+    /// and extracts its output.
     fn verify_and_extract_output<
         Da: sov_rollup_interface::da::DaSpec,
         Root: Serialize + serde::de::DeserializeOwned,
     >(
-        _serialized_proof: &[u8],
-        _code_commitment: &Self::CodeCommitment,
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
-        unimplemented!()
+        extract_state_transition(Self::verify(serialized_proof, code_commitment)?)
+    }
+
+    /// Verifies a proof generated by a [`MockZkvm`] running an aggregation program, and extracts
+    /// its output.
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        extract_committed_output(Self::verify(serialized_proof, code_commitment)?)
     }
 }
 
 impl sov_rollup_interface::zk::ZkvmGuest for MockZkGuest {
     fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
-        unimplemented!()
+        let hint = self
+            .hints
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("The host provided no more hints");
+        bincode::deserialize(&hint).expect("Failed to deserialize the hint")
+    }
+
+    fn commit<T: Serialize>(&self, item: &T) {
+        let mut output = self.output.lock().unwrap();
+        bincode::serialize_into(&mut *output, item).unwrap();
+    }
+}
+
+fn verify_mock_proof<'a>(
+    serialized_proof: &'a [u8],
+    code_commitment: &MockCodeCommitment,
+) -> Result<&'a [u8], anyhow::Error> {
+    let proof = MockProof::decode(serialized_proof)?;
+    anyhow::ensure!(
+        proof.program_id.matches(code_commitment),
+        "Proof failed to verify against requested code commitment"
+    );
+    anyhow::ensure!(proof.is_valid, "Proof is not valid");
+    Ok(proof.log)
+}
+
+// Returns the public input of a proof generated by the `MockZkvm`, without verifying it.
+fn public_input(proof: &Proof) -> Result<&[u8], anyhow::Error> {
+    match proof {
+        Proof::PublicInput(proof) => Ok(MockProof::decode(proof)?.log),
+        Proof::Full(_) => panic!("Mock DA doesn't generate real proofs"),
     }
+}
+
+// Returns the output committed by the program which generated a proof with the given
+// public input.
+fn extract_committed_output<T: serde::de::DeserializeOwned>(
+    public_input: &[u8],
+) -> Result<T, anyhow::Error> {
+    // The validity condition is only used by the proofs which don't run a program.
+    match bincode::deserialize::<MockPublicInput<()>>(public_input) {
+        Ok(MockPublicInput::Output(output)) => Ok(bincode::deserialize(&output)?),
+        _ => Err(anyhow::anyhow!("The proof wasn't generated by a program")),
+    }
+}
 
-    fn commit<T: Serialize>(&self, _item: &T) {
-        unimplemented!()
+fn extract_state_transition<Da: DaSpec, Root: Serialize + serde::de::DeserializeOwned>(
+    public_input: &[u8],
+) -> Result<StateTransition<Da, Root>, anyhow::Error> {
+    let public_input: MockPublicInput<Da::ValidityCondition> = bincode::deserialize(public_input)?;
+    match public_input {
+        MockPublicInput::Hint(data) => {
+            let st: StateTransitionData<Root, (), Da> = bincode::deserialize(&data.hint)?;
+
+            Ok(StateTransition {
+                initial_state_root: st.initial_state_root,
+                final_state_root: st.final_state_root,
+                slot_hash: st.da_block_header.hash(),
+                validity_condition: data.validity_condition,
+            })
+        }
+        MockPublicInput::Output(output) => Ok(bincode::deserialize(&output)?),
     }
 }
 
@@ -240,6 +382,15 @@ struct ProofInfo<ValidityCond> {
     validity_condition: ValidityCond,
 }
 
+// The public input of the proofs generated by the `MockZkvm`.
+#[derive(Debug, Serialize, Deserialize)]
+enum MockPublicInput<ValidityCond> {
+    // The first hint, when the mock doesn't run a program.
+    Hint(ProofInfo<ValidityCond>),
+    // The outputs committed by the program.
+    Output(Vec<u8>),
+}
+
 #[test]
 fn test_mock_proof_round_trip() {
     let proof = MockProof {
@@ -133,4 +133,18 @@ impl Zkvm for Risc0Guest {
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
         todo!()
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Self::CodeCommitment, Da, Root>,
+        Self::Error,
+    > {
+        // The aggregation program verifies proofs in the guest: issue #633
+        todo!()
+    }
 }
@@ -1,9 +1,16 @@
 //! This module implements the [`ZkvmHost`] trait for the RISC0 VM.
 
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
 use risc0_zkvm::{ExecutorEnvBuilder, ExecutorImpl, InnerReceipt, Journal, Receipt, Session};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use sov_rollup_interface::zk::{Proof, Zkvm, ZkvmHost};
+use sov_rollup_interface::zk::{
+    AggregatedStateTransition, ExecutionStats, FunctionCycles, Proof, ProofWithStats, Zkvm,
+    ZkvmHost,
+};
 
 use crate::guest::Risc0Guest;
 use crate::Risc0MethodId;
@@ -16,20 +23,37 @@ pub struct Risc0Host<'a> {
     elf: &'a [u8],
 }
 
+/// The cycles spent in the functions annotated with the `cycle_tracker` macro during a run.
+type CyclesByFunction = Arc<Mutex<BTreeMap<String, FunctionCycles>>>;
+
 #[cfg(not(feature = "bench"))]
 #[inline(always)]
-fn add_benchmarking_callbacks(env: ExecutorEnvBuilder<'_>) -> ExecutorEnvBuilder<'_> {
+fn add_benchmarking_callbacks<'a>(
+    env: ExecutorEnvBuilder<'a>,
+    _cycles_by_function: &CyclesByFunction,
+) -> ExecutorEnvBuilder<'a> {
     env
 }
 
 #[cfg(feature = "bench")]
-fn add_benchmarking_callbacks(mut env: ExecutorEnvBuilder<'_>) -> ExecutorEnvBuilder<'_> {
+fn add_benchmarking_callbacks<'a>(
+    mut env: ExecutorEnvBuilder<'a>,
+    cycles_by_function: &CyclesByFunction,
+) -> ExecutorEnvBuilder<'a> {
     use sov_zk_cycle_utils::{cycle_count_callback, get_syscall_name, get_syscall_name_cycles};
 
-    use crate::metrics::metrics_callback;
+    use crate::metrics::record_metric;
 
     let metrics_syscall_name = get_syscall_name();
-    env.io_callback(metrics_syscall_name, metrics_callback);
+    let cycles_by_function = cycles_by_function.clone();
+    env.io_callback(metrics_syscall_name, move |input| {
+        let (function, cycles) = record_metric(input)?;
+        let mut cycles_by_function = cycles_by_function.lock().expect("Lock was poisoned");
+        let function_cycles = cycles_by_function.entry(function).or_default();
+        function_cycles.calls += 1;
+        function_cycles.cycles += cycles;
+        Ok(risc0_zkvm::Bytes::new())
+    });
 
     let cycles_syscall_name = get_syscall_name_cycles();
     env.io_callback(cycles_syscall_name, cycle_count_callback);
@@ -49,13 +73,18 @@ impl<'a> Risc0Host<'a> {
     /// Run a computation in the zkVM without generating a receipt.
     /// This creates the "Session" trace without invoking the heavy cryptographic machinery.
     pub fn run_without_proving(&mut self) -> anyhow::Result<Session> {
-        let env = add_benchmarking_callbacks(ExecutorEnvBuilder::default())
+        self.execute(&Default::default())
+    }
+
+    fn execute(&mut self, cycles_by_function: &CyclesByFunction) -> anyhow::Result<Session> {
+        let env = add_benchmarking_callbacks(ExecutorEnvBuilder::default(), cycles_by_function)
             .write_slice(&self.env)
             .build()
             .unwrap();
         let mut executor = ExecutorImpl::from_elf(env, self.elf)?;
         Ok(executor.run()?)
     }
+
     /// Run a computation in the zkvm and generate a receipt.
     pub fn run(&mut self) -> anyhow::Result<Receipt> {
         let session = self.run_without_proving()?;
@@ -85,16 +114,29 @@ impl<'a> ZkvmHost for Risc0Host<'a> {
         Risc0Guest::with_hints(std::mem::take(&mut self.env))
     }
 
-    fn run(&mut self, with_proof: bool) -> Result<Proof, anyhow::Error> {
-        if with_proof {
-            let receipt = self.run()?;
-            let data = bincode::serialize(&receipt)?;
-            Ok(Proof::Full(data))
+    fn run(&mut self, with_proof: bool) -> Result<ProofWithStats, anyhow::Error> {
+        let start = Instant::now();
+        let cycles_by_function = CyclesByFunction::default();
+        let session = self.execute(&cycles_by_function)?;
+
+        let proof = if with_proof {
+            let receipt = session.prove()?;
+            Proof::Full(bincode::serialize(&receipt)?)
         } else {
-            let session = self.run_without_proving()?;
-            let data = bincode::serialize(&session.journal)?;
-            Ok(Proof::PublicInput(data))
-        }
+            Proof::PublicInput(bincode::serialize(&session.journal)?)
+        };
+
+        let cycles_by_function =
+            std::mem::take(&mut *cycles_by_function.lock().expect("Lock was poisoned"));
+        Ok(ProofWithStats {
+            proof,
+            stats: ExecutionStats {
+                cycles: session.get_cycles()?,
+                segments: session.segments.len() as u64,
+                wall_time: start.elapsed(),
+                cycles_by_function,
+            },
+        })
     }
 
     fn extract_output<Da: sov_rollup_interface::da::DaSpec, Root: Serialize + DeserializeOwned>(
@@ -111,6 +153,24 @@ impl<'a> ZkvmHost for Risc0Host<'a> {
             }
         }
     }
+
+    fn extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        proof: &Proof,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        match proof {
+            Proof::PublicInput(journal) => {
+                let journal: Journal = bincode::deserialize(journal)?;
+                Ok(journal.decode()?)
+            }
+            Proof::Full(data) => {
+                let receipt: Receipt = bincode::deserialize(data)?;
+                Ok(receipt.journal.decode()?)
+            }
+        }
+    }
 }
 
 impl<'host> Zkvm for Risc0Host<'host> {
@@ -135,6 +195,17 @@ impl<'host> Zkvm for Risc0Host<'host> {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 /// A verifier for Risc0 proofs.
@@ -162,6 +233,17 @@ impl Zkvm for Risc0Verifier {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 fn verify_from_slice<'a>(
@@ -19,6 +19,13 @@ pub mod metrics;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Risc0MethodId([u32; 8]);
 
+impl Risc0MethodId {
+    /// Creates a method ID from the image ID of a Risc0 binary, as generated by `risc0-build`.
+    pub fn new(id: [u32; 8]) -> Self {
+        Self(id)
+    }
+}
+
 impl Matches<Self> for Risc0MethodId {
     fn matches(&self, other: &Self) -> bool {
         self.0 == other.0
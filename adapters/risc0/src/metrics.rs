@@ -43,7 +43,13 @@ fn deserialize_custom(serialized: Bytes) -> Result<(String, u64), anyhow::Error>
 /// in the Risc0 VM and invoked whenever a function annotated with the [`sov-zk-cycle-utils::cycle_tracker`]
 /// macro is invoked.
 pub fn metrics_callback(input: Bytes) -> Result<Bytes, anyhow::Error> {
-    let met_tuple = deserialize_custom(input)?;
-    add_value(met_tuple.0, met_tuple.1);
+    record_metric(input)?;
     Ok(Bytes::new())
 }
+
+/// Adds the metric sent by the Risc0 zkvm to the global map, and returns its name and value.
+pub fn record_metric(input: Bytes) -> Result<(String, u64), anyhow::Error> {
+    let (metric, value) = deserialize_custom(input)?;
+    add_value(metric.clone(), value);
+    Ok((metric, value))
+}
@@ -9,7 +9,11 @@ fn main() {
 
         let elf = r#"
             pub const ROLLUP_ELF: &[u8] = &[];
+            pub const ROLLUP_ID: [u32; 8] = [0; 8];
             pub const MOCK_DA_ELF: &[u8] = &[];
+            pub const MOCK_DA_ID: [u32; 8] = [0; 8];
+            pub const MOCK_DA_COMPRESSED_ELF: &[u8] = &[];
+            pub const MOCK_DA_COMPRESSED_ID: [u32; 8] = [0; 8];
         "#;
 
         std::fs::write(methods_path, elf).expect("Failed to write mock rollup elf");
@@ -19,20 +23,31 @@ fn main() {
     }
 }
 
-#[cfg(not(feature = "bench"))]
 fn get_guest_options() -> HashMap<&'static str, risc0_build::GuestOptions> {
-    HashMap::new()
-}
+    // Both guests must verify the same witness format the native prover produces.
+    let mut common_features = Vec::new();
+    if cfg!(feature = "multiproof") {
+        common_features.push("multiproof".to_string());
+    }
+    let mut mock_features = common_features.clone();
+    if cfg!(feature = "bench") {
+        mock_features.push("bench".to_string());
+    }
 
-#[cfg(feature = "bench")]
-fn get_guest_options() -> HashMap<&'static str, risc0_build::GuestOptions> {
     let mut guest_pkg_to_options = HashMap::new();
-    guest_pkg_to_options.insert(
-        "sov-demo-prover-guest-mock",
-        risc0_build::GuestOptions {
-            features: vec!["bench".to_string()],
-            ..Default::default()
-        },
-    );
+    for (guest_pkg, features) in [
+        ("sov-demo-prover-guest-mock", mock_features),
+        ("sov-demo-prover-guest-celestia", common_features),
+    ] {
+        if !features.is_empty() {
+            guest_pkg_to_options.insert(
+                guest_pkg,
+                risc0_build::GuestOptions {
+                    features,
+                    ..Default::default()
+                },
+            );
+        }
+    }
     guest_pkg_to_options
 }
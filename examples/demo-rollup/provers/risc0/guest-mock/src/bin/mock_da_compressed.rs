@@ -0,0 +1,27 @@
+#![no_main]
+//! The mock DA guest of a rollup running with the [`ZkCompressedContext`],
+//! whose native prover produces a compressed witness.
+use demo_stf::runtime::Runtime;
+use demo_stf::StfVerifier;
+use sov_mock_da::MockDaVerifier;
+use sov_modules_api::default_context::ZkCompressedContext;
+use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
+use sov_modules_stf_blueprint::StfBlueprint;
+use sov_risc0_adapter::guest::Risc0Guest;
+use sov_state::ZkStorage;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let guest = Risc0Guest::new();
+    let storage = ZkStorage::new();
+
+    let stf: StfBlueprint<ZkCompressedContext, _, _, Runtime<_, _>, BasicKernel<_, _>> =
+        StfBlueprint::new();
+
+    let stf_verifier = StfVerifier::new(stf, MockDaVerifier {});
+
+    stf_verifier
+        .run_block(guest, storage)
+        .expect("Prover must be honest");
+}
@@ -0,0 +1,104 @@
+use anyhow::Context as _;
+use clap::Parser;
+use sov_demo_rollup::{initialize_logging, CelestiaDemoRollup, MockDemoRollup};
+use sov_light_client::LightClient;
+use sov_mock_da::MockDaConfig;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::Spec;
+use sov_modules_rollup_blueprint::RollupBlueprint;
+use sov_risc0_adapter::host::Risc0Verifier;
+use sov_risc0_adapter::Risc0MethodId;
+use sov_rollup_interface::services::da::DaService;
+use sov_state::Storage;
+use sov_stf_runner::{from_toml_path, RollupConfig};
+use tracing::info;
+
+type StateRoot = <<DefaultContext as Spec>::Storage as Storage>::Root;
+
+/// Light client for the demo rollup. Follows the rollup by verifying the proofs posted to the
+/// DA layer, and serves balances verified against the latest proven state root.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The data layer type.
+    #[arg(long, default_value = "mock")]
+    da_layer: SupportedDaLayer,
+
+    /// The path to the rollup config.
+    #[arg(long, default_value = "mock_rollup_config.toml")]
+    rollup_config_path: String,
+
+    /// The JSON encoded method ID of the guest program proving the rollup,
+    /// e.g. `[1,2,3,4,5,6,7,8]`.
+    #[arg(long)]
+    code_commitment: String,
+
+    /// The RPC address of the full node which serves the state proofs.
+    /// Defaults to the RPC address in the rollup config.
+    #[arg(long)]
+    node_url: Option<String>,
+
+    /// The address on which the light client serves RPC.
+    #[arg(long, default_value = "127.0.0.1:12346")]
+    rpc_address: String,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum SupportedDaLayer {
+    Celestia,
+    Mock,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    initialize_logging();
+
+    let args = Args::parse();
+    let rollup_config_path = args.rollup_config_path.as_str();
+
+    match args.da_layer {
+        SupportedDaLayer::Mock => {
+            let rollup_config: RollupConfig<MockDaConfig> = from_toml_path(rollup_config_path)
+                .context("Failed to read rollup configuration")?;
+            let da_service = MockDemoRollup::default().create_da_service(&rollup_config).await;
+            run_light_client(da_service, &rollup_config, &args).await
+        }
+        SupportedDaLayer::Celestia => {
+            let rollup_config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+                from_toml_path(rollup_config_path)
+                    .context("Failed to read rollup configuration")?;
+            let da_service = CelestiaDemoRollup {}
+                .create_da_service(&rollup_config)
+                .await;
+            run_light_client(da_service, &rollup_config, &args).await
+        }
+    }
+}
+
+async fn run_light_client<Da: DaService, DaConfig>(
+    da_service: Da,
+    rollup_config: &RollupConfig<DaConfig>,
+    args: &Args,
+) -> Result<(), anyhow::Error> {
+    let code_commitment: Risc0MethodId =
+        serde_json::from_str(&args.code_commitment).context("Invalid code commitment")?;
+    let node_url = args.node_url.clone().unwrap_or_else(|| {
+        let rpc_config = &rollup_config.runner.rpc_config;
+        format!("http://{}:{}", rpc_config.bind_host, rpc_config.bind_port)
+    });
+
+    let light_client = LightClient::<_, Risc0Verifier, StateRoot>::new(
+        da_service,
+        code_commitment,
+        rollup_config.runner.start_height,
+        None,
+    );
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build(&args.rpc_address)
+        .await?;
+    info!("Serving the light client RPC on {}", server.local_addr()?);
+    let _server_handle = server.start(light_client.rpc_methods::<DefaultContext>(&node_url)?);
+
+    light_client.run().await
+}
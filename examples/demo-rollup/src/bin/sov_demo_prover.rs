@@ -0,0 +1,93 @@
+use anyhow::Context as _;
+use clap::Parser;
+use sov_demo_rollup::{initialize_logging, CelestiaDemoRollup, MockDemoRollup};
+use sov_mock_da::MockDaConfig;
+use sov_modules_rollup_blueprint::RollupBlueprint;
+use sov_stf_runner::{from_toml_path, RollupConfig, RollupProverConfig};
+
+/// Standalone prover for the demo rollup. Proves the slots executed by a `sov-demo-rollup`
+/// node started with `remote_prover = true`, and posts the proofs to the DA layer. The proofs are
+/// submitted to the node with the `remote_prover_token` of the rollup config.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The data layer type.
+    #[arg(long, default_value = "mock")]
+    da_layer: SupportedDaLayer,
+
+    /// The path to the rollup config.
+    #[arg(long, default_value = "mock_rollup_config.toml")]
+    rollup_config_path: String,
+
+    /// The RPC address of the full node. Defaults to the RPC address in the rollup config.
+    #[arg(long)]
+    node_url: Option<String>,
+
+    /// How the slots are proven. The node verifies the proofs against the method ID of the
+    /// guest, so only the proofs generated with `prove` are accepted.
+    #[arg(long, default_value = "prove")]
+    prover_config: SupportedProverConfig,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum SupportedDaLayer {
+    Celestia,
+    Mock,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum SupportedProverConfig {
+    Execute,
+    Prove,
+}
+
+impl From<SupportedProverConfig> for RollupProverConfig {
+    fn from(config: SupportedProverConfig) -> Self {
+        match config {
+            SupportedProverConfig::Execute => RollupProverConfig::Execute,
+            SupportedProverConfig::Prove => RollupProverConfig::Prove,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    initialize_logging();
+
+    let args = Args::parse();
+    let rollup_config_path = args.rollup_config_path.as_str();
+    let prover_config = args.prover_config.into();
+
+    match args.da_layer {
+        SupportedDaLayer::Mock => {
+            let rollup_config: RollupConfig<MockDaConfig> = from_toml_path(rollup_config_path)
+                .context("Failed to read rollup configuration")?;
+            let node_url = node_url(args.node_url, &rollup_config);
+            MockDemoRollup::default()
+                .create_remote_prover(&node_url, &rollup_config, prover_config)
+                .await?
+                .run()
+                .await;
+        }
+        SupportedDaLayer::Celestia => {
+            let rollup_config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+                from_toml_path(rollup_config_path)
+                    .context("Failed to read rollup configuration")?;
+            let node_url = node_url(args.node_url, &rollup_config);
+            CelestiaDemoRollup {}
+                .create_remote_prover(&node_url, &rollup_config, prover_config)
+                .await?
+                .run()
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+fn node_url<DaConfig>(node_url: Option<String>, rollup_config: &RollupConfig<DaConfig>) -> String {
+    node_url.unwrap_or_else(|| {
+        let rpc_config = &rollup_config.runner.rpc_config;
+        format!("http://{}:{}", rpc_config.bind_host, rpc_config.bind_port)
+    })
+}
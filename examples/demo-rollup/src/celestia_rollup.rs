@@ -10,6 +10,7 @@ use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
 use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
+use sov_risc0_adapter::Risc0MethodId;
 use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
 use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
@@ -71,6 +72,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
         crate::eth::register_ethereum::<Self::DaService>(
             da_service.clone(),
             storage.clone(),
+            ledger_db,
             &mut rpc_methods,
         )?;
 
@@ -91,11 +93,15 @@ impl RollupBlueprint for CelestiaDemoRollup {
         .await
     }
 
+    fn code_commitment(&self) -> Risc0MethodId {
+        Risc0MethodId::new(risc0::ROLLUP_ID)
+    }
+
     async fn create_prover_service(
         &self,
         prover_config: RollupProverConfig,
         rollup_config: &RollupConfig<Self::DaConfig>,
-        _da_service: &Self::DaService,
+        da_service: &Self::DaService,
     ) -> Self::ProverService {
         let vm = Risc0Host::new(risc0::ROLLUP_ELF);
         let zk_stf = StfBlueprint::new();
@@ -109,6 +115,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
             vm,
             zk_stf,
             da_verifier,
+            da_service.clone(),
             prover_config,
             zk_storage,
             rollup_config.prover_service,
@@ -1,12 +1,16 @@
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Context as _;
 use sov_cli::wallet_state::PrivateKeyAndAddress;
+use sov_db::ledger_db::LedgerDB;
 use sov_ethereum::experimental::EthRpcConfig;
-use sov_ethereum::GasPriceOracleConfig;
+use sov_ethereum::{Ethereum, GasPriceOracleConfig, TxPoolConfig};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::{PrivateKey, Spec};
 use sov_prover_storage_manager::SnapshotManager;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
 use sov_rollup_interface::services::da::DaService;
 use sov_state::ProverStorage;
 
@@ -23,12 +27,31 @@ fn read_sov_tx_signer_priv_key() -> Result<DefaultPrivateKey, anyhow::Error> {
     Ok(key_and_address.private_key)
 }
 
+/// The address of the rollup transaction signer, which sends the dev calls of the `evm_*` endpoints.
+#[cfg(feature = "dev")]
+pub(crate) fn sov_tx_signer_address() -> Result<<DefaultContext as Spec>::Address, anyhow::Error> {
+    Ok(read_sov_tx_signer_priv_key()?.to_address())
+}
+
 // register ethereum methods.
 pub(crate) fn register_ethereum<Da: DaService>(
     da_service: Da,
     storage: ProverStorage<sov_state::DefaultStorageSpec, SnapshotManager>,
+    ledger_db: &LedgerDB,
     methods: &mut jsonrpsee::RpcModule<()>,
 ) -> Result<(), anyhow::Error> {
+    let ethereum_rpc = ethereum_rpc(da_service, storage, ledger_db)?;
+    methods
+        .merge(ethereum_rpc)
+        .context("Failed to merge Ethereum RPC modules")
+}
+
+// create the ethereum methods, to be extended before being registered.
+pub(crate) fn ethereum_rpc<Da: DaService>(
+    da_service: Da,
+    storage: ProverStorage<sov_state::DefaultStorageSpec, SnapshotManager>,
+    ledger_db: &LedgerDB,
+) -> Result<jsonrpsee::RpcModule<Ethereum<DefaultContext, Da>>, anyhow::Error> {
     let eth_rpc_config = {
         let eth_signer = eth_dev_signer();
         EthRpcConfig::<DefaultContext> {
@@ -36,14 +59,17 @@ pub(crate) fn register_ethereum<Da: DaService>(
             sov_tx_signer_priv_key: read_sov_tx_signer_priv_key()?,
             eth_signer,
             gas_price_oracle_config: GasPriceOracleConfig::default(),
+            txpool_config: TxPoolConfig::default(),
         }
     };
 
-    let ethereum_rpc =
-        sov_ethereum::get_ethereum_rpc::<DefaultContext, Da>(da_service, eth_rpc_config, storage);
-    methods
-        .merge(ethereum_rpc)
-        .context("Failed to merge Ethereum RPC modules")
+    Ok(sov_ethereum::get_ethereum_rpc::<DefaultContext, Da>(
+        da_service,
+        eth_rpc_config,
+        storage,
+        Arc::new(ledger_db.clone()),
+        ledger_db.subscribe_slots()?,
+    ))
 }
 
 // TODO: #840
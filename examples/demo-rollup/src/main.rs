@@ -1,12 +1,18 @@
+use std::path::PathBuf;
+
 use anyhow::Context as _;
 use clap::Parser;
 use demo_stf::genesis_config::GenesisPaths;
+use sov_db::schema::types::JmtValue;
 use sov_demo_rollup::{initialize_logging, CelestiaDemoRollup, MockDemoRollup};
 use sov_mock_da::MockDaConfig;
-use sov_modules_rollup_blueprint::{Rollup, RollupBlueprint};
+use sov_modules_rollup_blueprint::{
+    replay_slots, ReplayReport, Rollup, RollupBlueprint, RootMismatch,
+};
 use sov_modules_stf_blueprint::kernels::basic::{
     BasicKernelGenesisConfig, BasicKernelGenesisPaths,
 };
+use sov_state::DefaultStorageSpec;
 use sov_stf_runner::{from_toml_path, RollupConfig, RollupProverConfig};
 use tracing::log::debug;
 
@@ -27,6 +33,32 @@ struct Args {
     /// The path to the rollup config.
     #[arg(long, default_value = "mock_rollup_config.toml")]
     rollup_config_path: String,
+
+    /// Serves the `evm_*` developer endpoints, to control the state of a local rollup, and sets
+    /// the rollup transaction signer as the dev authority of the EVM genesis.
+    /// Only available with the mock data layer and the `dev` feature.
+    #[arg(long)]
+    dev_rpc: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Re-executes a range of executed slots on a scratch copy of the state, and reports the first
+    /// slot whose state root differs from the recorded one. The rollup must be stopped.
+    Replay {
+        /// The number of the first slot to replay.
+        #[arg(long)]
+        from: u64,
+        /// The number of the last slot to replay.
+        #[arg(long)]
+        to: u64,
+        /// The directory where the copy of the state is made. It must not exist yet.
+        #[arg(long)]
+        scratch_path: PathBuf,
+    },
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -42,6 +74,47 @@ async fn main() -> Result<(), anyhow::Error> {
     let args = Args::parse();
     let rollup_config_path = args.rollup_config_path.as_str();
 
+    if let Some(Command::Replay {
+        from,
+        to,
+        scratch_path,
+    }) = args.command
+    {
+        let report = match args.da_layer {
+            SupportedDaLayer::Mock => {
+                let rollup_config: RollupConfig<MockDaConfig> = from_toml_path(rollup_config_path)
+                    .context("Failed to read rollup configuration")?;
+                replay_slots::<_, DefaultStorageSpec>(
+                    &MockDemoRollup::default(),
+                    &rollup_config,
+                    from..=to,
+                    &scratch_path,
+                )
+                .await?
+            }
+            SupportedDaLayer::Celestia => {
+                let rollup_config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+                    from_toml_path(rollup_config_path)
+                        .context("Failed to read rollup configuration")?;
+                replay_slots::<_, DefaultStorageSpec>(
+                    &CelestiaDemoRollup {},
+                    &rollup_config,
+                    from..=to,
+                    &scratch_path,
+                )
+                .await?
+            }
+        };
+        return print_replay_report(&report);
+    }
+
+    if args.dev_rpc && !matches!(args.da_layer, SupportedDaLayer::Mock) {
+        anyhow::bail!("The developer endpoints are only available with the mock data layer");
+    }
+    if args.dev_rpc && !cfg!(feature = "dev") {
+        anyhow::bail!("The developer endpoints need the `dev` feature");
+    }
+
     match args.da_layer {
         SupportedDaLayer::Mock => {
             let rollup = new_rollup_with_mock_da(
@@ -51,6 +124,7 @@ async fn main() -> Result<(), anyhow::Error> {
                 },
                 rollup_config_path,
                 RollupProverConfig::Execute,
+                args.dev_rpc,
             )
             .await?;
             rollup.run().await
@@ -107,6 +181,7 @@ async fn new_rollup_with_mock_da(
     kernel_genesis_paths: &BasicKernelGenesisPaths,
     rollup_config_path: &str,
     prover_config: RollupProverConfig,
+    dev_rpc: bool,
 ) -> Result<Rollup<MockDemoRollup>, anyhow::Error> {
     debug!("Starting mock rollup with config {}", rollup_config_path);
 
@@ -120,7 +195,7 @@ async fn new_rollup_with_mock_da(
         )?,
     };
 
-    let mock_rollup = MockDemoRollup {};
+    let mock_rollup = MockDemoRollup { dev_rpc };
     mock_rollup
         .create_new_rollup(
             rt_genesis_paths,
@@ -130,3 +205,50 @@ async fn new_rollup_with_mock_da(
         )
         .await
 }
+
+fn print_replay_report(report: &ReplayReport) -> Result<(), anyhow::Error> {
+    for (slot_number, source) in &report.matching_slots {
+        println!("Slot {slot_number} matches the recorded state (block from {source:?})");
+    }
+    let Some(divergence) = &report.divergence else {
+        println!("No divergence found");
+        return Ok(());
+    };
+
+    println!(
+        "Slot {} at DA height {} diverges: replayed state root 0x{}",
+        divergence.slot_number,
+        divergence.da_height,
+        hex::encode(&divergence.replayed_root)
+    );
+    for mismatch in &divergence.mismatches {
+        match mismatch {
+            RootMismatch::State { recorded } => {
+                println!("  node state root: 0x{}", hex::encode(recorded))
+            }
+            RootMismatch::Ledger { recorded_slot } => {
+                println!("  ledger slot of the replayed root: {recorded_slot:?}")
+            }
+            RootMismatch::ChainState { recorded } => {
+                println!("  chain state transition root: 0x{}", hex::encode(recorded))
+            }
+        }
+    }
+    for write in &divergence.writes {
+        println!(
+            "  key 0x{}: replayed {}, recorded {}",
+            hex::encode(&write.key),
+            format_write(&write.replayed),
+            format_write(&write.recorded)
+        );
+    }
+    anyhow::bail!("Slot {} diverges", divergence.slot_number)
+}
+
+fn format_write(value: &Option<JmtValue>) -> String {
+    match value {
+        None => "nothing".to_string(),
+        Some(None) => "a deletion".to_string(),
+        Some(Some(value)) => format!("0x{}", hex::encode(value)),
+    }
+}
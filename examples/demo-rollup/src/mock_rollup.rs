@@ -4,18 +4,26 @@ use demo_stf::runtime::Runtime;
 use sov_db::ledger_db::LedgerDB;
 use sov_mock_da::{MockDaConfig, MockDaService, MockDaSpec};
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
+use sov_modules_api::runtime::capabilities::Kernel;
 use sov_modules_api::{Address, Spec};
 use sov_modules_rollup_blueprint::RollupBlueprint;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
-use sov_modules_stf_blueprint::StfBlueprint;
+use sov_modules_stf_blueprint::{GenesisParams, Runtime as RuntimeTrait, StfBlueprint};
 use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
+use sov_risc0_adapter::Risc0MethodId;
 use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
 use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
 
 /// Rollup with MockDa
-pub struct MockDemoRollup {}
+#[derive(Default)]
+pub struct MockDemoRollup {
+    /// Whether to serve the `evm_*` developer endpoints, which need the `dev` feature.
+    /// The rollup transaction signer of the RPC is then set as the dev authority of the EVM
+    /// genesis, and the RPC reads the state of the blocks which aren't finalized yet.
+    pub dev_rpc: bool,
+}
 
 #[async_trait]
 impl RollupBlueprint for MockDemoRollup {
@@ -49,6 +57,37 @@ impl RollupBlueprint for MockDemoRollup {
         >,
     >;
 
+    fn create_genesis_config(
+        &self,
+        rt_genesis_paths: &<Self::NativeRuntime as RuntimeTrait<
+            Self::NativeContext,
+            Self::DaSpec,
+        >>::GenesisPaths,
+        kernel_genesis: <Self::NativeKernel as Kernel<Self::NativeContext, Self::DaSpec>>::GenesisConfig,
+        _rollup_config: &RollupConfig<Self::DaConfig>,
+    ) -> anyhow::Result<
+        GenesisParams<
+            <Self::NativeRuntime as RuntimeTrait<Self::NativeContext, Self::DaSpec>>::GenesisConfig,
+            <Self::NativeKernel as Kernel<Self::NativeContext, Self::DaSpec>>::GenesisConfig,
+        >,
+    > {
+        #[allow(unused_mut)]
+        let mut rt_genesis = <Self::NativeRuntime as RuntimeTrait<
+            Self::NativeContext,
+            Self::DaSpec,
+        >>::genesis_config(rt_genesis_paths)?;
+
+        #[cfg(feature = "dev")]
+        if self.dev_rpc {
+            rt_genesis.evm.dev_authority = Some(crate::eth::sov_tx_signer_address()?);
+        }
+
+        Ok(GenesisParams {
+            runtime: rt_genesis,
+            kernel: kernel_genesis,
+        })
+    }
+
     fn create_rpc_methods(
         &self,
         storage: &<Self::NativeContext as Spec>::Storage,
@@ -66,11 +105,19 @@ impl RollupBlueprint for MockDemoRollup {
         >(storage, ledger_db, da_service, sequencer)?;
 
         #[cfg(feature = "experimental")]
-        crate::eth::register_ethereum::<Self::DaService>(
-            da_service.clone(),
-            storage.clone(),
-            &mut rpc_methods,
-        )?;
+        {
+            #[allow(unused_mut)]
+            let mut ethereum_rpc = crate::eth::ethereum_rpc::<Self::DaService>(
+                da_service.clone(),
+                storage.clone(),
+                ledger_db,
+            )?;
+            #[cfg(feature = "dev")]
+            if self.dev_rpc {
+                sov_ethereum::experimental::dev::register_dev_rpc_methods(&mut ethereum_rpc)?;
+            }
+            rpc_methods.merge(ethereum_rpc)?;
+        }
 
         Ok(rpc_methods)
     }
@@ -82,11 +129,15 @@ impl RollupBlueprint for MockDemoRollup {
         MockDaService::new(rollup_config.da.sender_address)
     }
 
+    fn code_commitment(&self) -> Risc0MethodId {
+        Risc0MethodId::new(risc0::MOCK_DA_ID)
+    }
+
     async fn create_prover_service(
         &self,
         prover_config: RollupProverConfig,
         rollup_config: &RollupConfig<Self::DaConfig>,
-        _da_service: &Self::DaService,
+        da_service: &Self::DaService,
     ) -> Self::ProverService {
         let vm = Risc0Host::new(risc0::MOCK_DA_ELF);
         let zk_stf = StfBlueprint::new();
@@ -97,6 +148,7 @@ impl RollupBlueprint for MockDemoRollup {
             vm,
             zk_stf,
             da_verifier,
+            da_service.clone(),
             prover_config,
             zk_storage,
             rollup_config.prover_service,
@@ -110,6 +162,12 @@ impl RollupBlueprint for MockDemoRollup {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
         };
-        ProverStorageManager::new(storage_config)
+        let storage_manager = ProverStorageManager::new(storage_config)?;
+        // The developer endpoints keep blocks unfinalized to revert them.
+        Ok(if self.dev_rpc {
+            storage_manager.with_unfinalized_reads()
+        } else {
+            storage_manager
+        })
     }
 }
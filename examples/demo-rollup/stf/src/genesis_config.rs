@@ -109,7 +109,7 @@ fn create_genesis_config<C: Context, Da: DaSpec>(
     let nft_config: NonFungibleTokenConfig = read_json_file(&genesis_paths.nft_path)?;
 
     #[cfg(feature = "experimental")]
-    let evm_config: EvmConfig = read_json_file(&genesis_paths.evm_genesis_path)?;
+    let evm_config: EvmConfig<C> = read_json_file(&genesis_paths.evm_genesis_path)?;
 
     Ok(GenesisConfig::new(
         bank_config,
@@ -58,7 +58,16 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         // Before executing each batch, check that the sender is registered as a sequencer
-        self.sequencer_registry.begin_blob_hook(blob, working_set)
+        self.sequencer_registry.begin_blob_hook(blob, working_set)?;
+
+        // The sequencer of the batch receives the priority fees of its EVM transactions
+        #[cfg(feature = "experimental")]
+        self.evm.begin_blob_hook(
+            self.sequencer_registry
+                .get_sequencer_rollup_address(&blob.sender(), working_set),
+            working_set,
+        );
+        Ok(())
     }
 
     fn end_blob_hook(
@@ -66,6 +75,9 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
         result: Self::BlobResult,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
+        #[cfg(feature = "experimental")]
+        self.evm.end_blob_hook(working_set);
+
         match result {
             SequencerOutcome::Rewarded(_reward) => {
                 // TODO: Process reward here or above.
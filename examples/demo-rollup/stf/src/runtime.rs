@@ -90,8 +90,11 @@ where
     type GenesisPaths = GenesisPaths;
 
     #[cfg(feature = "native")]
-    fn rpc_methods(storage: <C as Spec>::Storage) -> jsonrpsee::RpcModule<()> {
-        get_rpc_methods::<C, Da>(storage)
+    fn rpc_methods(
+        storage: <C as Spec>::Storage,
+        state_versions: std::sync::Arc<dyn sov_modules_api::rpc::StateVersionProvider>,
+    ) -> jsonrpsee::RpcModule<()> {
+        get_rpc_methods::<C, Da>(storage, state_versions)
     }
 
     #[cfg(feature = "native")]
@@ -0,0 +1,84 @@
+use sov_data_generators::new_test_blob_from_batch;
+use sov_mock_da::{MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
+use sov_modules_api::default_context::{CompressedContext, ZkCompressedContext};
+use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
+use sov_modules_stf_blueprint::{Batch, SequencerOutcome, StfBlueprint};
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::{CompressedStorageSpec, CompressedWitness, ZkStorage};
+
+use crate::runtime::Runtime;
+use crate::tests::da_simulation::simulate_da;
+use crate::tests::stf_tests::read_private_key;
+use crate::tests::{get_genesis_config_for_context, Da};
+
+type Vm = sov_mock_zkvm::MockZkvm<<Da as sov_modules_api::DaSpec>::ValidityCondition>;
+
+#[test]
+fn test_compressed_witness_is_verified_in_zk() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let config = sov_state::config::Config {
+        path: tempdir.path().to_path_buf(),
+    };
+    let mut storage_manager =
+        ProverStorageManager::<MockDaSpec, CompressedStorageSpec>::new(config).unwrap();
+
+    let native_stf: StfBlueprint<
+        CompressedContext,
+        Da,
+        Vm,
+        Runtime<CompressedContext, Da>,
+        BasicKernel<CompressedContext, Da>,
+    > = StfBlueprint::new();
+
+    let genesis_block = MockBlock::default();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = native_stf.init_chain(storage, get_genesis_config_for_context());
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    let block_1 = genesis_block.next_mock();
+    let txs = simulate_da(read_private_key::<CompressedContext>().private_key);
+    let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let native_result = native_stf.apply_slot(
+        &genesis_root,
+        storage,
+        Default::default(),
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut [blob.clone()],
+    );
+    assert_eq!(
+        SequencerOutcome::Rewarded(0),
+        native_result.batch_receipts[0].inner
+    );
+
+    // The guest receives the witness in its serialized, compressed form.
+    let witness: CompressedWitness =
+        bincode::deserialize(&bincode::serialize(&native_result.witness).unwrap()).unwrap();
+
+    let zk_stf: StfBlueprint<
+        ZkCompressedContext,
+        Da,
+        Vm,
+        Runtime<ZkCompressedContext, Da>,
+        BasicKernel<ZkCompressedContext, Da>,
+    > = StfBlueprint::new();
+    let zk_result = zk_stf.apply_slot(
+        &genesis_root,
+        ZkStorage::new(),
+        witness,
+        &block_1.header,
+        &block_1.validity_cond,
+        &mut [blob],
+    );
+
+    assert_eq!(native_result.state_root, zk_result.state_root);
+}
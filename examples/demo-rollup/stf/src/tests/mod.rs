@@ -2,7 +2,7 @@ use std::path::Path;
 
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
-use sov_modules_api::DaSpec;
+use sov_modules_api::{Context, DaSpec};
 use sov_modules_stf_blueprint::kernels::basic::{BasicKernel, BasicKernelGenesisConfig};
 use sov_modules_stf_blueprint::{GenesisParams, StfBlueprint};
 use sov_prover_storage_manager::ProverStorageManager;
@@ -12,6 +12,7 @@ use sov_stf_runner::read_json_file;
 use crate::genesis_config::{get_genesis_config, GenesisPaths};
 use crate::runtime::{GenesisConfig, Runtime};
 
+mod compressed_witness_tests;
 mod da_simulation;
 mod stf_tests;
 mod tx_revert_tests;
@@ -39,10 +40,14 @@ pub(crate) fn create_storage_manager_for_tests(
 pub(crate) fn get_genesis_config_for_tests<Da: DaSpec>(
 ) -> GenesisParams<GenesisConfig<DefaultContext, Da>, BasicKernelGenesisConfig<DefaultContext, Da>>
 {
+    get_genesis_config_for_context::<DefaultContext, Da>()
+}
+
+pub(crate) fn get_genesis_config_for_context<C: Context, Da: DaSpec>(
+) -> GenesisParams<GenesisConfig<C, Da>, BasicKernelGenesisConfig<C, Da>> {
     let integ_test_conf_dir: &Path = "../../test-data/genesis/integration-tests".as_ref();
     let rt_params =
-        get_genesis_config::<DefaultContext, Da>(&GenesisPaths::from_dir(integ_test_conf_dir))
-            .unwrap();
+        get_genesis_config::<C, Da>(&GenesisPaths::from_dir(integ_test_conf_dir)).unwrap();
 
     let chain_state = read_json_file(integ_test_conf_dir.join("chain_state.json")).unwrap();
     let kernel_params = BasicKernelGenesisConfig { chain_state };
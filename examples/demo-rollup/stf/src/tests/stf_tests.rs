@@ -274,7 +274,7 @@ fn test_sequencer_unknown_sequencer() {
     assert!(!has_tx_events(&apply_blob_outcome));
 }
 
-fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
+pub(crate) fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
     let token_deployer_data =
         std::fs::read_to_string("../../test-data/keys/token_deployer_private_key.json")
             .expect("Unable to read file to string");
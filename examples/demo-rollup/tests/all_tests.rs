@@ -2,4 +2,5 @@
 mod bank;
 #[cfg(feature = "experimental")]
 mod evm;
+mod replay;
 mod test_helpers;
@@ -31,6 +31,7 @@ async fn bank_tx_tests() -> Result<(), anyhow::Error> {
                 chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
             },
             RollupProverConfig::Execute,
+            false,
         )
         .await;
     });
@@ -98,6 +99,7 @@ async fn send_test_create_token_tx(rpc_address: SocketAddr) -> Result<(), anyhow
         client.http(),
         user_address,
         token_address,
+        None,
     )
     .await?;
     assert_eq!(balance_response.amount.unwrap_or_default(), 1000);
@@ -28,6 +28,7 @@ async fn evm_tx_tests() -> Result<(), anyhow::Error> {
                 chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
             },
             RollupProverConfig::Skip,
+            false,
         )
         .await;
     });
@@ -39,6 +40,145 @@ async fn evm_tx_tests() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[cfg(feature = "dev")]
+#[tokio::test]
+async fn evm_dev_rpc_tests() -> Result<(), anyhow::Error> {
+    let (port_tx, port_rx) = tokio::sync::oneshot::channel();
+
+    let rollup_task = tokio::spawn(async {
+        start_rollup(
+            port_tx,
+            GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+            BasicKernelGenesisPaths {
+                chain_state: "../test-data/genesis/integration-tests/chain_state.json".into(),
+            },
+            RollupProverConfig::Skip,
+            true,
+        )
+        .await;
+    });
+
+    let port = port_rx.await.unwrap();
+    dev_rpc_test(port).await.unwrap();
+    rollup_task.abort();
+    Ok(())
+}
+
+#[cfg(feature = "dev")]
+async fn dev_rpc_test(rpc_address: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
+    use ethers_core::types::transaction::eip2718::TypedTransaction;
+    use ethers_core::types::{Eip1559TransactionRequest, H256, U256};
+    use jsonrpsee::rpc_params;
+
+    let chain_id: u64 = 1;
+    let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+        .parse::<LocalWallet>()
+        .unwrap()
+        .with_chain_id(chain_id);
+    let from_addr = Address::from_str("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266").unwrap();
+    let client = TestClient::new(
+        chain_id,
+        key,
+        from_addr,
+        SimpleStorageContract::default(),
+        rpc_address,
+    )
+    .await;
+
+    // An account without key, a contract and a recipient.
+    let account = Address::repeat_byte(0xaa);
+    let contract = Address::repeat_byte(0xcc);
+    let recipient = Address::repeat_byte(0x11);
+    let balance = U256::exp10(18);
+
+    let set: bool = client
+        .dev_request("evm_setBalance", rpc_params![account, balance])
+        .await?;
+    assert!(set);
+    assert_eq!(client.eth_get_balance(account).await, balance);
+
+    // PUSH1 0x2a PUSH1 0x00 SSTORE STOP
+    let code = vec![0x60, 0x2a, 0x60, 0x00, 0x55, 0x00];
+    let _: bool = client
+        .dev_request(
+            "evm_setCode",
+            rpc_params![contract, ethers_core::types::Bytes::from(code.clone())],
+        )
+        .await?;
+    assert_eq!(client.eth_get_code(contract).await.to_vec(), code);
+
+    let _: bool = client
+        .dev_request(
+            "evm_setStorageAt",
+            rpc_params![contract, U256::from(1), H256::from_low_u64_be(42)],
+        )
+        .await?;
+    assert_eq!(
+        client.eth_get_storage_at(contract, U256::from(1)).await,
+        U256::from(42)
+    );
+
+    // Mining produces a block, and increasing the time moves the following ones forward.
+    let before = client.eth_get_block_by_number(None).await;
+    let _: String = client.dev_request("evm_mine", rpc_params![]).await?;
+    let mined = client.eth_get_block_by_number(None).await;
+    assert_eq!(
+        mined.number.unwrap().as_u64(),
+        before.number.unwrap().as_u64() + 1
+    );
+    let offset: u64 = client
+        .dev_request("evm_increaseTime", rpc_params![3600u64])
+        .await?;
+    assert_eq!(offset, 3600);
+    let later = client.eth_get_block_by_number(None).await;
+    assert!(later.timestamp >= mined.timestamp + U256::from(3600));
+
+    // An impersonated account sends transactions without its key.
+    let impersonated: bool = client
+        .dev_request("evm_impersonateAccount", rpc_params![account])
+        .await?;
+    assert!(impersonated);
+    let transfer = Eip1559TransactionRequest::new()
+        .from(account)
+        .to(recipient)
+        .value(1000u64)
+        .chain_id(chain_id)
+        .max_priority_fee_per_gas(10u64)
+        .max_fee_per_gas(100000001u64);
+    let pending = client
+        .eth_send_transaction(TypedTransaction::Eip1559(transfer))
+        .await;
+    let _: u64 = client.dev_request("sov_mineSlot", rpc_params![]).await?;
+    let receipt = pending.await?.unwrap();
+    assert_eq!(receipt.from, account);
+    assert_eq!(client.eth_get_balance(recipient).await, U256::from(1000));
+
+    let stopped: bool = client
+        .dev_request("evm_stopImpersonatingAccount", rpc_params![account])
+        .await?;
+    assert!(stopped);
+
+    // A reverted snapshot restores the state it was taken at.
+    let balance = client.eth_get_balance(account).await;
+    let snapshot: U256 = client.dev_request("evm_snapshot", rpc_params![]).await?;
+    let _: bool = client
+        .dev_request("evm_setBalance", rpc_params![account, U256::from(7)])
+        .await?;
+    assert_eq!(client.eth_get_balance(account).await, U256::from(7));
+    let reverted: bool = client
+        .dev_request("evm_revert", rpc_params![snapshot])
+        .await?;
+    assert!(reverted);
+    assert_eq!(client.eth_get_balance(account).await, balance);
+    // A snapshot is dropped once reverted.
+    let reverted: bool = client
+        .dev_request("evm_revert", rpc_params![snapshot])
+        .await?;
+    assert!(!reverted);
+
+    Ok(())
+}
+
 async fn send_tx_test_to_eth(rpc_address: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
     let chain_id: u64 = 1;
     let key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
@@ -138,6 +278,14 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
     assert_eq!(latest_block.transactions.len(), 1);
     assert_eq!(latest_block.transactions[0].hash, tx_hash);
 
+    // The transaction is re-executed on top of the state of the first block
+    let call_frame = client.debug_trace_transaction_calls(tx_hash).await;
+    assert_eq!(
+        call_frame["to"],
+        serde_json::json!(format!("{:?}", contract_address))
+    );
+    assert_eq!(call_frame["type"], "CALL");
+
     // This should just pass without error
     client
         .set_value_call(contract_address, set_arg)
@@ -225,6 +225,15 @@ impl TestClient {
         Ok(ethereum_types::U256::from(resp_array))
     }
 
+    #[cfg(feature = "dev")]
+    pub(crate) async fn dev_request<R: serde::de::DeserializeOwned>(
+        &self,
+        method: &str,
+        params: jsonrpsee::core::params::ArrayParams,
+    ) -> Result<R, jsonrpsee::core::Error> {
+        self.http_client.request(method, params).await
+    }
+
     pub(crate) async fn eth_accounts(&self) -> Vec<Address> {
         self.http_client
             .request("eth_accounts", rpc_params![])
@@ -326,6 +335,16 @@ impl TestClient {
             .map_err(|e| e.into())
     }
 
+    pub(crate) async fn debug_trace_transaction_calls(&self, tx_hash: TxHash) -> serde_json::Value {
+        self.http_client
+            .request(
+                "debug_traceTransaction",
+                rpc_params![tx_hash, serde_json::json!({ "tracer": "callTracer" })],
+            )
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_estimate_gas(
         &self,
         tx: TypedTransaction,
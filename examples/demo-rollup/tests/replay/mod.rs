@@ -0,0 +1,176 @@
+use std::path::Path;
+use std::time::Duration;
+
+use demo_stf::genesis_config::GenesisPaths;
+use demo_stf::runtime::Runtime;
+use sov_db::ledger_db::LedgerDB;
+use sov_demo_rollup::MockDemoRollup;
+use sov_mock_da::{MockAddress, MockDaConfig, MockDaService, MockDaSpec};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::Spec;
+use sov_modules_rollup_blueprint::{replay_slots, BlockSource, RollupBlueprint, RootMismatch};
+use sov_modules_stf_blueprint::kernels::basic::{BasicKernel, BasicKernelGenesisConfig};
+use sov_modules_stf_blueprint::StfBlueprint;
+use sov_risc0_adapter::host::Risc0Host;
+use sov_rollup_interface::da::Time;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::StateTransitionData;
+use sov_state::{DefaultStorageSpec, Storage};
+use sov_stf_runner::{
+    InitVariant, ProverServiceConfig, RollupConfig, RollupProverConfig, RpcConfig, RunnerConfig,
+    StateTransitionRunner, StorageConfig,
+};
+
+type Root = <<DefaultContext as Spec>::Storage as Storage>::Root;
+type Witness = <<DefaultContext as Spec>::Storage as Storage>::Witness;
+
+/// The number of slots executed by the node before the replay.
+const EXECUTED_SLOTS: u64 = 4;
+
+#[tokio::test]
+async fn test_replay_matches_executed_slots() -> Result<(), anyhow::Error> {
+    let storage_dir = tempfile::tempdir()?;
+    let rollup_config = execute_slots(storage_dir.path()).await?;
+
+    // The transition of the last executed slot isn't recorded in the chain state yet.
+    let report = replay_slots::<_, DefaultStorageSpec>(
+        &MockDemoRollup::default(),
+        &rollup_config,
+        1..=EXECUTED_SLOTS - 1,
+        &storage_dir.path().join("replay"),
+    )
+    .await?;
+
+    assert_eq!(None, report.divergence);
+    assert_eq!(
+        vec![
+            (1, BlockSource::Ledger),
+            (2, BlockSource::Ledger),
+            (3, BlockSource::Ledger),
+        ],
+        report.matching_slots
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replay_detects_tampered_state_root() -> Result<(), anyhow::Error> {
+    let storage_dir = tempfile::tempdir()?;
+    let rollup_config = execute_slots(storage_dir.path()).await?;
+
+    // The block stored for slot 2 no longer leads to the state root recorded by the node.
+    let ledger_db = LedgerDB::with_path(storage_dir.path())?;
+    let (da_height, block_hash, job) = ledger_db.get_next_proving_job(2)?.unwrap();
+    let mut transition_data: StateTransitionData<Root, Witness, MockDaSpec> =
+        bincode::deserialize(&job)?;
+    let header = &mut transition_data.da_block_header;
+    header.time = Time::from_secs(header.time.secs() + 1);
+    ledger_db.put_proving_job(da_height, block_hash, bincode::serialize(&transition_data)?)?;
+    drop(ledger_db);
+
+    let report = replay_slots::<_, DefaultStorageSpec>(
+        &MockDemoRollup::default(),
+        &rollup_config,
+        1..=EXECUTED_SLOTS - 1,
+        &storage_dir.path().join("replay"),
+    )
+    .await?;
+
+    assert_eq!(vec![(1, BlockSource::Ledger)], report.matching_slots);
+    let divergence = report.divergence.unwrap();
+    assert_eq!(2, divergence.slot_number);
+    assert_eq!(2, divergence.da_height);
+    assert!(divergence
+        .mismatches
+        .iter()
+        .any(|mismatch| matches!(mismatch, RootMismatch::State { .. })));
+    assert!(divergence
+        .mismatches
+        .iter()
+        .any(|mismatch| matches!(mismatch, RootMismatch::ChainState { .. })));
+    assert!(!divergence.writes.is_empty());
+
+    Ok(())
+}
+
+// Executes `EXECUTED_SLOTS` mock DA slots with a node storing its data in `path`, and stops it.
+// The node leaves the proving to a remote prover, so the state transitions of the slots stay in
+// its ledger.
+async fn execute_slots(path: &Path) -> Result<RollupConfig<MockDaConfig>, anyhow::Error> {
+    let rollup_config = RollupConfig {
+        storage: StorageConfig {
+            path: path.to_path_buf(),
+        },
+        runner: RunnerConfig {
+            start_height: 1,
+            rpc_config: RpcConfig {
+                bind_host: "127.0.0.1".into(),
+                bind_port: 0,
+            },
+            max_proving_backlog: 64,
+            remote_prover: true,
+            remote_prover_token: None,
+        },
+        da: MockDaConfig {
+            sender_address: MockAddress::from([0; 32]),
+        },
+        prover_service: ProverServiceConfig {
+            aggregated_proof_block_jump: 1,
+        },
+    };
+
+    let blueprint = MockDemoRollup::default();
+    let da_service = MockDaService::new(rollup_config.da.sender_address);
+    let ledger_db = blueprint.create_ledger_db(&rollup_config);
+    let kernel_genesis = BasicKernelGenesisConfig {
+        chain_state: serde_json::from_str(&std::fs::read_to_string(
+            "../test-data/genesis/integration-tests/chain_state.json",
+        )?)?,
+    };
+    let genesis_params = blueprint.create_genesis_config(
+        &GenesisPaths::from_dir("../test-data/genesis/integration-tests"),
+        kernel_genesis,
+        &rollup_config,
+    )?;
+    let prover_service = blueprint
+        .create_prover_service(RollupProverConfig::Skip, &rollup_config, &da_service)
+        .await;
+    let mut runner = StateTransitionRunner::new(
+        rollup_config.runner.clone(),
+        da_service.clone(),
+        ledger_db.clone(),
+        StfBlueprint::<
+            DefaultContext,
+            MockDaSpec,
+            Risc0Host<'static>,
+            Runtime<DefaultContext, MockDaSpec>,
+            BasicKernel<DefaultContext, MockDaSpec>,
+        >::new(),
+        blueprint.create_storage_manager(&rollup_config)?,
+        InitVariant::Genesis {
+            block_header: da_service.get_last_finalized_block_header().await?,
+            genesis_params,
+        },
+        prover_service,
+    )?;
+    let runner_task = tokio::spawn(async move { runner.run_in_process().await });
+
+    // Empty slots, and a slot with a blob which isn't a valid batch.
+    for _ in 1..EXECUTED_SLOTS {
+        da_service.produce_empty_block().await?;
+    }
+    da_service.send_transaction(&[1, 2, 3]).await?;
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while ledger_db.get_head_slot()?.map(|(number, _)| number.0) != Some(EXECUTED_SLOTS) {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok::<_, anyhow::Error>(())
+    })
+    .await??;
+
+    // The databases of the node are closed once the runner is dropped.
+    runner_task.abort();
+    let _ = runner_task.await;
+    Ok(rollup_config)
+}
@@ -17,6 +17,7 @@ pub async fn start_rollup(
     rt_genesis_paths: GenesisPaths,
     kernel_genesis_paths: BasicKernelGenesisPaths,
     rollup_prover_config: RollupProverConfig,
+    dev_rpc: bool,
 ) {
     let temp_dir = tempfile::tempdir().unwrap();
     let temp_path = temp_dir.path();
@@ -31,6 +32,9 @@ pub async fn start_rollup(
                 bind_host: "127.0.0.1".into(),
                 bind_port: 0,
             },
+            max_proving_backlog: 64,
+            remote_prover: false,
+            remote_prover_token: None,
         },
         da: MockDaConfig {
             sender_address: MockAddress::from([0; 32]),
@@ -40,7 +44,7 @@ pub async fn start_rollup(
         },
     };
 
-    let mock_demo_rollup = MockDemoRollup {};
+    let mock_demo_rollup = MockDemoRollup { dev_rpc };
 
     let kernel_genesis = BasicKernelGenesisConfig {
         chain_state: serde_json::from_str(
@@ -8,12 +8,13 @@ use sov_schema_db::{Schema, SchemaBatch, SeekKeyEncoder, DB};
 
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber, TxByHash,
-    TxByNumber, LEDGER_TABLES,
+    BatchByHash, BatchByNumber, EventByKey, EventByModule, EventByNumber, ProofBySlotRange,
+    ProvingJobByHeight, SlotByHash, SlotByNumber, SlotByStateRoot, TxByHash, TxByNumber,
+    LEDGER_TABLES,
 };
 use crate::schema::types::{
-    split_tx_for_storage, BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot,
-    StoredTransaction, TxNumber,
+    split_tx_for_storage, BatchNumber, DbHash, EventNumber, SlotNumber, StoredBatch, StoredProof,
+    StoredSlot, StoredTransaction, TxNumber,
 };
 
 mod rpc;
@@ -52,6 +53,7 @@ pub struct ItemNumbers {
 pub struct SlotCommit<S: SlotData, B, T> {
     slot_data: S,
     batch_receipts: Vec<BatchReceipt<B, T>>,
+    state_root: Option<Vec<u8>>,
     num_txs: usize,
     num_events: usize,
 }
@@ -72,6 +74,7 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
         Self {
             slot_data,
             batch_receipts: vec![],
+            state_root: None,
             num_txs: 0,
             num_events: 0,
         }
@@ -83,6 +86,12 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
         self.batch_receipts.push(batch);
         self.num_events += events_this_batch;
     }
+
+    /// Records the state root reached at the end of the slot, so that the
+    /// slot's state can later be looked up by its root
+    pub fn set_state_root(&mut self, state_root: impl AsRef<[u8]>) {
+        self.state_root = Some(state_root.as_ref().to_vec());
+    }
 }
 
 impl LedgerDB {
@@ -210,6 +219,9 @@ impl LedgerDB {
         schema_batch: &mut SchemaBatch,
     ) -> Result<(), anyhow::Error> {
         schema_batch.put::<EventByNumber>(event_number, event)?;
+        if let Some(module) = event.module() {
+            schema_batch.put::<EventByModule>(&(module.to_vec(), *event_number), &())?;
+        }
         schema_batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
     }
 
@@ -288,6 +300,12 @@ impl LedgerDB {
             &SlotNumber(current_item_numbers.slot_number),
             &mut schema_batch,
         )?;
+        if let Some(state_root) = data_to_commit.state_root {
+            schema_batch.put::<SlotByStateRoot>(
+                &state_root,
+                &SlotNumber(current_item_numbers.slot_number),
+            )?;
+        }
 
         self.db.write_schemas(schema_batch)?;
 
@@ -313,6 +331,136 @@ impl LedgerDB {
         }
     }
 
+    /// Persists a proving job for the DA block at `da_height` with the given hash, replacing
+    /// the jobs of the other blocks at this height. Returns `true` if a job was replaced.
+    pub fn put_proving_job(
+        &self,
+        da_height: u64,
+        block_hash: DbHash,
+        job: Vec<u8>,
+    ) -> anyhow::Result<bool> {
+        let mut schema_batch = SchemaBatch::new();
+        let replaced = match self.get_next_proving_job(da_height)? {
+            Some((height, hash, _)) if height == da_height => {
+                schema_batch.delete::<ProvingJobByHeight>(&(height, hash))?;
+                true
+            }
+            _ => false,
+        };
+        schema_batch.put::<ProvingJobByHeight>(&(da_height, block_hash), &job)?;
+        self.db.write_schemas(schema_batch)?;
+        Ok(replaced)
+    }
+
+    /// Returns the pending proving job with the lowest DA height starting from `da_height`, if any,
+    /// along with the hash of its DA block.
+    pub fn get_next_proving_job(
+        &self,
+        da_height: u64,
+    ) -> anyhow::Result<Option<(u64, DbHash, Vec<u8>)>> {
+        let mut iter = self.db.iter::<ProvingJobByHeight>()?;
+        iter.seek(&(da_height, DbHash::default()))?;
+
+        match iter.next() {
+            Some(Ok(item)) => {
+                let ((height, hash), job) = item.into_tuple();
+                Ok(Some((height, hash, job)))
+            }
+            Some(Err(e)) => Err(e),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the hash of the DA block of the pending proving job at `da_height`, if any.
+    pub fn get_proving_job_hash(&self, da_height: u64) -> anyhow::Result<Option<DbHash>> {
+        Ok(self
+            .get_next_proving_job(da_height)?
+            .and_then(|(height, hash, _)| (height == da_height).then_some(hash)))
+    }
+
+    /// Removes all the proving jobs up to `da_height` inclusive, once their slots are proven.
+    /// Returns the number of removed jobs.
+    pub fn delete_proving_jobs_until(&self, da_height: u64) -> anyhow::Result<u64> {
+        let mut iter = self.db.iter::<ProvingJobByHeight>()?;
+        iter.seek_to_first();
+
+        let mut schema_batch = SchemaBatch::new();
+        let mut deleted = 0;
+        for item in iter {
+            let key = item?.key;
+            if key.0 > da_height {
+                break;
+            }
+            schema_batch.delete::<ProvingJobByHeight>(&key)?;
+            deleted += 1;
+        }
+        self.db.write_schemas(schema_batch)?;
+        Ok(deleted)
+    }
+
+    /// Removes all the proving jobs starting from `da_height`. Used when the DA chain forks and
+    /// the corresponding blocks are executed again. Returns the number of removed jobs.
+    pub fn delete_proving_jobs_from(&self, da_height: u64) -> anyhow::Result<u64> {
+        let mut iter = self.db.iter::<ProvingJobByHeight>()?;
+        iter.seek(&(da_height, DbHash::default()))?;
+
+        let mut schema_batch = SchemaBatch::new();
+        let mut deleted = 0;
+        for item in iter {
+            schema_batch.delete::<ProvingJobByHeight>(&item?.key)?;
+            deleted += 1;
+        }
+        self.db.write_schemas(schema_batch)?;
+        Ok(deleted)
+    }
+
+    /// Returns the number of pending proving jobs. This scans the whole table, so it's only
+    /// meant to be called on startup.
+    pub fn count_proving_jobs(&self) -> anyhow::Result<u64> {
+        let mut iter = self.db.iter::<ProvingJobByHeight>()?;
+        iter.seek_to_first();
+
+        let mut count = 0;
+        for item in iter {
+            item?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Records a proof posted to the DA, which covers the slots from `start_slot` to `end_slot` inclusive.
+    pub fn put_proof(
+        &self,
+        start_slot: SlotNumber,
+        end_slot: SlotNumber,
+        proof: &StoredProof,
+    ) -> anyhow::Result<()> {
+        self.db
+            .put::<ProofBySlotRange>(&(start_slot, end_slot), proof)
+    }
+
+    /// Returns the proof covering the given slot, along with the range of slots it covers.
+    pub fn get_proof_by_slot_number(
+        &self,
+        slot_number: SlotNumber,
+    ) -> anyhow::Result<Option<(SlotNumber, SlotNumber, StoredProof)>> {
+        let mut iter = self.db.iter::<ProofBySlotRange>()?;
+        // The last proof starting at, or before, the slot
+        iter.seek_for_prev(&(slot_number, SlotNumber(u64::MAX)))?;
+
+        match iter.next() {
+            Some(Ok(item)) => {
+                let ((start_slot, end_slot), proof) = item.into_tuple();
+                if end_slot < slot_number {
+                    return Ok(None);
+                }
+                Ok(Some((start_slot, end_slot, proof)))
+            }
+            Some(Err(e)) => Err(e),
+            _ => Ok(None),
+        }
+    }
+
     /// Get the most recent committed slot, if any
     pub fn get_head_slot(&self) -> anyhow::Result<Option<(SlotNumber, StoredSlot)>> {
         let mut iter = self.db.iter::<SlotByNumber>()?;
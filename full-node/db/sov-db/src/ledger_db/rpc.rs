@@ -1,14 +1,16 @@
 use serde::de::DeserializeOwned;
 use sov_rollup_interface::rpc::{
-    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
-    LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset,
-    TxIdentifier, TxResponse,
+    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventFilter, EventIdentifier, EventRange,
+    EventResponse, FilteredEventsResponse, ItemOrHash, LedgerRpcProvider, ProofResponse, QueryMode,
+    SlotIdAndOffset, SlotIdentifier, SlotResponse, StateIdentifier, StateVersionProvider,
+    TxIdAndOffset, TxIdentifier, TxResponse,
 };
 use sov_rollup_interface::stf::Event;
 use tokio::sync::broadcast::Receiver;
 
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, TxByHash, TxByNumber,
+    BatchByHash, BatchByNumber, EventByModule, EventByNumber, SlotByHash, SlotByNumber,
+    SlotByStateRoot, TxByHash, TxByNumber,
 };
 use crate::schema::types::{
     BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
@@ -22,6 +24,12 @@ const MAX_BATCHES_PER_REQUEST: u64 = 20;
 const MAX_TRANSACTIONS_PER_REQUEST: u64 = 100;
 /// The maximum number of events that can be requested in a single RPC range query
 const MAX_EVENTS_PER_REQUEST: u64 = 500;
+/// The maximum number of events that a single filtered event query inspects
+/// before returning a (possibly partial) page
+const MAX_EVENTS_SCANNED_PER_REQUEST: u64 = 10_000;
+/// The version of the state written at genesis. Every slot then commits exactly one
+/// new version, so the state after slot `n` is at version `n + GENESIS_STATE_VERSION`
+const GENESIS_STATE_VERSION: u64 = 1;
 
 use super::LedgerDB;
 
@@ -270,11 +278,132 @@ impl LedgerRpcProvider for LedgerDB {
         self.get_transactions(&ids, query_mode)
     }
 
+    fn get_filtered_events(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<FilteredEventsResponse, anyhow::Error> {
+        let limit = limit.unwrap_or(MAX_EVENTS_PER_REQUEST);
+        // An empty page would return its own start as the next cursor.
+        anyhow::ensure!(limit > 0, "the limit of events must be at least 1");
+        anyhow::ensure!(
+            limit <= MAX_EVENTS_PER_REQUEST,
+            "requested too many events. Requested: {}. Max: {}",
+            limit,
+            MAX_EVENTS_PER_REQUEST
+        );
+
+        let range = match &filter.range {
+            Some(range) => self.resolve_event_range(range)?,
+            None => EventNumber(0)..EventNumber(u64::MAX),
+        };
+        let start = EventNumber(cursor.map_or(range.start.0, |cursor| cursor.max(range.start.0)));
+
+        // Events emitted by a given module are read from the module index,
+        // everything else requires a scan of the events by number.
+        let candidates: Box<dyn Iterator<Item = anyhow::Result<(EventNumber, Event)>> + '_> =
+            match &filter.module {
+                Some(module) => {
+                    let mut iter = self.db.iter::<EventByModule>()?;
+                    iter.seek(&(module.clone(), start))?;
+                    Box::new(
+                        iter.map_while(move |item| match item {
+                            Ok(item) if &item.key.0 == module => Some(Ok(item.key.1)),
+                            Ok(_) => None,
+                            Err(e) => Some(Err(e)),
+                        })
+                        .map(|number| {
+                            let number = number?;
+                            let event =
+                                self.db.get::<EventByNumber>(&number)?.ok_or_else(|| {
+                                    anyhow::anyhow!("event {} is indexed but missing", number.0)
+                                })?;
+                            Ok((number, event))
+                        }),
+                    )
+                }
+                None => {
+                    let mut iter = self.db.iter::<EventByNumber>()?;
+                    iter.seek(&start)?;
+                    Box::new(iter.map(|item| item.map(|item| item.into_tuple())))
+                }
+            };
+
+        let mut events = Vec::new();
+        let mut next_cursor = None;
+        let mut scanned = 0;
+        for candidate in candidates {
+            let (number, event) = candidate?;
+            if number >= range.end {
+                break;
+            }
+            if events.len() as u64 == limit || scanned == MAX_EVENTS_SCANNED_PER_REQUEST {
+                next_cursor = Some(number.0);
+                break;
+            }
+            scanned += 1;
+
+            if filter.matches(&event) {
+                events.push(EventResponse {
+                    number: number.0,
+                    event,
+                });
+            }
+        }
+
+        Ok(FilteredEventsResponse {
+            events,
+            next_cursor,
+        })
+    }
+
+    fn get_proof_by_slot(
+        &self,
+        slot_id: &SlotIdentifier,
+    ) -> Result<Option<ProofResponse>, anyhow::Error> {
+        let slot_number = match self.resolve_slot_identifier(slot_id)? {
+            Some(slot_number) => slot_number,
+            None => return Ok(None),
+        };
+
+        Ok(self
+            .get_proof_by_slot_number(slot_number)?
+            .map(|(start_slot, end_slot, proof)| ProofResponse {
+                start_slot: start_slot.into(),
+                end_slot: end_slot.into(),
+                da_height: proof.da_height,
+                state_transition: proof.state_transition.map(Into::into),
+            }))
+    }
+
     fn subscribe_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
         Ok(self.slot_subscriptions.subscribe())
     }
 }
 
+impl StateVersionProvider for LedgerDB {
+    fn get_state_version(&self, state_id: &StateIdentifier) -> Result<Option<u64>, anyhow::Error> {
+        let slot_number = match state_id {
+            // The genesis state is not committed as a slot
+            StateIdentifier::SlotHeight(0) => 0,
+            StateIdentifier::SlotHeight(height) => {
+                match self.db.get::<SlotByNumber>(&SlotNumber(*height))? {
+                    Some(_) => *height,
+                    None => return Ok(None),
+                }
+            }
+            StateIdentifier::StateRoot(state_root) => {
+                match self.db.get::<SlotByStateRoot>(state_root)? {
+                    Some(slot_number) => slot_number.0,
+                    None => return Ok(None),
+                }
+            }
+        };
+        Ok(Some(slot_number + GENESIS_STATE_VERSION))
+    }
+}
+
 impl LedgerDB {
     fn resolve_slot_identifier(
         &self,
@@ -346,6 +475,54 @@ impl LedgerDB {
         }
     }
 
+    /// Resolves an [`EventRange`] into the (exclusive) range of numbers of the events
+    /// it contains. Items which haven't been committed yet are assumed to contain
+    /// no events.
+    fn resolve_event_range(
+        &self,
+        range: &EventRange,
+    ) -> Result<std::ops::Range<EventNumber>, anyhow::Error> {
+        let (start, end) = match range {
+            EventRange::Slots { start, end } => (
+                self.first_event_of_slot(SlotNumber(*start))?,
+                self.first_event_of_slot(SlotNumber(end.saturating_add(1)))?,
+            ),
+            EventRange::Batches { start, end } => (
+                self.first_event_of_batch(BatchNumber(*start))?,
+                self.first_event_of_batch(BatchNumber(end.saturating_add(1)))?,
+            ),
+            EventRange::Transactions { start, end } => (
+                self.first_event_of_tx(TxNumber(*start))?,
+                self.first_event_of_tx(TxNumber(end.saturating_add(1)))?,
+            ),
+        };
+        Ok(start..end.max(start))
+    }
+
+    fn first_event_of_slot(&self, slot_number: SlotNumber) -> Result<EventNumber, anyhow::Error> {
+        match self.db.get::<SlotByNumber>(&slot_number)? {
+            Some(slot) => self.first_event_of_batch(slot.batches.start),
+            None => Ok(EventNumber(self.get_next_items_numbers().event_number)),
+        }
+    }
+
+    fn first_event_of_batch(
+        &self,
+        batch_number: BatchNumber,
+    ) -> Result<EventNumber, anyhow::Error> {
+        match self.db.get::<BatchByNumber>(&batch_number)? {
+            Some(batch) => self.first_event_of_tx(batch.txs.start),
+            None => Ok(EventNumber(self.get_next_items_numbers().event_number)),
+        }
+    }
+
+    fn first_event_of_tx(&self, tx_number: TxNumber) -> Result<EventNumber, anyhow::Error> {
+        Ok(match self.db.get::<TxByNumber>(&tx_number)? {
+            Some(tx) => tx.events.start,
+            None => EventNumber(self.get_next_items_numbers().event_number),
+        })
+    }
+
     fn populate_slot_response<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         number: u64,
@@ -429,9 +606,137 @@ impl LedgerDB {
 #[cfg(test)]
 mod tests {
     use sov_mock_da::{MockBlob, MockBlock};
-    use sov_rollup_interface::rpc::LedgerRpcProvider;
+    use sov_rollup_interface::rpc::{
+        EventFilter, EventRange, LedgerRpcProvider, SlotIdentifier, StateIdentifier,
+        StateVersionProvider,
+    };
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
 
     use crate::ledger_db::{LedgerDB, SlotCommit};
+    use crate::schema::types::{SlotNumber, StoredProof};
+
+    fn commit_slot_with_events(db: &LedgerDB, events: Vec<Event>) {
+        let mut slot = SlotCommit::<_, u32, u32>::new(MockBlock::default());
+        slot.add_batch(BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts: vec![TransactionReceipt {
+                tx_hash: [0; 32],
+                body_to_save: None,
+                events,
+                receipt: 0,
+            }],
+            inner: 0,
+        });
+        db.commit_slot(slot).unwrap();
+    }
+
+    fn event_numbers(
+        db: &LedgerDB,
+        filter: &EventFilter,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> (Vec<u64>, Option<u64>) {
+        let page = db.get_filtered_events(filter, cursor, limit).unwrap();
+        (
+            page.events.into_iter().map(|event| event.number).collect(),
+            page.next_cursor,
+        )
+    }
+
+    #[test]
+    fn test_filtered_events() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        commit_slot_with_events(
+            &db,
+            vec![
+                Event::new("transfer", "a").with_module(&[1]),
+                Event::new("mint", "b").with_module(&[2]),
+            ],
+        );
+        commit_slot_with_events(&db, vec![Event::new("transfer", "c").with_module(&[1])]);
+
+        let by_key = EventFilter {
+            key_prefix: Some(b"trans".to_vec()),
+            ..Default::default()
+        };
+        assert_eq!(event_numbers(&db, &by_key, None, None), (vec![1, 3], None));
+
+        let by_module = EventFilter {
+            module: Some(vec![2]),
+            ..Default::default()
+        };
+        assert_eq!(event_numbers(&db, &by_module, None, None), (vec![2], None));
+
+        let by_slot = EventFilter {
+            module: Some(vec![1]),
+            range: Some(EventRange::Slots { start: 2, end: 2 }),
+            ..Default::default()
+        };
+        assert_eq!(event_numbers(&db, &by_slot, None, None), (vec![3], None));
+
+        let by_tx = EventFilter {
+            range: Some(EventRange::Transactions { start: 1, end: 1 }),
+            ..Default::default()
+        };
+        assert_eq!(event_numbers(&db, &by_tx, None, None), (vec![1, 2], None));
+
+        // Paginate through all the events
+        let all = EventFilter::default();
+        assert_eq!(
+            event_numbers(&db, &all, None, Some(2)),
+            (vec![1, 2], Some(3))
+        );
+        assert_eq!(event_numbers(&db, &all, Some(3), Some(2)), (vec![3], None));
+        assert!(db.get_filtered_events(&all, None, Some(501)).is_err());
+        assert!(db.get_filtered_events(&all, None, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_state_versions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        for state_root in [[1u8; 32], [2u8; 32]] {
+            let mut slot = SlotCommit::<_, u32, u32>::new(MockBlock::default());
+            slot.set_state_root(state_root);
+            db.commit_slot(slot).unwrap();
+        }
+
+        let version = |state_id| db.get_state_version(&state_id).unwrap();
+        assert_eq!(version(StateIdentifier::SlotHeight(0)), Some(1));
+        assert_eq!(version(StateIdentifier::SlotHeight(2)), Some(3));
+        assert_eq!(version(StateIdentifier::SlotHeight(3)), None);
+        assert_eq!(version(StateIdentifier::StateRoot(vec![1; 32])), Some(2));
+        assert_eq!(version(StateIdentifier::StateRoot(vec![3; 32])), None);
+    }
+
+    #[test]
+    fn test_proofs_by_slot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        let proof = |da_height| StoredProof {
+            da_height,
+            state_transition: None,
+        };
+        db.put_proof(SlotNumber(1), SlotNumber(1), &proof(10))
+            .unwrap();
+        db.put_proof(SlotNumber(2), SlotNumber(4), &proof(12))
+            .unwrap();
+
+        let da_height = |slot| {
+            db.get_proof_by_slot(&SlotIdentifier::Number(slot))
+                .unwrap()
+                .map(|proof| (proof.start_slot, proof.end_slot, proof.da_height))
+        };
+        assert_eq!(da_height(1), Some((1, 1, 10)));
+        assert_eq!(da_height(3), Some((2, 4, 12)));
+        assert_eq!(da_height(4), Some((2, 4, 12)));
+        assert_eq!(da_height(5), None);
+    }
+
     #[test]
     fn test_slot_subscription() {
         let temp_dir = tempfile::tempdir().unwrap();
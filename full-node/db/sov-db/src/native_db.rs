@@ -49,6 +49,29 @@ impl<Q> NativeDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Copies the accessory state stored under `path` to `destination`, as it was at `version`.
+    /// The values written by later versions are left out of the copy.
+    pub fn copy_at_version(
+        path: impl AsRef<Path>,
+        destination: impl AsRef<Path>,
+        version: Version,
+    ) -> anyhow::Result<()> {
+        Self::setup_schema_db(path)?
+            .create_checkpoint(destination.as_ref().join(Self::DB_PATH_SUFFIX))?;
+        let copy = Self::setup_schema_db(destination)?;
+
+        let mut batch = SchemaBatch::new();
+        let mut values = copy.iter::<ModuleAccessoryState>()?;
+        values.seek_to_first();
+        for item in values {
+            let (key, written_version) = item?.key;
+            if written_version > version {
+                batch.delete::<ModuleAccessoryState>(&(key, written_version))?;
+            }
+        }
+        copy.write_schemas(batch)
+    }
 }
 
 impl<Q: QueryManager> NativeDB<Q> {
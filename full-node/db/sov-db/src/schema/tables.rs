@@ -4,6 +4,7 @@
 //! Slot Tables:
 //! - `SlotNumber -> StoredSlot`
 //! - `SlotNumber -> Vec<BatchNumber>`
+//! - `StateRoot -> SlotNumber`
 //!
 //! Batch Tables:
 //! - `BatchNumber -> StoredBatch`
@@ -16,12 +17,17 @@
 //! Event Tables:
 //! - `(EventKey, TxNumber) -> EventNumber`
 //! - `EventNumber -> (EventKey, EventValue)`
+//! - `(ModuleAddress, EventNumber) -> ()`
 //!
 //! JMT Tables:
 //! - `KeyHash -> Key`
 //! - `(Key, Version) -> JmtValue`
 //! - `NodeKey -> Node`
 //!
+//! Proving Tables:
+//! - `(DaHeight, SlotHash) -> ProvingJob`
+//! - `(SlotNumber, SlotNumber) -> StoredProof`
+//!
 //! Module Accessory State Table:
 //! - `(ModuleAddress, Key) -> Value`
 
@@ -29,13 +35,13 @@ use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 use jmt::storage::{NibblePath, Node, NodeKey};
 use jmt::Version;
-use sov_rollup_interface::stf::{Event, EventKey};
+use sov_rollup_interface::stf::{Event, EventKey, EventValue};
 use sov_schema_db::schema::{KeyDecoder, KeyEncoder, ValueCodec};
 use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
     AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, EventNumber, JmtValue, SlotNumber,
-    StateKey, StoredBatch, StoredSlot, StoredTransaction, TxNumber,
+    StateKey, StoredBatch, StoredProof, StoredSlot, StoredTransaction, TxNumber,
 };
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
@@ -51,12 +57,16 @@ pub const STATE_TABLES: &[&str] = &[
 pub const LEDGER_TABLES: &[&str] = &[
     SlotByNumber::table_name(),
     SlotByHash::table_name(),
+    SlotByStateRoot::table_name(),
     BatchByHash::table_name(),
     BatchByNumber::table_name(),
     TxByHash::table_name(),
     TxByNumber::table_name(),
     EventByKey::table_name(),
     EventByNumber::table_name(),
+    EventByModule::table_name(),
+    ProvingJobByHeight::table_name(),
+    ProofBySlotRange::table_name(),
 ];
 
 /// A list of all tables used by the NativeDB. These tables store
@@ -171,9 +181,22 @@ macro_rules! define_table_with_default_codec {
 macro_rules! define_table_with_seek_key_codec {
     ($(#[$docs:meta])+ ($table_name:ident) $key:ty => $value:ty) => {
         define_table_without_codec!($(#[$docs])+ ( $table_name ) $key => $value);
+        impl_seek_key_codec!($table_name, $key);
+        impl_borsh_value_codec!($table_name, $value);
+    };
+}
 
+/// Implements the big-endian [`bincode`] key codec of [`define_table_with_seek_key_codec`],
+/// for tables which implement their value codec separately.
+macro_rules! impl_seek_key_codec {
+    ($table_name:ident, $key:ty) => {
         impl ::sov_schema_db::schema::KeyEncoder<$table_name> for $key {
-            fn encode_key(&self) -> ::std::result::Result<::sov_rollup_interface::maybestd::vec::Vec<u8>, ::sov_schema_db::CodecError> {
+            fn encode_key(
+                &self,
+            ) -> ::std::result::Result<
+                ::sov_rollup_interface::maybestd::vec::Vec<u8>,
+                ::sov_schema_db::CodecError,
+            > {
                 use ::anyhow::Context as _;
                 use ::bincode::Options as _;
 
@@ -181,7 +204,10 @@ macro_rules! define_table_with_seek_key_codec {
                     .with_fixint_encoding()
                     .with_big_endian();
 
-                bincode_options.serialize(self).context("Failed to serialize key").map_err(Into::into)
+                bincode_options
+                    .serialize(self)
+                    .context("Failed to serialize key")
+                    .map_err(Into::into)
             }
         }
 
@@ -194,17 +220,23 @@ macro_rules! define_table_with_seek_key_codec {
                     .with_fixint_encoding()
                     .with_big_endian();
 
-                bincode_options.deserialize_from(&mut &data[..]).context("Failed to deserialize key").map_err(Into::into)
+                bincode_options
+                    .deserialize_from(&mut &data[..])
+                    .context("Failed to deserialize key")
+                    .map_err(Into::into)
             }
         }
 
         impl ::sov_schema_db::SeekKeyEncoder<$table_name> for $key {
-            fn encode_seek_key(&self) -> ::std::result::Result<::sov_rollup_interface::maybestd::vec::Vec<u8>, ::sov_schema_db::CodecError> {
+            fn encode_seek_key(
+                &self,
+            ) -> ::std::result::Result<
+                ::sov_rollup_interface::maybestd::vec::Vec<u8>,
+                ::sov_schema_db::CodecError,
+            > {
                 <Self as ::sov_schema_db::schema::KeyEncoder<$table_name>>::encode_key(self)
             }
         }
-
-        impl_borsh_value_codec!($table_name, $value);
     };
 }
 
@@ -218,6 +250,11 @@ define_table_with_default_codec!(
     (SlotByHash) DbHash => SlotNumber
 );
 
+define_table_with_default_codec!(
+    /// A "secondary index" for slot data by the state root reached at the end of the slot
+    (SlotByStateRoot) Vec<u8> => SlotNumber
+);
+
 define_table_with_seek_key_codec!(
     /// The primary source for batch data
     (BatchByNumber) BatchNumber => StoredBatch
@@ -238,16 +275,49 @@ define_table_with_default_codec!(
     (TxByHash) DbHash => TxNumber
 );
 
-define_table_with_seek_key_codec!(
+define_table_without_codec!(
     /// The primary store for event data
     (EventByNumber) EventNumber => Event
 );
 
+impl_seek_key_codec!(EventByNumber, EventNumber);
+
+impl ValueCodec<EventByNumber> for Event {
+    fn encode_value(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        self.try_to_vec().map_err(CodecError::from)
+    }
+
+    fn decode_value(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        // Events stored before they were attributed to modules are encoded without the
+        // trailing module, and are read back as events without one.
+        Event::deserialize_reader(&mut &data[..]).or_else(|e| {
+            let (key, value) = <(EventKey, EventValue)>::try_from_slice(data).map_err(|_| e)?;
+            Ok(Event::from_parts(key, value))
+        })
+    }
+}
+
 define_table_with_default_codec!(
     /// A "secondary index" for event data by key
     (EventByKey) (EventKey, TxNumber, EventNumber) => ()
 );
 
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for event data by emitting module, ordered by event number
+    (EventByModule) (Vec<u8>, EventNumber) => ()
+);
+
+define_table_with_seek_key_codec!(
+    /// The state transitions which were executed but not proven yet, by the height and hash of
+    /// their DA block. Values are serialized `StateTransitionData`, which is opaque to the database.
+    (ProvingJobByHeight) (u64, DbHash) => Vec<u8>
+);
+
+define_table_with_seek_key_codec!(
+    /// The proofs posted to the DA, by the (inclusive) range of slots they cover
+    (ProofBySlotRange) (SlotNumber, SlotNumber) => StoredProof
+);
+
 define_table_without_codec!(
     /// The source of truth for JMT nodes
     (JmtNodes) NodeKey => Node
@@ -386,3 +456,27 @@ impl ValueCodec<ModuleAccessoryState> for AccessoryStateValue {
         Ok(Self::deserialize_reader(&mut &data[..])?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_without_module_are_still_decoded() {
+        let event = Event::new("key", "value");
+        let legacy = (event.key().clone(), event.value().clone())
+            .try_to_vec()
+            .unwrap();
+        assert_eq!(
+            <Event as ValueCodec<EventByNumber>>::decode_value(&legacy).unwrap(),
+            event
+        );
+
+        let event = event.with_module(&[1; 32]);
+        let encoded = <Event as ValueCodec<EventByNumber>>::encode_value(&event).unwrap();
+        assert_eq!(
+            <Event as ValueCodec<EventByNumber>>::decode_value(&encoded).unwrap(),
+            event
+        );
+    }
+}
@@ -3,8 +3,10 @@ use std::sync::Arc;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use sov_rollup_interface::rpc::{BatchResponse, TxIdentifier, TxResponse};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::rpc::{BatchResponse, StateTransitionResponse, TxIdentifier, TxResponse};
 use sov_rollup_interface::stf::{Event, EventKey, TransactionReceipt};
+use sov_rollup_interface::zk::StateTransition;
 
 /// A cheaply cloneable bytes abstraction for use within the trust boundary of the node
 /// (i.e. when interfacing with the database). Serializes and deserializes more efficiently,
@@ -118,6 +120,64 @@ impl<R: DeserializeOwned> TryFrom<StoredTransaction> for TxResponse<R> {
     }
 }
 
+/// The on-disk format of a proof posted to the DA.
+#[derive(Debug, PartialEq, Eq, Clone, BorshDeserialize, BorshSerialize)]
+pub struct StoredProof {
+    /// The height of the DA block in which the proof was posted.
+    pub da_height: u64,
+    /// The public output of the proof, if the prover produced one.
+    pub state_transition: Option<StoredStateTransition>,
+}
+
+/// The on-disk format of a [`StateTransition`], with its DA-specific fields serialized.
+#[derive(Debug, PartialEq, Eq, Clone, BorshDeserialize, BorshSerialize)]
+pub struct StoredStateTransition {
+    /// The state root before the transition.
+    pub initial_state_root: Vec<u8>,
+    /// The state root after the transition.
+    pub final_state_root: Vec<u8>,
+    /// The hash of the last DA slot of the transition.
+    pub slot_hash: DbHash,
+    /// The borsh serialized validity condition of the transition.
+    pub validity_condition: Vec<u8>,
+}
+
+impl<Da: DaSpec, Root: AsRef<[u8]>> From<StateTransition<Da, Root>> for StoredStateTransition {
+    fn from(state_transition: StateTransition<Da, Root>) -> Self {
+        Self {
+            initial_state_root: state_transition.initial_state_root.as_ref().to_vec(),
+            final_state_root: state_transition.final_state_root.as_ref().to_vec(),
+            slot_hash: state_transition.slot_hash.into(),
+            validity_condition: state_transition
+                .validity_condition
+                .try_to_vec()
+                .expect("serialization to vec is infallible"),
+        }
+    }
+}
+
+impl From<StoredStateTransition> for StateTransitionResponse {
+    fn from(value: StoredStateTransition) -> Self {
+        Self {
+            initial_state_root: value.initial_state_root,
+            final_state_root: value.final_state_root,
+            slot_hash: value.slot_hash,
+            validity_condition: value.validity_condition,
+        }
+    }
+}
+
+impl From<StateTransitionResponse> for StoredStateTransition {
+    fn from(value: StateTransitionResponse) -> Self {
+        Self {
+            initial_state_root: value.initial_state_root,
+            final_state_root: value.final_state_root,
+            slot_hash: value.slot_hash,
+            validity_condition: value.validity_condition,
+        }
+    }
+}
+
 /// Split a `TransactionReceipt` into a `StoredTransaction` and a list of `Event`s for storage in the database.
 pub fn split_tx_for_storage<R: Serialize>(
     tx: TransactionReceipt<R>,
@@ -1,14 +1,16 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 use jmt::storage::{HasPreimage, TreeReader, TreeWriter};
 use jmt::{KeyHash, Version};
+use sov_schema_db::schema::{KeyDecoder, ValueCodec};
 use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
-use sov_schema_db::SchemaBatch;
+use sov_schema_db::{Operation, SchemaBatch};
 
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
-use crate::schema::types::StateKey;
+use crate::schema::types::{JmtValue, StateKey};
 
 /// A typed wrapper around the db for storing rollup state. Internally,
 /// this is roughly just an [`Arc<sov_schema_db::DB>`] with pointer to list of non-finalized snapshots
@@ -57,6 +59,91 @@ impl<Q> StateDB<Q> {
         ))?;
         Ok(ReadOnlyDbSnapshot::from(inner))
     }
+
+    /// Initialize [`sov_schema_db::DB`] in read-only mode.
+    /// Unlike [`StateDB::setup_schema_db`], it can be opened while the database is used by another instance.
+    pub fn setup_read_only_schema_db(path: impl AsRef<Path>) -> anyhow::Result<sov_schema_db::DB> {
+        let state_db_path = path.as_ref().join(Self::DB_PATH_SUFFIX);
+        sov_schema_db::DB::open_cf_readonly(
+            &gen_rocksdb_options(&Default::default(), true),
+            state_db_path,
+            Self::DB_NAME,
+            STATE_TABLES.to_vec(),
+        )
+    }
+
+    /// Copies the state stored under `path` to `destination`, as it was at `version`.
+    /// The values and the tree nodes written by later versions are left out of the copy,
+    /// so a [`StateDB`] opened on `destination` continues from `version`.
+    pub fn copy_at_version(
+        path: impl AsRef<Path>,
+        destination: impl AsRef<Path>,
+        version: Version,
+    ) -> anyhow::Result<()> {
+        Self::setup_schema_db(path)?
+            .create_checkpoint(destination.as_ref().join(Self::DB_PATH_SUFFIX))?;
+        let copy = Self::setup_schema_db(destination)?;
+
+        let mut batch = SchemaBatch::new();
+        let mut nodes = copy.iter::<JmtNodes>()?;
+        nodes.seek_to_first();
+        for item in nodes {
+            let node_key = item?.key;
+            if node_key.version() > version {
+                batch.delete::<JmtNodes>(&node_key)?;
+            }
+        }
+        let mut values = copy.iter::<JmtValues>()?;
+        values.seek_to_first();
+        for item in values {
+            let (key, written_version) = item?.key;
+            if written_version > version {
+                batch.delete::<JmtValues>(&(key, written_version))?;
+            }
+        }
+        copy.write_schemas(batch)
+    }
+
+    /// Returns the values written to the state at `version`, keyed by their state key.
+    /// This scans all the values stored in `db`.
+    pub fn get_values_written_at(
+        db: &sov_schema_db::DB,
+        version: Version,
+    ) -> anyhow::Result<BTreeMap<Vec<u8>, JmtValue>> {
+        let mut values = db.iter::<JmtValues>()?;
+        values.seek_to_first();
+        let mut written = BTreeMap::new();
+        for item in values {
+            let ((key, written_version), value) = item?.into_tuple();
+            if written_version == version {
+                written.insert(key, value);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Returns the values written to the state at `version` by the snapshot, keyed by their state key.
+    /// The snapshot is the one produced by [`StateDB::freeze`].
+    pub fn get_snapshot_values_written_at(
+        snapshot: &ReadOnlyDbSnapshot,
+        version: Version,
+    ) -> anyhow::Result<BTreeMap<Vec<u8>, JmtValue>> {
+        let mut written = BTreeMap::new();
+        for (key, operation) in snapshot.iter::<JmtValues>() {
+            let (key, written_version) =
+                <(StateKey, Version) as KeyDecoder<JmtValues>>::decode_key(key)?;
+            if written_version != version {
+                continue;
+            }
+            if let Operation::Put { value } = operation {
+                written.insert(
+                    key,
+                    <JmtValue as ValueCodec<JmtValues>>::decode_value(value)?,
+                );
+            }
+        }
+        Ok(written)
+    }
 }
 
 impl<Q: QueryManager> StateDB<Q> {
@@ -115,6 +202,13 @@ impl<Q: QueryManager> StateDB<Q> {
         *version
     }
 
+    /// Get the latest version written to the database, if any.
+    /// Unlike [`StateDB::get_next_version`], it isn't affected by [`StateDB::max_out_next_version`].
+    pub fn get_latest_version(&self) -> anyhow::Result<Option<Version>> {
+        let last_key_value = self.db.get_largest::<JmtNodes>()?;
+        Ok(last_key_value.map(|(k, _)| k.version()))
+    }
+
     /// Used to always query for latest possible version!
     pub fn max_out_next_version(&self) {
         let mut version = self.next_version.lock().unwrap();
@@ -188,6 +282,7 @@ impl<Q: QueryManager> HasPreimage for StateDB<Q> {
 
 #[cfg(test)]
 mod state_db_tests {
+    use std::collections::BTreeMap;
     use std::sync::{Arc, RwLock};
 
     use jmt::storage::{NodeBatch, TreeReader, TreeWriter};
@@ -195,6 +290,7 @@ mod state_db_tests {
     use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, ReadOnlyLock};
 
     use super::StateDB;
+    use crate::schema::tables::JmtValues;
 
     #[test]
     fn test_simple() {
@@ -216,4 +312,37 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+
+    #[test]
+    fn test_copy_at_version() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let source = tmpdir.path().join("source");
+        let destination = tmpdir.path().join("destination");
+        let key = vec![2u8; 100];
+
+        {
+            let db = StateDB::<NoopQueryManager>::setup_schema_db(&source).unwrap();
+            for version in 1..=3u64 {
+                db.put::<JmtValues>(&(key.clone(), version), &Some(vec![version as u8]))
+                    .unwrap();
+            }
+        }
+
+        StateDB::<NoopQueryManager>::copy_at_version(&source, &destination, 2).unwrap();
+
+        let copy = StateDB::<NoopQueryManager>::setup_schema_db(&destination).unwrap();
+        assert_eq!(
+            StateDB::<NoopQueryManager>::get_values_written_at(&copy, 2).unwrap(),
+            BTreeMap::from([(key.clone(), Some(vec![2]))])
+        );
+        assert!(StateDB::<NoopQueryManager>::get_values_written_at(&copy, 3)
+            .unwrap()
+            .is_empty());
+
+        let source = StateDB::<NoopQueryManager>::setup_schema_db(&source).unwrap();
+        assert_eq!(
+            StateDB::<NoopQueryManager>::get_values_written_at(&source, 3).unwrap(),
+            BTreeMap::from([(key, Some(vec![3]))])
+        );
+    }
 }
@@ -3,6 +3,8 @@ use std::collections::VecDeque;
 
 use borsh::BorshSerialize;
 use sov_modules_api::transaction::Transaction;
+#[cfg(feature = "dev")]
+use sov_modules_api::PrivateKey;
 
 pub struct EthBatchBuilder<C: sov_modules_api::Context> {
     mempool: VecDeque<Vec<u8>>,
@@ -26,6 +28,18 @@ impl<C: sov_modules_api::Context> EthBatchBuilder<C> {
         }
     }
 
+    /// The public key of the rollup transaction signer.
+    #[cfg(feature = "dev")]
+    pub fn pub_key(&self) -> C::PublicKey {
+        self.sov_tx_signer_private_key.pub_key()
+    }
+
+    /// Sets the nonce of the next signed transaction, when the state of the signer was reverted.
+    #[cfg(feature = "dev")]
+    pub fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = nonce;
+    }
+
     /// Signs messages with the private key of the `EthBatchBuilder` and make them `transactions`.
     /// Returns the blob of signed transactions.
     fn make_blob(&mut self) -> Vec<Vec<u8>> {
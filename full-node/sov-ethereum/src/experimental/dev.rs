@@ -0,0 +1,325 @@
+//! Developer endpoints in the style of Hardhat and Anvil, for rollups running on the mock DA layer.
+//!
+//! The state is changed with the [`DevCallMessage`]s of the EVM module, signed by the rollup
+//! transaction signer of the RPC, which must be the dev authority of the EVM genesis. Each endpoint
+//! changing the state produces a DA block and returns once the rollup has processed it.
+//!
+//! `evm_snapshot` keeps the DA blocks above the current head unfinalized, so that the
+//! `ProverStorageManager` keeps their state in snapshots rather than committing it, and the RPC
+//! reads it with unfinalized reads. `evm_revert` replaces these blocks with empty ones, and the
+//! rollup executes them again on the state of the snapshot. The block numbers therefore keep
+//! increasing after a revert. While a snapshot is held, traces, the ledger RPC and the
+//! subscriptions only see the blocks finalized before it.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use borsh::ser::BorshSerialize;
+use demo_stf::runtime::Runtime;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use reth_primitives::{Address, Bytes, H256, U256};
+use sov_evm::{CallMessage, DevCallMessage, Evm};
+use sov_mock_da::{MockBlockHeader, MockDaService, MockDaSpec};
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{EncodeCall, WorkingSet};
+use tokio::sync::broadcast;
+
+use super::{account_nonce, Ethereum, ETH_RPC_ERROR};
+use crate::filters::{block_header, head_block_number};
+
+/// How long an endpoint waits for the rollup to process the DA block it produced.
+const BLOCK_PROCESSING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the state is polled for the processed block: the held DA blocks aren't committed
+/// to the ledger, so their slots aren't notified.
+const BLOCK_POLLING_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The snapshots taken by `evm_snapshot`, by id.
+#[derive(Default)]
+pub struct Snapshots {
+    next_id: u64,
+    snapshots: BTreeMap<u64, Snapshot>,
+}
+
+struct Snapshot {
+    /// The height of the DA block whose state is kept.
+    da_height: u64,
+    /// The EVM block produced from that DA block.
+    evm_head: u64,
+    /// The time offset of the DA blocks at that height.
+    time_offset: u64,
+}
+
+impl Snapshots {
+    /// The height above which DA blocks are held, the one of the oldest snapshot.
+    fn held_height(&self) -> Option<u64> {
+        self.snapshots.values().map(|s| s.da_height).min()
+    }
+}
+
+/// Registers the `evm_*` developer endpoints, and `sov_mineSlot`.
+pub fn register_dev_rpc_methods<C: sov_modules_api::Context>(
+    rpc: &mut RpcModule<Ethereum<C, MockDaService>>,
+) -> Result<(), jsonrpsee::core::Error> {
+    rpc.register_async_method("evm_mine", |_, ethereum| async move {
+        ethereum.mine().await?;
+        Ok::<_, ErrorObjectOwned>("0x0")
+    })?;
+
+    rpc.register_async_method("sov_mineSlot", |_, ethereum| async move {
+        Ok::<_, ErrorObjectOwned>(ethereum.mine().await?.height)
+    })?;
+
+    rpc.register_async_method("evm_setBalance", |parameters, ethereum| async move {
+        let mut parameters = parameters.sequence();
+        let address: Address = parameters.next()?;
+        let balance: U256 = parameters.next()?;
+
+        ethereum
+            .dev_call(DevCallMessage::SetBalance {
+                address: address.0,
+                balance: balance.to_be_bytes(),
+            })
+            .await?;
+        Ok::<_, ErrorObjectOwned>(true)
+    })?;
+
+    rpc.register_async_method("evm_setCode", |parameters, ethereum| async move {
+        let mut parameters = parameters.sequence();
+        let address: Address = parameters.next()?;
+        let code: Bytes = parameters.next()?;
+
+        ethereum
+            .dev_call(DevCallMessage::SetCode {
+                address: address.0,
+                code: code.to_vec(),
+            })
+            .await?;
+        Ok::<_, ErrorObjectOwned>(true)
+    })?;
+
+    rpc.register_async_method("evm_setStorageAt", |parameters, ethereum| async move {
+        let mut parameters = parameters.sequence();
+        let address: Address = parameters.next()?;
+        let slot: U256 = parameters.next()?;
+        let value: H256 = parameters.next()?;
+
+        ethereum
+            .dev_call(DevCallMessage::SetStorageAt {
+                address: address.0,
+                slot: slot.to_be_bytes(),
+                value: value.0,
+            })
+            .await?;
+        Ok::<_, ErrorObjectOwned>(true)
+    })?;
+
+    rpc.register_async_method("evm_increaseTime", |parameters, ethereum| async move {
+        let seconds: u64 = parameters.one()?;
+
+        // The DA blocks carry the time of `sov-chain-state`, and the EVM blocks their own timestamps.
+        let offset = ethereum.da_service.increase_time(seconds);
+        ethereum
+            .dev_call(DevCallMessage::IncreaseTime { seconds })
+            .await?;
+        Ok::<_, ErrorObjectOwned>(offset)
+    })?;
+
+    rpc.register_method("evm_impersonateAccount", |parameters, ethereum| {
+        let address: Address = parameters.one()?;
+        ethereum.impersonated.lock().unwrap().insert(address);
+        Ok::<_, ErrorObjectOwned>(true)
+    })?;
+
+    rpc.register_method("evm_stopImpersonatingAccount", |parameters, ethereum| {
+        let address: Address = parameters.one()?;
+        Ok::<_, ErrorObjectOwned>(ethereum.impersonated.lock().unwrap().remove(&address))
+    })?;
+
+    rpc.register_async_method("evm_snapshot", |_, ethereum| async move {
+        let id = ethereum.snapshot().await?;
+        Ok::<_, ErrorObjectOwned>(U256::from(id))
+    })?;
+
+    rpc.register_async_method("evm_revert", |parameters, ethereum| async move {
+        let id: U256 = parameters.one()?;
+        ethereum.revert(id.try_into().unwrap_or(u64::MAX)).await
+    })?;
+
+    Ok(())
+}
+
+impl<C: sov_modules_api::Context> Ethereum<C, MockDaService> {
+    /// Produces a DA block with the pending transactions of the pool, if any.
+    async fn mine(&self) -> Result<MockBlockHeader, ErrorObjectOwned> {
+        let (hashes, messages): (Vec<_>, _) = self
+            .with_txpool(|txpool, nonce_of| txpool.unsubmitted_pending(nonce_of))
+            .into_iter()
+            .unzip();
+        self.submit_and_wait(messages, &hashes).await
+    }
+
+    /// Produces a DA block applying the dev call.
+    async fn dev_call(&self, call: DevCallMessage) -> Result<MockBlockHeader, ErrorObjectOwned> {
+        let message =
+            <Runtime<C, MockDaSpec> as EncodeCall<Evm<C>>>::encode_call(CallMessage::Dev { call });
+        self.submit_and_wait(vec![message], &[]).await
+    }
+
+    /// Keeps the state at the DA head, once the rollup has processed it, and returns its id.
+    async fn snapshot(&self) -> Result<u64, ErrorObjectOwned> {
+        let header = self
+            .da_service
+            .get_head_block_header()
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+        let evm = Evm::<C>::default();
+        let mut evm_head = head_block_number(&evm, &mut WorkingSet::new(self.storage.clone()));
+        if header.height > 0 {
+            // The DA head is the last block the rollup processes, the EVM head comes from it
+            // unless it's still being processed.
+            evm_head = self
+                .wait_for_block(self.slot_notifications.resubscribe(), evm_head, &header)
+                .await?;
+        }
+
+        let (id, held_height) = {
+            let mut snapshots = self.snapshots.lock().unwrap();
+            let id = snapshots.next_id;
+            snapshots.next_id += 1;
+            snapshots.snapshots.insert(
+                id,
+                Snapshot {
+                    da_height: header.height,
+                    evm_head,
+                    time_offset: self.da_service.time_offset(),
+                },
+            );
+            (id, snapshots.held_height())
+        };
+        self.da_service.hold_finality(held_height).await;
+        Ok(id)
+    }
+
+    /// Goes back to the state of the snapshot, which is dropped with the later ones. Returns
+    /// whether the snapshot existed.
+    async fn revert(&self, id: u64) -> Result<bool, ErrorObjectOwned> {
+        let (snapshot, held_height) = {
+            let mut snapshots = self.snapshots.lock().unwrap();
+            let Some(snapshot) = snapshots.snapshots.remove(&id) else {
+                return Ok(false);
+            };
+            snapshots.snapshots.split_off(&id);
+            (snapshot, snapshots.held_height())
+        };
+
+        let head = self
+            .da_service
+            .get_head_block_header()
+            .await
+            .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+        self.da_service.set_time_offset(snapshot.time_offset);
+        if head.height > snapshot.da_height {
+            let slot_notifications = self.slot_notifications.resubscribe();
+            let header = self
+                .da_service
+                .rewind_to(snapshot.da_height)
+                .await
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            self.wait_for_block(slot_notifications, snapshot.evm_head + 1, &header)
+                .await?;
+
+            // The transactions of the rollup signer in the replaced blocks were reverted too.
+            let mut batch_builder = self.batch_builder.lock().unwrap();
+            let nonce = account_nonce(
+                batch_builder.pub_key(),
+                &mut WorkingSet::<C>::new(self.storage.clone()),
+            );
+            batch_builder.set_nonce(nonce);
+        }
+        self.da_service.hold_finality(held_height).await;
+        Ok(true)
+    }
+
+    /// Submits the messages in a new DA block, which is empty without messages, and waits
+    /// until the rollup has processed it. The pool transactions with the given hashes are
+    /// marked submitted once the block is produced.
+    async fn submit_and_wait(
+        &self,
+        messages: Vec<Vec<u8>>,
+        hashes: &[H256],
+    ) -> Result<MockBlockHeader, ErrorObjectOwned> {
+        let slot_notifications = self.slot_notifications.resubscribe();
+        let evm = Evm::<C>::default();
+        let next_block = head_block_number(&evm, &mut WorkingSet::new(self.storage.clone())) + 1;
+
+        let batch = if messages.is_empty() {
+            vec![]
+        } else {
+            self.build_batch(messages, Some(1))
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?
+        };
+        let header = if batch.is_empty() {
+            self.da_service.produce_empty_block().await
+        } else {
+            let blob = batch
+                .try_to_vec()
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            self.da_service.submit_blob(&blob).await
+        }
+        .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+        self.txpool.lock().unwrap().mark_submitted(hashes);
+
+        self.wait_for_block(slot_notifications, next_block, &header)
+            .await?;
+        Ok(header)
+    }
+
+    /// Waits for the EVM block produced from the DA block, whose hash is its `mix_hash`.
+    async fn wait_for_block(
+        &self,
+        mut slot_notifications: broadcast::Receiver<u64>,
+        mut next_block: u64,
+        da_header: &MockBlockHeader,
+    ) -> Result<u64, ErrorObjectOwned> {
+        let evm = Evm::<C>::default();
+        let da_hash = H256(da_header.hash.0);
+        let deadline = tokio::time::Instant::now() + BLOCK_PROCESSING_TIMEOUT;
+        loop {
+            {
+                let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+                let head = head_block_number(&evm, &mut working_set);
+                for number in next_block..=head {
+                    if block_header(&evm, number, &mut working_set).mix_hash == da_hash {
+                        return Ok(number);
+                    }
+                }
+                next_block = next_block.max(head + 1);
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(to_jsonrpsee_error_object(
+                    format!(
+                        "The rollup didn't process the DA block at height {}",
+                        da_header.height
+                    ),
+                    ETH_RPC_ERROR,
+                ));
+            }
+            // A lagging receiver only missed some slots: the new blocks are read from the state.
+            match tokio::time::timeout(BLOCK_POLLING_INTERVAL, slot_notifications.recv()).await {
+                Ok(Ok(_)) | Ok(Err(broadcast::error::RecvError::Lagged(_))) | Err(_) => {}
+                Ok(Err(broadcast::error::RecvError::Closed)) => {
+                    return Err(to_jsonrpsee_error_object(
+                        format!(
+                            "The rollup didn't process the DA block at height {}",
+                            da_header.height
+                        ),
+                        ETH_RPC_ERROR,
+                    ))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,268 @@
+//! Filters polled with `eth_getFilterChanges`, and subscriptions to the new blocks of the EVM.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+use futures::future::Either;
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::{PendingSubscriptionSink, SubscriptionMessage};
+use reth_primitives::{H256, U256};
+use reth_rpc_types::{Filter, FilterChanges};
+use sov_evm::{EthFilterError, Evm};
+use sov_modules_api::WorkingSet;
+use tokio::sync::broadcast;
+
+/// Filters which aren't polled for this long are uninstalled, as in go-ethereum.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// What a filter or a subscription reports about the new blocks.
+#[derive(Debug, Clone)]
+pub(crate) enum NewBlocksKind {
+    /// The logs matching the filter.
+    Logs(Box<Filter>),
+    /// The blocks themselves: their hashes for filters, and their headers for subscriptions.
+    Blocks,
+}
+
+struct ActiveFilter {
+    kind: NewBlocksKind,
+    /// The first block which wasn't reported yet.
+    next_block: u64,
+    last_poll: Instant,
+}
+
+/// The filters installed with `eth_newFilter` and `eth_newBlockFilter`.
+#[derive(Default)]
+pub(crate) struct Filters {
+    last_id: u64,
+    active: HashMap<U256, ActiveFilter>,
+}
+
+impl Filters {
+    /// Installs a filter reporting the blocks after `head`, and returns its id.
+    pub(crate) fn install(&mut self, kind: NewBlocksKind, head: u64) -> U256 {
+        self.evict_expired();
+        self.last_id += 1;
+        let id = U256::from(self.last_id);
+        self.active.insert(
+            id,
+            ActiveFilter {
+                kind,
+                next_block: head + 1,
+                last_poll: Instant::now(),
+            },
+        );
+        id
+    }
+
+    /// Removes a filter, returning whether it was installed.
+    pub(crate) fn uninstall(&mut self, id: U256) -> bool {
+        self.evict_expired();
+        self.active.remove(&id).is_some()
+    }
+
+    /// Returns the kind of a filter, and the blocks up to `head` it didn't report yet.
+    /// These blocks are then considered reported.
+    pub(crate) fn poll(
+        &mut self,
+        id: U256,
+        head: u64,
+    ) -> Result<(NewBlocksKind, RangeInclusive<u64>), EthFilterError> {
+        self.evict_expired();
+        let filter = self
+            .active
+            .get_mut(&id)
+            .ok_or(EthFilterError::FilterNotFound)?;
+        let blocks = filter.next_block..=head;
+        filter.next_block = filter.next_block.max(head + 1);
+        filter.last_poll = Instant::now();
+        Ok((filter.kind.clone(), blocks))
+    }
+
+    /// Returns the filter of a log filter.
+    pub(crate) fn log_filter(&mut self, id: U256) -> Result<Filter, EthFilterError> {
+        self.evict_expired();
+        match self.active.get_mut(&id) {
+            Some(ActiveFilter {
+                kind: NewBlocksKind::Logs(filter),
+                last_poll,
+                ..
+            }) => {
+                *last_poll = Instant::now();
+                Ok(*filter.clone())
+            }
+            _ => Err(EthFilterError::FilterNotFound),
+        }
+    }
+
+    fn evict_expired(&mut self) {
+        self.active
+            .retain(|_, filter| filter.last_poll.elapsed() < FILTER_TIMEOUT);
+    }
+}
+
+impl NewBlocksKind {
+    /// Returns what the filter reports about the given blocks.
+    pub(crate) fn filter_changes<C: sov_modules_api::Context>(
+        &self,
+        evm: &Evm<C>,
+        blocks: RangeInclusive<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<FilterChanges, EthFilterError> {
+        Ok(match self {
+            NewBlocksKind::Logs(filter) => FilterChanges::Logs(evm.logs_in_blocks(
+                filter,
+                blocks_in_filter_range(filter, blocks),
+                working_set,
+            )?),
+            NewBlocksKind::Blocks => FilterChanges::Hashes(
+                blocks
+                    .map(|number| {
+                        block_header(evm, number, working_set)
+                            .hash
+                            .unwrap_or_default()
+                    })
+                    .collect::<Vec<H256>>(),
+            ),
+        })
+    }
+
+    /// Returns the notifications sent to a subscriber about the given blocks.
+    fn notifications<C: sov_modules_api::Context>(
+        &self,
+        evm: &Evm<C>,
+        blocks: RangeInclusive<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<Vec<SubscriptionMessage>> {
+        let mut messages = vec![];
+        match self {
+            NewBlocksKind::Logs(filter) => {
+                let blocks = blocks_in_filter_range(filter, blocks);
+                for log in evm.logs_in_blocks(filter, blocks, working_set)? {
+                    messages.push(SubscriptionMessage::from_json(&log)?);
+                }
+            }
+            NewBlocksKind::Blocks => {
+                for number in blocks {
+                    let header = block_header(evm, number, working_set);
+                    messages.push(SubscriptionMessage::from_json(&header)?);
+                }
+            }
+        }
+        Ok(messages)
+    }
+}
+
+/// Returns the number of the head block of the EVM.
+pub(crate) fn head_block_number<C: sov_modules_api::Context>(
+    evm: &Evm<C>,
+    working_set: &mut WorkingSet<C>,
+) -> u64 {
+    evm.block_number(working_set)
+        .expect("Block number must be available")
+        .as_limbs()[0]
+}
+
+pub(crate) fn block_header<C: sov_modules_api::Context>(
+    evm: &Evm<C>,
+    number: u64,
+    working_set: &mut WorkingSet<C>,
+) -> reth_rpc_types::Header {
+    evm.get_block_by_number(Some(format!("0x{:x}", number)), None, working_set)
+        .expect("Block must be available")
+        .expect("Block must be set")
+        .header
+        .clone()
+}
+
+/// Restricts the blocks to the range of the filter, if it has one.
+fn blocks_in_filter_range(filter: &Filter, blocks: RangeInclusive<u64>) -> RangeInclusive<u64> {
+    let from = filter.get_from_block().unwrap_or_default();
+    let to = filter.get_to_block().unwrap_or(u64::MAX);
+    (*blocks.start()).max(from)..=(*blocks.end()).min(to)
+}
+
+/// Sends the notifications about the blocks added to the EVM after each slot to the subscriber,
+/// until the subscription ends.
+pub(crate) async fn forward_new_blocks<C: sov_modules_api::Context>(
+    kind: NewBlocksKind,
+    storage: C::Storage,
+    mut slot_notifications: broadcast::Receiver<u64>,
+    pending_subscription: PendingSubscriptionSink,
+) -> SubscriptionResult {
+    let evm = Evm::<C>::default();
+    let mut next_block = head_block_number(&evm, &mut WorkingSet::<C>::new(storage.clone())) + 1;
+
+    let subscription = pending_subscription.accept().await?;
+    let closed = subscription.closed();
+    futures::pin_mut!(closed);
+
+    loop {
+        let next_slot = slot_notifications.recv();
+        futures::pin_mut!(next_slot);
+        match futures::future::select(closed, next_slot).await {
+            Either::Left(_) => break Ok(()),
+            Either::Right((outcome, channel_closing_future)) => {
+                // A lagging receiver only missed some slots: the new blocks are read from the state.
+                if let Err(broadcast::error::RecvError::Closed) = outcome {
+                    break Ok(());
+                }
+                let messages = {
+                    let mut working_set = WorkingSet::<C>::new(storage.clone());
+                    let head = head_block_number(&evm, &mut working_set);
+                    let messages = kind.notifications(&evm, next_block..=head, &mut working_set)?;
+                    next_block = next_block.max(head + 1);
+                    messages
+                };
+                for msg in messages {
+                    // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                    if subscription.send(msg).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                closed = channel_closing_future;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_report_each_block_once() {
+        let mut filters = Filters::default();
+        let id = filters.install(NewBlocksKind::Blocks, 3);
+
+        let (_, blocks) = filters.poll(id, 3).unwrap();
+        assert!(blocks.is_empty());
+        let (_, blocks) = filters.poll(id, 5).unwrap();
+        assert_eq!(blocks, 4..=5);
+        let (_, blocks) = filters.poll(id, 5).unwrap();
+        assert!(blocks.is_empty());
+
+        assert!(filters.uninstall(id));
+        assert!(!filters.uninstall(id));
+        assert!(matches!(
+            filters.poll(id, 5),
+            Err(EthFilterError::FilterNotFound)
+        ));
+    }
+
+    #[test]
+    fn only_log_filters_have_logs() {
+        let mut filters = Filters::default();
+        let blocks = filters.install(NewBlocksKind::Blocks, 0);
+        let logs = filters.install(
+            NewBlocksKind::Logs(Box::new(Filter::new().from_block(2))),
+            0,
+        );
+        assert_ne!(blocks, logs);
+
+        assert!(filters.log_filter(blocks).is_err());
+        let filter = filters.log_filter(logs).unwrap();
+        assert_eq!(blocks_in_filter_range(&filter, 0..=5), 2..=5);
+    }
+}
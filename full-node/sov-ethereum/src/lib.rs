@@ -1,17 +1,27 @@
 #[cfg(feature = "experimental")]
 mod batch_builder;
 #[cfg(feature = "experimental")]
+mod filters;
+#[cfg(feature = "experimental")]
 mod gas_price;
 #[cfg(feature = "experimental")]
+mod trace;
+#[cfg(feature = "experimental")]
+mod txpool;
+#[cfg(feature = "experimental")]
 pub use experimental::{get_ethereum_rpc, Ethereum};
 #[cfg(feature = "experimental")]
 pub use gas_price::gas_oracle::GasPriceOracleConfig;
 #[cfg(feature = "experimental")]
 pub use sov_evm::DevSigner;
+#[cfg(feature = "experimental")]
+pub use txpool::TxPoolConfig;
 
 #[cfg(feature = "experimental")]
 pub mod experimental {
     use std::array::TryFromSliceError;
+    #[cfg(feature = "dev")]
+    use std::collections::HashSet;
     use std::sync::{Arc, Mutex};
 
     use borsh::ser::BorshSerialize;
@@ -19,18 +29,27 @@ pub mod experimental {
     use ethers::types::{Bytes, H256};
     use jsonrpsee::types::ErrorObjectOwned;
     use jsonrpsee::RpcModule;
-    use reth_primitives::{TransactionSignedNoHash as RethTransactionSignedNoHash, U128, U256};
-    use reth_rpc_types::{CallRequest, TransactionRequest, TypedTransactionRequest};
+    use reth_primitives::{Address, BlockNumberOrTag, TransactionSignedEcRecovered, U128, U256};
+    use reth_rpc_types::trace::geth::GethDebugTracingOptions;
+    use reth_rpc_types::{CallRequest, Filter, TransactionRequest, TypedTransactionRequest};
     use sov_evm::{CallMessage, Evm, RlpEvmTransaction};
+    use sov_modules_api::rpc::StateVersionProvider;
     use sov_modules_api::utils::to_jsonrpsee_error_object;
     use sov_modules_api::{EncodeCall, PrivateKey, WorkingSet};
     use sov_rollup_interface::services::da::DaService;
+    use tokio::sync::broadcast;
 
     use super::batch_builder::EthBatchBuilder;
+    use super::filters::{forward_new_blocks, head_block_number, Filters, NewBlocksKind};
+    use super::trace::{working_set_after_block, working_set_before_block};
+    use super::txpool::{SenderState, TxPool};
     #[cfg(feature = "local")]
     use super::DevSigner;
     use crate::gas_price::gas_oracle::GasPriceOracle;
-    use crate::GasPriceOracleConfig;
+    use crate::{GasPriceOracleConfig, TxPoolConfig};
+
+    #[cfg(feature = "dev")]
+    pub mod dev;
 
     const ETH_RPC_ERROR: &str = "ETH_RPC_ERROR";
 
@@ -39,6 +58,7 @@ pub mod experimental {
         pub min_blob_size: Option<usize>,
         pub sov_tx_signer_priv_key: C::PrivateKey,
         pub gas_price_oracle_config: GasPriceOracleConfig,
+        pub txpool_config: TxPoolConfig,
         #[cfg(feature = "local")]
         pub eth_signer: DevSigner,
     }
@@ -47,6 +67,8 @@ pub mod experimental {
         da_service: Da,
         eth_rpc_config: EthRpcConfig<C>,
         storage: C::Storage,
+        state_versions: Arc<dyn StateVersionProvider>,
+        slot_notifications: broadcast::Receiver<u64>,
     ) -> RpcModule<Ethereum<C, Da>> {
         // Unpack config
         let EthRpcConfig {
@@ -55,20 +77,14 @@ pub mod experimental {
             #[cfg(feature = "local")]
             eth_signer,
             gas_price_oracle_config,
+            txpool_config,
         } = eth_rpc_config;
 
         // Fetch nonce from storage
-        let accounts = sov_accounts::Accounts::<C>::default();
-        let sov_tx_signer_account = accounts
-            .get_account(
-                sov_tx_signer_priv_key.pub_key(),
-                &mut WorkingSet::<C>::new(storage.clone()),
-            )
-            .unwrap();
-        let sov_tx_signer_nonce: u64 = match sov_tx_signer_account {
-            sov_accounts::Response::AccountExists { nonce, .. } => nonce,
-            sov_accounts::Response::AccountEmpty { .. } => 0,
-        };
+        let sov_tx_signer_nonce = account_nonce(
+            sov_tx_signer_priv_key.pub_key(),
+            &mut WorkingSet::<C>::new(storage.clone()),
+        );
 
         let mut rpc = RpcModule::new(Ethereum::new(
             da_service,
@@ -78,9 +94,12 @@ pub mod experimental {
                 min_blob_size,
             ))),
             gas_price_oracle_config,
+            txpool_config,
             #[cfg(feature = "local")]
             eth_signer,
             storage,
+            state_versions,
+            slot_notifications,
         ));
 
         register_rpc_methods(&mut rpc).expect("Failed to register sequencer RPC methods");
@@ -90,10 +109,23 @@ pub mod experimental {
     pub struct Ethereum<C: sov_modules_api::Context, Da: DaService> {
         da_service: Da,
         batch_builder: Arc<Mutex<EthBatchBuilder<C>>>,
+        /// The EVM transactions waiting to be submitted, or to be executed once submitted.
+        txpool: Mutex<TxPool>,
         gas_price_oracle: GasPriceOracle<C>,
         #[cfg(feature = "local")]
         eth_signer: DevSigner,
         storage: C::Storage,
+        /// Resolves the archival state which blocks and calls are traced on.
+        state_versions: Arc<dyn StateVersionProvider>,
+        filters: Mutex<Filters>,
+        /// Notified of each slot processed by the rollup, to push the new blocks to subscribers.
+        slot_notifications: broadcast::Receiver<u64>,
+        /// The accounts `eth_sendTransaction` sends unsigned transactions for, see [`dev`].
+        #[cfg(feature = "dev")]
+        impersonated: Mutex<HashSet<Address>>,
+        /// The states `evm_revert` can go back to, see [`dev`].
+        #[cfg(feature = "dev")]
+        snapshots: Mutex<dev::Snapshots>,
     }
 
     impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
@@ -101,18 +133,29 @@ pub mod experimental {
             da_service: Da,
             batch_builder: Arc<Mutex<EthBatchBuilder<C>>>,
             gas_price_oracle_config: GasPriceOracleConfig,
+            txpool_config: TxPoolConfig,
             #[cfg(feature = "local")] eth_signer: DevSigner,
             storage: C::Storage,
+            state_versions: Arc<dyn StateVersionProvider>,
+            slot_notifications: broadcast::Receiver<u64>,
         ) -> Self {
             let evm = Evm::<C>::default();
             let gas_price_oracle = GasPriceOracle::new(evm, gas_price_oracle_config);
             Self {
                 da_service,
                 batch_builder,
+                txpool: Mutex::new(TxPool::new(txpool_config)),
                 gas_price_oracle,
                 #[cfg(feature = "local")]
                 eth_signer,
                 storage,
+                state_versions,
+                filters: Mutex::new(Filters::default()),
+                slot_notifications,
+                #[cfg(feature = "dev")]
+                impersonated: Mutex::new(HashSet::new()),
+                #[cfg(feature = "dev")]
+                snapshots: Mutex::new(dev::Snapshots::default()),
             }
         }
     }
@@ -121,15 +164,80 @@ pub mod experimental {
         fn make_raw_tx(
             &self,
             raw_tx: RlpEvmTransaction,
-        ) -> Result<(H256, Vec<u8>), jsonrpsee::core::Error> {
-            let signed_transaction: RethTransactionSignedNoHash = raw_tx.clone().try_into()?;
-
-            let tx_hash = signed_transaction.hash();
+        ) -> Result<(TransactionSignedEcRecovered, Vec<u8>), jsonrpsee::core::Error> {
+            let signed_transaction: TransactionSignedEcRecovered = raw_tx.clone().try_into()?;
 
-            let tx = CallMessage { tx: raw_tx };
+            let tx = CallMessage::Transact { tx: raw_tx };
             let message = <Runtime<C, Da::Spec> as EncodeCall<sov_evm::Evm<C>>>::encode_call(tx);
 
-            Ok((H256::from(tx_hash), message))
+            Ok((signed_transaction, message))
+        }
+
+        /// Checks the transaction against the latest state of its sender and adds it to the pool.
+        fn add_to_pool(&self, raw_tx: RlpEvmTransaction) -> Result<H256, ErrorObjectOwned> {
+            let (transaction, message) = self
+                .make_raw_tx(raw_tx)
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            self.add_recovered_to_pool(transaction, message)
+        }
+
+        /// Adds an unsigned transaction of an impersonated account to the pool.
+        #[cfg(feature = "dev")]
+        fn add_impersonated_to_pool(
+            &self,
+            transaction: reth_primitives::Transaction,
+            from: Address,
+        ) -> Result<H256, ErrorObjectOwned> {
+            let tx = RlpEvmTransaction::unsigned(transaction.clone());
+            let signed = reth_primitives::TransactionSigned::from_transaction_and_signature(
+                transaction,
+                Default::default(),
+            );
+            let transaction = TransactionSignedEcRecovered::from_signed_transaction(signed, from);
+
+            let call = sov_evm::DevCallMessage::Impersonate { from: from.0, tx };
+            let message = <Runtime<C, Da::Spec> as EncodeCall<sov_evm::Evm<C>>>::encode_call(
+                CallMessage::Dev { call },
+            );
+            self.add_recovered_to_pool(transaction, message)
+        }
+
+        fn add_recovered_to_pool(
+            &self,
+            transaction: TransactionSignedEcRecovered,
+            message: Vec<u8>,
+        ) -> Result<H256, ErrorObjectOwned> {
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+            let sender = transaction.signer();
+            let sender = SenderState {
+                nonce: state_nonce(&evm, sender, &mut working_set),
+                balance: evm
+                    .get_balance(sender, None, &mut working_set)
+                    .unwrap_or_default(),
+            };
+
+            let tx_hash = self.with_txpool(|txpool, nonce_of| {
+                txpool.add(transaction, message, sender, nonce_of)
+            })?;
+            Ok(H256::from(tx_hash))
+        }
+
+        /// The nonce following the transactions of the address in the latest state and in the pool.
+        fn pending_nonce(&self, address: Address, working_set: &mut WorkingSet<C>) -> u64 {
+            let nonce = state_nonce(&Evm::<C>::default(), address, working_set);
+            self.txpool.lock().unwrap().pending_nonce(address, nonce)
+        }
+
+        /// Calls `f` with the pool and the nonces of the latest state.
+        fn with_txpool<T>(
+            &self,
+            f: impl FnOnce(&mut TxPool, &mut dyn FnMut(Address) -> u64) -> T,
+        ) -> T {
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(self.storage.clone());
+            let mut nonce_of = |address| state_nonce(&evm, address, &mut working_set);
+            f(&mut self.txpool.lock().unwrap(), &mut nonce_of)
         }
 
         async fn build_and_submit_batch(
@@ -176,12 +284,30 @@ pub mod experimental {
 
             Ok(batch)
         }
+    }
 
-        fn add_messages(&self, messages: Vec<Vec<u8>>) {
-            self.batch_builder.lock().unwrap().add_messages(messages);
+    /// The nonce of the next transaction of the rollup account.
+    fn account_nonce<C: sov_modules_api::Context>(
+        pub_key: C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        let accounts = sov_accounts::Accounts::<C>::default();
+        match accounts.get_account(pub_key, working_set).unwrap() {
+            sov_accounts::Response::AccountExists { nonce, .. } => nonce,
+            sov_accounts::Response::AccountEmpty { .. } => 0,
         }
     }
 
+    fn state_nonce<C: sov_modules_api::Context>(
+        evm: &Evm<C>,
+        address: Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        evm.get_transaction_count(address, None, working_set)
+            .map(|nonce| nonce.as_u64())
+            .unwrap_or_default()
+    }
+
     fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         rpc: &mut RpcModule<Ethereum<C, Da>>,
     ) -> Result<(), jsonrpsee::core::Error> {
@@ -213,7 +339,11 @@ pub mod experimental {
         rpc.register_async_method("eth_publishBatch", |params, ethereum| async move {
             let mut params_iter = params.sequence();
 
-            let mut txs = Vec::default();
+            // The pending transactions of the pool are submitted first.
+            let (hashes, mut txs): (Vec<_>, Vec<_>) = ethereum
+                .with_txpool(|txpool, nonce_of| txpool.unsubmitted_pending(nonce_of))
+                .into_iter()
+                .unzip();
             while let Some(tx) = params_iter.optional_next::<Vec<u8>>()? {
                 txs.push(tx)
             }
@@ -222,6 +352,8 @@ pub mod experimental {
                 .build_and_submit_batch(txs, Some(1))
                 .await
                 .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            // The transactions of a batch which didn't reach the DA layer are submitted again.
+            ethereum.txpool.lock().unwrap().mark_submitted(&hashes);
 
             Ok::<String, ErrorObjectOwned>("Submitted transaction".to_string())
         })?;
@@ -233,16 +365,187 @@ pub mod experimental {
 
                 let raw_evm_tx = RlpEvmTransaction { rlp: data.to_vec() };
 
-                let (tx_hash, raw_message) = ethereum
-                    .make_raw_tx(raw_evm_tx)
-                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+                ethereum.add_to_pool(raw_evm_tx)
+            },
+        )?;
+
+        rpc.register_method("eth_getTransactionCount", |parameters, ethereum| {
+            let mut parameters = parameters.sequence();
+            let address: Address = parameters.next()?;
+            let block: Option<String> = parameters.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+            let nonce: reth_primitives::U64 = if block.as_deref() == Some("pending") {
+                ethereum.pending_nonce(address, &mut working_set).into()
+            } else {
+                evm.get_transaction_count(address, block, &mut working_set)?
+            };
+            Ok::<_, ErrorObjectOwned>(nonce)
+        })?;
+
+        rpc.register_method("txpool_content", |_, ethereum| {
+            Ok::<_, ErrorObjectOwned>(
+                ethereum.with_txpool(|txpool, nonce_of| txpool.content(nonce_of)),
+            )
+        })?;
+
+        rpc.register_method("txpool_inspect", |_, ethereum| {
+            Ok::<_, ErrorObjectOwned>(
+                ethereum.with_txpool(|txpool, nonce_of| txpool.inspect(nonce_of)),
+            )
+        })?;
+
+        rpc.register_method("txpool_status", |_, ethereum| {
+            Ok::<_, ErrorObjectOwned>(
+                ethereum.with_txpool(|txpool, nonce_of| txpool.status(nonce_of)),
+            )
+        })?;
 
-                ethereum.add_messages(vec![raw_message]);
+        rpc.register_method("eth_newFilter", |parameters, ethereum| {
+            let filter: Filter = parameters.one()?;
+            let evm = Evm::<C>::default();
+            let head = head_block_number(&evm, &mut WorkingSet::new(ethereum.storage.clone()));
+            let id = ethereum
+                .filters
+                .lock()
+                .unwrap()
+                .install(NewBlocksKind::Logs(Box::new(filter)), head);
+            Ok::<_, ErrorObjectOwned>(id)
+        })?;
 
-                Ok::<_, ErrorObjectOwned>(tx_hash)
+        rpc.register_method("eth_newBlockFilter", |_, ethereum| {
+            let evm = Evm::<C>::default();
+            let head = head_block_number(&evm, &mut WorkingSet::new(ethereum.storage.clone()));
+            let id = ethereum
+                .filters
+                .lock()
+                .unwrap()
+                .install(NewBlocksKind::Blocks, head);
+            Ok::<_, ErrorObjectOwned>(id)
+        })?;
+
+        rpc.register_method("eth_getFilterChanges", |parameters, ethereum| {
+            let id: U256 = parameters.one()?;
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+            let head = head_block_number(&evm, &mut working_set);
+            let (kind, blocks) = ethereum.filters.lock().unwrap().poll(id, head)?;
+            Ok::<_, ErrorObjectOwned>(kind.filter_changes(&evm, blocks, &mut working_set)?)
+        })?;
+
+        rpc.register_method("eth_getFilterLogs", |parameters, ethereum| {
+            let id: U256 = parameters.one()?;
+            let filter = ethereum.filters.lock().unwrap().log_filter(id)?;
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::new(ethereum.storage.clone());
+            Ok::<_, ErrorObjectOwned>(evm.logs_for_filter(&filter, &mut working_set)?)
+        })?;
+
+        rpc.register_method("eth_uninstallFilter", |parameters, ethereum| {
+            let id: U256 = parameters.one()?;
+            Ok::<_, ErrorObjectOwned>(ethereum.filters.lock().unwrap().uninstall(id))
+        })?;
+
+        rpc.register_subscription(
+            "eth_subscribe",
+            "eth_subscription",
+            "eth_unsubscribe",
+            |parameters, pending_subscription, ethereum| async move {
+                let mut parameters = parameters.sequence();
+                let kind = match parameters.next::<String>()?.as_str() {
+                    "newHeads" => NewBlocksKind::Blocks,
+                    "logs" => NewBlocksKind::Logs(Box::new(
+                        parameters.optional_next::<Filter>()?.unwrap_or_default(),
+                    )),
+                    other => {
+                        pending_subscription
+                            .reject(to_jsonrpsee_error_object(
+                                format!("Unsupported subscription: {}", other),
+                                ETH_RPC_ERROR,
+                            ))
+                            .await;
+                        return Ok(());
+                    }
+                };
+                forward_new_blocks::<C>(
+                    kind,
+                    ethereum.storage.clone(),
+                    ethereum.slot_notifications.resubscribe(),
+                    pending_subscription,
+                )
+                .await
             },
         )?;
 
+        rpc.register_method("debug_traceTransaction", |parameters, ethereum| {
+            let mut parameters = parameters.sequence();
+            let tx_hash: reth_primitives::H256 = parameters.next()?;
+            let opts: GethDebugTracingOptions = parameters.optional_next()?.unwrap_or_default();
+
+            let evm = Evm::<C>::default();
+            let (replay, index) =
+                evm.transaction_replay(tx_hash, &mut WorkingSet::new(ethereum.storage.clone()))?;
+            let mut working_set = working_set_before_block(
+                ethereum.storage.clone(),
+                ethereum.state_versions.as_ref(),
+                &replay,
+            )?;
+            Ok::<_, ErrorObjectOwned>(evm.trace_transaction(
+                &replay,
+                index,
+                &opts,
+                &mut working_set,
+            )?)
+        })?;
+
+        rpc.register_method("debug_traceBlockByNumber", |parameters, ethereum| {
+            let mut parameters = parameters.sequence();
+            let block: BlockNumberOrTag = parameters.next()?;
+            let opts: GethDebugTracingOptions = parameters.optional_next()?.unwrap_or_default();
+
+            let evm = Evm::<C>::default();
+            let replay = evm.block_replay(block, &mut WorkingSet::new(ethereum.storage.clone()))?;
+            let mut working_set = working_set_before_block(
+                ethereum.storage.clone(),
+                ethereum.state_versions.as_ref(),
+                &replay,
+            )?;
+            Ok::<_, ErrorObjectOwned>(evm.trace_block(&replay, None, &opts, &mut working_set)?)
+        })?;
+
+        rpc.register_method("debug_traceCall", |parameters, ethereum| {
+            let mut parameters = parameters.sequence();
+            let request: CallRequest = parameters.next()?;
+            let block: BlockNumberOrTag = parameters.optional_next()?.unwrap_or_default();
+            let opts: GethDebugTracingOptions = parameters.optional_next()?.unwrap_or_default();
+
+            let evm = Evm::<C>::default();
+            let replay = evm.block_replay(block, &mut WorkingSet::new(ethereum.storage.clone()))?;
+            let mut working_set = working_set_after_block(
+                ethereum.storage.clone(),
+                ethereum.state_versions.as_ref(),
+                &replay,
+            )?;
+            Ok::<_, ErrorObjectOwned>(evm.trace_call(request, &replay, &opts, &mut working_set)?)
+        })?;
+
+        rpc.register_method("eth_getProof", |parameters, ethereum| {
+            let mut parameters = parameters.sequence();
+            let address: Address = parameters.next()?;
+            let keys: Vec<U256> = parameters.next()?;
+            let block: BlockNumberOrTag = parameters.optional_next()?.unwrap_or_default();
+
+            let evm = Evm::<C>::default();
+            let replay = evm.block_replay(block, &mut WorkingSet::new(ethereum.storage.clone()))?;
+            let mut working_set = working_set_after_block(
+                ethereum.storage.clone(),
+                ethereum.state_versions.as_ref(),
+                &replay,
+            )?;
+            Ok::<_, ErrorObjectOwned>(evm.get_proof(address, keys, &replay, &mut working_set)?)
+        })?;
+
         #[cfg(feature = "local")]
         rpc.register_async_method("eth_accounts", |_parameters, ethereum| async move {
             Ok::<_, ErrorObjectOwned>(ethereum.eth_signer.signers())
@@ -259,24 +562,26 @@ pub mod experimental {
                 .from
                 .ok_or(to_jsonrpsee_error_object("No from address", ETH_RPC_ERROR))?;
 
-            // return error if not in signers
-            if !ethereum.eth_signer.signers().contains(&from) {
+            // return error if not in signers, unless impersonated
+            #[cfg(feature = "dev")]
+            let impersonated = ethereum.impersonated.lock().unwrap().contains(&from);
+            #[cfg(not(feature = "dev"))]
+            let impersonated = false;
+            if !impersonated && !ethereum.eth_signer.signers().contains(&from) {
                 return Err(to_jsonrpsee_error_object(
                     "From address not in signers",
                     ETH_RPC_ERROR,
                 ));
             }
 
-            let raw_evm_tx = {
+            let transaction = {
                 let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
 
-                // set nonce if none
+                // set nonce if none, after the transactions of the pool
                 if transaction_request.nonce.is_none() {
-                    let nonce = evm
-                        .get_transaction_count(from, None, &mut working_set)
-                        .unwrap_or_default();
+                    let nonce = ethereum.pending_nonce(from, &mut working_set);
 
-                    transaction_request.nonce = Some(nonce);
+                    transaction_request.nonce = Some(nonce.into());
                 }
 
                 // get current chain id
@@ -328,27 +633,26 @@ pub mod experimental {
                 };
 
                 // get raw transaction
-                let transaction = into_transaction(transaction_request).map_err(|_| {
+                into_transaction(transaction_request).map_err(|_| {
                     to_jsonrpsee_error_object("Invalid types in transaction request", ETH_RPC_ERROR)
-                })?;
+                })?
+            };
 
-                // sign transaction
-                let signed_tx = ethereum
-                    .eth_signer
-                    .sign_transaction(transaction, from)
-                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            // impersonated accounts send their transactions unsigned
+            #[cfg(feature = "dev")]
+            if impersonated {
+                return ethereum.add_impersonated_to_pool(transaction, from);
+            }
 
-                RlpEvmTransaction {
-                    rlp: signed_tx.envelope_encoded().to_vec(),
-                }
-            };
-            let (tx_hash, raw_message) = ethereum
-                .make_raw_tx(raw_evm_tx)
+            // sign transaction
+            let signed_tx = ethereum
+                .eth_signer
+                .sign_transaction(transaction, from)
                 .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
 
-            ethereum.add_messages(vec![raw_message]);
-
-            Ok::<_, ErrorObjectOwned>(tx_hash)
+            ethereum.add_to_pool(RlpEvmTransaction {
+                rlp: signed_tx.envelope_encoded().to_vec(),
+            })
         })?;
 
         Ok(())
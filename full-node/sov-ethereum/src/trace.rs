@@ -0,0 +1,54 @@
+//! Working sets on top of the archival state which blocks and calls are traced on.
+
+use jsonrpsee::types::ErrorObjectOwned;
+use reth_primitives::H256;
+use sov_evm::{BlockReplay, EthApiError};
+use sov_modules_api::rpc::{StateIdentifier, StateVersionProvider};
+use sov_modules_api::utils::working_set_at;
+use sov_modules_api::{Spec, WorkingSet};
+
+/// Returns a working set on top of the state the transactions of the block were executed on.
+pub(crate) fn working_set_before_block<C: sov_modules_api::Context>(
+    storage: <C as Spec>::Storage,
+    state_versions: &dyn StateVersionProvider,
+    replay: &BlockReplay<C>,
+) -> Result<WorkingSet<C>, ErrorObjectOwned> {
+    let parent_state_root = replay.parent_state_root().ok_or(EthApiError::Unsupported(
+        "The genesis block is not traceable",
+    ))?;
+    working_set_at_block(
+        storage,
+        state_versions,
+        replay.number() - 1,
+        parent_state_root,
+    )
+}
+
+/// Returns a working set on top of the state reached after the block.
+pub(crate) fn working_set_after_block<C: sov_modules_api::Context>(
+    storage: <C as Spec>::Storage,
+    state_versions: &dyn StateVersionProvider,
+    replay: &BlockReplay<C>,
+) -> Result<WorkingSet<C>, ErrorObjectOwned> {
+    working_set_at_block(
+        storage,
+        state_versions,
+        replay.number(),
+        replay.state_root(),
+    )
+}
+
+fn working_set_at_block<C: sov_modules_api::Context>(
+    storage: <C as Spec>::Storage,
+    state_versions: &dyn StateVersionProvider,
+    number: u64,
+    state_root: H256,
+) -> Result<WorkingSet<C>, ErrorObjectOwned> {
+    // The genesis state is not committed as a slot, so it's only known by its height.
+    let state_id = if number == 0 {
+        StateIdentifier::SlotHeight(0)
+    } else {
+        StateIdentifier::StateRoot(state_root.0.to_vec())
+    };
+    working_set_at(storage, state_versions, Some(state_id))
+}
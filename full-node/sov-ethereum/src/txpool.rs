@@ -0,0 +1,571 @@
+//! The pool of the EVM transactions sent to the node, queued by sender and nonce until they can be
+//! submitted in a batch.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use reth_primitives::{Address, TransactionSignedEcRecovered, H256, U256, U64};
+use reth_rpc_types::txpool::{TxpoolContent, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus};
+use serde::{Deserialize, Serialize};
+use sov_evm::{RpcInvalidTransactionError, RpcPoolError};
+
+/// The default minimum fee increase of a replacement transaction, in percent
+pub const DEFAULT_PRICE_BUMP: u128 = 10;
+
+/// The default maximum number of transactions of a sender in the pool
+pub const DEFAULT_MAX_ACCOUNT_SLOTS: usize = 16;
+
+/// The default maximum number of transactions in the pool
+pub const DEFAULT_MAX_POOL_SIZE: usize = 4096;
+
+/// Settings for the transaction pool configured by node operators
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxPoolConfig {
+    /// The minimum increase of both the fee cap and the priority fee of a transaction replacing
+    /// another one with the same nonce, in percent
+    pub price_bump: u128,
+
+    /// The maximum number of transactions of a sender in the pool
+    pub max_account_slots: usize,
+
+    /// The maximum number of transactions in the pool
+    pub max_pool_size: usize,
+}
+
+impl Default for TxPoolConfig {
+    fn default() -> Self {
+        TxPoolConfig {
+            price_bump: DEFAULT_PRICE_BUMP,
+            max_account_slots: DEFAULT_MAX_ACCOUNT_SLOTS,
+            max_pool_size: DEFAULT_MAX_POOL_SIZE,
+        }
+    }
+}
+
+/// The account of a sender in the latest state, which its transactions are checked against.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SenderState {
+    pub(crate) nonce: u64,
+    pub(crate) balance: U256,
+}
+
+struct PoolTransaction {
+    transaction: TransactionSignedEcRecovered,
+    /// The transaction as a call message of the runtime.
+    message: Vec<u8>,
+    /// Whether the transaction was already submitted in a batch.
+    submitted: bool,
+}
+
+/// The transactions of each sender by nonce.
+///
+/// The transactions following the nonce of their sender without gap are pending: they can be
+/// executed, and are submitted with the next batch. The others are queued until the gap is filled.
+/// Submitted transactions stay in the pool, so that they count in the pending nonce of their
+/// sender, until their nonce is used in the latest state.
+#[derive(Default)]
+pub(crate) struct TxPool {
+    config: TxPoolConfig,
+    senders: HashMap<Address, BTreeMap<u64, PoolTransaction>>,
+}
+
+impl TxPool {
+    pub(crate) fn new(config: TxPoolConfig) -> Self {
+        Self {
+            config,
+            senders: HashMap::new(),
+        }
+    }
+
+    /// Adds a transaction whose sender is in the given state, replacing the transaction of the
+    /// sender with the same nonce if its fees are bumped enough. Returns the transaction hash.
+    ///
+    /// The sender must be able to pay for all its transactions in the pool. `nonce_of` gives the
+    /// nonces of the latest state, to drop the executed transactions when the pool is full.
+    pub(crate) fn add(
+        &mut self,
+        transaction: TransactionSignedEcRecovered,
+        message: Vec<u8>,
+        sender: SenderState,
+        nonce_of: impl FnMut(Address) -> u64,
+    ) -> Result<H256, RpcPoolError> {
+        let nonce = transaction.nonce();
+        if nonce < sender.nonce {
+            return Err(RpcInvalidTransactionError::NonceTooLow.into());
+        }
+
+        let hash = transaction.hash();
+        let is_new = match self
+            .senders
+            .get(&transaction.signer())
+            .and_then(|transactions| transactions.get(&nonce))
+        {
+            Some(existing) if existing.transaction.hash() == hash => {
+                return Err(RpcPoolError::AlreadyKnown);
+            }
+            Some(existing) => {
+                if !is_replacement(&existing.transaction, &transaction, self.config.price_bump) {
+                    return Err(RpcPoolError::ReplaceUnderpriced);
+                }
+                false
+            }
+            None => true,
+        };
+        if is_new && self.len() >= self.config.max_pool_size {
+            self.prune(nonce_of);
+            if self.len() >= self.config.max_pool_size {
+                return Err(RpcPoolError::TxPoolOverflow);
+            }
+        }
+
+        let transactions = self.senders.entry(transaction.signer()).or_default();
+        *transactions = transactions.split_off(&sender.nonce);
+        if is_new && transactions.len() >= self.config.max_account_slots {
+            return Err(RpcPoolError::TxPoolOverflow);
+        }
+        // The transaction it replaces, if any, won't be paid for.
+        let queued_cost = transactions
+            .iter()
+            .filter(|(queued_nonce, _)| **queued_nonce != nonce)
+            .map(|(_, queued)| cost(&queued.transaction))
+            .fold(U256::ZERO, |total, cost| total.saturating_add(cost));
+        if queued_cost.saturating_add(cost(&transaction)) > sender.balance {
+            return Err(RpcInvalidTransactionError::InsufficientFunds.into());
+        }
+
+        transactions.insert(
+            nonce,
+            PoolTransaction {
+                transaction,
+                message,
+                submitted: false,
+            },
+        );
+        Ok(hash)
+    }
+
+    /// The nonce following the pending transactions of a sender whose nonce is `nonce` in the
+    /// latest state.
+    pub(crate) fn pending_nonce(&self, address: Address, nonce: u64) -> u64 {
+        self.senders
+            .get(&address)
+            .map(|transactions| pending_end(transactions, nonce))
+            .unwrap_or(nonce)
+    }
+
+    /// The hashes and the messages of the pending transactions which weren't submitted yet, in
+    /// nonce order for each sender. They're only marked as submitted by
+    /// [`TxPool::mark_submitted`], once their batch reached the DA layer.
+    pub(crate) fn unsubmitted_pending(
+        &mut self,
+        nonce_of: impl FnMut(Address) -> u64,
+    ) -> Vec<(H256, Vec<u8>)> {
+        let nonces = self.prune(nonce_of);
+        let mut messages = Vec::new();
+        for (address, transactions) in &self.senders {
+            let nonce = nonces[address];
+            let end = pending_end(transactions, nonce);
+            for (_, pending) in transactions.range(nonce..end) {
+                if !pending.submitted {
+                    messages.push((pending.transaction.hash(), pending.message.clone()));
+                }
+            }
+        }
+        messages
+    }
+
+    /// Marks the transactions with the given hashes as submitted. The transactions replaced
+    /// in the meantime aren't in the pool anymore, so their replacements aren't marked.
+    pub(crate) fn mark_submitted(&mut self, hashes: &[H256]) {
+        let hashes: HashSet<&H256> = hashes.iter().collect();
+        for transactions in self.senders.values_mut() {
+            for pending in transactions.values_mut() {
+                if hashes.contains(&pending.transaction.hash()) {
+                    pending.submitted = true;
+                }
+            }
+        }
+    }
+
+    /// The pending and queued transactions, as returned by `txpool_content`.
+    pub(crate) fn content(&mut self, nonce_of: impl FnMut(Address) -> u64) -> TxpoolContent {
+        let mut content = TxpoolContent::default();
+        self.for_each(nonce_of, |pending, transaction| {
+            let section = if pending {
+                &mut content.pending
+            } else {
+                &mut content.queued
+            };
+            section.entry(transaction.signer()).or_default().insert(
+                transaction.nonce().to_string(),
+                reth_rpc_types_compat::from_recovered(transaction.clone()),
+            );
+        });
+        content
+    }
+
+    /// A summary of the pending and queued transactions, as returned by `txpool_inspect`.
+    pub(crate) fn inspect(&mut self, nonce_of: impl FnMut(Address) -> u64) -> TxpoolInspect {
+        let mut inspect = TxpoolInspect::default();
+        self.for_each(nonce_of, |pending, transaction| {
+            let section = if pending {
+                &mut inspect.pending
+            } else {
+                &mut inspect.queued
+            };
+            section.entry(transaction.signer()).or_default().insert(
+                transaction.nonce().to_string(),
+                TxpoolInspectSummary {
+                    to: transaction.to(),
+                    value: U256::from(transaction.value()),
+                    gas: U256::from(transaction.gas_limit()),
+                    gas_price: U256::from(transaction.max_fee_per_gas()),
+                },
+            );
+        });
+        inspect
+    }
+
+    /// The number of pending and queued transactions, as returned by `txpool_status`.
+    pub(crate) fn status(&mut self, nonce_of: impl FnMut(Address) -> u64) -> TxpoolStatus {
+        let (mut pending, mut queued) = (0u64, 0u64);
+        self.for_each(nonce_of, |is_pending, _| {
+            if is_pending {
+                pending += 1;
+            } else {
+                queued += 1;
+            }
+        });
+        TxpoolStatus {
+            pending: U64::from(pending),
+            queued: U64::from(queued),
+        }
+    }
+
+    /// Calls `f` with each transaction, and whether it's pending.
+    fn for_each(
+        &mut self,
+        nonce_of: impl FnMut(Address) -> u64,
+        mut f: impl FnMut(bool, &TransactionSignedEcRecovered),
+    ) {
+        let nonces = self.prune(nonce_of);
+        for (address, transactions) in &self.senders {
+            let end = pending_end(transactions, nonces[address]);
+            for (nonce, pending) in transactions {
+                f(*nonce < end, &pending.transaction);
+            }
+        }
+    }
+
+    /// The number of transactions in the pool.
+    fn len(&self) -> usize {
+        self.senders.values().map(BTreeMap::len).sum()
+    }
+
+    /// Drops the transactions whose nonce is used in the latest state, and returns the nonce of
+    /// each sender.
+    fn prune(&mut self, mut nonce_of: impl FnMut(Address) -> u64) -> HashMap<Address, u64> {
+        let mut nonces = HashMap::new();
+        self.senders.retain(|address, transactions| {
+            let nonce = nonce_of(*address);
+            *transactions = transactions.split_off(&nonce);
+            nonces.insert(*address, nonce);
+            !transactions.is_empty()
+        });
+        nonces
+    }
+}
+
+/// The nonce following the transactions which follow `nonce` without gap.
+fn pending_end(transactions: &BTreeMap<u64, PoolTransaction>, nonce: u64) -> u64 {
+    let mut end = nonce;
+    for next in transactions.range(nonce..).map(|(next, _)| *next) {
+        if next != end {
+            break;
+        }
+        end += 1;
+    }
+    end
+}
+
+/// The maximum amount the transaction can take from the balance of its sender.
+fn cost(transaction: &TransactionSignedEcRecovered) -> U256 {
+    U256::from(transaction.gas_limit()) * U256::from(transaction.max_fee_per_gas())
+        + U256::from(transaction.value())
+}
+
+/// Whether `new` bumps both the fee cap and the priority fee of `existing` by `price_bump` percent.
+fn is_replacement(
+    existing: &TransactionSignedEcRecovered,
+    new: &TransactionSignedEcRecovered,
+    price_bump: u128,
+) -> bool {
+    let priority_fee = |transaction: &TransactionSignedEcRecovered| {
+        transaction
+            .max_priority_fee_per_gas()
+            .unwrap_or_else(|| transaction.max_fee_per_gas())
+    };
+    let is_bumped =
+        |existing: u128, new: u128| new >= existing.saturating_mul(100 + price_bump) / 100;
+    is_bumped(existing.max_fee_per_gas(), new.max_fee_per_gas())
+        && is_bumped(priority_fee(existing), priority_fee(new))
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::{
+        AccessList, Bytes, Signature, Transaction, TransactionKind, TransactionSigned, TxEip1559,
+    };
+
+    use super::*;
+
+    const BALANCE: u64 = 1_000_000_000;
+
+    fn sender() -> SenderState {
+        SenderState {
+            nonce: 0,
+            balance: U256::from(BALANCE),
+        }
+    }
+
+    fn transaction(
+        signer: Address,
+        nonce: u64,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    ) -> TransactionSignedEcRecovered {
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 21_000,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to: TransactionKind::Call(Address::zero()),
+            value: 0,
+            input: Bytes::default(),
+            access_list: AccessList::default(),
+        });
+        TransactionSignedEcRecovered::from_signed_transaction(
+            TransactionSigned::from_transaction_and_signature(transaction, Signature::default()),
+            signer,
+        )
+    }
+
+    fn add(pool: &mut TxPool, nonce: u64) -> Result<H256, RpcPoolError> {
+        pool.add(
+            transaction(Address::zero(), nonce, 100, 10),
+            vec![nonce as u8],
+            sender(),
+            |_| 0,
+        )
+    }
+
+    /// Submits the pending transactions of a sender whose nonce is `nonce`.
+    fn submit(pool: &mut TxPool, nonce: u64) -> Vec<Vec<u8>> {
+        let (hashes, messages): (Vec<_>, Vec<_>) =
+            pool.unsubmitted_pending(|_| nonce).into_iter().unzip();
+        pool.mark_submitted(&hashes);
+        messages
+    }
+
+    #[test]
+    fn transactions_after_a_nonce_gap_are_queued() {
+        let mut pool = TxPool::default();
+        add(&mut pool, 0).unwrap();
+        add(&mut pool, 2).unwrap();
+        assert_eq!(pool.pending_nonce(Address::zero(), 0), 1);
+        let status = pool.status(|_| 0);
+        assert_eq!(
+            (status.pending, status.queued),
+            (U64::from(1), U64::from(1))
+        );
+
+        add(&mut pool, 1).unwrap();
+        assert_eq!(pool.pending_nonce(Address::zero(), 0), 3);
+        let content = pool.content(|_| 0);
+        assert_eq!(content.pending[&Address::zero()].len(), 3);
+        assert!(content.queued.is_empty());
+    }
+
+    #[test]
+    fn pending_transactions_are_submitted_once() {
+        let mut pool = TxPool::default();
+        add(&mut pool, 1).unwrap();
+        add(&mut pool, 0).unwrap();
+        add(&mut pool, 3).unwrap();
+
+        assert_eq!(submit(&mut pool, 0), vec![vec![0], vec![1]]);
+        assert!(submit(&mut pool, 0).is_empty());
+        // Submitted transactions count in the pending nonce until they're executed.
+        assert_eq!(pool.pending_nonce(Address::zero(), 0), 2);
+
+        add(&mut pool, 2).unwrap();
+        assert_eq!(submit(&mut pool, 2), vec![vec![2], vec![3]]);
+        let status = pool.status(|_| 4);
+        assert_eq!(
+            (status.pending, status.queued),
+            (U64::from(0), U64::from(0))
+        );
+    }
+
+    #[test]
+    fn replacements_must_bump_both_fees() {
+        let mut pool = TxPool::default();
+        let signer = Address::zero();
+        let hash = pool
+            .add(transaction(signer, 0, 100, 10), vec![0], sender(), |_| 0)
+            .unwrap();
+
+        assert!(matches!(
+            pool.add(transaction(signer, 0, 100, 10), vec![0], sender(), |_| 0),
+            Err(RpcPoolError::AlreadyKnown)
+        ));
+        for (max_fee_per_gas, max_priority_fee_per_gas) in [(200, 10), (109, 20)] {
+            assert!(matches!(
+                pool.add(
+                    transaction(signer, 0, max_fee_per_gas, max_priority_fee_per_gas),
+                    vec![1],
+                    sender(),
+                    |_| 0
+                ),
+                Err(RpcPoolError::ReplaceUnderpriced)
+            ));
+        }
+
+        let replacement = pool
+            .add(transaction(signer, 0, 110, 11), vec![1], sender(), |_| 0)
+            .unwrap();
+        assert_ne!(replacement, hash);
+        let content = pool.content(|_| 0);
+        assert_eq!(content.pending[&signer]["0"].hash, replacement);
+        assert_eq!(submit(&mut pool, 0), vec![vec![1]]);
+    }
+
+    #[test]
+    fn invalid_transactions_are_rejected() {
+        let mut pool = TxPool::new(TxPoolConfig {
+            max_account_slots: 2,
+            ..Default::default()
+        });
+        let signer = Address::zero();
+
+        let used_nonce = SenderState {
+            nonce: 1,
+            ..sender()
+        };
+        assert!(matches!(
+            pool.add(transaction(signer, 0, 100, 10), vec![0], used_nonce, |_| 0),
+            Err(RpcPoolError::Invalid(
+                RpcInvalidTransactionError::NonceTooLow
+            ))
+        ));
+
+        // The gas limit can't be paid at the fee cap.
+        let fee_cap = (BALANCE / 21_000 + 1) as u128;
+        assert!(matches!(
+            pool.add(
+                transaction(signer, 0, fee_cap, 10),
+                vec![0],
+                sender(),
+                |_| 0
+            ),
+            Err(RpcPoolError::Invalid(
+                RpcInvalidTransactionError::InsufficientFunds
+            ))
+        ));
+
+        add(&mut pool, 0).unwrap();
+        add(&mut pool, 1).unwrap();
+        assert!(matches!(
+            add(&mut pool, 2),
+            Err(RpcPoolError::TxPoolOverflow)
+        ));
+    }
+
+    #[test]
+    fn failed_submissions_are_retried() {
+        let mut pool = TxPool::default();
+        add(&mut pool, 0).unwrap();
+
+        let pending = pool.unsubmitted_pending(|_| 0);
+        assert_eq!(pending.len(), 1);
+        // The batch didn't reach the DA layer, so the transaction is handed out again.
+        assert_eq!(pool.unsubmitted_pending(|_| 0), pending);
+        pool.mark_submitted(&[pending[0].0]);
+        assert!(pool.unsubmitted_pending(|_| 0).is_empty());
+    }
+
+    #[test]
+    fn the_pool_size_is_bounded() {
+        let mut pool = TxPool::new(TxPoolConfig {
+            max_pool_size: 2,
+            ..Default::default()
+        });
+        let other_sender = Address::repeat_byte(1);
+        add(&mut pool, 0).unwrap();
+        add(&mut pool, 1).unwrap();
+        assert!(matches!(
+            pool.add(
+                transaction(other_sender, 0, 100, 10),
+                vec![2],
+                sender(),
+                |_| 0
+            ),
+            Err(RpcPoolError::TxPoolOverflow)
+        ));
+        // Replacements don't grow the pool.
+        pool.add(
+            transaction(Address::zero(), 1, 110, 11),
+            vec![3],
+            sender(),
+            |_| 0,
+        )
+        .unwrap();
+
+        // The executed transactions make room for new ones.
+        pool.add(
+            transaction(other_sender, 0, 100, 10),
+            vec![2],
+            sender(),
+            |address| if address == Address::zero() { 1 } else { 0 },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn senders_must_pay_for_all_their_transactions() {
+        let mut pool = TxPool::default();
+        let signer = Address::zero();
+        // Each transaction costs a bit more than a third of the balance.
+        let fee_cap = (BALANCE / 3 / 21_000 + 1) as u128;
+        for nonce in 0..2 {
+            pool.add(
+                transaction(signer, nonce, fee_cap, 10),
+                vec![nonce as u8],
+                sender(),
+                |_| 0,
+            )
+            .unwrap();
+        }
+        assert!(matches!(
+            pool.add(
+                transaction(signer, 2, fee_cap, 10),
+                vec![2],
+                sender(),
+                |_| 0
+            ),
+            Err(RpcPoolError::Invalid(
+                RpcInvalidTransactionError::InsufficientFunds
+            ))
+        ));
+
+        // A replacement only pays for itself.
+        pool.add(
+            transaction(signer, 1, fee_cap * 11 / 10, 11),
+            vec![1],
+            sender(),
+            |_| 0,
+        )
+        .unwrap();
+    }
+}
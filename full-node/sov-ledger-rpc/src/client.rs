@@ -5,7 +5,8 @@
 
 use jsonrpsee::proc_macros::rpc;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventFilter, EventIdentifier, EventResponse, FilteredEventsResponse,
+    ProofResponse, QueryMode, SlotIdentifier, TxIdentifier,
 };
 use sov_rollup_interface::stf::Event;
 
@@ -138,8 +139,36 @@ where
         query_mode: QueryMode,
     ) -> RpcResult<Vec<Option<Tx>>>;
 
+    /// Gets a page of the events matching a filter, ordered by event number.
+    /// Pass the `next_cursor` of the response as `cursor` to fetch the next page.
+    #[method(name = "getFilteredEvents")]
+    async fn get_filtered_events(
+        &self,
+        filter: EventFilter,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> RpcResult<FilteredEventsResponse>;
+
+    /// Gets the proof posted to the DA which covers a slot.
+    #[method(name = "getProofBySlot")]
+    async fn get_proof_by_slot(&self, slot_id: SlotIdentifier) -> RpcResult<Option<ProofResponse>>;
+
     /// Subscription method to receive a notification each time a slot is
     /// processed.
     #[subscription(name = "subscribeSlots", item = u64)]
     async fn subscribe_slots(&self) -> SubscriptionResult;
+
+    /// Subscription method to receive each processed slot, with its children
+    /// populated according to the query mode.
+    #[subscription(name = "subscribeSlotResponses", item = Slot)]
+    async fn subscribe_slot_responses(&self, query_mode: QueryMode) -> SubscriptionResult;
+
+    /// Subscription method to receive each transaction of the processed slots.
+    #[subscription(name = "subscribeTransactions", item = Tx)]
+    async fn subscribe_transactions(&self) -> SubscriptionResult;
+
+    /// Subscription method to receive the events of the processed slots which
+    /// match the filter. The range of the filter is ignored.
+    #[subscription(name = "subscribeEvents", item = EventResponse)]
+    async fn subscribe_events(&self, filter: EventFilter) -> SubscriptionResult;
 }
@@ -1,12 +1,16 @@
 //! A JSON-RPC server implementation for any [`LedgerRpcProvider`].
 
+use std::sync::Arc;
+
 use futures::future::Either;
+use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::types::ErrorObjectOwned;
-use jsonrpsee::{RpcModule, SubscriptionMessage};
+use jsonrpsee::{PendingSubscriptionSink, RpcModule, SubscriptionMessage};
 use serde::de::DeserializeOwned;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventFilter, EventIdentifier, EventRange, ItemOrHash, LedgerRpcProvider,
+    QueryMode, SlotIdentifier, TxIdentifier,
 };
 
 use crate::HexHash;
@@ -142,48 +146,163 @@ where
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
 
+    // Filtered getters.
+    rpc.register_method("ledger_getFilteredEvents", move |params, ledger| {
+        let args: FilteredEventsArgs = params.parse()?;
+        ledger
+            .get_filtered_events(&args.0, args.1, args.2)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+
+    // Proofs.
+    rpc.register_method("ledger_getProofBySlot", move |params, ledger| {
+        let slot_id: SlotIdentifier = params.one()?;
+        ledger
+            .get_proof_by_slot(&slot_id)
+            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+
+    // Subscriptions.
     rpc.register_subscription(
         "ledger_subscribeSlots",
         "ledger_slotProcessed",
         "ledger_unsubscribeSlots",
-        |_, pending_subscription, db| async move {
-            // Register with the ledgerDB to receive callbacks
-            let mut rx = db
-                .subscribe_slots()
-                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
-
-            // Accept the subscription. This message is sent immediately
-            let subscription = pending_subscription.accept().await?;
-            let closed = subscription.closed();
-            futures::pin_mut!(closed);
-
-            // This loop continues running until the subscription ends.
-            loop {
-                let next_msg = rx.recv();
-                futures::pin_mut!(next_msg);
-                match futures::future::select(closed, next_msg).await {
-                    // If the subscription closed, we're done
-                    Either::Left(_) => break Ok(()),
-                    // Otherwise, we need to send the message
-                    Either::Right((outcome, channel_closing_future)) => {
-                        let msg = SubscriptionMessage::from_json(&outcome?)?;
-                        // Sending only fails if the subscriber has canceled, so we can stop sending messages
-                        if subscription.send(msg).await.is_err() {
-                            break Ok(());
+        |_, pending_subscription, db| {
+            forward_slot_notifications(db, pending_subscription, |_, slot_number| {
+                Ok(vec![SubscriptionMessage::from_json(&slot_number)?])
+            })
+        },
+    )?;
+    rpc.register_subscription(
+        "ledger_subscribeSlotResponses",
+        "ledger_slotResponse",
+        "ledger_unsubscribeSlotResponses",
+        |params, pending_subscription, db| async move {
+            let query_mode = params
+                .sequence()
+                .optional_next()?
+                .unwrap_or(QueryMode::Full);
+            forward_slot_notifications(db, pending_subscription, move |ledger, slot_number| {
+                let slot = ledger.get_slot_by_number::<B, Tx>(slot_number, query_mode)?;
+                Ok(match slot {
+                    Some(slot) => vec![SubscriptionMessage::from_json(&slot)?],
+                    None => vec![],
+                })
+            })
+            .await
+        },
+    )?;
+    rpc.register_subscription(
+        "ledger_subscribeTransactions",
+        "ledger_transaction",
+        "ledger_unsubscribeTransactions",
+        |_, pending_subscription, db| {
+            forward_slot_notifications(db, pending_subscription, |ledger, slot_number| {
+                let mut messages = vec![];
+                let slot = ledger.get_slot_by_number::<B, Tx>(slot_number, QueryMode::Full)?;
+                for batch in slot.and_then(|slot| slot.batches).unwrap_or_default() {
+                    if let ItemOrHash::Full(batch) = batch {
+                        for tx in batch.txs.unwrap_or_default() {
+                            if let ItemOrHash::Full(tx) = tx {
+                                messages.push(SubscriptionMessage::from_json(&tx)?);
+                            }
                         }
-                        closed = channel_closing_future;
                     }
                 }
-            }
+                Ok(messages)
+            })
+        },
+    )?;
+    rpc.register_subscription(
+        "ledger_subscribeEvents",
+        "ledger_event",
+        "ledger_unsubscribeEvents",
+        |params, pending_subscription, db| async move {
+            let filter: EventFilter = params.sequence().optional_next()?.unwrap_or_default();
+            forward_slot_notifications(db, pending_subscription, move |ledger, slot_number| {
+                let filter = EventFilter {
+                    range: Some(EventRange::Slots {
+                        start: slot_number,
+                        end: slot_number,
+                    }),
+                    ..filter.clone()
+                };
+                let mut messages = vec![];
+                let mut cursor = None;
+                loop {
+                    let page = ledger.get_filtered_events(&filter, cursor, None)?;
+                    for event in page.events {
+                        messages.push(SubscriptionMessage::from_json(&event)?);
+                    }
+                    match page.next_cursor {
+                        Some(next_cursor) => cursor = Some(next_cursor),
+                        None => break,
+                    }
+                }
+                Ok(messages)
+            })
+            .await
         },
     )?;
 
     Ok(rpc)
 }
 
+/// Sends the messages built by `notifications_for_slot` to the subscriber
+/// each time a slot is committed to the ledger, until the subscription ends.
+async fn forward_slot_notifications<T, F>(
+    ledger: Arc<T>,
+    pending_subscription: PendingSubscriptionSink,
+    notifications_for_slot: F,
+) -> SubscriptionResult
+where
+    T: LedgerRpcProvider + Send + Sync + 'static,
+    F: Fn(&T, u64) -> anyhow::Result<Vec<SubscriptionMessage>> + Send,
+{
+    // Register with the ledgerDB to receive callbacks
+    let mut rx = ledger
+        .subscribe_slots()
+        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+
+    // Accept the subscription. This message is sent immediately
+    let subscription = pending_subscription.accept().await?;
+    let closed = subscription.closed();
+    futures::pin_mut!(closed);
+
+    // This loop continues running until the subscription ends.
+    loop {
+        let next_msg = rx.recv();
+        futures::pin_mut!(next_msg);
+        match futures::future::select(closed, next_msg).await {
+            // If the subscription closed, we're done
+            Either::Left(_) => break Ok(()),
+            // Otherwise, we need to send the messages for the new slot
+            Either::Right((outcome, channel_closing_future)) => {
+                let messages = notifications_for_slot(ledger.as_ref(), outcome?)
+                    .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))?;
+                for msg in messages {
+                    // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                    if subscription.send(msg).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                closed = channel_closing_future;
+            }
+        }
+    }
+}
+
 #[derive(serde::Deserialize)]
 struct RangeArgs(u64, u64, #[serde(default)] QueryMode);
 
+/// The arguments of a filtered event query: the filter, the cursor of the page and the page size.
+#[derive(serde::Deserialize)]
+struct FilteredEventsArgs(
+    EventFilter,
+    #[serde(default)] Option<u64>,
+    #[serde(default)] Option<u64>,
+);
+
 /// A structure containing serialized query arguments for RPC queries.
 #[derive(serde::Deserialize)]
 struct QueryArgs<T>(T, #[serde(default)] QueryMode);
@@ -9,8 +9,8 @@ use sov_ledger_rpc::server::rpc_module;
 use sov_ledger_rpc::HexHash;
 use sov_modules_api::Event;
 use sov_rollup_interface::rpc::{
-    BatchResponse, EventIdentifier, QueryMode, SlotResponse, TxIdAndOffset, TxIdentifier,
-    TxResponse,
+    BatchResponse, EventIdentifier, QueryMode, SlotIdentifier, SlotResponse, TxIdAndOffset,
+    TxIdentifier, TxResponse,
 };
 use tempfile::tempdir;
 
@@ -103,6 +103,18 @@ async fn getters_succeed() {
         .get_txs_range(0, 1, QueryMode::Compact)
         .await
         .unwrap();
+
+    rpc_client
+        .get_filtered_events(Default::default(), None, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        rpc_client
+            .get_proof_by_slot(SlotIdentifier::Number(1))
+            .await
+            .unwrap(),
+        None
+    );
 }
 
 #[tokio::test]
@@ -111,6 +123,15 @@ async fn subscribe_slots_succeeds() {
     let rpc_client = rpc_client(addr).await;
 
     rpc_client.subscribe_slots().await.unwrap();
+    rpc_client
+        .subscribe_slot_responses(QueryMode::Full)
+        .await
+        .unwrap();
+    rpc_client.subscribe_transactions().await.unwrap();
+    rpc_client
+        .subscribe_events(Default::default())
+        .await
+        .unwrap();
 }
 
 #[tokio::test]
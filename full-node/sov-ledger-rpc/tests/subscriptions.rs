@@ -0,0 +1,95 @@
+use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
+use jsonrpsee::rpc_params;
+use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_ledger_rpc::server::rpc_module;
+use sov_mock_da::MockBlock;
+use sov_rollup_interface::rpc::{EventFilter, EventResponse, QueryMode, SlotResponse, TxResponse};
+use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+use tempfile::tempdir;
+
+type Client = jsonrpsee::ws_client::WsClient;
+
+async fn rpc_server_and_client(db: LedgerDB) -> (jsonrpsee::server::ServerHandle, Client) {
+    let rpc_module = rpc_module::<LedgerDB, u32, u32>(db).unwrap();
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    let client = jsonrpsee::ws_client::WsClientBuilder::new()
+        .build(format!("ws://{}", addr))
+        .await
+        .unwrap();
+    (server.start(rpc_module), client)
+}
+
+fn commit_slot(db: &LedgerDB, events: Vec<Event>) {
+    let mut slot = SlotCommit::<_, u32, u32>::new(MockBlock::default());
+    slot.add_batch(BatchReceipt {
+        batch_hash: [1; 32],
+        tx_receipts: vec![TransactionReceipt {
+            tx_hash: [2; 32],
+            body_to_save: None,
+            events,
+            receipt: 7,
+        }],
+        inner: 3,
+    });
+    db.commit_slot(slot).unwrap();
+}
+
+#[tokio::test]
+async fn subscriptions_receive_committed_items() {
+    let dir = tempdir().unwrap();
+    let db = LedgerDB::with_path(dir.path()).unwrap();
+    let (_server_handle, client) = rpc_server_and_client(db.clone()).await;
+
+    let mut slots: Subscription<SlotResponse<u32, u32>> = client
+        .subscribe(
+            "ledger_subscribeSlotResponses",
+            rpc_params![QueryMode::Compact],
+            "ledger_unsubscribeSlotResponses",
+        )
+        .await
+        .unwrap();
+    let mut txs: Subscription<TxResponse<u32>> = client
+        .subscribe(
+            "ledger_subscribeTransactions",
+            rpc_params![],
+            "ledger_unsubscribeTransactions",
+        )
+        .await
+        .unwrap();
+    let filter = EventFilter {
+        key_prefix: Some(b"transfer".to_vec()),
+        ..Default::default()
+    };
+    let mut events: Subscription<EventResponse> = client
+        .subscribe(
+            "ledger_subscribeEvents",
+            rpc_params![filter],
+            "ledger_unsubscribeEvents",
+        )
+        .await
+        .unwrap();
+
+    commit_slot(
+        &db,
+        vec![
+            Event::new("mint", "a").with_module(&[1]),
+            Event::new("transfer", "b").with_module(&[1]),
+        ],
+    );
+
+    let slot = slots.next().await.unwrap().unwrap();
+    assert_eq!(1, slot.number);
+    assert_eq!(1..2, slot.batch_range);
+
+    let tx = txs.next().await.unwrap().unwrap();
+    assert_eq!([2; 32], tx.hash);
+    assert_eq!(7, tx.custom_receipt);
+
+    let event = events.next().await.unwrap().unwrap();
+    assert_eq!(2, event.number);
+    assert_eq!(Event::new("transfer", "b").with_module(&[1]), event.event);
+}
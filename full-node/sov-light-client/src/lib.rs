@@ -0,0 +1,168 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+mod rpc;
+
+use std::sync::Arc;
+
+use anyhow::ensure;
+use borsh::BorshDeserialize;
+pub use rpc::{ProvenStateResponse, VerifiedBalanceResponse, LIGHT_CLIENT_RPC_ERROR};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{Matches, Proof, StateTransition, Zkvm};
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+/// The latest state of the rollup proven to a [`LightClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenState<Root> {
+    /// The state root reached at the end of the last proven slot.
+    pub state_root: Root,
+    /// The hash of the DA block of the last proven slot.
+    pub slot_hash: [u8; 32],
+    /// The height of the DA block in which the proof was posted.
+    pub da_height: u64,
+}
+
+/// A client which follows a rollup by verifying the proofs posted to the DA layer,
+/// without executing its transactions.
+///
+/// Each proof must verify against the code commitment of the program which proves the slots
+/// of the rollup, and start from the state root proven by the previous one; proofs that don't
+/// are ignored. When the proofs are aggregated (see [`LightClient::with_aggregation`]), they
+/// must verify against the code commitment of the aggregation program instead, and commit
+/// to the program which proved the aggregated slots. Unless the client is created with
+/// a trusted state root, the first valid proof it finds is trusted to start from the right state.
+///
+/// The validity conditions of the proven state transitions aren't checked yet.
+pub struct LightClient<Da: DaService, Vm: Zkvm, Root> {
+    da_service: Da,
+    code_commitment: Vm::CodeCommitment,
+    aggregation_program: Option<Vm::CodeCommitment>,
+    next_height: u64,
+    // The state root which the next proof must start from.
+    state_root: Option<Root>,
+    proven_state: Arc<watch::Sender<Option<ProvenState<Root>>>>,
+}
+
+impl<Da, Vm, Root> LightClient<Da, Vm, Root>
+where
+    Da: DaService,
+    Vm: Zkvm,
+    Root: Serialize + DeserializeOwned + Clone + Eq + Send + Sync,
+{
+    /// Creates a client which scans the DA blocks from `start_height`, verifying proofs against
+    /// `code_commitment`, the commitment to the program which proves the slots of the rollup.
+    /// The first proof must start from `trusted_state_root`, if one is given.
+    pub fn new(
+        da_service: Da,
+        code_commitment: Vm::CodeCommitment,
+        start_height: u64,
+        trusted_state_root: Option<Root>,
+    ) -> Self {
+        Self {
+            da_service,
+            code_commitment,
+            aggregation_program: None,
+            next_height: start_height,
+            state_root: trusted_state_root,
+            proven_state: Arc::new(watch::channel(None).0),
+        }
+    }
+
+    /// Verifies the aggregated proofs generated by the program with the commitment
+    /// `aggregation_program`, instead of the proofs of single slots.
+    pub fn with_aggregation(mut self, aggregation_program: Vm::CodeCommitment) -> Self {
+        self.aggregation_program = Some(aggregation_program);
+        self
+    }
+
+    /// Returns the latest proven state, if any proof was accepted so far.
+    pub fn latest_proven_state(&self) -> Option<ProvenState<Root>> {
+        self.proven_state.borrow().clone()
+    }
+
+    /// Follows the DA layer, waiting for new blocks once it caught up with it. Never returns,
+    /// unless the DA service fails.
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        info!("Following the DA layer from height {}", self.next_height);
+        loop {
+            self.da_service
+                .get_block_at(self.next_height)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to fetch the DA block: {}", e))?;
+            self.process_block(self.next_height).await?;
+            self.next_height += 1;
+        }
+    }
+
+    /// Verifies the proofs posted in the DA block at `height`, in order, and
+    /// updates the latest proven state with the ones which are accepted.
+    pub async fn process_block(&mut self, height: u64) -> anyhow::Result<()> {
+        let proofs = self
+            .da_service
+            .get_aggregated_proofs_at(height)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to fetch the proofs at height {}: {}", height, e)
+            })?;
+
+        // Blobs which aren't proofs have no proof data
+        for serialized_proof in proofs.iter().filter(|proof| !proof.is_empty()) {
+            match self.verify_proof(serialized_proof) {
+                Ok(state_transition) => {
+                    debug!(
+                        "Accepted the proof posted at height {}: {:?}",
+                        height, state_transition.slot_hash
+                    );
+                    self.state_root = Some(state_transition.final_state_root.clone());
+                    self.proven_state.send_replace(Some(ProvenState {
+                        state_root: state_transition.final_state_root,
+                        slot_hash: state_transition.slot_hash.into(),
+                        da_height: height,
+                    }));
+                }
+                Err(e) => warn!("Ignoring a proof posted at height {}: {:?}", height, e),
+            }
+        }
+        Ok(())
+    }
+
+    fn verify_proof(
+        &self,
+        serialized_proof: &[u8],
+    ) -> anyhow::Result<StateTransition<Da::Spec, Root>> {
+        let proof = Proof::try_from_slice(serialized_proof)?;
+        let proof = match &proof {
+            Proof::PublicInput(data) | Proof::Full(data) => data,
+        };
+        let state_transition = match &self.aggregation_program {
+            Some(aggregation_program) => {
+                let output = Vm::verify_and_extract_aggregated_output::<Da::Spec, Root>(
+                    proof,
+                    aggregation_program,
+                )
+                .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?;
+                // The aggregation program verifies the slot proofs against the commitment
+                // provided by the prover, which isn't trusted.
+                ensure!(
+                    output.slot_program.matches(&self.code_commitment),
+                    "The proof aggregates the proofs of another program"
+                );
+                output.state_transition
+            }
+            None => Vm::verify_and_extract_output::<Da::Spec, Root>(proof, &self.code_commitment)
+                .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?,
+        };
+
+        if let Some(state_root) = &self.state_root {
+            ensure!(
+                state_transition.initial_state_root == *state_root,
+                "The proof doesn't start from the latest proven state root"
+            );
+        }
+        Ok(state_transition)
+    }
+}
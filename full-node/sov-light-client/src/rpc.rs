@@ -0,0 +1,134 @@
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+use jsonrpsee::types::error::UNKNOWN_ERROR_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_bank::{Amount, BalanceResponse, Bank};
+use sov_modules_api::rpc::StateIdentifier;
+use sov_modules_api::{Context, Spec, ValueWithProof};
+use sov_rollup_interface::rpc::utils;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::Zkvm;
+use sov_state::Storage;
+use tokio::sync::watch;
+
+use crate::{LightClient, ProvenState};
+
+/// The message of the errors returned by the light client RPC methods.
+pub const LIGHT_CLIENT_RPC_ERROR: &str = "LIGHT_CLIENT_RPC_ERROR";
+
+type Root<C> = <<C as Spec>::Storage as Storage>::Root;
+
+/// The latest proven state, as returned by `lightClient_getLatestProvenState`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenStateResponse {
+    /// The hex encoded state root reached at the end of the last proven slot.
+    #[serde(with = "utils::rpc_hex")]
+    pub state_root: Vec<u8>,
+    /// The hex encoded hash of the DA block of the last proven slot.
+    #[serde(with = "utils::rpc_hex")]
+    pub slot_hash: [u8; 32],
+    /// The height of the DA block in which the proof was posted.
+    pub da_height: u64,
+}
+
+impl<Root: AsRef<[u8]>> From<ProvenState<Root>> for ProvenStateResponse {
+    fn from(state: ProvenState<Root>) -> Self {
+        Self {
+            state_root: state.state_root.as_ref().to_vec(),
+            slot_hash: state.slot_hash,
+            da_height: state.da_height,
+        }
+    }
+}
+
+/// A balance verified against the latest proven state, as returned by `lightClient_getBalance`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedBalanceResponse {
+    /// The balance of the user, or `None` if the user or the token has no balance.
+    pub amount: Option<Amount>,
+    /// The latest proven state, against which the balance was verified.
+    pub proven_state: ProvenStateResponse,
+}
+
+/// The context of the RPC methods served by a [`LightClient`].
+pub struct LightClientRpc<C: Context> {
+    node: HttpClient,
+    bank: Bank<C>,
+    proven_state: watch::Receiver<Option<ProvenState<Root<C>>>>,
+}
+
+impl<C: Context> LightClientRpc<C> {
+    fn latest_proven_state(&self) -> Option<ProvenState<Root<C>>> {
+        self.proven_state.borrow().clone()
+    }
+
+    // Fetches the balance from the full node along with proofs of the storage entries it was
+    // read from, and recomputes it from the entries proven against the latest proven state root.
+    async fn verified_balance(
+        &self,
+        user_address: C::Address,
+        token_address: C::Address,
+    ) -> anyhow::Result<VerifiedBalanceResponse> {
+        let proven_state = self
+            .latest_proven_state()
+            .ok_or_else(|| anyhow::anyhow!("No state was proven yet"))?;
+
+        let state_id = StateIdentifier::StateRoot(proven_state.state_root.as_ref().to_vec());
+        let response: ValueWithProof<BalanceResponse> = self
+            .node
+            .request(
+                "bank_balanceOfWithProof",
+                rpc_params![&user_address, &token_address, state_id],
+            )
+            .await?;
+        let entries = response.verify::<C::Storage>(&proven_state.state_root)?;
+        let amount = self
+            .bank
+            .get_proven_balance_of(&user_address, &token_address, &entries)?;
+
+        Ok(VerifiedBalanceResponse {
+            amount,
+            proven_state: proven_state.into(),
+        })
+    }
+}
+
+impl<Da: DaService, Vm: Zkvm, Root> LightClient<Da, Vm, Root> {
+    /// Returns the RPC methods of the light client. Balances are fetched from the full node
+    /// serving RPC at `node_url`, and verified against the latest proven state.
+    pub fn rpc_methods<C>(&self, node_url: &str) -> anyhow::Result<RpcModule<LightClientRpc<C>>>
+    where
+        C: Context,
+        C::Storage: Storage<Root = Root>,
+        C::Address: DeserializeOwned,
+    {
+        let mut rpc = RpcModule::new(LightClientRpc {
+            node: HttpClientBuilder::default().build(node_url)?,
+            bank: Bank::default(),
+            proven_state: self.proven_state.subscribe(),
+        });
+
+        rpc.register_method("lightClient_getLatestProvenState", |_, rpc| {
+            Ok::<_, ErrorObjectOwned>(rpc.latest_proven_state().map(ProvenStateResponse::from))
+        })?;
+        rpc.register_async_method("lightClient_getBalance", |params, rpc| async move {
+            let (user_address, token_address): (C::Address, C::Address) = params.parse()?;
+            rpc.verified_balance(user_address, token_address)
+                .await
+                .map_err(to_rpc_error)
+        })?;
+        Ok(rpc)
+    }
+}
+
+fn to_rpc_error(err: anyhow::Error) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(
+        UNKNOWN_ERROR_CODE,
+        LIGHT_CLIENT_RPC_ERROR,
+        Some(err.to_string()),
+    )
+}
@@ -0,0 +1,332 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jsonrpsee::rpc_params;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, TokenConfig};
+use sov_light_client::{LightClient, ProvenState, ProvenStateResponse, VerifiedBalanceResponse};
+use sov_mock_da::{MockAddress, MockBlockHeader, MockDaService, MockDaSpec, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockZkGuest, MockZkvm};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::rpc::StateIdentifier;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Address, Module, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{
+    AggregatedStateTransition, StateTransition, StateTransitionData, ZkvmGuest, ZkvmHost,
+};
+use sov_state::Storage;
+
+type C = DefaultContext;
+type Root = <<C as Spec>::Storage as Storage>::Root;
+
+#[tokio::test]
+async fn test_follows_chained_proofs() -> Result<(), anyhow::Error> {
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let mut light_client = LightClient::<_, MockZkGuest, Vec<u8>>::new(
+        da_service.clone(),
+        MockCodeCommitment([0; 32]),
+        1,
+        Some(vec![0]),
+    );
+
+    // Rollup transactions, invalid proofs and proofs which don't chain are ignored.
+    da_service.send_transaction(&[1, 2, 3]).await?;
+    for proof in [
+        make_proof(1, vec![0], vec![1]),
+        vec![1, 2, 3],
+        make_proof(2, vec![7], vec![8]),
+        make_proof(2, vec![1], vec![2]),
+    ] {
+        da_service.send_aggregated_zk_proof(&proof).await?;
+    }
+    let last_height = da_service.get_head_block_header().await?.height;
+    for height in 1..=last_height {
+        light_client.process_block(height).await?;
+    }
+
+    assert_eq!(
+        Some(ProvenState {
+            state_root: vec![2],
+            slot_hash: MockBlockHeader::from_height(2).hash.into(),
+            da_height: last_height,
+        }),
+        light_client.latest_proven_state()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejects_proofs_from_untrusted_state() -> Result<(), anyhow::Error> {
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let mut light_client = LightClient::<_, MockZkGuest, Vec<u8>>::new(
+        da_service.clone(),
+        MockCodeCommitment([0; 32]),
+        1,
+        Some(vec![0]),
+    );
+
+    let height = da_service
+        .send_aggregated_zk_proof(&make_proof(1, vec![5], vec![6]))
+        .await?;
+    light_client.process_block(height).await?;
+    assert_eq!(None, light_client.latest_proven_state());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejects_proofs_of_another_program() -> Result<(), anyhow::Error> {
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let mut light_client = LightClient::<_, MockZkGuest, Vec<u8>>::new(
+        da_service.clone(),
+        MockCodeCommitment([0; 32]),
+        1,
+        Some(vec![0]),
+    );
+
+    let height = da_service
+        .send_aggregated_zk_proof(&make_program_proof(
+            1,
+            vec![0],
+            vec![1],
+            MockCodeCommitment([1; 32]),
+        ))
+        .await?;
+    light_client.process_block(height).await?;
+    assert_eq!(None, light_client.latest_proven_state());
+
+    // The same state transition is accepted when proven by the expected program.
+    let height = da_service
+        .send_aggregated_zk_proof(&make_proof(1, vec![0], vec![1]))
+        .await?;
+    light_client.process_block(height).await?;
+    assert_eq!(
+        Some(vec![1]),
+        light_client
+            .latest_proven_state()
+            .map(|state| state.state_root)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_follows_aggregated_proofs() -> Result<(), anyhow::Error> {
+    let aggregation_program = MockCodeCommitment([9; 32]);
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let mut light_client = LightClient::<_, MockZkGuest, Vec<u8>>::new(
+        da_service.clone(),
+        MockCodeCommitment([0; 32]),
+        1,
+        Some(vec![0]),
+    )
+    .with_aggregation(aggregation_program.clone());
+
+    // Proofs of single slots, and aggregated proofs of another slot program are ignored.
+    for proof in [
+        make_proof(2, vec![0], vec![2]),
+        make_aggregated_proof(2, vec![0], vec![2], MockCodeCommitment([1; 32])),
+        make_aggregated_proof(2, vec![0], vec![3], MockCodeCommitment([0; 32])),
+    ] {
+        da_service.send_aggregated_zk_proof(&proof).await?;
+    }
+    let last_height = da_service.get_head_block_header().await?.height;
+    for height in 1..=last_height {
+        light_client.process_block(height).await?;
+    }
+
+    assert_eq!(
+        Some(ProvenState {
+            state_root: vec![3],
+            slot_hash: MockBlockHeader::from_height(2).hash.into(),
+            da_height: last_height,
+        }),
+        light_client.latest_proven_state()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verified_balance() -> Result<(), anyhow::Error> {
+    let initial_balance = 100;
+    let user_address = generate_address::<C>("user");
+    let bank_config = BankConfig::<C> {
+        tokens: vec![TokenConfig {
+            token_name: "InitialToken".to_owned(),
+            address_and_balances: vec![(user_address, initial_balance)],
+            authorized_minters: vec![],
+            salt: 5,
+        }],
+    };
+    let token_address = get_genesis_token_address::<C>("InitialToken", 5);
+
+    // The state of the rollup, as executed by the full node.
+    let tmpdir = tempfile::tempdir()?;
+    let storage = new_orphan_storage(tmpdir.path())?;
+    let mut working_set = WorkingSet::<C>::new(storage.clone());
+    Bank::<C>::default().genesis(&bank_config, &mut working_set)?;
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    let (state_root, node_batch) = storage.compute_state_update(cache_log, &witness)?;
+    storage.commit(&node_batch, &Default::default());
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await?;
+    let node_url = format!("http://{}", server.local_addr()?);
+    let _server_handle = server.start(node_rpc_methods(storage)?);
+
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let light_client = LightClient::<_, MockZkGuest, Root>::new(
+        da_service.clone(),
+        MockCodeCommitment([0; 32]),
+        1,
+        None,
+    );
+    let rpc = light_client.rpc_methods::<C>(&node_url)?;
+    tokio::spawn(light_client.run());
+
+    // Nothing can be verified before a state is proven.
+    let response: Result<VerifiedBalanceResponse, _> = rpc
+        .call(
+            "lightClient_getBalance",
+            rpc_params![user_address, token_address],
+        )
+        .await;
+    assert!(response.is_err());
+
+    let genesis_root = Root::try_from_slice(&[0; 32])?;
+    da_service
+        .send_aggregated_zk_proof(&make_proof(1, genesis_root, state_root.clone()))
+        .await?;
+
+    let proven_state = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let proven_state: Option<ProvenStateResponse> = rpc
+                .call("lightClient_getLatestProvenState", rpc_params![])
+                .await
+                .unwrap();
+            match proven_state {
+                Some(proven_state) => return proven_state,
+                None => tokio::time::sleep(Duration::from_millis(50)).await,
+            }
+        }
+    })
+    .await?;
+    assert_eq!(state_root.as_ref(), proven_state.state_root.as_slice());
+
+    let response: VerifiedBalanceResponse = rpc
+        .call(
+            "lightClient_getBalance",
+            rpc_params![user_address, token_address],
+        )
+        .await?;
+    assert_eq!(
+        VerifiedBalanceResponse {
+            amount: Some(initial_balance),
+            proven_state,
+        },
+        response
+    );
+
+    let other_user = generate_address::<C>("other_user");
+    let response: VerifiedBalanceResponse = rpc
+        .call(
+            "lightClient_getBalance",
+            rpc_params![other_user, token_address],
+        )
+        .await?;
+    assert_eq!(None, response.amount);
+
+    Ok(())
+}
+
+// Serves the `bank_balanceOfWithProof` method of a full node, over the latest state.
+fn node_rpc_methods(
+    storage: <C as Spec>::Storage,
+) -> Result<RpcModule<<C as Spec>::Storage>, anyhow::Error> {
+    let mut rpc = RpcModule::new(storage);
+    rpc.register_method("bank_balanceOfWithProof", |params, storage| {
+        let (user_address, token_address, _): (Address, Address, StateIdentifier) =
+            params.parse()?;
+        let mut working_set = WorkingSet::<C>::new(storage.clone());
+        working_set.record_reads();
+        let balance = Bank::<C>::default()
+            .balance_of(user_address, token_address, &mut working_set)
+            .unwrap();
+        Ok::<_, ErrorObjectOwned>(working_set.prove_recorded_reads(balance).unwrap())
+    })?;
+    Ok(rpc)
+}
+
+// Returns a serialized mock proof of the transition between two state roots, as posted to the DA.
+fn make_proof<StateRoot: Serialize + DeserializeOwned>(
+    height: u64,
+    initial_state_root: StateRoot,
+    final_state_root: StateRoot,
+) -> Vec<u8> {
+    make_program_proof(
+        height,
+        initial_state_root,
+        final_state_root,
+        MockCodeCommitment([0; 32]),
+    )
+}
+
+// Returns a serialized mock proof of the transition between two state roots, generated by the
+// program with the given code commitment.
+fn make_program_proof<StateRoot: Serialize + DeserializeOwned>(
+    height: u64,
+    initial_state_root: StateRoot,
+    final_state_root: StateRoot,
+    code_commitment: MockCodeCommitment,
+) -> Vec<u8> {
+    let mut vm = MockZkvm::new(MockValidityCond::default()).with_code_commitment(code_commitment);
+    vm.add_hint(StateTransitionData::<StateRoot, (), MockDaSpec> {
+        initial_state_root,
+        final_state_root,
+        da_block_header: MockBlockHeader::from_height(height),
+        inclusion_proof: [0; 32],
+        completeness_proof: (),
+        blobs: vec![],
+        state_transition_witness: (),
+    });
+    vm.make_proof();
+    vm.run(false).unwrap().proof.try_to_vec().unwrap()
+}
+
+// Returns a serialized mock proof of an aggregation program, which claims that the slots up
+// to `height` were proven by `slot_program`.
+fn make_aggregated_proof(
+    height: u64,
+    initial_state_root: Vec<u8>,
+    final_state_root: Vec<u8>,
+    slot_program: MockCodeCommitment,
+) -> Vec<u8> {
+    let output = AggregatedStateTransition::<_, MockDaSpec, _> {
+        state_transition: StateTransition {
+            initial_state_root,
+            final_state_root,
+            slot_hash: MockBlockHeader::from_height(height).hash,
+            validity_condition: MockValidityCond::default(),
+        },
+        slot_program,
+    };
+    let mut vm = MockZkvm::with_program(
+        MockValidityCond::default(),
+        Arc::new(move |guest| {
+            guest.commit(&output);
+            Ok(())
+        }),
+    )
+    .with_code_commitment(MockCodeCommitment([9; 32]));
+    vm.make_proof();
+    vm.run(false).unwrap().proof.try_to_vec().unwrap()
+}
@@ -0,0 +1,164 @@
+extern crate criterion;
+
+use std::sync::Arc;
+
+use criterion::measurement::WallTime;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkGroup, Criterion};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sov_mock_da::MockBlockHeader;
+use sov_prover_storage_manager::ProverStorageManager;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_state::storage::{CacheKey, CacheValue, StorageKey};
+use sov_state::{
+    CompressedStorageSpec, DefaultStorageSpec, MerkleProofSpec, OrderedReadsAndWrites, Storage,
+};
+
+type Da = sov_mock_da::MockDaSpec;
+
+fn generate_random_bytes<R: Rng>(rng: &mut R, count: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|_| {
+            let inner_vec_size = rng.gen_range(32..=256);
+            (0..inner_vec_size).map(|_| rng.gen::<u8>()).collect()
+        })
+        .collect()
+}
+
+/// Produces the witness of a slot reading `num_reads` of the `num_keys` keys of the state,
+/// and writing as many new ones.
+fn generate_witness<S: MerkleProofSpec>(
+    path: &std::path::Path,
+    num_keys: usize,
+    num_reads: usize,
+) -> S::Witness {
+    let config = sov_state::config::Config {
+        path: path.to_path_buf(),
+    };
+    let mut storage_manager = ProverStorageManager::<Da, S>::new(config).unwrap();
+
+    let seed: [u8; 32] = [1; 32];
+    let mut rng = StdRng::from_seed(seed);
+    let keys = generate_random_bytes(&mut rng, num_keys);
+    let values = generate_random_bytes(&mut rng, num_keys);
+
+    let block_header = MockBlockHeader::from_height(1);
+    let storage = storage_manager.create_storage_on(&block_header).unwrap();
+    let ordered_writes = keys
+        .iter()
+        .zip(values.iter())
+        .map(|(key, value)| {
+            (
+                CacheKey {
+                    key: Arc::new(key.clone()),
+                },
+                Some(CacheValue {
+                    value: Arc::new(value.clone()),
+                }),
+            )
+        })
+        .collect();
+    let (_, state_update) = storage
+        .compute_state_update(
+            OrderedReadsAndWrites {
+                ordered_reads: Default::default(),
+                ordered_writes,
+            },
+            &Default::default(),
+        )
+        .unwrap();
+    storage.commit(&state_update, &OrderedReadsAndWrites::default());
+    storage_manager
+        .save_change_set(&block_header, storage)
+        .unwrap();
+    storage_manager.finalize(&block_header).unwrap();
+
+    let block_header = MockBlockHeader::from_height(2);
+    let storage = storage_manager.create_storage_on(&block_header).unwrap();
+    let witness = S::Witness::default();
+    let mut ordered_reads = Vec::with_capacity(num_reads);
+    for _ in 0..num_reads {
+        let idx = rng.gen_range(0..num_keys);
+        let cache_key = CacheKey {
+            key: Arc::new(keys[idx].clone()),
+        };
+        let value = storage.get(&StorageKey::from(cache_key.clone()), None, &witness);
+        assert!(value.is_some());
+        ordered_reads.push((
+            cache_key,
+            Some(CacheValue {
+                value: Arc::new(values[idx].clone()),
+            }),
+        ));
+    }
+    let ordered_writes = generate_random_bytes(&mut rng, num_reads)
+        .into_iter()
+        .map(|key| {
+            (
+                CacheKey { key: Arc::new(key) },
+                Some(CacheValue {
+                    value: Arc::new(vec![1; 32]),
+                }),
+            )
+        })
+        .collect();
+    storage
+        .compute_state_update(
+            OrderedReadsAndWrites {
+                ordered_reads,
+                ordered_writes,
+            },
+            &witness,
+        )
+        .unwrap();
+    witness
+}
+
+fn bench_witness_encoding<S: MerkleProofSpec>(
+    g: &mut BenchmarkGroup<WallTime>,
+    name: &str,
+    num_keys: usize,
+    num_reads: usize,
+) {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let witness = generate_witness::<S>(tmpdir.path(), num_keys, num_reads);
+    let serialized = bincode::serialize(&witness).unwrap();
+    println!(
+        "{}: witness of {} reads over {} keys is {} bytes",
+        name,
+        num_reads,
+        num_keys,
+        serialized.len()
+    );
+
+    g.bench_function(
+        format!("{}/serialize/keys={}/reads={}", name, num_keys, num_reads),
+        |b| b.iter(|| black_box(bincode::serialize(&witness).unwrap())),
+    );
+    g.bench_function(
+        format!("{}/deserialize/keys={}/reads={}", name, num_keys, num_reads),
+        |b| {
+            b.iter(|| {
+                let witness: S::Witness = bincode::deserialize(&serialized).unwrap();
+                black_box(witness)
+            })
+        },
+    );
+}
+
+fn witness_encoding_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Witness");
+    let checks = [(1_000, 100), (10_000, 1_000), (30_000, 3_000)];
+    for (num_keys, num_reads) in checks {
+        bench_witness_encoding::<DefaultStorageSpec>(&mut group, "array", num_keys, num_reads);
+        bench_witness_encoding::<CompressedStorageSpec>(
+            &mut group,
+            "compressed",
+            num_keys,
+            num_reads,
+        );
+    }
+}
+
+criterion_group!(benches, witness_encoding_benchmark);
+criterion_main!(benches);
@@ -29,6 +29,8 @@ pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     // This is for tracking "finalized" storage and detect errors
     // TODO: Should be removed after https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218
     orphaned_snapshots: HashSet<SnapshotId>,
+    // Whether the "finalized" storage reads the last saved snapshot, see `with_unfinalized_reads`
+    unfinalized_reads: bool,
 
     // Same reference for individual managers
     snapshot_id_to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
@@ -56,6 +58,7 @@ where
             latest_snapshot_id: 0,
             block_hash_to_snapshot_id: Default::default(),
             orphaned_snapshots: Default::default(),
+            unfinalized_reads: false,
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
@@ -72,6 +75,14 @@ where
         Ok(Self::with_db_handles(state_db, native_db))
     }
 
+    /// Makes the storage given by [`HierarchicalStorageManager::create_finalized_storage`] read
+    /// the state of the last saved block, even if it isn't finalized yet.
+    /// Used by local nodes, which keep blocks unfinalized to be able to revert them.
+    pub fn with_unfinalized_reads(mut self) -> Self {
+        self.unfinalized_reads = true;
+        self
+    }
+
     #[cfg(test)]
     fn is_empty(&self) -> bool {
         self.chain_forks.is_empty()
@@ -138,6 +149,12 @@ where
         // Return error here, as underlying database can return error
         state_manager.commit_snapshot(snapshot_id)?;
         native_manager.commit_snapshot(snapshot_id)?;
+        // "Finalized" storage reading this snapshot reads it from the database now
+        for orphan_snapshot_id in &self.orphaned_snapshots {
+            if snapshot_id_to_parent.get(orphan_snapshot_id) == Some(snapshot_id) {
+                snapshot_id_to_parent.remove(orphan_snapshot_id);
+            }
+        }
 
         // All siblings of current snapshot
         let mut to_discard: Vec<_> = self
@@ -286,6 +303,10 @@ where
         }
 
         if self.orphaned_snapshots.remove(&snapshot_id) {
+            self.snapshot_id_to_parent
+                .write()
+                .unwrap()
+                .remove(&snapshot_id);
             tracing::debug!(
                 "Discarded reference to 'finalized' snapshot={}",
                 snapshot_id
@@ -310,6 +331,12 @@ where
             state_manager.add_snapshot(state_snapshot);
             native_manager.add_snapshot(native_snapshot);
         }
+        if self.unfinalized_reads {
+            let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+            for orphan_snapshot_id in &self.orphaned_snapshots {
+                snapshot_id_to_parent.insert(*orphan_snapshot_id, snapshot_id);
+            }
+        }
         tracing::debug!(
             "Snapshot id={} for block={:?} has been saved to StorageManager",
             snapshot_id,
@@ -866,6 +893,80 @@ mod tests {
         assert!(storage_manager.is_empty());
     }
 
+    #[test]
+    fn finalized_storage_with_unfinalized_reads() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db)
+                .with_unfinalized_reads();
+        let finalized_storage = storage_manager.create_finalized_storage().unwrap();
+        let witness = ArrayWitness::default();
+
+        // Blocks A -> B, then A -> C replacing B
+        let block_a = MockBlockHeader {
+            prev_hash: MockHash::from([0; 32]),
+            hash: MockHash::from([1; 32]),
+            height: 1,
+            time: Time::now(),
+        };
+        let block_b = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([2; 32]),
+            height: 2,
+            time: Time::now(),
+        };
+        let block_c = MockBlockHeader {
+            prev_hash: MockHash::from([1; 32]),
+            hash: MockHash::from([12; 32]),
+            height: 2,
+            time: Time::now(),
+        };
+
+        // The finalized storage reads the last saved block.
+        for (block, value) in [(&block_a, 1), (&block_b, 2), (&block_c, 3)] {
+            let storage = storage_manager.create_storage_on(block).unwrap();
+            {
+                let mut state_operations = OrderedReadsAndWrites::default();
+                state_operations.ordered_writes.push(write_op(1, value));
+                let mut native_operations = OrderedReadsAndWrites::default();
+                native_operations
+                    .ordered_writes
+                    .push(write_op(2, value * 10));
+                let (_, state_update) = storage
+                    .compute_state_update(state_operations, &witness)
+                    .unwrap();
+                storage.commit(&state_update, &native_operations);
+            }
+            storage_manager.save_change_set(block, storage).unwrap();
+
+            assert_eq!(
+                Some(value_from(value).into()),
+                finalized_storage.get(&key_from(1).into(), None, &witness)
+            );
+            assert_eq!(
+                Some(value_from(value * 10).into()),
+                finalized_storage.get_accessory(&key_from(2).into(), None)
+            );
+        }
+
+        // And the database once the block is finalized.
+        storage_manager.finalize(&block_a).unwrap();
+        validate_internal_consistency(&storage_manager);
+        storage_manager.finalize(&block_c).unwrap();
+        assert!(storage_manager.is_empty());
+        assert_eq!(
+            Some(value_from(3).into()),
+            finalized_storage.get(&key_from(1).into(), None, &witness)
+        );
+        assert_eq!(
+            Some(value_from(30).into()),
+            finalized_storage.get_accessory(&key_from(2).into(), None)
+        );
+    }
+
     #[test]
     fn lifecycle_simulation() {
         let tmpdir = tempfile::tempdir().unwrap();
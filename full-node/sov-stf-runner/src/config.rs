@@ -12,6 +12,22 @@ pub struct RunnerConfig {
     pub start_height: u64,
     /// RPC configuration.
     pub rpc_config: RpcConfig,
+    /// The maximum number of executed slots waiting to be proven.
+    /// Execution pauses while the backlog is full.
+    #[serde(default = "default_max_proving_backlog")]
+    pub max_proving_backlog: u64,
+    /// Whether the slots are proven by a [`RemoteProver`](crate::RemoteProver) instead of
+    /// the node itself. The node then only serves the state transitions to prove over RPC.
+    #[serde(default)]
+    pub remote_prover: bool,
+    /// The token a [`RemoteProver`](crate::RemoteProver) must submit its proofs with.
+    /// Without it, the proofs of remote provers are rejected.
+    #[serde(default)]
+    pub remote_prover_token: Option<String>,
+}
+
+fn default_max_proving_backlog() -> u64 {
+    64
 }
 
 /// RPC configuration.
@@ -34,6 +50,8 @@ pub struct StorageConfig {
 #[derive(Debug, Clone, PartialEq, Deserialize, Copy)]
 pub struct ProverServiceConfig {
     /// The "distance"  measured in the number of blocks between two consecutive aggregated proofs.
+    /// The proofs of single slots are posted to the DA when it is lower than 2, or when the
+    /// prover service has no aggregation program.
     pub aggregated_proof_block_jump: u64,
 }
 
@@ -109,6 +127,9 @@ mod tests {
                     bind_host: "127.0.0.1".to_string(),
                     bind_port: 12345,
                 },
+                max_proving_backlog: 64,
+                remote_prover: false,
+                remote_prover_token: None,
             },
 
             da: sov_celestia_adapter::CelestiaConfig {
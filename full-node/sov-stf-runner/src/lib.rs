@@ -9,6 +9,10 @@ mod config;
 pub mod mock;
 #[cfg(feature = "native")]
 mod prover_service;
+#[cfg(feature = "native")]
+mod proving;
+#[cfg(feature = "native")]
+mod remote_prover;
 
 #[cfg(feature = "native")]
 use std::path::Path;
@@ -20,6 +24,13 @@ pub use config::RpcConfig;
 #[cfg(feature = "native")]
 pub use prover_service::*;
 #[cfg(feature = "native")]
+pub use proving::{
+    PostedProofs, ProvingJob, ProvingProgress, ProvingQueue, RemoteProof, RemoteProofVerifier,
+    ZkProofVerifier,
+};
+#[cfg(feature = "native")]
+pub use remote_prover::RemoteProver;
+#[cfg(feature = "native")]
 mod runner;
 #[cfg(feature = "native")]
 pub use config::{from_toml_path, ProverServiceConfig, RollupConfig, RunnerConfig, StorageConfig};
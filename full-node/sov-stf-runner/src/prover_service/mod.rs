@@ -1,10 +1,15 @@
 mod parallel;
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
 pub use parallel::ParallelProverService;
 use serde::Serialize;
+use sov_db::schema::types::StoredStateTransition;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
-use sov_rollup_interface::zk::StateTransitionData;
+use sov_rollup_interface::zk::{ExecutionStats, StateTransitionData};
 use thiserror::Error;
 
 /// The possible configurations of the prover.
@@ -32,9 +37,28 @@ pub enum WitnessSubmissionStatus {
 #[derive(Debug, Eq, PartialEq)]
 pub enum ProofSubmissionStatus {
     /// Indicates successful submission of the proof to the DA.
-    Success,
+    Success(SubmittedProof),
     /// Indicates that proof generation is currently in progress.
     ProofGenerationInProgress,
+    /// Indicates that the proof was generated, and is kept until it can be aggregated with
+    /// the proofs of the next slots.
+    AwaitingAggregation,
+}
+
+/// A proof which was posted to the DA.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SubmittedProof {
+    /// The height of the DA block in which the proof was posted, or `None` if the prover
+    /// doesn't generate proofs ([`RollupProverConfig::Skip`] or [`RollupProverConfig::Simulate`]),
+    /// in which case nothing is posted.
+    pub da_height: Option<u64>,
+    /// The number of consecutive slots covered by the proof, ending with the slot it was
+    /// submitted for.
+    pub num_slots: u64,
+    /// The serialized proof, as posted to the DA, or `None` if nothing was posted.
+    pub proof: Option<Vec<u8>>,
+    /// The public output of the proof, if the prover produced one.
+    pub state_transition: Option<StoredStateTransition>,
 }
 
 /// Represents the current status of proof generation.
@@ -89,10 +113,34 @@ pub trait ProverService {
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofProcessingStatus, ProverServiceError>;
 
-    /// Sends the ZK proof to the DA.
-    /// This method is noy yet fully implemented: see #1185
+    /// Sends the ZK proof to the DA, once it is generated. A prover service which aggregates
+    /// proofs keeps the proofs of consecutive slots until it can post their aggregated proof.
     async fn send_proof_to_da(
         &self,
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error>;
+
+    /// Returns the statistics of the zkVM run which proved the block corresponding to
+    /// `block_header_hash`, if the prover service records them.
+    fn execution_stats(
+        &self,
+        _block_header_hash: &<<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
+    ) -> Option<ExecutionStats> {
+        None
+    }
+}
+
+/// Creates the `prover_getExecutionStats` RPC method, which returns the statistics of the
+/// zkVM run which proved a slot, by the hash of its DA block.
+pub fn prover_service_rpc_methods<Ps>(prover_service: Arc<Ps>) -> anyhow::Result<RpcModule<Arc<Ps>>>
+where
+    Ps: ProverService + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(prover_service);
+    rpc.register_method("prover_getExecutionStats", |params, prover_service| {
+        let block_header_hash: <<Ps::DaService as DaService>::Spec as DaSpec>::SlotHash =
+            params.one()?;
+        Ok::<_, ErrorObjectOwned>(prover_service.execution_stats(&block_header_hash))
+    })?;
+    Ok(rpc)
 }
@@ -0,0 +1,62 @@
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::zk::{AggregatedProofData, Proof, ZkvmHost};
+
+use crate::ProofGenConfig;
+
+// Aggregates the proofs of consecutive slots with a second guest program, which runs the
+// `AggregatedProofVerifier` over the proofs generated by the slot program.
+pub(crate) struct ProofAggregator<Vm: ZkvmHost> {
+    vm: Vm,
+    slot_program: Vm::CodeCommitment,
+}
+
+impl<Vm: ZkvmHost> ProofAggregator<Vm> {
+    pub(crate) fn new(vm: Vm, slot_program: Vm::CodeCommitment) -> Self {
+        Self { vm, slot_program }
+    }
+}
+
+impl<Vm: ZkvmHost + 'static> ProofAggregator<Vm> {
+    /// Generates the aggregated proof of consecutive slots, given their proofs and
+    /// the headers of their DA blocks in slot order.
+    pub(crate) async fn aggregate<V, Da>(
+        &self,
+        slots: Vec<(Proof, <Da::Spec as DaSpec>::BlockHeader)>,
+        config: &ProofGenConfig<V, Da, Vm>,
+    ) -> Result<Proof, anyhow::Error>
+    where
+        Da: DaService,
+        V: StateTransitionFunction<Vm::Guest, Da::Spec>,
+    {
+        let with_proof = match config {
+            // Slots proven without running the zkVM have nothing to verify.
+            ProofGenConfig::Skip | ProofGenConfig::Simulate(_) => {
+                return Ok(Proof::PublicInput(Vec::default()))
+            }
+            ProofGenConfig::Execute => false,
+            ProofGenConfig::Prover => true,
+        };
+
+        let (proofs, da_block_headers) = slots
+            .into_iter()
+            .map(|(proof, da_block_header)| match proof {
+                Proof::PublicInput(data) | Proof::Full(data) => (data, da_block_header),
+            })
+            .unzip();
+
+        let mut vm = self.vm.clone();
+        vm.add_hint(AggregatedProofData::<_, Da::Spec> {
+            slot_program: self.slot_program.clone(),
+            proofs,
+            da_block_headers,
+        });
+
+        tokio::task::spawn_blocking(move || {
+            tracing::info_span!("proof_aggregation")
+                .in_scope(|| vm.run(with_proof).map(|run| run.proof))
+        })
+        .await?
+    }
+}
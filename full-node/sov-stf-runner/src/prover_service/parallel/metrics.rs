@@ -0,0 +1,66 @@
+//! Prometheus metrics about the cost of proving slots, registered in the default registry.
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_int_counter_vec, Histogram, IntCounterVec,
+};
+use sov_rollup_interface::zk::ExecutionStats;
+
+static PROVER_SLOT_CYCLES: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "prover_slot_cycles",
+        "Number of zkVM cycles spent proving a slot",
+        exponential_buckets(/*start=*/ 1e5, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+static PROVER_SLOT_SEGMENTS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "prover_slot_segments",
+        "Number of zkVM segments the proof of a slot was split into",
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+static PROVER_SLOT_PROVING_TIME_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "prover_slot_proving_time_seconds",
+        "Time spent proving a slot in seconds",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 24).unwrap(),
+    )
+    .unwrap()
+});
+
+static PROVER_FUNCTION_CYCLES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "prover_function_cycles",
+        "Number of zkVM cycles spent in the functions annotated with the cycle_tracker macro",
+        &["function"]
+    )
+    .unwrap()
+});
+
+static PROVER_FUNCTION_CALLS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "prover_function_calls",
+        "Number of calls to the functions annotated with the cycle_tracker macro",
+        &["function"]
+    )
+    .unwrap()
+});
+
+/// Records the statistics of a zkVM run which proved a slot.
+pub(crate) fn record_execution_stats(stats: &ExecutionStats) {
+    PROVER_SLOT_CYCLES.observe(stats.cycles as f64);
+    PROVER_SLOT_SEGMENTS.observe(stats.segments as f64);
+    PROVER_SLOT_PROVING_TIME_SECONDS.observe(stats.wall_time.as_secs_f64());
+    for (function, function_cycles) in &stats.cycles_by_function {
+        PROVER_FUNCTION_CYCLES
+            .with_label_values(&[function])
+            .inc_by(function_cycles.cycles);
+        PROVER_FUNCTION_CALLS
+            .with_label_values(&[function])
+            .inc_by(function_cycles.calls);
+    }
+}
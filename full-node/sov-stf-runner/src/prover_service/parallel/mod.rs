@@ -1,24 +1,32 @@
+mod aggregator;
+mod metrics;
 mod prover;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use aggregator::ProofAggregator;
 use async_trait::async_trait;
+use borsh::BorshSerialize;
 use prover::Prover;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{StateTransitionData, ZkvmHost};
+use sov_rollup_interface::zk::{ExecutionStats, Proof, StateTransitionData, ZkvmHost};
 
 use super::{ProverService, ProverServiceError};
 use crate::config::ProverServiceConfig;
 use crate::verifier::StateTransitionVerifier;
 use crate::{
     ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, RollupProverConfig,
-    WitnessSubmissionStatus,
+    SubmittedProof, WitnessSubmissionStatus,
 };
 
 /// Prover service that generates proofs in parallel.
+///
+/// With an aggregation program (see [`ParallelProverService::with_aggregation`]), the proofs of
+/// every `aggregated_proof_block_jump` consecutive slots are aggregated into a single proof
+/// before being posted to the DA.
 pub struct ParallelProverService<StateRoot, Witness, Da, Vm, V>
 where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]>,
@@ -29,9 +37,15 @@ where
 {
     vm: Vm,
     prover_config: Arc<ProofGenConfig<V, Da, Vm>>,
+    da_service: Da,
 
     zk_storage: V::PreState,
     prover_state: Prover<StateRoot, Witness, Da>,
+
+    aggregator: Option<ProofAggregator<Vm>>,
+    aggregated_proof_block_jump: u64,
+    // The proofs of the slots awaiting aggregation, in slot order.
+    pending_aggregation: Mutex<Vec<(Proof, <Da::Spec as DaSpec>::BlockHeader)>>,
 }
 
 impl<StateRoot, Witness, Da, Vm, V> ParallelProverService<StateRoot, Witness, Da, Vm, V>
@@ -48,6 +62,7 @@ where
         vm: Vm,
         zk_stf: V,
         da_verifier: Da::Verifier,
+        da_service: Da,
         config: RollupProverConfig,
         zk_storage: V::PreState,
         num_threads: usize,
@@ -68,19 +83,36 @@ where
         Self {
             vm,
             prover_config,
-            prover_state: Prover::new(
-                num_threads,
-                prover_service_config.aggregated_proof_block_jump,
-            ),
+            da_service,
+            prover_state: Prover::new(num_threads),
             zk_storage,
+            aggregator: None,
+            aggregated_proof_block_jump: prover_service_config.aggregated_proof_block_jump,
+            pending_aggregation: Default::default(),
         }
     }
 
+    /// Aggregates the proofs of consecutive slots with the program run by `aggregation_vm`,
+    /// which verifies them with the [`AggregatedProofVerifier`](crate::verifier::AggregatedProofVerifier).
+    /// `slot_program` is the commitment to the program which proves single slots.
+    ///
+    /// The zkVM must be able to verify proofs inside the guest, which the Risc0 adapter doesn't
+    /// support yet (#633).
+    pub fn with_aggregation(
+        mut self,
+        aggregation_vm: Vm,
+        slot_program: Vm::CodeCommitment,
+    ) -> Self {
+        self.aggregator = Some(ProofAggregator::new(aggregation_vm, slot_program));
+        self
+    }
+
     /// Creates a new prover.
     pub fn new_with_default_workers(
         vm: Vm,
         zk_stf: V,
         da_verifier: Da::Verifier,
+        da_service: Da,
         config: RollupProverConfig,
         zk_storage: V::PreState,
         prover_service_config: ProverServiceConfig,
@@ -92,6 +124,7 @@ where
             vm,
             zk_stf,
             da_verifier,
+            da_service,
             config,
             zk_storage,
             num_cpus - 1,
@@ -147,7 +180,114 @@ where
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error> {
-        self.prover_state
-            .get_proof_submission_status_and_remove_on_success(block_header_hash)
+        let (proof, da_block_header) =
+            match self.prover_state.get_proof(block_header_hash.clone())? {
+                Some(proof) => proof,
+                None => return Ok(ProofSubmissionStatus::ProofGenerationInProgress),
+            };
+
+        let aggregator = match &self.aggregator {
+            Some(aggregator) if self.aggregated_proof_block_jump > 1 => aggregator,
+            _ => {
+                let submitted_proof = self.submit_proof(&proof, 1, false).await?;
+                // The proof is kept until it is posted, so that a failed submission can be retried.
+                self.prover_state.remove_proof(&block_header_hash);
+                return Ok(ProofSubmissionStatus::Success(submitted_proof));
+            }
+        };
+
+        let slots = {
+            let mut pending = self.pending_aggregation.lock().expect("Lock was poisoned");
+            pending.push((proof, da_block_header));
+            self.prover_state.remove_proof(&block_header_hash);
+
+            if (pending.len() as u64) < self.aggregated_proof_block_jump {
+                return Ok(ProofSubmissionStatus::AwaitingAggregation);
+            }
+            pending.clone()
+        };
+
+        let num_slots = slots.len() as u64;
+        let result = match aggregator.aggregate(slots, &self.prover_config).await {
+            Ok(aggregated_proof) => self.submit_proof(&aggregated_proof, num_slots, true).await,
+            Err(e) => Err(e),
+        };
+
+        let mut pending = self.pending_aggregation.lock().expect("Lock was poisoned");
+        match result {
+            Ok(submitted_proof) => {
+                pending.clear();
+                Ok(ProofSubmissionStatus::Success(submitted_proof))
+            }
+            Err(e) => {
+                // The last slot is proven again when the submission is retried.
+                pending.pop();
+                Err(e)
+            }
+        }
+    }
+
+    fn execution_stats(
+        &self,
+        block_header_hash: &<Da::Spec as DaSpec>::SlotHash,
+    ) -> Option<ExecutionStats> {
+        self.prover_state.get_execution_stats(block_header_hash)
+    }
+}
+
+impl<StateRoot, Witness, Da, Vm, V> ParallelProverService<StateRoot, Witness, Da, Vm, V>
+where
+    StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
+    Witness: Serialize + DeserializeOwned,
+    Da: DaService,
+    Vm: ZkvmHost,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync,
+{
+    // Posts the proof of the last `num_slots` slots to the DA, `aggregated` by the aggregation
+    // program or generated by the slot program.
+    async fn submit_proof(
+        &self,
+        proof: &Proof,
+        num_slots: u64,
+        aggregated: bool,
+    ) -> Result<SubmittedProof, anyhow::Error> {
+        // Without a zkVM run there is no proof, so there is nothing to post.
+        if let ProofGenConfig::Skip | ProofGenConfig::Simulate(_) = *self.prover_config {
+            return Ok(SubmittedProof {
+                da_height: None,
+                num_slots,
+                proof: None,
+                state_transition: None,
+            });
+        }
+
+        let state_transition = if aggregated {
+            Vm::extract_aggregated_output::<Da::Spec, StateRoot>(proof)
+                .map(|output| output.state_transition)
+        } else {
+            Vm::extract_output::<Da::Spec, StateRoot>(proof)
+        };
+        // Not every zkVM exposes the public output of its proofs to the host.
+        let state_transition = match state_transition {
+            Ok(state_transition) => Some(state_transition.into()),
+            Err(e) => {
+                tracing::debug!("The proof has no public output: {:?}", e);
+                None
+            }
+        };
+
+        let serialized_proof = proof.try_to_vec()?;
+        let da_height = self
+            .da_service
+            .send_aggregated_zk_proof(&serialized_proof)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to send the proof to the DA: {}", e))?;
+
+        Ok(SubmittedProof {
+            da_height: Some(da_height),
+            num_slots,
+            proof: Some(serialized_proof),
+            state_transition,
+        })
     }
 }
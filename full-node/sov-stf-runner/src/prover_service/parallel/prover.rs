@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
 
@@ -8,26 +8,44 @@ use serde::Serialize;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{Proof, StateTransitionData, ZkvmHost};
+use sov_rollup_interface::zk::{ExecutionStats, Proof, StateTransitionData, ZkvmHost};
 
+use super::metrics;
 use super::ProverServiceError;
-use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, WitnessSubmissionStatus,
-};
+use crate::{ProofGenConfig, ProofProcessingStatus, WitnessSubmissionStatus};
 
 enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
     WitnessSubmitted(StateTransitionData<StateRoot, Witness, Da>),
     ProvingInProgress,
-    Proved(Proof),
+    Proved(Proof, Da::BlockHeader),
     Err(anyhow::Error),
 }
 
 struct ProverState<StateRoot, Witness, Da: DaSpec> {
     prover_status: HashMap<Da::SlotHash, ProverStatus<StateRoot, Witness, Da>>,
     pending_tasks_count: usize,
+    // The statistics of the latest zkVM runs, oldest first.
+    execution_stats: VecDeque<(Da::SlotHash, ExecutionStats)>,
 }
 
+// The number of slots for which execution statistics are kept.
+const MAX_EXECUTION_STATS: usize = 1024;
+
 impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
+    fn insert_witness(
+        &mut self,
+        hash: Da::SlotHash,
+        state_transition_data: StateTransitionData<StateRoot, Witness, Da>,
+    ) -> WitnessSubmissionStatus {
+        match self.prover_status.entry(hash) {
+            Entry::Occupied(_) => WitnessSubmissionStatus::WitnessExist,
+            Entry::Vacant(v) => {
+                v.insert(ProverStatus::WitnessSubmitted(state_transition_data));
+                WitnessSubmissionStatus::SubmittedForProving
+            }
+        }
+    }
+
     fn remove(&mut self, hash: &Da::SlotHash) -> Option<ProverStatus<StateRoot, Witness, Da>> {
         self.prover_status.remove(hash)
     }
@@ -44,9 +62,12 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         &mut self,
         hash: Da::SlotHash,
         proof: Result<Proof, anyhow::Error>,
+        da_block_header: Da::BlockHeader,
     ) -> Option<ProverStatus<StateRoot, Witness, Da>> {
         match proof {
-            Ok(p) => self.prover_status.insert(hash, ProverStatus::Proved(p)),
+            Ok(p) => self
+                .prover_status
+                .insert(hash, ProverStatus::Proved(p, da_block_header)),
             Err(e) => self.prover_status.insert(hash, ProverStatus::Err(e)),
         }
     }
@@ -58,6 +79,23 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         self.prover_status.get(&hash)
     }
 
+    fn record_execution_stats(&mut self, hash: Da::SlotHash, stats: ExecutionStats) {
+        metrics::record_execution_stats(&stats);
+        if self.execution_stats.len() == MAX_EXECUTION_STATS {
+            self.execution_stats.pop_front();
+        }
+        self.execution_stats.push_back((hash, stats));
+    }
+
+    fn get_execution_stats(&self, hash: &Da::SlotHash) -> Option<&ExecutionStats> {
+        // The latest run is the relevant one if a slot was proven several times.
+        self.execution_stats
+            .iter()
+            .rev()
+            .find(|(slot_hash, _)| slot_hash == hash)
+            .map(|(_, stats)| stats)
+    }
+
     fn inc_task_count_if_not_busy(&mut self, num_threads: usize) -> bool {
         if self.pending_tasks_count >= num_threads {
             return false;
@@ -79,7 +117,6 @@ pub(crate) struct Prover<StateRoot, Witness, Da: DaService> {
     prover_state: Arc<RwLock<ProverState<StateRoot, Witness, Da::Spec>>>,
     num_threads: usize,
     pool: rayon::ThreadPool,
-    _aggregated_proof_block_jump: u64,
 }
 
 impl<StateRoot, Witness, Da> Prover<StateRoot, Witness, Da>
@@ -88,7 +125,7 @@ where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
 {
-    pub(crate) fn new(num_threads: usize, _aggregated_proof_block_jump: u64) -> Self {
+    pub(crate) fn new(num_threads: usize) -> Self {
         Self {
             num_threads,
             pool: rayon::ThreadPoolBuilder::new()
@@ -99,8 +136,8 @@ where
             prover_state: Arc::new(RwLock::new(ProverState {
                 prover_status: Default::default(),
                 pending_tasks_count: Default::default(),
+                execution_stats: Default::default(),
             })),
-            _aggregated_proof_block_jump,
         }
     }
 
@@ -109,18 +146,9 @@ where
         state_transition_data: StateTransitionData<StateRoot, Witness, Da::Spec>,
     ) -> WitnessSubmissionStatus {
         let header_hash = state_transition_data.da_block_header.hash();
-        let data = ProverStatus::WitnessSubmitted(state_transition_data);
 
         let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
-        let entry = prover_state.prover_status.entry(header_hash);
-
-        match entry {
-            Entry::Occupied(_) => WitnessSubmissionStatus::WitnessExist,
-            Entry::Vacant(v) => {
-                v.insert(data);
-                WitnessSubmissionStatus::SubmittedForProving
-            }
-        }
+        prover_state.insert_witness(header_hash, state_transition_data)
     }
 
     pub(crate) fn start_proving<Vm, V>(
@@ -138,64 +166,80 @@ where
         let prover_state_clone = self.prover_state.clone();
         let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
 
-        let prover_status = prover_state
-            .remove(&block_header_hash)
-            .ok_or_else(|| anyhow::anyhow!("Missing witness for block: {:?}", block_header_hash))?;
+        let state_transition_data = match prover_state.get_prover_status(block_header_hash.clone())
+        {
+            None => {
+                return Err(
+                    anyhow::anyhow!("Missing witness for block: {:?}", block_header_hash).into(),
+                )
+            }
+            Some(ProverStatus::ProvingInProgress) => {
+                return Err(anyhow::anyhow!(
+                    "Proof generation for {:?} still in progress",
+                    block_header_hash
+                )
+                .into())
+            }
+            // A proof which failed to be posted, and is posted again by `send_proof_to_da`.
+            Some(ProverStatus::Proved(..)) => return Ok(ProofProcessingStatus::ProvingInProgress),
+            Some(ProverStatus::Err(_)) => match prover_state.remove(&block_header_hash) {
+                Some(ProverStatus::Err(e)) => return Err(e.into()),
+                _ => unreachable!("The status was checked above"),
+            },
+            Some(ProverStatus::WitnessSubmitted(_)) => {
+                // Initiate a new proving job only if the prover is not busy.
+                if !prover_state.inc_task_count_if_not_busy(self.num_threads) {
+                    prover_state.remove(&block_header_hash);
+                    return Ok(ProofProcessingStatus::Busy);
+                }
 
-        match prover_status {
-            ProverStatus::WitnessSubmitted(state_transition_data) => {
-                let start_prover = prover_state.inc_task_count_if_not_busy(self.num_threads);
+                match prover_state.set_to_proving(block_header_hash.clone()) {
+                    Some(ProverStatus::WitnessSubmitted(state_transition_data)) => {
+                        state_transition_data
+                    }
+                    _ => unreachable!("The status was checked above"),
+                }
+            }
+        };
 
-                // Initiate a new proving job only if the prover is not busy.
-                if start_prover {
-                    prover_state.set_to_proving(block_header_hash.clone());
-                    vm.add_hint(state_transition_data);
+        let da_block_header = state_transition_data.da_block_header.clone();
+        vm.add_hint(state_transition_data);
 
-                    self.pool.spawn(move || {
-                        tracing::info_span!("guest_execution").in_scope(|| {
-                            let proof = make_proof(vm, config, zk_storage);
+        self.pool.spawn(move || {
+            tracing::info_span!("guest_execution").in_scope(|| {
+                let proof = make_proof(vm, config, zk_storage);
 
-                            let mut prover_state =
-                                prover_state_clone.write().expect("Lock was poisoned");
+                let mut prover_state = prover_state_clone.write().expect("Lock was poisoned");
 
-                            prover_state.set_to_proved(block_header_hash, proof);
-                            prover_state.dec_task_count();
-                        })
-                    });
+                let proof = match proof {
+                    Ok((proof, Some(stats))) => {
+                        prover_state.record_execution_stats(block_header_hash.clone(), stats);
+                        Ok(proof)
+                    }
+                    Ok((proof, None)) => Ok(proof),
+                    Err(e) => Err(e),
+                };
+                prover_state.set_to_proved(block_header_hash, proof, da_block_header);
+                prover_state.dec_task_count();
+            })
+        });
 
-                    Ok(ProofProcessingStatus::ProvingInProgress)
-                } else {
-                    Ok(ProofProcessingStatus::Busy)
-                }
-            }
-            ProverStatus::ProvingInProgress => Err(anyhow::anyhow!(
-                "Proof generation for {:?} still in progress",
-                block_header_hash
-            )
-            .into()),
-            ProverStatus::Proved(_) => Err(anyhow::anyhow!(
-                "Witness for block_header_hash {:?}, submitted multiple times.",
-                block_header_hash,
-            )
-            .into()),
-            ProverStatus::Err(e) => Err(e.into()),
-        }
+        Ok(ProofProcessingStatus::ProvingInProgress)
     }
 
-    pub(crate) fn get_proof_submission_status_and_remove_on_success(
+    /// Returns the proof for the block along with the block header, or `None` if it is still
+    /// being generated.
+    pub(crate) fn get_proof(
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
-    ) -> Result<ProofSubmissionStatus, anyhow::Error> {
-        let mut prover_state = self.prover_state.write().unwrap();
+    ) -> Result<Option<(Proof, <Da::Spec as DaSpec>::BlockHeader)>, anyhow::Error> {
+        let prover_state = self.prover_state.read().expect("Lock was poisoned");
         let status = prover_state.get_prover_status(block_header_hash.clone());
 
         match status {
-            Some(ProverStatus::ProvingInProgress) => {
-                Ok(ProofSubmissionStatus::ProofGenerationInProgress)
-            }
-            Some(ProverStatus::Proved(_)) => {
-                prover_state.remove(&block_header_hash);
-                Ok(ProofSubmissionStatus::Success)
+            Some(ProverStatus::ProvingInProgress) => Ok(None),
+            Some(ProverStatus::Proved(proof, da_block_header)) => {
+                Ok(Some((proof.clone(), da_block_header.clone())))
             }
             Some(ProverStatus::WitnessSubmitted(_)) => Err(anyhow::anyhow!(
                 "Witness for {:?} was submitted, but the proof generation is not triggered.",
@@ -208,13 +252,29 @@ where
             )),
         }
     }
+
+    /// Returns the statistics of the zkVM run which proved the block, if they are still kept.
+    pub(crate) fn get_execution_stats(
+        &self,
+        block_header_hash: &<Da::Spec as DaSpec>::SlotHash,
+    ) -> Option<ExecutionStats> {
+        let prover_state = self.prover_state.read().expect("Lock was poisoned");
+        prover_state.get_execution_stats(block_header_hash).cloned()
+    }
+
+    /// Forgets the proof for the block, once it was sent to the DA.
+    pub(crate) fn remove_proof(&self, block_header_hash: &<Da::Spec as DaSpec>::SlotHash) {
+        let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
+        prover_state.remove(block_header_hash);
+    }
 }
 
+// Generates the proof, along with the statistics of the zkVM run if the guest runs in the zkVM.
 fn make_proof<V, Vm, Da>(
     mut vm: Vm,
     config: Arc<ProofGenConfig<V, Da, Vm>>,
     zk_storage: V::PreState,
-) -> Result<Proof, anyhow::Error>
+) -> Result<(Proof, Option<ExecutionStats>), anyhow::Error>
 where
     Da: DaService,
     Vm: ZkvmHost + 'static,
@@ -222,12 +282,12 @@ where
     V::PreState: Send + Sync + 'static,
 {
     match config.deref() {
-        ProofGenConfig::Skip => Ok(Proof::PublicInput(Vec::default())),
+        ProofGenConfig::Skip => Ok((Proof::PublicInput(Vec::default()), None)),
         ProofGenConfig::Simulate(verifier) => verifier
             .run_block(vm.simulate_with_hints(), zk_storage)
-            .map(|_| Proof::PublicInput(Vec::default()))
+            .map(|_| (Proof::PublicInput(Vec::default()), None))
             .map_err(|e| anyhow::anyhow!("Guest execution must succeed but failed with {:?}", e)),
-        ProofGenConfig::Execute => vm.run(false),
-        ProofGenConfig::Prover => vm.run(true),
+        ProofGenConfig::Execute => vm.run(false).map(|run| (run.proof, Some(run.stats))),
+        ProofGenConfig::Prover => vm.run(true).map(|run| (run.proof, Some(run.stats))),
     }
 }
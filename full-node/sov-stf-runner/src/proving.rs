@@ -0,0 +1,686 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use anyhow::ensure;
+use borsh::BorshDeserialize;
+use jsonrpsee::types::error::UNKNOWN_ERROR_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::{SlotNumber, StoredProof, StoredStateTransition};
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::rpc::utils;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{Matches, Proof, StateTransitionData, Zkvm};
+use tokio::sync::watch;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    ProofProcessingStatus, ProofSubmissionStatus, ProverService, ProverServiceError, SubmittedProof,
+};
+
+/// The delay before the first retry of a failed proving attempt. It doubles with each failure.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+/// The maximum delay between two proving attempts of the same slot.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// How often the prover service is polled for a proof that is being generated.
+const PROOF_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The message of the errors returned by the prover RPC methods.
+const PROVER_RPC_ERROR: &str = "PROVER_RPC_ERROR";
+
+/// A state transition waiting to be proven, as served to a [`RemoteProver`](crate::RemoteProver).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvingJob {
+    /// The DA height of the slot.
+    pub da_height: u64,
+    /// The hash of the DA block of the slot.
+    #[serde(with = "utils::rpc_hex")]
+    pub block_hash: [u8; 32],
+    /// The hex encoded, bincode serialized [`StateTransitionData`] of the slot.
+    #[serde(with = "utils::rpc_hex")]
+    pub transition_data: Vec<u8>,
+}
+
+/// A proof which a [`RemoteProver`](crate::RemoteProver) posted to the DA.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteProof {
+    /// The DA height of the last slot covered by the proof.
+    pub da_height: u64,
+    /// The hash of the DA block of the last slot covered by the proof.
+    #[serde(with = "utils::rpc_hex")]
+    pub block_hash: [u8; 32],
+    /// The number of consecutive slots covered by the proof.
+    pub num_slots: u64,
+    /// The hex encoded proof, as posted to the DA.
+    #[serde(default, with = "utils::rpc_hex")]
+    pub proof: Vec<u8>,
+    /// The height of the DA block in which the proof was posted, if the prover posts its proofs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub posted_at: Option<u64>,
+}
+
+/// Verifies the proofs submitted by a [`RemoteProver`](crate::RemoteProver) before the node
+/// records them.
+pub trait RemoteProofVerifier: Send + Sync {
+    /// Verifies that `proof` proves the state transition of the `num_slots` slots from the one
+    /// serialized in the `first_job` to the one serialized in the `last_job`, as executed by
+    /// the node. Returns the public output of the proof.
+    fn verify(
+        &self,
+        proof: &[u8],
+        num_slots: u64,
+        first_job: &[u8],
+        last_job: &[u8],
+    ) -> anyhow::Result<StoredStateTransition>;
+}
+
+/// Looks up the proofs posted to the DA, to check that a [`RemoteProver`](crate::RemoteProver)
+/// posted its proofs where it claims to.
+#[async_trait::async_trait]
+pub trait PostedProofs: Send + Sync {
+    /// Returns the proofs posted in the DA block at `da_height`, which must exist.
+    async fn proofs_at(&self, da_height: u64) -> anyhow::Result<Vec<Vec<u8>>>;
+}
+
+#[async_trait::async_trait]
+impl<Da: DaService> PostedProofs for Da {
+    async fn proofs_at(&self, da_height: u64) -> anyhow::Result<Vec<Vec<u8>>> {
+        let head = self
+            .get_head_block_header()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get the DA head: {}", e))?;
+        ensure!(
+            da_height <= head.height(),
+            "The DA block at height {} doesn't exist yet",
+            da_height
+        );
+        self.get_aggregated_proofs_at(da_height)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get the proofs at height {}: {}", da_height, e))
+    }
+}
+
+/// Verifies the proofs of a zkVM program against the state transitions executed by the node.
+pub struct ZkProofVerifier<Vm: Zkvm, Da, Root, Witness> {
+    code_commitment: Vm::CodeCommitment,
+    aggregation_program: Option<Vm::CodeCommitment>,
+    phantom: PhantomData<fn() -> (Da, Root, Witness)>,
+}
+
+impl<Vm: Zkvm, Da, Root, Witness> ZkProofVerifier<Vm, Da, Root, Witness> {
+    /// Creates a verifier for the proofs of the slot program with the given `code_commitment`.
+    pub fn new(code_commitment: Vm::CodeCommitment) -> Self {
+        Self {
+            code_commitment,
+            aggregation_program: None,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Expects the proofs to be generated by the `aggregation_program`, which aggregates the
+    /// proofs of the slot program (see
+    /// [`ParallelProverService::with_aggregation`](crate::ParallelProverService::with_aggregation)).
+    pub fn with_aggregation(mut self, aggregation_program: Vm::CodeCommitment) -> Self {
+        self.aggregation_program = Some(aggregation_program);
+        self
+    }
+}
+
+impl<Vm, Da, Root, Witness> RemoteProofVerifier for ZkProofVerifier<Vm, Da, Root, Witness>
+where
+    Vm: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned + PartialEq + AsRef<[u8]>,
+    Witness: DeserializeOwned,
+{
+    fn verify(
+        &self,
+        proof: &[u8],
+        num_slots: u64,
+        first_job: &[u8],
+        last_job: &[u8],
+    ) -> anyhow::Result<StoredStateTransition> {
+        let proof = Proof::try_from_slice(proof)?;
+        let proof = match &proof {
+            Proof::PublicInput(data) | Proof::Full(data) => data,
+        };
+        let state_transition = match &self.aggregation_program {
+            Some(aggregation_program) => {
+                let output = Vm::verify_and_extract_aggregated_output::<Da, Root>(
+                    proof,
+                    aggregation_program,
+                )
+                .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?;
+                ensure!(
+                    output.slot_program.matches(&self.code_commitment),
+                    "The proof aggregates the proofs of another program"
+                );
+                output.state_transition
+            }
+            None => {
+                ensure!(num_slots == 1, "Expected a proof of a single slot");
+                Vm::verify_and_extract_output::<Da, Root>(proof, &self.code_commitment)
+                    .map_err(|e| anyhow::anyhow!("The proof failed to verify: {:?}", e))?
+            }
+        };
+
+        let first_job: StateTransitionData<Root, Witness, Da> = bincode::deserialize(first_job)?;
+        let last_job: StateTransitionData<Root, Witness, Da> = bincode::deserialize(last_job)?;
+        ensure!(
+            state_transition.initial_state_root == first_job.initial_state_root,
+            "The proof doesn't start from the state root of the first slot"
+        );
+        ensure!(
+            state_transition.final_state_root == last_job.final_state_root,
+            "The proof doesn't end with the state root of the last slot"
+        );
+        ensure!(
+            state_transition.slot_hash == last_job.da_block_header.hash(),
+            "The proof doesn't end with the DA block of the last slot"
+        );
+        Ok(state_transition.into())
+    }
+}
+
+/// How far proving lags behind execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvingProgress {
+    /// The DA height of the last executed slot.
+    pub executed_height: u64,
+    /// The DA height of the last slot whose proof was submitted to the DA.
+    pub proven_height: u64,
+    /// The number of slots by which the proven height lags behind the executed height.
+    pub lag: u64,
+    /// The number of executed slots waiting to be proven.
+    pub backlog: u64,
+    /// The number of proven slots whose proofs wait to be aggregated with the proofs of the
+    /// next slots before being submitted to the DA.
+    pub awaiting_aggregation: u64,
+}
+
+impl ProvingProgress {
+    fn new(
+        executed_height: u64,
+        proven_height: u64,
+        pending_jobs: u64,
+        awaiting_aggregation: u64,
+    ) -> Self {
+        Self {
+            executed_height,
+            proven_height,
+            lag: executed_height.saturating_sub(proven_height),
+            backlog: pending_jobs.saturating_sub(awaiting_aggregation),
+            awaiting_aggregation,
+        }
+    }
+
+    /// Returns the DA height of the next slot to prove.
+    fn next_height(&self) -> u64 {
+        self.proven_height + self.awaiting_aggregation + 1
+    }
+
+    /// Returns the number of jobs in the queue, including those awaiting aggregation.
+    fn pending_jobs(&self) -> u64 {
+        self.backlog + self.awaiting_aggregation
+    }
+}
+
+/// A persistent queue of state transitions waiting to be proven.
+///
+/// Execution pushes the witness of every executed slot to the queue, and a prover task
+/// ([`ProvingQueue::run_prover`]) proves them one by one, in slot order. Jobs are stored in
+/// the [`LedgerDB`], so the slots which were executed but not proven before a shutdown are
+/// proven after a restart. Once the queue holds `max_backlog` jobs, execution waits for
+/// the prover to catch up.
+///
+/// The proofs posted to the DA are recorded in the [`LedgerDB`], by the slots they cover.
+/// When the prover service aggregates the proofs of several slots, the jobs of the slots
+/// awaiting aggregation are kept until the aggregated proof is posted, so they are proven
+/// again after a restart.
+///
+/// Jobs are identified by the hash of their DA block as well as its height, so that the proof of
+/// a slot which was reverted by a reorg of the DA chain while it was being proven is dropped,
+/// instead of being recorded for the slot which replaced it.
+///
+/// The proofs of a [`RemoteProver`](crate::RemoteProver) are only accepted with the token set by
+/// [`ProvingQueue::with_remote_prover_token`], and are checked by the verifier set by
+/// [`ProvingQueue::with_proof_verifier`] before being recorded.
+#[derive(Clone)]
+pub struct ProvingQueue {
+    ledger_db: LedgerDB,
+    first_da_height: u64,
+    max_backlog: u64,
+    progress: Arc<watch::Sender<ProvingProgress>>,
+    proof_verifier: Option<Arc<dyn RemoteProofVerifier>>,
+    posted_proofs: Option<Arc<dyn PostedProofs>>,
+    remote_prover_token: Option<String>,
+}
+
+impl ProvingQueue {
+    /// Creates a queue on top of the jobs persisted in the `ledger_db`.
+    /// `first_da_height` is the DA height of the first slot of the rollup, and `executed_height`
+    /// is the DA height of the last slot executed before the queue was created.
+    pub fn new(
+        ledger_db: LedgerDB,
+        first_da_height: u64,
+        executed_height: u64,
+        max_backlog: u64,
+    ) -> anyhow::Result<Self> {
+        let proven_height = match ledger_db.get_next_proving_job(0)? {
+            Some((da_height, _, _)) => da_height.saturating_sub(1),
+            None => executed_height,
+        };
+        let pending_jobs = ledger_db.count_proving_jobs()?;
+        if pending_jobs > 0 {
+            info!("Resuming proving with {} pending slots", pending_jobs);
+        }
+
+        let progress = ProvingProgress::new(executed_height, proven_height, pending_jobs, 0);
+        Ok(Self {
+            ledger_db,
+            first_da_height,
+            max_backlog: max_backlog.max(1),
+            progress: Arc::new(watch::channel(progress).0),
+            proof_verifier: None,
+            posted_proofs: None,
+            remote_prover_token: None,
+        })
+    }
+
+    /// Verifies the proofs submitted by a [`RemoteProver`](crate::RemoteProver) with `verifier`,
+    /// and looks them up in `posted_proofs` at the DA height reported by the prover.
+    /// Without a verifier, remote proofs are rejected.
+    pub fn with_proof_verifier(
+        mut self,
+        verifier: impl RemoteProofVerifier + 'static,
+        posted_proofs: impl PostedProofs + 'static,
+    ) -> Self {
+        self.proof_verifier = Some(Arc::new(verifier));
+        self.posted_proofs = Some(Arc::new(posted_proofs));
+        self
+    }
+
+    /// Accepts the proofs of a [`RemoteProver`](crate::RemoteProver) submitted with `token`.
+    /// Without a token, remote proofs are rejected.
+    pub fn with_remote_prover_token(mut self, token: String) -> Self {
+        self.remote_prover_token = Some(token);
+        self
+    }
+
+    /// Returns the current progress of the proving pipeline.
+    pub fn progress(&self) -> ProvingProgress {
+        *self.progress.borrow()
+    }
+
+    /// Enqueues the state transition of the slot at `da_height` for proving.
+    /// Waits until the backlog has room for it.
+    pub async fn push<StateRoot, Witness, Da>(
+        &self,
+        da_height: u64,
+        transition_data: &StateTransitionData<StateRoot, Witness, Da>,
+    ) -> anyhow::Result<()>
+    where
+        StateRoot: Serialize + DeserializeOwned,
+        Witness: Serialize + DeserializeOwned,
+        Da: sov_rollup_interface::da::DaSpec,
+    {
+        if self.progress().backlog >= self.max_backlog {
+            debug!(
+                "Proving backlog is full, waiting for the prover before executing height {}",
+                da_height + 1
+            );
+        }
+        self.progress
+            .subscribe()
+            .wait_for(|progress| progress.backlog < self.max_backlog)
+            .await?;
+
+        let job = bincode::serialize(transition_data)?;
+        let block_hash = transition_data.da_block_header.hash().into();
+        let replaced = self.ledger_db.put_proving_job(da_height, block_hash, job)?;
+        self.progress.send_modify(|progress| {
+            *progress = ProvingProgress::new(
+                da_height,
+                progress.proven_height,
+                progress.pending_jobs() + u64::from(!replaced),
+                progress.awaiting_aggregation,
+            );
+        });
+        Ok(())
+    }
+
+    /// Drops the jobs of all the slots starting from `da_height`, because they were
+    /// reverted by a reorg of the DA chain.
+    pub fn discard_from(&self, da_height: u64) -> anyhow::Result<()> {
+        let discarded = self.ledger_db.delete_proving_jobs_from(da_height)?;
+        self.progress.send_modify(|progress| {
+            let pending_jobs = progress.pending_jobs().saturating_sub(discarded);
+            let executed_height = progress.executed_height.min(da_height.saturating_sub(1));
+            let proven_height = progress.proven_height.min(executed_height);
+            let awaiting_aggregation = progress
+                .awaiting_aggregation
+                .min(executed_height - proven_height);
+            *progress = ProvingProgress::new(
+                executed_height,
+                proven_height,
+                pending_jobs,
+                awaiting_aggregation,
+            );
+        });
+        Ok(())
+    }
+
+    /// Proves the queued slots in order and submits their proofs to the DA.
+    /// A slot is retried until it is proven, so this never returns unless the queue
+    /// can't be read from the database.
+    pub async fn run_prover<Ps>(self, prover_service: Arc<Ps>) -> anyhow::Result<()>
+    where
+        Ps: ProverService + Send + Sync + 'static,
+        Ps::StateRoot: DeserializeOwned,
+        Ps::Witness: DeserializeOwned,
+    {
+        loop {
+            self.progress
+                .subscribe()
+                .wait_for(|progress| progress.backlog > 0)
+                .await?;
+
+            let next_height = self.progress().next_height();
+            let (da_height, block_hash, job) =
+                match self.ledger_db.get_next_proving_job(next_height)? {
+                    Some(job) => job,
+                    // The job was discarded by a reorg.
+                    None => continue,
+                };
+
+            match prove_with_retries(prover_service.as_ref(), da_height, &job).await {
+                Some(submitted_proof) => {
+                    self.record_proof(da_height, block_hash, submitted_proof)?;
+                }
+                None if !self.is_current_job(da_height, block_hash)? => {
+                    warn!(
+                        "The slot at height {} was reverted while it was being proven",
+                        da_height
+                    );
+                }
+                None => {
+                    debug!("Proof for height {} awaits aggregation", da_height);
+                    self.progress.send_modify(|progress| {
+                        *progress = ProvingProgress::new(
+                            progress.executed_height,
+                            progress.proven_height,
+                            progress.pending_jobs(),
+                            progress.awaiting_aggregation + 1,
+                        );
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the job at `da_height` is still the one of the DA block with the given hash,
+    /// i.e. it wasn't reverted by a reorg.
+    fn is_current_job(&self, da_height: u64, block_hash: [u8; 32]) -> anyhow::Result<bool> {
+        Ok(self.ledger_db.get_proving_job_hash(da_height)? == Some(block_hash))
+    }
+
+    /// Records a proof of the slots up to `da_height` which was posted to the DA,
+    /// and drops their jobs. When the prover doesn't generate proofs, nothing is posted and
+    /// the jobs are only dropped. `block_hash` is the hash of the DA block of the last proven slot:
+    /// the proof is ignored if this block was reverted by a reorg in the meantime.
+    fn record_proof(
+        &self,
+        da_height: u64,
+        block_hash: [u8; 32],
+        submitted_proof: SubmittedProof,
+    ) -> anyhow::Result<()> {
+        // The DA blocks are chained by their hashes, so if the last proven block is still
+        // current, so are all the previous ones.
+        if !self.is_current_job(da_height, block_hash)? {
+            warn!(
+                "Ignoring the proof for height {}, whose block was reverted",
+                da_height
+            );
+            return Ok(());
+        }
+
+        match submitted_proof.da_height {
+            Some(posted_at) => {
+                info!(
+                    "Proof for {} slots up to height {} was posted to the DA at height {}",
+                    submitted_proof.num_slots, da_height, posted_at
+                );
+
+                let end_slot = self.slot_number(da_height);
+                let start_slot = SlotNumber(
+                    (end_slot.0 + 1)
+                        .saturating_sub(submitted_proof.num_slots)
+                        .max(1),
+                );
+                self.ledger_db.put_proof(
+                    start_slot,
+                    end_slot,
+                    &StoredProof {
+                        da_height: posted_at,
+                        state_transition: submitted_proof.state_transition,
+                    },
+                )?;
+            }
+            None => debug!(
+                "{} slots up to height {} were processed without a proof",
+                submitted_proof.num_slots, da_height
+            ),
+        }
+        let proven_jobs = self.ledger_db.delete_proving_jobs_until(da_height)?;
+        self.progress.send_modify(|progress| {
+            let pending_jobs = progress.pending_jobs().saturating_sub(proven_jobs);
+            let proven_height = progress.proven_height.max(da_height);
+            *progress =
+                ProvingProgress::new(progress.executed_height, proven_height, pending_jobs, 0);
+        });
+        Ok(())
+    }
+
+    /// Verifies a proof submitted by a [`RemoteProver`](crate::RemoteProver), checks that it was
+    /// posted to the DA where the prover claims, and records it.
+    async fn submit_remote_proof(&self, proof: RemoteProof) -> anyhow::Result<()> {
+        if !self.is_current_job(proof.da_height, proof.block_hash)? {
+            warn!(
+                "Ignoring the remote proof for height {}, whose block was reverted",
+                proof.da_height
+            );
+            return Ok(());
+        }
+        let (verifier, posted_proofs) = self
+            .proof_verifier
+            .as_ref()
+            .zip(self.posted_proofs.as_ref())
+            .ok_or_else(|| anyhow::anyhow!("The node doesn't verify remote proofs"))?;
+        let posted_at = proof
+            .posted_at
+            .ok_or_else(|| anyhow::anyhow!("The proof wasn't posted to the DA"))?;
+        ensure!(proof.num_slots > 0, "The proof covers no slot");
+
+        let first_height = (proof.da_height + 1)
+            .checked_sub(proof.num_slots)
+            .ok_or_else(|| anyhow::anyhow!("The proof covers slots before the first one"))?;
+        let first_job = match self.ledger_db.get_next_proving_job(first_height)? {
+            Some((da_height, _, job)) if da_height == first_height => job,
+            _ => anyhow::bail!(
+                "The slot at height {} isn't waiting for a proof",
+                first_height
+            ),
+        };
+        let last_job = match self.ledger_db.get_next_proving_job(proof.da_height)? {
+            Some((da_height, _, job)) if da_height == proof.da_height => job,
+            _ => anyhow::bail!(
+                "The slot at height {} isn't waiting for a proof",
+                proof.da_height
+            ),
+        };
+        let state_transition =
+            verifier.verify(&proof.proof, proof.num_slots, &first_job, &last_job)?;
+        // The ledger reports the height the proof was included at, which the prover can't choose.
+        ensure!(
+            posted_proofs
+                .proofs_at(posted_at)
+                .await?
+                .contains(&proof.proof),
+            "The proof wasn't posted to the DA at height {}",
+            posted_at
+        );
+
+        self.record_proof(
+            proof.da_height,
+            proof.block_hash,
+            SubmittedProof {
+                da_height: Some(posted_at),
+                num_slots: proof.num_slots,
+                proof: Some(proof.proof),
+                state_transition: Some(state_transition),
+            },
+        )
+    }
+
+    /// Returns `true` if `token` is the one configured for the remote prover.
+    fn is_remote_prover_token(&self, token: &str) -> bool {
+        match &self.remote_prover_token {
+            // Compares every byte, so that the time taken doesn't leak the token.
+            Some(expected) => {
+                expected.len() == token.len()
+                    && expected
+                        .bytes()
+                        .zip(token.bytes())
+                        .fold(0, |diff, (a, b)| diff | (a ^ b))
+                        == 0
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of the rollup slot processed at `da_height`.
+    fn slot_number(&self, da_height: u64) -> SlotNumber {
+        SlotNumber(da_height.saturating_sub(self.first_da_height) + 1)
+    }
+
+    /// Spawns [`ProvingQueue::run_prover`] on the tokio runtime.
+    pub fn spawn_prover<Ps>(&self, prover_service: Arc<Ps>)
+    where
+        Ps: ProverService + Send + Sync + 'static,
+        Ps::StateRoot: DeserializeOwned,
+        Ps::Witness: DeserializeOwned,
+    {
+        let queue = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = queue.run_prover(prover_service).await {
+                error!("The prover stopped: {:?}", e);
+            }
+        });
+    }
+
+    /// Creates the RPC methods reporting the progress of the prover, and serving the
+    /// queued jobs to a [`RemoteProver`](crate::RemoteProver).
+    pub fn rpc_methods(&self) -> anyhow::Result<RpcModule<Self>> {
+        let mut rpc = RpcModule::new(self.clone());
+        rpc.register_method("prover_getProgress", |_, queue| {
+            Ok::<_, ErrorObjectOwned>(queue.progress())
+        })?;
+        rpc.register_method("prover_getNextJob", |params, queue| {
+            let da_height: u64 = params.one()?;
+            let job = queue
+                .ledger_db
+                .get_next_proving_job(da_height)
+                .map_err(to_rpc_error)?;
+            Ok::<_, ErrorObjectOwned>(job.map(|(da_height, block_hash, transition_data)| {
+                ProvingJob {
+                    da_height,
+                    block_hash,
+                    transition_data,
+                }
+            }))
+        })?;
+        rpc.register_async_method("prover_submitProof", |params, queue| async move {
+            let mut params = params.sequence();
+            let proof: RemoteProof = params.next()?;
+            let token: String = params.next()?;
+            if !queue.is_remote_prover_token(&token) {
+                return Err(to_rpc_error(anyhow::anyhow!("Invalid remote prover token")));
+            }
+            queue
+                .submit_remote_proof(proof)
+                .await
+                .map_err(to_rpc_error)?;
+            Ok::<_, ErrorObjectOwned>(queue.progress())
+        })?;
+        Ok(rpc)
+    }
+}
+
+fn to_rpc_error(err: anyhow::Error) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(UNKNOWN_ERROR_CODE, PROVER_RPC_ERROR, Some(err.to_string()))
+}
+
+/// Proves the state transition serialized in `job`, retrying until the proof is submitted
+/// to the DA. Returns `None` if the proof awaits aggregation with the proofs of the next slots.
+pub(crate) async fn prove_with_retries<Ps>(
+    prover_service: &Ps,
+    da_height: u64,
+    job: &[u8],
+) -> Option<SubmittedProof>
+where
+    Ps: ProverService,
+    Ps::StateRoot: DeserializeOwned,
+    Ps::Witness: DeserializeOwned,
+{
+    let mut retry_delay = INITIAL_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        match prove_slot(prover_service, job).await {
+            Ok(submitted_proof) => return submitted_proof,
+            Err(e) => {
+                warn!(
+                    "Proving attempt {} for height {} failed: {:?}. Retrying in {:?}",
+                    attempt, da_height, e, retry_delay
+                );
+                sleep(retry_delay).await;
+                retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Makes one attempt to prove the state transition serialized in `job` and submit it to the DA.
+/// Returns `None` if the proof awaits aggregation with the proofs of the next slots.
+async fn prove_slot<Ps>(prover_service: &Ps, job: &[u8]) -> anyhow::Result<Option<SubmittedProof>>
+where
+    Ps: ProverService,
+    Ps::StateRoot: DeserializeOwned,
+    Ps::Witness: DeserializeOwned,
+{
+    let transition_data: StateTransitionData<
+        Ps::StateRoot,
+        Ps::Witness,
+        <Ps::DaService as DaService>::Spec,
+    > = bincode::deserialize(job)?;
+    let header_hash = transition_data.da_block_header.hash();
+
+    prover_service.submit_witness(transition_data).await;
+    match prover_service.prove(header_hash.clone()).await? {
+        ProofProcessingStatus::ProvingInProgress => {}
+        // The prover drops the witness of the jobs it rejects, so it is submitted again on retry.
+        ProofProcessingStatus::Busy => return Err(ProverServiceError::ProverBusy.into()),
+    }
+
+    loop {
+        match prover_service.send_proof_to_da(header_hash.clone()).await? {
+            ProofSubmissionStatus::Success(submitted_proof) => return Ok(Some(submitted_proof)),
+            ProofSubmissionStatus::AwaitingAggregation => return Ok(None),
+            ProofSubmissionStatus::ProofGenerationInProgress => sleep(PROOF_POLL_INTERVAL).await,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::params::ArrayParams;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+use serde::de::DeserializeOwned;
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+
+use crate::proving::prove_with_retries;
+use crate::{ProverService, ProvingJob, ProvingProgress, RemoteProof};
+
+/// How often the full node is polled for new jobs, once the prover caught up with it.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// The delay before retrying a failed request to the full node.
+const RPC_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// A prover running apart from the full node which executes the rollup.
+///
+/// It pulls the state transitions of the executed slots from the RPC server of the node
+/// (see [`ProvingQueue::rpc_methods`](crate::ProvingQueue::rpc_methods)) and proves them in
+/// order with its own [`ProverService`], which posts the proofs to the DA. The posted proofs
+/// are then reported back to the node, which verifies them and records them in its ledger.
+/// The node must be configured with `remote_prover = true`, so that it doesn't prove the slots
+/// itself, and with the `remote_prover_token` the prover authenticates with.
+///
+/// The prover keeps no state: after a restart, it resumes from the last slot proven by the node.
+/// It also goes back to the first slot the node still waits a proof for, when its slots were
+/// reverted by a reorg of the DA chain or its proof was rejected.
+pub struct RemoteProver<Ps> {
+    client: HttpClient,
+    token: String,
+    prover_service: Arc<Ps>,
+}
+
+impl<Ps> RemoteProver<Ps> {
+    /// Creates a prover for the full node serving RPC at `node_url`, which accepts the proofs
+    /// submitted with `token`.
+    pub fn new(node_url: &str, token: String, prover_service: Arc<Ps>) -> anyhow::Result<Self> {
+        let client = HttpClientBuilder::default().build(node_url)?;
+        Ok(Self {
+            client,
+            token,
+            prover_service,
+        })
+    }
+}
+
+impl<Ps> RemoteProver<Ps>
+where
+    Ps: ProverService + Send + Sync + 'static,
+    Ps::StateRoot: DeserializeOwned,
+    Ps::Witness: DeserializeOwned,
+{
+    /// Proves the slots executed by the full node as they come. Never returns.
+    pub async fn run(self) {
+        let progress: ProvingProgress = self.request("prover_getProgress", rpc_params![]).await;
+        let mut next_height = progress.proven_height + 1;
+        info!(
+            "Proving the slots of the full node from height {}",
+            next_height
+        );
+        // The hashes of the blocks of the slots proven by this prover, whose proofs await
+        // aggregation, by height.
+        let mut awaiting_aggregation = BTreeMap::new();
+
+        loop {
+            if let Some(da_height) = self
+                .reverted_height(next_height, &awaiting_aggregation)
+                .await
+            {
+                warn!(
+                    "The full node waits for the proof of height {}, proving again from there",
+                    da_height
+                );
+                next_height = da_height;
+                awaiting_aggregation.clear();
+            }
+
+            let job: Option<ProvingJob> = self
+                .request("prover_getNextJob", rpc_params![next_height])
+                .await;
+            let job = match job {
+                Some(job) => job,
+                None => {
+                    sleep(JOB_POLL_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            let submitted_proof = prove_with_retries(
+                self.prover_service.as_ref(),
+                job.da_height,
+                &job.transition_data,
+            )
+            .await;
+            match submitted_proof {
+                Some(submitted_proof) => {
+                    awaiting_aggregation.clear();
+                    let proof = RemoteProof {
+                        da_height: job.da_height,
+                        block_hash: job.block_hash,
+                        num_slots: submitted_proof.num_slots,
+                        proof: submitted_proof.proof.unwrap_or_default(),
+                        posted_at: submitted_proof.da_height,
+                    };
+                    self.submit_proof(proof).await;
+                }
+                None => {
+                    awaiting_aggregation.insert(job.da_height, job.block_hash);
+                }
+            }
+            next_height = job.da_height + 1;
+        }
+    }
+
+    // Returns the height of the first slot the full node waits a proof for, if it is below
+    // `next_height` and isn't awaiting aggregation: the proof of this slot was rejected, or the
+    // slots from there were reverted by a reorg and executed again.
+    async fn reverted_height(
+        &self,
+        next_height: u64,
+        awaiting_aggregation: &BTreeMap<u64, [u8; 32]>,
+    ) -> Option<u64> {
+        let progress: ProvingProgress = self.request("prover_getProgress", rpc_params![]).await;
+        let first_job: ProvingJob = self
+            .request::<Option<ProvingJob>>(
+                "prover_getNextJob",
+                rpc_params![progress.proven_height + 1],
+            )
+            .await?;
+        if first_job.da_height >= next_height {
+            return None;
+        }
+        if awaiting_aggregation.get(&first_job.da_height) != Some(&first_job.block_hash) {
+            return Some(first_job.da_height);
+        }
+        // The DA blocks are chained by their hashes, so if the block of the last slot awaiting
+        // aggregation is still current, so are all the previous ones.
+        let (&last_height, &last_hash) = awaiting_aggregation.iter().next_back()?;
+        let last_job: Option<ProvingJob> = self
+            .request("prover_getNextJob", rpc_params![last_height])
+            .await;
+        match last_job {
+            Some(job) if job.da_height == last_height && job.block_hash == last_hash => None,
+            _ => Some(first_job.da_height),
+        }
+    }
+
+    // Submits a proof to the full node, retrying until it is received. A proof rejected by the
+    // node is dropped: the node keeps serving its slots, which are then proven again.
+    async fn submit_proof(&self, proof: RemoteProof) {
+        loop {
+            let result: Result<ProvingProgress, _> = self
+                .client
+                .request("prover_submitProof", rpc_params![&proof, &self.token])
+                .await;
+            match result {
+                Ok(_) => return,
+                Err(jsonrpsee::core::Error::Call(e)) => {
+                    warn!(
+                        "The full node rejected the proof for height {}: {}",
+                        proof.da_height, e
+                    );
+                    return;
+                }
+                Err(e) => {
+                    warn!(
+                        "Submitting the proof for height {} failed: {}. Retrying in {:?}",
+                        proof.da_height, e, RPC_RETRY_DELAY
+                    );
+                    sleep(RPC_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+
+    // Sends a request to the full node, retrying until it succeeds.
+    async fn request<R: DeserializeOwned>(&self, method: &str, params: ArrayParams) -> R {
+        loop {
+            match self.client.request(method, params.clone()).await {
+                Ok(response) => return response,
+                Err(e) => {
+                    warn!(
+                        "Request {} to the full node failed: {}. Retrying in {:?}",
+                        method, e, RPC_RETRY_DELAY
+                    );
+                    sleep(RPC_RETRY_DELAY).await;
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use jsonrpsee::RpcModule;
 use sov_db::ledger_db::{LedgerDB, SlotCommit};
@@ -12,7 +13,9 @@ use tokio::sync::oneshot;
 use tracing::{debug, info};
 
 use crate::verifier::StateTransitionVerifier;
-use crate::{ProofSubmissionStatus, ProverService, RunnerConfig};
+use crate::{
+    prover_service_rpc_methods, ProverService, ProvingQueue, RemoteProofVerifier, RunnerConfig,
+};
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 type GenesisParams<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::GenesisParams;
@@ -33,7 +36,9 @@ where
     ledger_db: LedgerDB,
     state_root: StateRoot<Stf, Vm, Da::Spec>,
     listen_address: SocketAddr,
-    prover_service: Ps,
+    prover_service: Arc<Ps>,
+    proving_queue: ProvingQueue,
+    remote_prover: bool,
 }
 
 /// Represents the possible modes of execution for a zkVM program
@@ -78,7 +83,10 @@ where
         ChangeSet = Sm::NativeChangeSet,
     >,
 
-    Ps: ProverService<StateRoot = Stf::StateRoot, Witness = Stf::Witness, DaService = Da>,
+    Ps: ProverService<StateRoot = Stf::StateRoot, Witness = Stf::Witness, DaService = Da>
+        + Send
+        + Sync
+        + 'static,
 {
     /// Creates a new `StateTransitionRunner`.
     ///
@@ -128,6 +136,15 @@ where
         let item_numbers = ledger_db.get_next_items_numbers();
         let last_slot_processed_before_shutdown = item_numbers.slot_number - 1;
         let start_height = runner_config.start_height + last_slot_processed_before_shutdown;
+        let mut proving_queue = ProvingQueue::new(
+            ledger_db.clone(),
+            runner_config.start_height,
+            start_height.saturating_sub(1),
+            runner_config.max_proving_backlog,
+        )?;
+        if let Some(token) = runner_config.remote_prover_token {
+            proving_queue = proving_queue.with_remote_prover_token(token);
+        }
 
         Ok(Self {
             start_height,
@@ -137,16 +154,31 @@ where
             ledger_db,
             state_root: prev_state_root,
             listen_address,
-            prover_service,
+            prover_service: Arc::new(prover_service),
+            proving_queue,
+            remote_prover: runner_config.remote_prover,
         })
     }
 
     /// Starts a RPC server with provided rpc methods.
+    /// The methods reporting the progress and the costs of the prover are served alongside them.
     pub async fn start_rpc_server(
         &self,
-        methods: RpcModule<()>,
+        mut methods: RpcModule<()>,
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) {
+        let prover_methods = self
+            .proving_queue
+            .rpc_methods()
+            .expect("Failed to create the prover RPC methods");
+        methods
+            .merge(prover_methods)
+            .expect("Prover RPC methods should not conflict with the rollup RPC methods");
+        let prover_service_methods = prover_service_rpc_methods(self.prover_service.clone())
+            .expect("Failed to create the prover service RPC methods");
+        methods
+            .merge(prover_service_methods)
+            .expect("Prover RPC methods should not conflict with the rollup RPC methods");
         let listen_address = self.listen_address;
         let _handle = tokio::spawn(async move {
             let server = jsonrpsee::server::ServerBuilder::default()
@@ -165,10 +197,37 @@ where
         });
     }
 
+    /// Verifies the proofs submitted by a [`RemoteProver`](crate::RemoteProver) with `verifier`,
+    /// and looks them up on the DA layer. Must be called before starting the RPC server.
+    pub fn with_remote_proof_verifier(
+        mut self,
+        verifier: impl RemoteProofVerifier + 'static,
+    ) -> Self {
+        self.proving_queue = self
+            .proving_queue
+            .with_proof_verifier(verifier, self.da_service.clone());
+        self
+    }
+
+    /// Returns the queue of the slots waiting to be proven.
+    pub fn proving_queue(&self) -> &ProvingQueue {
+        &self.proving_queue
+    }
+
     /// Runs the rollup.
+    ///
+    /// Proving happens on a separate task: executed slots are pushed to the [`ProvingQueue`],
+    /// and execution only waits for the prover when the proving backlog is full. With a remote
+    /// prover, the queued slots are proven by a [`RemoteProver`](crate::RemoteProver) instead.
     pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
+        if !self.remote_prover {
+            self.proving_queue.spawn_prover(self.prover_service.clone());
+        }
+
         let mut seen_block_headers: VecDeque<<Da::Spec as DaSpec>::BlockHeader> = VecDeque::new();
         let mut seen_receipts: VecDeque<_> = VecDeque::new();
+        // The state roots the seen blocks were applied on, restored when they are reverted.
+        let mut seen_pre_state_roots: VecDeque<StateRoot<Stf, Vm, Da::Spec>> = VecDeque::new();
         let mut height = self.start_height;
         loop {
             debug!("Requesting data for height {}", height);
@@ -180,6 +239,9 @@ where
                     tracing::warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
                     while let Some(seen_block_header) = seen_block_headers.pop_back() {
                         seen_receipts.pop_back();
+                        let pre_state_root = seen_pre_state_roots
+                            .pop_back()
+                            .expect("Each seen block has a pre-state root");
                         let block = self
                             .da_service
                             .get_block_at(seen_block_header.height())
@@ -187,9 +249,11 @@ where
                         if block.header().prev_hash() == seen_block_header.prev_hash() {
                             height = seen_block_header.height();
                             filtered_block = block;
+                            self.state_root = pre_state_root;
                             break;
                         }
                     }
+                    self.proving_queue.discard_from(height)?;
                     tracing::info!("Resuming execution on height={}", height);
                 }
             }
@@ -216,7 +280,6 @@ where
                 .storage_manager
                 .create_storage_on(filtered_block.header())?;
             let slot_result = self.stf.apply_slot(
-                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                 &self.state_root,
                 pre_state,
                 Default::default(),
@@ -228,6 +291,7 @@ where
             for receipt in slot_result.batch_receipts {
                 data_to_commit.add_batch(receipt);
             }
+            data_to_commit.set_state_root(&slot_result.state_root);
 
             let (inclusion_proof, completeness_proof) = self
                 .da_service
@@ -236,7 +300,6 @@ where
 
             let transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec> =
                 StateTransitionData {
-                    // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                     initial_state_root: self.state_root.clone(),
                     final_state_root: slot_result.state_root.clone(),
                     da_block_header: filtered_block.header().clone(),
@@ -250,41 +313,17 @@ where
                 .save_change_set(filtered_block.header(), slot_result.change_set)?;
 
             // ----------------
-            // Create ZK proof.
-            {
-                let header_hash = transition_data.da_block_header.hash();
-                self.prover_service.submit_witness(transition_data).await;
-                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185):
-                //   This section will be moved and called upon block finalization once we have fork management ready.
-                self.prover_service
-                    .prove(header_hash.clone())
-                    .await
-                    .expect("The proof creation should succeed");
-
-                loop {
-                    let status = self
-                        .prover_service
-                        .send_proof_to_da(header_hash.clone())
-                        .await;
-
-                    match status {
-                        Ok(ProofSubmissionStatus::Success) => {
-                            break;
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add timeout handling.
-                        Ok(ProofSubmissionStatus::ProofGenerationInProgress) => {
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add handling for DA submission errors.
-                        Err(e) => panic!("{:?}", e),
-                    }
-                }
-            }
+            // Hand the state transition over to the prover.
+            // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185):
+            //   Proving should start upon block finalization once we have fork management ready.
+            self.proving_queue.push(height, &transition_data).await?;
+
             let next_state_root = slot_result.state_root;
 
             seen_receipts.push_back(data_to_commit);
 
-            self.state_root = next_state_root;
+            let pre_state_root = std::mem::replace(&mut self.state_root, next_state_root);
+            seen_pre_state_roots.push_back(pre_state_root);
             seen_block_headers.push_back(filtered_block.header().clone());
             height += 1;
 
@@ -310,6 +349,7 @@ where
                     );
                     self.storage_manager.finalize(earliest_seen_header)?;
                     seen_block_headers.pop_front();
+                    seen_pre_state_roots.pop_front();
                     let receipts = seen_receipts.pop_front().unwrap();
                     self.ledger_db.commit_slot(receipts)?;
                     continue;
@@ -1,8 +1,15 @@
 use std::marker::PhantomData;
 
-use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier};
+use anyhow::{anyhow, ensure};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, DaVerifier};
+use sov_rollup_interface::digest::Digest;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{StateTransition, StateTransitionData, Zkvm, ZkvmGuest};
+use sov_rollup_interface::zk::{
+    AggregatedProofData, AggregatedStateTransition, StateTransition, StateTransitionData, Zkvm,
+    ZkvmGuest,
+};
 /// Verifies a state transition
 pub struct StateTransitionVerifier<ST, Da, Zk>
 where
@@ -60,3 +67,118 @@ where
         Ok(())
     }
 }
+
+/// Verifies the proofs of consecutive slots, and combines them into a single state transition
+pub struct AggregatedProofVerifier<Da, Zk, Root> {
+    phantom: PhantomData<(Da, Zk, Root)>,
+}
+
+impl<Da, Zk, Root> Default for AggregatedProofVerifier<Da, Zk, Root> {
+    fn default() -> Self {
+        Self {
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Da, Zk, Root> AggregatedProofVerifier<Da, Zk, Root>
+where
+    Da: DaSpec,
+    Zk: ZkvmGuest,
+    Root: Serialize + DeserializeOwned + PartialEq,
+{
+    /// Create an [`AggregatedProofVerifier`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the proofs of the next slots, and commit the state transition they make together,
+    /// along with the commitment to the program which proved them
+    pub fn run_aggregation<H: Digest>(&self, zkvm: Zk) -> anyhow::Result<()> {
+        let data: AggregatedProofData<Zk::CodeCommitment, Da> = zkvm.read_from_host();
+        let out = AggregatedStateTransition {
+            state_transition: aggregate_state_transitions::<Zk, Da, Root, H>(&data)?,
+            slot_program: data.slot_program,
+        };
+
+        zkvm.commit(&out);
+        Ok(())
+    }
+}
+
+/// Verifies the proofs of consecutive slots, and returns the state transition from the initial
+/// state of the first slot to the final state of the last one.
+///
+/// Fails unless every proof is valid for the slot program and covers its DA block, each block
+/// extends the previous one, each slot starts from the final state root of the previous one,
+/// and the validity conditions of the slots can be combined.
+pub fn aggregate_state_transitions<Zk, Da, Root, H>(
+    data: &AggregatedProofData<Zk::CodeCommitment, Da>,
+) -> anyhow::Result<StateTransition<Da, Root>>
+where
+    Zk: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned + PartialEq,
+    H: Digest,
+{
+    ensure!(
+        data.proofs.len() == data.da_block_headers.len(),
+        "Got {} DA block headers for {} proofs",
+        data.da_block_headers.len(),
+        data.proofs.len()
+    );
+
+    let mut slots = data.proofs.iter().zip(&data.da_block_headers).enumerate();
+    let mut aggregated = match slots.next() {
+        Some((_, (proof, header))) => verify_slot::<Zk, Da, Root>(0, proof, header, data)?,
+        None => return Err(anyhow!("There are no proofs to aggregate")),
+    };
+
+    for (index, (proof, header)) in slots {
+        let transition = verify_slot::<Zk, Da, Root>(index, proof, header, data)?;
+        ensure!(
+            header.prev_hash() == aggregated.slot_hash,
+            "The DA block of slot {} doesn't extend the block of the previous slot",
+            index
+        );
+        ensure!(
+            transition.initial_state_root == aggregated.final_state_root,
+            "Slot {} doesn't start from the final state root of the previous slot",
+            index
+        );
+
+        aggregated = StateTransition {
+            initial_state_root: aggregated.initial_state_root,
+            final_state_root: transition.final_state_root,
+            slot_hash: transition.slot_hash,
+            validity_condition: aggregated
+                .validity_condition
+                .combine::<H>(transition.validity_condition)
+                .map_err(Into::<anyhow::Error>::into)?,
+        };
+    }
+
+    Ok(aggregated)
+}
+
+fn verify_slot<Zk, Da, Root>(
+    index: usize,
+    proof: &[u8],
+    header: &Da::BlockHeader,
+    data: &AggregatedProofData<Zk::CodeCommitment, Da>,
+) -> anyhow::Result<StateTransition<Da, Root>>
+where
+    Zk: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned,
+{
+    let transition: StateTransition<Da, Root> =
+        Zk::verify_and_extract_output(proof, &data.slot_program)
+            .map_err(|e| anyhow!("The proof of slot {} is invalid: {:?}", index, e))?;
+    ensure!(
+        transition.slot_hash == header.hash(),
+        "The proof of slot {} doesn't cover its DA block",
+        index
+    );
+    Ok(transition)
+}
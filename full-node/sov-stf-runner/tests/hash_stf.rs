@@ -1,3 +1,5 @@
+use std::sync::{Arc, Mutex};
+
 use sha2::Digest;
 use sov_mock_da::{
     MockAddress, MockBlob, MockBlock, MockBlockHeader, MockDaSpec, MockValidityCond,
@@ -15,18 +17,29 @@ use sov_state::{
 pub type S = DefaultStorageSpec;
 pub type Q = SnapshotManager;
 
+/// Height, pre-state root and state root of an applied slot.
+pub type AppliedSlot = (u64, [u8; 32], [u8; 32]);
+
 #[derive(Default, Clone)]
 pub struct HashStf<Cond> {
+    // Shared between the clones, to inspect the slots applied by the runner
+    applied_slots: Arc<Mutex<Vec<AppliedSlot>>>,
     phantom_data: std::marker::PhantomData<Cond>,
 }
 
 impl<Cond> HashStf<Cond> {
     pub fn new() -> Self {
         Self {
+            applied_slots: Default::default(),
             phantom_data: std::marker::PhantomData,
         }
     }
 
+    /// The slots applied so far, in order.
+    pub fn applied_slots(&self) -> Vec<AppliedSlot> {
+        self.applied_slots.lock().unwrap().clone()
+    }
+
     fn hash_key() -> StorageKey {
         let prefix = Prefix::new(b"root".to_vec());
         StorageKey::singleton(&prefix)
@@ -125,6 +138,11 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
         }
 
         let (state_root, storage) = HashStf::<Cond>::save_from_hasher(hasher, storage, &witness);
+        self.applied_slots.lock().unwrap().push((
+            slot_header.height(),
+            *pre_state_root,
+            state_root,
+        ));
 
         SlotResult {
             state_root,
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use sha2::Sha256;
+use sov_mock_da::{MockBlockHeader, MockDaSpec, MockHash, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockZkGuest, MockZkvm};
+use sov_rollup_interface::da::Time;
+use sov_rollup_interface::zk::{
+    AggregatedProofData, AggregatedStateTransition, Proof, StateTransition, StateTransitionData,
+    ZkvmHost,
+};
+use sov_stf_runner::verifier::{aggregate_state_transitions, AggregatedProofVerifier};
+
+#[test]
+fn test_aggregate_consecutive_slots() -> Result<(), anyhow::Error> {
+    let headers: Vec<_> = (1..=3)
+        .map(|height| make_header(height, height - 1))
+        .collect();
+    let proofs = headers
+        .iter()
+        .map(|header| {
+            prove_slot(
+                header,
+                vec![header.height as u8 - 1],
+                vec![header.height as u8],
+            )
+        })
+        .collect();
+
+    let mut aggregation_vm = MockZkvm::with_program(
+        MockValidityCond::default(),
+        Arc::new(|guest| {
+            AggregatedProofVerifier::<MockDaSpec, MockZkGuest, Vec<u8>>::new()
+                .run_aggregation::<Sha256>(guest)
+        }),
+    );
+    aggregation_vm.add_hint(make_aggregation_data(proofs, headers.clone()));
+    aggregation_vm.make_proof();
+    let aggregated_proof = aggregation_vm.run(false)?.proof;
+
+    let output: AggregatedStateTransition<MockCodeCommitment, MockDaSpec, Vec<u8>> =
+        MockZkvm::<MockValidityCond>::extract_aggregated_output(&aggregated_proof)?;
+    assert_eq!(
+        AggregatedStateTransition {
+            state_transition: StateTransition {
+                initial_state_root: vec![0],
+                final_state_root: vec![3],
+                slot_hash: headers[2].hash,
+                validity_condition: MockValidityCond::default(),
+            },
+            slot_program: MockCodeCommitment([0; 32]),
+        },
+        output
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_aggregation_rejects_non_consecutive_slots() {
+    let first = make_header(1, 0);
+    let second = make_header(2, 1);
+
+    // The second slot doesn't start from the final state of the first one.
+    let data = make_aggregation_data(
+        vec![
+            prove_slot(&first, vec![0], vec![1]),
+            prove_slot(&second, vec![2], vec![3]),
+        ],
+        vec![first.clone(), second.clone()],
+    );
+    let err =
+        aggregate_state_transitions::<MockZkGuest, MockDaSpec, Vec<u8>, Sha256>(&data).unwrap_err();
+    assert_eq!(
+        "Slot 1 doesn't start from the final state root of the previous slot",
+        err.to_string()
+    );
+
+    // The DA block of the second slot doesn't extend the block of the first one.
+    let fork = make_header(2, 7);
+    let data = make_aggregation_data(
+        vec![
+            prove_slot(&first, vec![0], vec![1]),
+            prove_slot(&fork, vec![1], vec![2]),
+        ],
+        vec![first.clone(), fork],
+    );
+    let err =
+        aggregate_state_transitions::<MockZkGuest, MockDaSpec, Vec<u8>, Sha256>(&data).unwrap_err();
+    assert_eq!(
+        "The DA block of slot 1 doesn't extend the block of the previous slot",
+        err.to_string()
+    );
+
+    // The proofs don't cover the given DA blocks.
+    let data = make_aggregation_data(
+        vec![
+            prove_slot(&second, vec![0], vec![1]),
+            prove_slot(&first, vec![1], vec![2]),
+        ],
+        vec![first, second],
+    );
+    let err =
+        aggregate_state_transitions::<MockZkGuest, MockDaSpec, Vec<u8>, Sha256>(&data).unwrap_err();
+    assert_eq!(
+        "The proof of slot 0 doesn't cover its DA block",
+        err.to_string()
+    );
+}
+
+#[test]
+fn test_aggregation_rejects_proofs_of_another_program() {
+    let header = make_header(1, 0);
+    let mut data = make_aggregation_data(vec![prove_slot(&header, vec![0], vec![1])], vec![header]);
+    data.slot_program = MockCodeCommitment([1; 32]);
+
+    let err =
+        aggregate_state_transitions::<MockZkGuest, MockDaSpec, Vec<u8>, Sha256>(&data).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("The proof of slot 0 is invalid"));
+}
+
+fn prove_slot(
+    header: &MockBlockHeader,
+    initial_state_root: Vec<u8>,
+    final_state_root: Vec<u8>,
+) -> Vec<u8> {
+    let mut vm = MockZkvm::new(MockValidityCond::default());
+    vm.add_hint(StateTransitionData::<Vec<u8>, (), MockDaSpec> {
+        initial_state_root,
+        final_state_root,
+        da_block_header: header.clone(),
+        inclusion_proof: [0; 32],
+        completeness_proof: (),
+        blobs: vec![],
+        state_transition_witness: (),
+    });
+    vm.make_proof();
+
+    match vm.run(false).unwrap().proof {
+        Proof::PublicInput(data) | Proof::Full(data) => data,
+    }
+}
+
+fn make_aggregation_data(
+    proofs: Vec<Vec<u8>>,
+    da_block_headers: Vec<MockBlockHeader>,
+) -> AggregatedProofData<MockCodeCommitment, MockDaSpec> {
+    AggregatedProofData {
+        slot_program: MockCodeCommitment([0; 32]),
+        proofs,
+        da_block_headers,
+    }
+}
+
+fn make_header(height: u64, prev_height: u64) -> MockBlockHeader {
+    MockBlockHeader {
+        prev_hash: MockHash::from([prev_height as u8; 32]),
+        hash: MockHash::from([height as u8; 32]),
+        height,
+        time: Time::now(),
+    }
+}
@@ -1,13 +1,19 @@
+use std::sync::Arc;
+
+use jsonrpsee::rpc_params;
 use sov_mock_da::{
-    MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash, MockValidityCond,
+    MockAddress, MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash,
+    MockValidityCond,
 };
 use sov_mock_zkvm::MockZkvm;
 use sov_rollup_interface::da::Time;
-use sov_rollup_interface::zk::StateTransitionData;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{ExecutionStats, StateTransitionData};
 use sov_stf_runner::mock::MockStf;
 use sov_stf_runner::{
-    ParallelProverService, ProofProcessingStatus, ProofSubmissionStatus, ProverService,
-    ProverServiceConfig, ProverServiceError, RollupProverConfig, WitnessSubmissionStatus,
+    prover_service_rpc_methods, ParallelProverService, ProofProcessingStatus,
+    ProofSubmissionStatus, ProverService, ProverServiceConfig, ProverServiceError,
+    RollupProverConfig, WitnessSubmissionStatus,
 };
 
 #[tokio::test]
@@ -162,6 +168,75 @@ async fn test_generate_multiple_proofs_for_the_same_witness() -> Result<(), anyh
     Ok(())
 }
 
+#[tokio::test]
+async fn test_execution_stats() -> Result<(), anyhow::Error> {
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover();
+    let prover_service = Arc::new(prover_service);
+    let rpc = prover_service_rpc_methods(prover_service.clone())?;
+
+    let header_hash = MockHash::from([0; 32]);
+    prover_service
+        .submit_witness(make_transition_data(header_hash))
+        .await;
+    prover_service.prove(header_hash).await?;
+    assert_eq!(None, prover_service.execution_stats(&header_hash));
+
+    vm.make_proof();
+    wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+
+    // The stats are kept after the proof is posted.
+    let stats: Option<ExecutionStats> = rpc
+        .call("prover_getExecutionStats", rpc_params![header_hash])
+        .await?;
+    let stats = stats.expect("The stats of the zkVM run should be recorded");
+    // The mock zkVM doesn't execute cycles.
+    assert_eq!(0, stats.cycles);
+    assert!(stats.cycles_by_function.is_empty());
+    assert_eq!(Some(stats), prover_service.execution_stats(&header_hash));
+
+    let stats: Option<ExecutionStats> = rpc
+        .call(
+            "prover_getExecutionStats",
+            rpc_params![MockHash::from([1; 32])],
+        )
+        .await?;
+    assert_eq!(None, stats);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_nothing_is_posted_without_proofs() -> Result<(), anyhow::Error> {
+    for prover_config in [RollupProverConfig::Skip, RollupProverConfig::Simulate] {
+        let TestProver {
+            prover_service,
+            da_service,
+            ..
+        } = make_prover_with_config(prover_config);
+
+        let header_hash = MockHash::from([0; 32]);
+        prover_service
+            .submit_witness(make_transition_data(header_hash))
+            .await;
+        prover_service.prove(header_hash).await?;
+
+        let submitted_proof = loop {
+            match prover_service.send_proof_to_da(header_hash).await? {
+                ProofSubmissionStatus::Success(submitted_proof) => break submitted_proof,
+                _ => tokio::time::sleep(tokio::time::Duration::from_millis(100)).await,
+            }
+        };
+        assert_eq!(None, submitted_proof.da_height);
+        assert_eq!(None, submitted_proof.state_transition);
+        // No block was created for a proof.
+        assert_eq!(0, da_service.get_head_block_header().await?.height);
+    }
+
+    Ok(())
+}
+
 struct TestProver {
     prover_service: ParallelProverService<
         [u8; 0],
@@ -171,6 +246,7 @@ struct TestProver {
         MockStf<MockValidityCond>,
     >,
     vm: MockZkvm<MockValidityCond>,
+    da_service: MockDaService,
     num_worker_threads: usize,
 }
 
@@ -186,7 +262,7 @@ async fn wait_for_proof_proof_da_submission(
 ) {
     for _ in 0..10 {
         let status = prover_service.send_proof_to_da(header_hash).await;
-        if let Ok(ProofSubmissionStatus::Success) = status {
+        if let Ok(ProofSubmissionStatus::Success(_)) = status {
             return;
         }
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
@@ -194,10 +270,14 @@ async fn wait_for_proof_proof_da_submission(
 }
 
 fn make_new_prover() -> TestProver {
+    make_prover_with_config(RollupProverConfig::Execute)
+}
+
+fn make_prover_with_config(prover_config: RollupProverConfig) -> TestProver {
     let num_threads = num_cpus::get();
     let vm = MockZkvm::new(MockValidityCond::default());
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
 
-    let prover_config = RollupProverConfig::Execute;
     let zk_stf = MockStf::<MockValidityCond>::default();
     let da_verifier = MockDaVerifier::default();
     TestProver {
@@ -205,6 +285,7 @@ fn make_new_prover() -> TestProver {
             vm.clone(),
             zk_stf,
             da_verifier,
+            da_service.clone(),
             prover_config,
             (),
             num_threads,
@@ -213,6 +294,7 @@ fn make_new_prover() -> TestProver {
             },
         ),
         vm,
+        da_service,
         num_worker_threads: num_threads,
     }
 }
@@ -0,0 +1,520 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use borsh::BorshSerialize;
+use sha2::Sha256;
+use sov_db::ledger_db::LedgerDB;
+use sov_mock_da::{
+    MockAddress, MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash,
+    MockValidityCond,
+};
+use sov_mock_zkvm::{MockCodeCommitment, MockZkGuest, MockZkvm};
+use sov_rollup_interface::da::Time;
+use sov_rollup_interface::rpc::{LedgerRpcProvider, SlotIdentifier};
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{StateTransitionData, ZkvmHost};
+use sov_stf_runner::mock::MockStf;
+use sov_stf_runner::verifier::AggregatedProofVerifier;
+use sov_stf_runner::{
+    ParallelProverService, ProverServiceConfig, ProvingJob, ProvingProgress, ProvingQueue,
+    RemoteProof, RemoteProver, RollupProverConfig, ZkProofVerifier,
+};
+
+const REMOTE_PROVER_TOKEN: &str = "remote-prover-token";
+
+#[tokio::test]
+async fn test_execution_waits_for_full_backlog() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let queue = ProvingQueue::new(ledger_db.clone(), 1, 0, 2)?;
+
+    queue.push(1, &make_transition_data(1)).await?;
+    queue.push(2, &make_transition_data(2)).await?;
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 2,
+            proven_height: 0,
+            lag: 2,
+            backlog: 2,
+            awaiting_aggregation: 0,
+        },
+        queue.progress()
+    );
+
+    // The backlog is full, so the next slot has to wait for the prover.
+    let third_push = queue.push(3, &make_transition_data(3));
+    assert!(tokio::time::timeout(Duration::from_millis(200), third_push)
+        .await
+        .is_err());
+
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    queue.spawn_prover(Arc::new(make_prover(da_service.clone(), 1)));
+    tokio::time::timeout(Duration::from_secs(10), async {
+        queue.push(3, &make_transition_data(3)).await?;
+        while queue.progress().proven_height < 3 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok::<_, anyhow::Error>(())
+    })
+    .await??;
+
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 3,
+            proven_height: 3,
+            lag: 0,
+            backlog: 0,
+            awaiting_aggregation: 0,
+        },
+        queue.progress()
+    );
+
+    // Every slot has its own proof, posted to the DA.
+    for slot in 1..=3 {
+        let proof = ledger_db
+            .get_proof_by_slot(&SlotIdentifier::Number(slot))?
+            .unwrap();
+        assert_eq!((slot, slot), (proof.start_slot, proof.end_slot));
+        assert!(da_service
+            .get_aggregated_proofs_at(proof.da_height)
+            .await?
+            .iter()
+            .any(|posted_proof| !posted_proof.is_empty()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_pending_jobs_survive_restart() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    {
+        let queue = ProvingQueue::new(LedgerDB::with_path(tmpdir.path())?, 1, 0, 10)?;
+        for height in 1..=3 {
+            queue.push(height, &make_transition_data(height)).await?;
+        }
+    }
+
+    let queue = ProvingQueue::new(LedgerDB::with_path(tmpdir.path())?, 1, 3, 10)?;
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 3,
+            proven_height: 0,
+            lag: 3,
+            backlog: 3,
+            awaiting_aggregation: 0,
+        },
+        queue.progress()
+    );
+
+    // Jobs reverted by a reorg are dropped.
+    queue.discard_from(3)?;
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 2,
+            proven_height: 0,
+            lag: 2,
+            backlog: 2,
+            awaiting_aggregation: 0,
+        },
+        queue.progress()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proof_of_reverted_slot_is_ignored() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let queue = make_remote_proving_queue(ledger_db.clone(), da_service.clone())?;
+    let rpc = queue.rpc_methods()?;
+
+    let reverted_transition_data = make_transition_data(1);
+    queue.push(1, &reverted_transition_data).await?;
+    let reverted_job: ProvingJob = rpc
+        .call("prover_getNextJob", jsonrpsee::rpc_params![1u64])
+        .await?;
+
+    // The DA chain forks while the slot is being proven, and the new block is executed.
+    let mut fork_transition_data = make_transition_data(1);
+    fork_transition_data.da_block_header.hash = MockHash::from([0xff; 32]);
+    queue.discard_from(1)?;
+    queue.push(1, &fork_transition_data).await?;
+
+    // The proof of the reverted block doesn't prove the new one.
+    let reverted_proof = make_remote_proof(
+        &da_service,
+        &reverted_job,
+        &reverted_transition_data,
+        [0; 32],
+    )
+    .await;
+    let progress: ProvingProgress = rpc
+        .call(
+            "prover_submitProof",
+            jsonrpsee::rpc_params![reverted_proof, REMOTE_PROVER_TOKEN],
+        )
+        .await?;
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 1,
+            proven_height: 0,
+            lag: 1,
+            backlog: 1,
+            awaiting_aggregation: 0,
+        },
+        progress
+    );
+    assert!(ledger_db
+        .get_proof_by_slot(&SlotIdentifier::Number(1))?
+        .is_none());
+
+    let fork_job: ProvingJob = rpc
+        .call("prover_getNextJob", jsonrpsee::rpc_params![1u64])
+        .await?;
+    assert_eq!([0xff; 32], fork_job.block_hash);
+    let fork_proof =
+        make_remote_proof(&da_service, &fork_job, &fork_transition_data, [0; 32]).await;
+    let progress: ProvingProgress = rpc
+        .call(
+            "prover_submitProof",
+            jsonrpsee::rpc_params![fork_proof, REMOTE_PROVER_TOKEN],
+        )
+        .await?;
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 1,
+            proven_height: 1,
+            lag: 0,
+            backlog: 0,
+            awaiting_aggregation: 0,
+        },
+        progress
+    );
+    assert!(ledger_db
+        .get_proof_by_slot(&SlotIdentifier::Number(1))?
+        .is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_proofs_are_verified() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let queue = make_remote_proving_queue(ledger_db.clone(), da_service.clone())?;
+    let rpc = queue.rpc_methods()?;
+
+    let transition_data = make_transition_data(1);
+    queue.push(1, &transition_data).await?;
+    let job: ProvingJob = rpc
+        .call("prover_getNextJob", jsonrpsee::rpc_params![1u64])
+        .await?;
+
+    let rejected_proofs = [
+        // Submitted without the token of the remote prover.
+        (
+            make_remote_proof(&da_service, &job, &transition_data, [0; 32]).await,
+            "wrong-token",
+        ),
+        // Generated by another program.
+        (
+            make_remote_proof(&da_service, &job, &transition_data, [1; 32]).await,
+            REMOTE_PROVER_TOKEN,
+        ),
+        // Proves the state transition of another slot.
+        (
+            make_remote_proof(&da_service, &job, &make_transition_data(2), [0; 32]).await,
+            REMOTE_PROVER_TOKEN,
+        ),
+        // Reported at the DA height where the proof of the other program was posted.
+        (
+            RemoteProof {
+                posted_at: Some(2),
+                ..make_remote_proof(&da_service, &job, &transition_data, [0; 32]).await
+            },
+            REMOTE_PROVER_TOKEN,
+        ),
+    ];
+    for (proof, token) in rejected_proofs {
+        assert!(rpc
+            .call::<_, ProvingProgress>("prover_submitProof", jsonrpsee::rpc_params![proof, token])
+            .await
+            .is_err());
+    }
+    assert_eq!(0, queue.progress().proven_height);
+    assert!(ledger_db
+        .get_proof_by_slot(&SlotIdentifier::Number(1))?
+        .is_none());
+
+    let proof = make_remote_proof(&da_service, &job, &transition_data, [0; 32]).await;
+    let progress: ProvingProgress = rpc
+        .call(
+            "prover_submitProof",
+            jsonrpsee::rpc_params![proof, REMOTE_PROVER_TOKEN],
+        )
+        .await?;
+    assert_eq!(1, progress.proven_height);
+    assert_eq!(
+        [1; 32],
+        ledger_db
+            .get_proof_by_slot(&SlotIdentifier::Number(1))?
+            .unwrap()
+            .state_transition
+            .unwrap()
+            .slot_hash
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proofs_are_aggregated() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let queue = ProvingQueue::new(ledger_db.clone(), 1, 0, 10)?;
+
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let aggregation_vm = MockZkvm::with_program(
+        MockValidityCond::default(),
+        Arc::new(|guest| {
+            AggregatedProofVerifier::<MockDaSpec, MockZkGuest, [u8; 0]>::new()
+                .run_aggregation::<Sha256>(guest)
+        }),
+    );
+    aggregation_vm.make_proof();
+    let prover = make_prover(da_service.clone(), 2)
+        .with_aggregation(aggregation_vm, MockCodeCommitment([0; 32]));
+    queue.spawn_prover(Arc::new(prover));
+
+    for height in 1..=5 {
+        queue.push(height, &make_transition_data(height)).await?;
+    }
+    tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let progress = queue.progress();
+            if progress.proven_height == 4 && progress.awaiting_aggregation == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await?;
+
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 5,
+            proven_height: 4,
+            lag: 1,
+            backlog: 0,
+            awaiting_aggregation: 1,
+        },
+        queue.progress()
+    );
+
+    // Each pair of slots has a single proof, which covers both.
+    for (start_slot, end_slot) in [(1, 2), (3, 4)] {
+        for slot in start_slot..=end_slot {
+            let proof = ledger_db
+                .get_proof_by_slot(&SlotIdentifier::Number(slot))?
+                .unwrap();
+            assert_eq!((start_slot, end_slot), (proof.start_slot, proof.end_slot));
+            assert_eq!(
+                [end_slot as u8; 32],
+                proof.state_transition.unwrap().slot_hash
+            );
+        }
+    }
+    // The last slot awaits the proof of the next one.
+    assert!(ledger_db
+        .get_proof_by_slot(&SlotIdentifier::Number(5))?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_prover() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let queue = make_remote_proving_queue(ledger_db.clone(), da_service.clone())?;
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await?;
+    let node_url = format!("http://{}", server.local_addr()?);
+    let _server_handle = server.start(queue.rpc_methods()?);
+
+    for height in 1..=3 {
+        queue.push(height, &make_transition_data(height)).await?;
+    }
+
+    // The slots are proven by a prover which only talks to the node over RPC.
+    let prover = RemoteProver::new(
+        &node_url,
+        REMOTE_PROVER_TOKEN.to_string(),
+        Arc::new(make_prover(da_service.clone(), 1)),
+    )?;
+    tokio::spawn(prover.run());
+
+    wait_for_proven_height(&queue, 3).await?;
+
+    assert_eq!(
+        ProvingProgress {
+            executed_height: 3,
+            proven_height: 3,
+            lag: 0,
+            backlog: 0,
+            awaiting_aggregation: 0,
+        },
+        queue.progress()
+    );
+
+    for slot in 1..=3 {
+        let proof = ledger_db
+            .get_proof_by_slot(&SlotIdentifier::Number(slot))?
+            .unwrap();
+        assert_eq!((slot, slot), (proof.start_slot, proof.end_slot));
+        assert_eq!([slot as u8; 32], proof.state_transition.unwrap().slot_hash);
+        assert!(!da_service
+            .get_aggregated_proofs_at(proof.da_height)
+            .await?
+            .is_empty());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remote_prover_proves_reverted_slots_again() -> Result<(), anyhow::Error> {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let ledger_db = LedgerDB::with_path(tmpdir.path())?;
+    let da_service = MockDaService::new(MockAddress::new([0; 32]));
+    let queue = make_remote_proving_queue(ledger_db.clone(), da_service.clone())?;
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await?;
+    let node_url = format!("http://{}", server.local_addr()?);
+    let _server_handle = server.start(queue.rpc_methods()?);
+
+    for height in 1..=2 {
+        queue.push(height, &make_transition_data(height)).await?;
+    }
+    let prover = RemoteProver::new(
+        &node_url,
+        REMOTE_PROVER_TOKEN.to_string(),
+        Arc::new(make_prover(da_service, 1)),
+    )?;
+    tokio::spawn(prover.run());
+    wait_for_proven_height(&queue, 2).await?;
+
+    // The last proven slot is reverted by a reorg, and its replacement is executed. The prover
+    // goes back to prove it, although it already proved a slot at this height.
+    let mut fork_transition_data = make_transition_data(2);
+    fork_transition_data.da_block_header.hash = MockHash::from([0xff; 32]);
+    queue.discard_from(2)?;
+    assert_eq!(1, queue.progress().proven_height);
+    queue.push(2, &fork_transition_data).await?;
+    wait_for_proven_height(&queue, 2).await?;
+
+    Ok(())
+}
+
+// Waits until the slots up to `da_height` are proven.
+async fn wait_for_proven_height(queue: &ProvingQueue, da_height: u64) -> anyhow::Result<()> {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while queue.progress().proven_height < da_height {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await?;
+    Ok(())
+}
+
+// Creates a queue accepting the mock proofs of a `RemoteProver` submitted with
+// `REMOTE_PROVER_TOKEN`, and posted to `da_service`.
+fn make_remote_proving_queue(
+    ledger_db: LedgerDB,
+    da_service: MockDaService,
+) -> anyhow::Result<ProvingQueue> {
+    Ok(ProvingQueue::new(ledger_db, 1, 0, 10)?
+        .with_remote_prover_token(REMOTE_PROVER_TOKEN.to_string())
+        .with_proof_verifier(
+            ZkProofVerifier::<MockZkGuest, MockDaSpec, [u8; 0], Vec<u8>>::new(MockCodeCommitment(
+                [0; 32],
+            )),
+            da_service,
+        ))
+}
+
+// Proves the state transition in `transition_data` with a program committed to by
+// `code_commitment`, posts the proof to `da_service`, and makes the remote proof of the `job`
+// with it.
+async fn make_remote_proof(
+    da_service: &MockDaService,
+    job: &ProvingJob,
+    transition_data: &StateTransitionData<[u8; 0], Vec<u8>, MockDaSpec>,
+    code_commitment: [u8; 32],
+) -> RemoteProof {
+    let mut vm = MockZkvm::new(MockValidityCond::default())
+        .with_code_commitment(MockCodeCommitment(code_commitment));
+    vm.add_hint(transition_data);
+    vm.make_proof();
+    let proof = vm.run(false).unwrap().proof.try_to_vec().unwrap();
+    let posted_at = da_service.send_aggregated_zk_proof(&proof).await.unwrap();
+    RemoteProof {
+        da_height: job.da_height,
+        block_hash: job.block_hash,
+        num_slots: 1,
+        proof,
+        posted_at: Some(posted_at),
+    }
+}
+
+fn make_prover(
+    da_service: MockDaService,
+    aggregated_proof_block_jump: u64,
+) -> ParallelProverService<
+    [u8; 0],
+    Vec<u8>,
+    MockDaService,
+    MockZkvm<MockValidityCond>,
+    MockStf<MockValidityCond>,
+> {
+    let vm = MockZkvm::new(MockValidityCond::default());
+    vm.make_proof();
+    ParallelProverService::new(
+        vm,
+        MockStf::<MockValidityCond>::default(),
+        MockDaVerifier::default(),
+        da_service,
+        RollupProverConfig::Execute,
+        (),
+        1,
+        ProverServiceConfig {
+            aggregated_proof_block_jump,
+        },
+    )
+}
+
+fn make_transition_data(height: u64) -> StateTransitionData<[u8; 0], Vec<u8>, MockDaSpec> {
+    StateTransitionData {
+        initial_state_root: [],
+        final_state_root: [],
+        da_block_header: MockBlockHeader {
+            prev_hash: MockHash::from([height as u8 - 1; 32]),
+            hash: MockHash::from([height as u8; 32]),
+            height,
+            time: Time::now(),
+        },
+        inclusion_proof: [0; 32],
+        completeness_proof: (),
+        blobs: vec![],
+        state_transition_witness: vec![],
+    }
+}
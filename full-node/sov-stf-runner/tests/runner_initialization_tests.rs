@@ -73,6 +73,9 @@ fn initialize_runner(
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
             },
+            max_proving_backlog: 64,
+            remote_prover: false,
+            remote_prover_token: None,
         },
         da: MockDaConfig {
             sender_address: address,
@@ -102,6 +105,7 @@ fn initialize_runner(
         vm,
         stf.clone(),
         verifier,
+        da_service.clone(),
         prover_config,
         // Should be ZkStorage, but we don't need it for this test
         storage_manager.create_finalized_storage().unwrap(),
@@ -67,10 +67,27 @@ async fn test_simple_reorg_case() {
         genesis_params,
     };
 
-    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service).await;
+    let stf = HashStf::<MockValidityCond>::new();
+    let (before, after) =
+        runner_execution(tmpdir.path(), init_variant, da_service, stf.clone()).await;
     assert_ne!(before, after);
     assert_eq!(expected_state_root, after);
 
+    // Blocks 1-4 of the main chain, then the fork replacing blocks 3 and 4.
+    let applied_slots = stf.applied_slots();
+    let heights: Vec<u64> = applied_slots.iter().map(|(height, _, _)| *height).collect();
+    assert_eq!(vec![1, 2, 3, 4, 3, 4, 5], heights);
+    // Each block is applied on the state root of its parent, including the first block of the fork.
+    assert_eq!(before, applied_slots[0].1);
+    for (index, (height, pre_state_root, _)) in applied_slots.iter().enumerate().skip(1) {
+        let (_, _, parent_state_root) = applied_slots[..index]
+            .iter()
+            .rev()
+            .find(|(parent_height, _, _)| parent_height + 1 == *height)
+            .unwrap();
+        assert_eq!(parent_state_root, pre_state_root);
+    }
+
     let committed_root_hash = get_saved_root_hash(tmpdir.path()).unwrap().unwrap();
 
     assert_eq!(expected_committed_root_hash.unwrap(), committed_root_hash);
@@ -105,7 +122,8 @@ async fn test_instant_finality_data_stored() {
         genesis_params,
     };
 
-    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service).await;
+    let stf = HashStf::<MockValidityCond>::new();
+    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service, stf).await;
     assert_ne!(before, after);
     assert_eq!(expected_state_root, after);
 
@@ -118,6 +136,7 @@ async fn runner_execution(
     path: &std::path::Path,
     init_variant: MockInitVariant,
     da_service: MockDaService,
+    stf: HashStf<MockValidityCond>,
 ) -> ([u8; 32], [u8; 32]) {
     let rollup_config = RollupConfig::<MockDaConfig> {
         storage: StorageConfig {
@@ -129,6 +148,9 @@ async fn runner_execution(
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
             },
+            max_proving_backlog: 64,
+            remote_prover: false,
+            remote_prover_token: None,
         },
         da: MockDaConfig {
             sender_address: da_service.get_sequencer_address(),
@@ -140,8 +162,6 @@ async fn runner_execution(
 
     let ledger_db = LedgerDB::with_path(path).unwrap();
 
-    let stf = HashStf::<MockValidityCond>::new();
-
     let storage_config = sov_state::config::Config {
         path: rollup_config.storage.path.clone(),
     };
@@ -155,6 +175,8 @@ async fn runner_execution(
         vm,
         stf.clone(),
         verifier,
+        // Proofs are posted to another DA, so that their blocks don't interfere with the planned fork
+        MockDaService::new(MockAddress::new([0; 32])),
         prover_config,
         // Should be ZkStorage, but we don't need it for this test
         storage_manager.create_finalized_storage().unwrap(),
@@ -2,6 +2,7 @@ use anyhow::{bail, Context, Result};
 #[cfg(feature = "native")]
 use sov_modules_api::macros::CliWalletArg;
 use sov_modules_api::{CallResponse, StateMapAccessor, WorkingSet};
+use sov_state::storage::{StateValueCodec, StorageKey, StorageValue};
 
 use crate::{Amount, Bank, Coins, Token};
 
@@ -259,6 +260,46 @@ impl<C: sov_modules_api::Context> Bank<C> {
             .and_then(|token| token.balances.get(&user_address, working_set))
     }
 
+    /// The storage keys read by [`get_balance_of`](Bank::get_balance_of), and so proven by its
+    /// `_with_proof` RPC method: the token, then the balance of the user.
+    pub fn balance_storage_keys(
+        &self,
+        user_address: &C::Address,
+        token_address: &C::Address,
+    ) -> [StorageKey; 2] {
+        let balances_prefix =
+            prefix_from_address_with_parent::<C>(self.tokens.prefix(), token_address);
+        [
+            StorageKey::new(self.tokens.prefix(), token_address, self.tokens.codec()),
+            StorageKey::new(&balances_prefix, user_address, self.tokens.codec()),
+        ]
+    }
+
+    /// Recomputes the balance returned by [`get_balance_of`](Bank::get_balance_of) from storage entries
+    /// proven against a trusted state root, such as the ones returned by
+    /// [`ValueWithProof::verify`](sov_modules_api::ValueWithProof::verify). Fails if an entry needed
+    /// to compute the balance wasn't proven.
+    pub fn get_proven_balance_of(
+        &self,
+        user_address: &C::Address,
+        token_address: &C::Address,
+        entries: &[(StorageKey, Option<StorageValue>)],
+    ) -> Result<Option<u64>> {
+        let token_key = StorageKey::new(self.tokens.prefix(), token_address, self.tokens.codec());
+        let token: Option<Token<C>> = proven_value(entries, &token_key, self.tokens.codec())?;
+        match token {
+            Some(token) => {
+                let balance_key = StorageKey::new(
+                    token.balances.prefix(),
+                    user_address,
+                    token.balances.codec(),
+                );
+                proven_value(entries, &balance_key, token.balances.codec())
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Get the name of a token by address
     pub fn get_token_name(
         &self,
@@ -281,6 +322,26 @@ impl<C: sov_modules_api::Context> Bank<C> {
     }
 }
 
+/// Looks up the value of `key` among proven storage entries and decodes it with `codec`.
+fn proven_value<V, VC: StateValueCodec<V>>(
+    entries: &[(StorageKey, Option<StorageValue>)],
+    key: &StorageKey,
+    codec: &VC,
+) -> Result<Option<V>> {
+    let (_, value) = entries
+        .iter()
+        .find(|(proven_key, _)| proven_key == key)
+        .with_context(|| format!("The storage entry {} wasn't proven", key))?;
+    value
+        .as_ref()
+        .map(|value| {
+            codec
+                .try_decode_value(value.value())
+                .map_err(|e| anyhow::anyhow!("Failed to decode the storage entry {}: {:?}", key, e))
+        })
+        .transpose()
+}
+
 /// Creates a new prefix from an already existing prefix `parent_prefix` and a `token_address`
 /// by extending the parent prefix.
 pub(crate) fn prefix_from_address_with_parent<C: sov_modules_api::Context>(
@@ -19,7 +19,7 @@ pub struct TotalSupplyResponse {
     pub amount: Option<Amount>,
 }
 
-#[rpc_gen(client, server, namespace = "bank")]
+#[rpc_gen(client, server, namespace = "bank", with_proofs)]
 impl<C: sov_modules_api::Context> Bank<C> {
     #[rpc_method(name = "balanceOf")]
     /// Rpc method that returns the balance of the user at the address `user_address` for the token
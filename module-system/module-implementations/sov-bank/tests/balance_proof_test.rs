@@ -0,0 +1,69 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank};
+use sov_modules_api::{Module, WorkingSet};
+use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
+use sov_state::storage::NativeStorage;
+use sov_state::{DefaultStorageSpec, ProverStorage, Storage};
+
+#[test]
+fn balance_proof_verifies_against_state_root() {
+    let initial_balance = 100;
+    let bank_config = create_bank_config_with_token(2, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let prover_storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::<C>::new(prover_storage.clone());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    let (_, node_batch) = prover_storage
+        .compute_state_update(cache_log, &witness)
+        .unwrap();
+    prover_storage.commit(&node_batch, &Default::default());
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let user_address = bank_config.tokens[0].address_and_balances[0].0;
+
+    let mut working_set = WorkingSet::<C>::new(prover_storage.clone());
+    working_set.record_reads();
+    let balance = bank
+        .balance_of(user_address, token_address, &mut working_set)
+        .unwrap();
+    let response = working_set.prove_recorded_reads(balance).unwrap();
+    assert_eq!(response.value.amount, Some(initial_balance));
+    assert_eq!(response.version, 1);
+
+    let state_root = prover_storage.get_root_hash(response.version).unwrap();
+    let entries = response
+        .verify::<ProverStorage<DefaultStorageSpec, SnapshotManager>>(&state_root)
+        .unwrap();
+    // `get_balance_of` reads the token, to find its balances, and then the balance of the user
+    assert_eq!(entries.len(), 2);
+    for key in bank.balance_storage_keys(&user_address, &token_address) {
+        assert!(entries
+            .iter()
+            .any(|(proven_key, value)| proven_key == &key && value.is_some()));
+    }
+    assert_eq!(
+        bank.get_proven_balance_of(&user_address, &token_address, &entries)
+            .unwrap(),
+        Some(initial_balance)
+    );
+    // The balance of another user can't be computed from these entries
+    let other_user = bank_config.tokens[0].address_and_balances[1].0;
+    assert!(bank
+        .get_proven_balance_of(&other_user, &token_address, &entries)
+        .is_err());
+
+    // A proof for a different value must be rejected
+    let mut tampered = response.clone();
+    tampered.proofs[0].value = None;
+    assert!(tampered
+        .verify::<ProverStorage<DefaultStorageSpec, SnapshotManager>>(&state_root)
+        .is_err());
+}
@@ -35,7 +35,8 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
     type Context = C;
 
     // This implementation returns three categories of blobs:
-    // 1. Any blobs sent by the preferred sequencer ("prority blobs")
+    // 1. Any blobs sent by the preferred sequencer ("prority blobs"). If the sequencer registry has a
+    //    leader schedule, the preferred sequencer rotates at the end of the slot.
     // 2. Any non-priority blobs which were sent `DEFERRED_SLOTS_COUNT` slots ago ("expiring deferred blobs")
     // 3. Some additional deferred blobs needed to fill the total requested by the sequencer, if applicable. ("bonus blobs")
     fn get_blobs_for_this_slot<'a, 'k, I>(
@@ -54,11 +55,19 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
                 .filter(|b| self.filter_by_allowed_sender(b, working_set.inner))
                 .map(Into::into)
                 .collect::<Vec<_>>();
+            let mut leader_submitted_blob = false;
             if let Some(sequencer) = self.get_preferred_sequencer(working_set.inner) {
+                leader_submitted_blob = blobs.iter().any(|b| b.as_ref().sender() == sequencer);
                 blobs.sort_by_key(|b: &BlobRefOrOwned<Da::BlobTransaction>| {
                     b.as_ref().sender() != sequencer
                 });
             }
+            let current_slot = self.get_true_slot_height(working_set);
+            self.sequencer_registry.advance_leader_schedule(
+                current_slot,
+                leader_submitted_blob,
+                working_set.inner,
+            );
             return Ok(blobs.into_iter().map(Into::into).collect());
         }
 
@@ -72,18 +81,22 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
             self.take_blobs_for_slot_height(slot_for_expiring_blobs, working_set.inner);
 
         // If there is no preferred sequencer, that's all we need to do
-        let preferred_sequencer =
-            if let Some(sequencer) = self.get_preferred_sequencer(working_set.inner) {
-                sequencer
-            } else {
-                // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/654
-                // Prevent double number of blobs being executed
-                return Ok(expiring_deferred_blobs
-                    .into_iter()
-                    .map(Into::into)
-                    .chain(current_blobs.into_iter().map(Into::into))
-                    .collect());
-            };
+        let preferred_sequencer = if let Some(sequencer) =
+            self.get_preferred_sequencer(working_set.inner)
+        {
+            sequencer
+        } else {
+            // The leader schedule (if any) elects a new preferred sequencer for the next slot
+            self.sequencer_registry
+                .advance_leader_schedule(current_slot, false, working_set.inner);
+            // TODO: https://github.com/Sovereign-Labs/sovereign-sdk/issues/654
+            // Prevent double number of blobs being executed
+            return Ok(expiring_deferred_blobs
+                .into_iter()
+                .map(Into::into)
+                .chain(current_blobs.into_iter().map(Into::into))
+                .collect());
+        };
 
         // If we reach this point, there is a preferred sequencer, so we need to handle cases 1 and 3.
 
@@ -157,6 +170,15 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
             self.store_blobs(current_slot, &to_defer, working_set.inner)?
         }
 
+        // The preferred sequencer may change for the next slot. This doesn't affect the blobs which
+        // were deferred so far: they are still executed once they expire, even if their sender
+        // becomes the preferred sequencer or leaves the registry in the meantime.
+        self.sequencer_registry.advance_leader_schedule(
+            current_slot,
+            !priority_blobs.is_empty(),
+            working_set.inner,
+        );
+
         Ok(priority_blobs
             .into_iter()
             .map(Into::into)
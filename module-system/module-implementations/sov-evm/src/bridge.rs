@@ -0,0 +1,298 @@
+//! Bridge between the `sov-bank` tokens and the EVM.
+//!
+//! The native balance of the EVM accounts is backed by a configured bank token: the tokens moved
+//! to the EVM are locked in the account of the module, and unlocked when they leave it. The base
+//! fees of the EVM transactions are burned unless a treasury is configured, and so are the bank
+//! tokens backing them. One unit of the bank token is one unit (wei) on the EVM side.
+//!
+//! Other bank tokens are exposed as ERC-20 precompiles, whose balances are the bank balances of
+//! the rollup addresses of the EVM accounts (see [`rollup_address`]), so those tokens never leave
+//! the bank. Either way, the supply is conserved across both sides.
+//!
+//! Tokens leave the EVM through the `withdraw` functions of the bridge, which emit a
+//! `Withdrawal(bytes32 indexed recipient, uint256 amount)` log:
+//! - `withdraw(bytes32 recipient)` of the native bridge contract deployed at genesis, with the
+//!   withdrawn amount attached as the value of the call. The log is settled after the transaction.
+//! - `withdraw(bytes32 recipient, uint256 amount)` of the ERC-20 precompiles, which transfers the
+//!   tokens of the caller right away.
+
+use anyhow::{bail, ensure, Result};
+use reth_primitives::{keccak256, Address, Bytes, Log, H256, U256};
+use sov_bank::{Amount, Coins};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, WorkingSet};
+
+use crate::evm::db_init::InitEvmDb;
+use crate::evm::erc20::ERC20_PRECOMPILE_CODE;
+use crate::evm::AccountInfo;
+use crate::Evm;
+
+/// Runtime code of the native bridge contract, see `NativeBridge.sol`.
+/// It accepts `withdraw(bytes32 recipient)`, and emits a `Withdrawal` log with the value of the call.
+const NATIVE_BRIDGE_CODE: &str = include_str!("evm/test_data/NativeBridge.bin-runtime");
+
+/// A bank token exposed as an ERC-20 precompile in the EVM.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct Erc20Token<C: sov_modules_api::Context> {
+    /// The address of the token in the bank.
+    pub token_address: C::Address,
+    /// The address of the ERC-20 precompile.
+    pub contract_address: Address,
+}
+
+/// Configuration of the bridge between the bank and the EVM.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct BridgeConfig<C: sov_modules_api::Context> {
+    /// The bank token backing the native balance of the EVM accounts.
+    pub native_token_address: C::Address,
+    /// The address of the native bridge contract deployed at genesis.
+    pub native_bridge_address: Address,
+    /// The other bank tokens exposed in the EVM.
+    #[serde(default)]
+    pub erc20_tokens: Vec<Erc20Token<C>>,
+}
+
+impl<C: sov_modules_api::Context> BridgeConfig<C> {
+    /// The ERC-20 precompile of the token, or `None` for the native token.
+    fn contract_of(&self, token_address: &C::Address) -> Result<Option<Address>> {
+        if token_address == &self.native_token_address {
+            return Ok(None);
+        }
+        match self
+            .erc20_tokens
+            .iter()
+            .find(|token| &token.token_address == token_address)
+        {
+            Some(token) => Ok(Some(token.contract_address)),
+            None => bail!("Token {} is not bridged to the EVM", token_address),
+        }
+    }
+}
+
+/// The topic of the `Withdrawal(bytes32,uint256)` log emitted by the bridge.
+pub(crate) fn withdrawal_topic() -> H256 {
+    keccak256("Withdrawal(bytes32,uint256)")
+}
+
+/// The EVM account a rollup address deposits to and withdraws from: the last 20 bytes of the
+/// keccak hash of the address.
+pub fn evm_address<C: sov_modules_api::Context>(address: &C::Address) -> Address {
+    Address::from_slice(&keccak256(address.as_ref())[12..])
+}
+
+/// The rollup address of an EVM account, used as the sender of its calls to the module
+/// precompiles: the keccak hash of the address, like [`evm_address`] in the other direction.
+pub fn rollup_address<C: sov_modules_api::Context>(address: Address) -> C::Address {
+    C::Address::try_from(&keccak256(address)[..]).expect("Rollup addresses must be 32 bytes long")
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Deploys the native bridge contract, and the code of the ERC-20 precompiles.
+    pub(crate) fn init_bridge(
+        &self,
+        config: &BridgeConfig<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let mut contracts = vec![(config.native_bridge_address, NATIVE_BRIDGE_CODE)];
+        contracts.extend(
+            config
+                .erc20_tokens
+                .iter()
+                .map(|token| (token.contract_address, ERC20_PRECOMPILE_CODE)),
+        );
+
+        for (address, code) in contracts {
+            ensure!(
+                self.accounts.get(&address, working_set).is_none(),
+                "The address {} of a bridge contract is already used",
+                address
+            );
+            let code =
+                Bytes::from(hex::decode(code.trim()).expect("Bridge code must be valid hex"));
+            let code_hash = keccak256(&code);
+
+            let mut evm_db = self.get_db(working_set);
+            evm_db.insert_account_info(
+                address,
+                AccountInfo {
+                    code_hash,
+                    ..Default::default()
+                },
+            );
+            evm_db.insert_code(code_hash, code);
+        }
+
+        self.bridge.set(config, working_set);
+        Ok(())
+    }
+
+    /// Credits `amount` of the token of the sender to `to` in the EVM: the native token is locked
+    /// in the module, and the other tokens are transferred to the rollup address of `to`.
+    pub(crate) fn deposit(
+        &self,
+        token_address: C::Address,
+        to: Address,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let bridge = self.get_bridge(working_set)?;
+        let coins = Coins {
+            amount,
+            token_address,
+        };
+
+        match bridge.contract_of(&coins.token_address)? {
+            None => {
+                self.bank
+                    .transfer_from(context.sender(), &self.address, coins, working_set)?;
+                let amount = U256::from(amount);
+                self.update_balance(to, working_set, |balance| Ok(balance + amount))?;
+            }
+            Some(_) => {
+                self.bank.transfer_from(
+                    context.sender(),
+                    &rollup_address::<C>(to),
+                    coins,
+                    working_set,
+                )?;
+            }
+        }
+        Ok(CallResponse::default())
+    }
+
+    /// Debits `amount` of the token from the EVM account of the sender, see [`evm_address`],
+    /// and returns them to the sender.
+    pub(crate) fn withdraw(
+        &self,
+        token_address: C::Address,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let bridge = self.get_bridge(working_set)?;
+        let from = evm_address::<C>(context.sender());
+        let coins = Coins {
+            amount,
+            token_address,
+        };
+
+        match bridge.contract_of(&coins.token_address)? {
+            None => {
+                let debited = U256::from(amount);
+                self.update_balance(from, working_set, |balance| {
+                    match balance.checked_sub(debited) {
+                        Some(balance) => Ok(balance),
+                        None => bail!("Insufficient balance of {} to withdraw {}", from, amount),
+                    }
+                })?;
+                self.bank
+                    .transfer_from(&self.address, context.sender(), coins, working_set)?;
+            }
+            Some(_) => {
+                self.bank.transfer_from(
+                    &rollup_address::<C>(from),
+                    context.sender(),
+                    coins,
+                    working_set,
+                )?;
+            }
+        }
+        Ok(CallResponse::default())
+    }
+
+    /// Unlocks the native tokens withdrawn by the `Withdrawal` logs of the native bridge contract.
+    /// The withdrawn native balance is burnt from the contract.
+    pub(crate) fn settle_withdrawals(
+        &self,
+        logs: &[Log],
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let Some(bridge) = self.bridge.get(working_set) else {
+            return Ok(());
+        };
+
+        let topic = withdrawal_topic();
+        for log in logs {
+            if log.address != bridge.native_bridge_address
+                || log.topics.len() != 2
+                || log.topics[0] != topic
+            {
+                continue;
+            }
+
+            let recipient = C::Address::try_from(log.topics[1].as_bytes())?;
+            let withdrawn = U256::try_from_be_slice(&log.data).unwrap_or_default();
+            let Ok(amount) = Amount::try_from(withdrawn) else {
+                bail!("Withdrawn amount {} exceeds the bank amounts", withdrawn);
+            };
+
+            self.update_balance(log.address, working_set, |balance| {
+                match balance.checked_sub(withdrawn) {
+                    Some(balance) => Ok(balance),
+                    None => bail!(
+                        "Insufficient balance of the native bridge to withdraw {}",
+                        withdrawn
+                    ),
+                }
+            })?;
+            self.bank.transfer_from(
+                &self.address,
+                &recipient,
+                Coins {
+                    amount,
+                    token_address: bridge.native_token_address.clone(),
+                },
+                working_set,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Burns the bank tokens backing `burned` of native balance burned in the EVM.
+    pub(crate) fn burn_native_backing(
+        &self,
+        burned: U256,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let Some(bridge) = self.bridge.get(working_set) else {
+            return Ok(());
+        };
+        if burned == U256::ZERO {
+            return Ok(());
+        }
+        let Ok(amount) = Amount::try_from(burned) else {
+            bail!("Burned amount {} exceeds the bank amounts", burned);
+        };
+        self.bank.burn(
+            Coins {
+                amount,
+                token_address: bridge.native_token_address,
+            },
+            &self.address,
+            working_set,
+        )
+    }
+
+    fn get_bridge(&self, working_set: &mut WorkingSet<C>) -> Result<BridgeConfig<C>> {
+        match self.bridge.get(working_set) {
+            Some(bridge) => Ok(bridge),
+            None => bail!("The bridge is not configured"),
+        }
+    }
+
+    /// Updates the native balance of the account, creating it if needed.
+    pub(crate) fn update_balance(
+        &self,
+        address: Address,
+        working_set: &mut WorkingSet<C>,
+        update: impl FnOnce(U256) -> Result<U256>,
+    ) -> Result<()> {
+        let mut db_account = self.get_or_create_account(address, working_set);
+        db_account.info.balance = update(db_account.info.balance)?;
+        self.accounts.set(&address, &db_account, working_set);
+        Ok(())
+    }
+}
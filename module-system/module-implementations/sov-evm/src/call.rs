@@ -1,48 +1,89 @@
+use std::convert::Infallible;
+
 use anyhow::Result;
-use reth_primitives::TransactionSignedEcRecovered;
+use reth_primitives::{Address, TransactionSignedEcRecovered, U256};
 use reth_revm::into_reth_log;
-use revm::primitives::{CfgEnv, EVMError, SpecId};
+use revm::primitives::{CfgEnv, EVMError, ExecutionResult, SpecId};
+use sov_bank::Amount;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
+use crate::evm::conversions::ensure_tx_type_supported;
 use crate::evm::db::EvmDb;
 use crate::evm::executor::{self};
 use crate::evm::primitive_types::{BlockEnv, Receipt, TransactionSignedAndRecovered};
 use crate::evm::{EvmChainConfig, RlpEvmTransaction};
 use crate::experimental::PendingTransaction;
-use crate::Evm;
+use crate::trace::BlockCallKind;
+#[cfg(feature = "dev")]
+use crate::DevCallMessage;
+use crate::{evm_address, EthApiError, Evm};
 
+/// EVM call message.
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize),
-    derive(serde::Deserialize)
+    derive(serde::Deserialize),
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
 )]
-
-/// EVM call message.
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
-pub struct CallMessage {
-    /// RLP encoded transaction.
-    pub tx: RlpEvmTransaction,
+pub enum CallMessage<C: sov_modules_api::Context> {
+    /// Executes an Ethereum transaction.
+    Transact {
+        /// RLP encoded transaction.
+        tx: RlpEvmTransaction,
+    },
+    /// Moves bank tokens of the sender to an EVM account, see [`crate::BridgeConfig`].
+    Deposit {
+        /// The address of the bridged bank token.
+        token_address: C::Address,
+        /// The EVM account credited with the tokens.
+        to: [u8; 20],
+        /// The amount of tokens to deposit.
+        amount: Amount,
+    },
+    /// Moves tokens from the EVM account of the sender, see [`crate::evm_address`], back to the bank.
+    Withdraw {
+        /// The address of the bridged bank token.
+        token_address: C::Address,
+        /// The amount of tokens to withdraw.
+        amount: Amount,
+    },
+    /// Controls the EVM state on a local chain, see [`crate::EvmConfig::dev_authority`].
+    #[cfg(feature = "dev")]
+    Dev {
+        /// The control to apply.
+        call: DevCallMessage,
+    },
 }
 
 impl<C: sov_modules_api::Context> Evm<C> {
     pub(crate) fn execute_call(
         &self,
         tx: RlpEvmTransaction,
-        _context: &C,
+        context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
         let evm_tx_recovered: TransactionSignedEcRecovered = tx.try_into()?;
-        let block_env = self
+        self.execute_recovered_call(evm_tx_recovered, context, working_set)
+    }
+
+    pub(crate) fn execute_recovered_call(
+        &self,
+        evm_tx_recovered: TransactionSignedEcRecovered,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut block_env = self
             .block_env
             .get(working_set)
             .expect("Pending block must be set");
+        // The priority fees go to the EVM account of the sequencer of the blob.
+        if let Some(sequencer) = self.blob_sequencer.get(working_set) {
+            block_env.coinbase = evm_address::<C>(&sequencer);
+        }
 
-        let cfg = self.cfg.get(working_set).expect("Evm config must be set");
-        let cfg_env = get_cfg_env(&block_env, cfg, None);
-
-        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
-        let result = executor::execute_tx(evm_db, &block_env, &evm_tx_recovered, cfg_env);
+        let result = self.apply_transaction(&evm_tx_recovered, &block_env, context, working_set)?;
         let previous_transaction = self.pending_transactions.last(working_set);
         let previous_transaction_cumulative_gas_used = previous_transaction
             .as_ref()
@@ -98,9 +139,71 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         self.pending_transactions
             .push(&pending_transaction, working_set);
+        self.record_block_call(
+            BlockCallKind::Transaction {
+                coinbase: block_env.coinbase,
+            },
+            context,
+            working_set,
+        );
 
         Ok(CallResponse::default())
     }
+
+    /// Executes the transaction in the environment of the block and commits it, along with its
+    /// calls to the modules, its withdrawals and its base fees.
+    pub(crate) fn apply_transaction(
+        &self,
+        evm_tx_recovered: &TransactionSignedEcRecovered,
+        block_env: &BlockEnv,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Result<ExecutionResult, EVMError<Infallible>>> {
+        let cfg = self.cfg.get(working_set).expect("Evm config must be set");
+        let base_fee_recipient = cfg.base_fee_recipient;
+        let cfg_env = get_cfg_env(block_env, cfg, None);
+        ensure_tx_type_supported(evm_tx_recovered.tx_type(), cfg_env.spec_id)
+            .map_err(EthApiError::from)?;
+
+        let mut precompiles = self.module_precompiles(context, working_set);
+        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
+        let result = executor::execute_tx_with_precompiles(
+            evm_db,
+            block_env,
+            evm_tx_recovered,
+            cfg_env,
+            &mut precompiles,
+        );
+
+        if let Ok(result) = &result {
+            self.settle_transaction(result, block_env, base_fee_recipient, working_set)?;
+        }
+        Ok(result)
+    }
+
+    /// Settles the withdrawals of a committed transaction, and pays its base fees to the treasury
+    /// or burns them.
+    pub(crate) fn settle_transaction(
+        &self,
+        result: &ExecutionResult,
+        block_env: &BlockEnv,
+        base_fee_recipient: Option<Address>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if result.is_success() {
+            let logs: Vec<_> = result.logs().into_iter().map(into_reth_log).collect();
+            self.settle_withdrawals(&logs, working_set)?;
+        }
+        // The EVM only pays the priority fees to the coinbase, so the base fees are burned
+        // unless they go to the treasury.
+        let base_fee = U256::from(block_env.basefee) * U256::from(result.gas_used());
+        match base_fee_recipient {
+            Some(treasury) => {
+                self.update_balance(treasury, working_set, |balance| Ok(balance + base_fee))
+            }
+            None => self.burn_native_backing(base_fee, working_set),
+        }
+    }
 }
 
 /// Get cfg env for a given block number
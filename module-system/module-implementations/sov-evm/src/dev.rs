@@ -0,0 +1,134 @@
+//! Developer controls of the EVM state, for local chains.
+//!
+//! They're only accepted from the dev authority set in genesis, see [`crate::EvmConfig::dev_authority`],
+//! and are rejected by chains without one.
+
+use anyhow::{ensure, Result};
+use reth_primitives::{
+    keccak256, Address, Bytes, Signature, Transaction, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, KECCAK_EMPTY, U256,
+};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, WorkingSet};
+
+use crate::evm::RlpEvmTransaction;
+use crate::Evm;
+
+/// A developer control of the EVM state, sent by the dev authority.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum DevCallMessage {
+    /// Sets the native balance of an account.
+    SetBalance {
+        /// The account, created if needed.
+        address: [u8; 20],
+        /// The big endian balance.
+        balance: [u8; 32],
+    },
+    /// Sets the code of an account, removing it if empty.
+    SetCode {
+        /// The account, created if needed.
+        address: [u8; 20],
+        /// The runtime code.
+        code: Vec<u8>,
+    },
+    /// Sets a storage slot of an account.
+    SetStorageAt {
+        /// The account, created if needed.
+        address: [u8; 20],
+        /// The big endian slot.
+        slot: [u8; 32],
+        /// The big endian value.
+        value: [u8; 32],
+    },
+    /// Moves the timestamp of the pending block, and so of the following ones, forward.
+    IncreaseTime {
+        /// The number of seconds to add.
+        seconds: u64,
+    },
+    /// Executes a transaction on behalf of an account, without its signature.
+    Impersonate {
+        /// The account sending the transaction.
+        from: [u8; 20],
+        /// The transaction, encoded with [`RlpEvmTransaction::unsigned`].
+        tx: RlpEvmTransaction,
+    },
+}
+
+impl RlpEvmTransaction {
+    /// Encodes a transaction with a placeholder signature, to be sent by an impersonated account.
+    pub fn unsigned(transaction: Transaction) -> Self {
+        let signed =
+            TransactionSigned::from_transaction_and_signature(transaction, Signature::default());
+        Self {
+            rlp: signed.envelope_encoded().to_vec(),
+        }
+    }
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    pub(crate) fn execute_dev_call(
+        &self,
+        call: DevCallMessage,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        ensure!(
+            self.dev_authority.get(working_set).as_ref() == Some(context.sender()),
+            "Dev calls are only accepted from the dev authority, {} is not",
+            context.sender()
+        );
+
+        match call {
+            DevCallMessage::SetBalance { address, balance } => {
+                let balance = U256::from_be_bytes(balance);
+                self.update_balance(address.into(), working_set, |_| Ok(balance))?;
+            }
+            DevCallMessage::SetCode { address, code } => {
+                let address = Address::from(address);
+                let mut db_account = self.get_or_create_account(address, working_set);
+                db_account.info.code_hash = if code.is_empty() {
+                    KECCAK_EMPTY
+                } else {
+                    let code = Bytes::from(code);
+                    let code_hash = keccak256(&code);
+                    self.code.set(&code_hash, &code, working_set);
+                    code_hash
+                };
+                self.accounts.set(&address, &db_account, working_set);
+            }
+            DevCallMessage::SetStorageAt {
+                address,
+                slot,
+                value,
+            } => {
+                let address = Address::from(address);
+                let db_account = self.get_or_create_account(address, working_set);
+                self.accounts.set(&address, &db_account, working_set);
+                db_account.storage.set(
+                    &U256::from_be_bytes(slot),
+                    &U256::from_be_bytes(value),
+                    working_set,
+                );
+            }
+            DevCallMessage::IncreaseTime { seconds } => {
+                let mut block_env = self
+                    .block_env
+                    .get(working_set)
+                    .expect("Pending block must be set");
+                block_env.timestamp += seconds;
+                self.block_env.set(&block_env, working_set);
+            }
+            DevCallMessage::Impersonate { from, tx } => {
+                let tx: TransactionSigned = TransactionSignedNoHash::try_from(tx)?.into();
+                let tx = TransactionSignedEcRecovered::from_signed_transaction(tx, from.into());
+                return self.execute_recovered_call(tx, context, working_set);
+            }
+        }
+        Ok(CallResponse::default())
+    }
+}
@@ -1,6 +1,6 @@
 // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/revm_utils.rs
 
-use reth_primitives::{AccessList, H256, U256};
+use reth_primitives::{AccessList, EIP4844_TX_TYPE_ID, H256, U256, U8};
 use reth_rpc_types::CallRequest;
 use revm::primitives::{TransactTo, TxEnv};
 
@@ -139,9 +139,19 @@ pub(crate) fn prepare_call_env(block_env: &BlockEnv, request: CallRequest) -> Et
         nonce,
         access_list,
         chain_id,
-        ..
+        transaction_type,
+        blob_versioned_hashes,
+        max_fee_per_blob_gas,
     } = request;
 
+    // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
+    if transaction_type == Some(U8::from(EIP4844_TX_TYPE_ID))
+        || !blob_versioned_hashes.is_empty()
+        || max_fee_per_blob_gas.is_some()
+    {
+        return Err(RpcInvalidTransactionError::TxTypeNotSupported.into());
+    }
+
     let CallFees {
         max_priority_fee_per_gas,
         gas_price,
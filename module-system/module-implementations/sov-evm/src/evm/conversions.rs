@@ -1,14 +1,16 @@
 use bytes::Bytes;
 use reth_primitives::{
-    Bytes as RethBytes, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    AccessList, Bytes as RethBytes, TransactionSigned, TransactionSignedEcRecovered,
+    TransactionSignedNoHash, TxType,
 };
 use revm::primitives::{
-    AccountInfo as ReVmAccountInfo, BlockEnv as ReVmBlockEnv, CreateScheme, TransactTo, TxEnv, U256,
+    AccountInfo as ReVmAccountInfo, BlobExcessGasAndPrice, BlockEnv as ReVmBlockEnv, CreateScheme,
+    SpecId, TransactTo, TxEnv, U256,
 };
 
 use super::primitive_types::{BlockEnv, RlpEvmTransaction, TransactionSignedAndRecovered};
 use super::AccountInfo;
-use crate::error::rpc::EthApiError;
+use crate::error::rpc::{EthApiError, RpcInvalidTransactionError};
 
 impl From<AccountInfo> for ReVmAccountInfo {
     fn from(info: AccountInfo) -> Self {
@@ -41,9 +43,10 @@ impl From<&BlockEnv> for ReVmBlockEnv {
             prevrandao: Some(block_env.prevrandao),
             basefee: U256::from(block_env.basefee),
             gas_limit: U256::from(block_env.gas_limit),
-            // EIP-4844 related field
+            // EIP-4844 related field, required from Cancun on. Blob transactions aren't
+            // supported, so there is never any excess blob gas.
             // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
-            blob_excess_gas_and_price: None,
+            blob_excess_gas_and_price: Some(BlobExcessGasAndPrice::new(0)),
         }
     }
 }
@@ -64,8 +67,11 @@ pub(crate) fn create_tx_env(tx: &TransactionSignedEcRecovered) -> TxEnv {
         data: Bytes::from(tx.input().to_vec()),
         chain_id: tx.chain_id(),
         nonce: Some(tx.nonce()),
-        // TODO handle access list
-        access_list: vec![],
+        access_list: tx
+            .access_list()
+            .cloned()
+            .map(AccessList::flattened)
+            .unwrap_or_default(),
         // EIP-4844 related fields
         // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
         blob_hashes: vec![],
@@ -85,10 +91,37 @@ impl TryFrom<RlpEvmTransaction> for TransactionSignedNoHash {
         let transaction = TransactionSigned::decode_enveloped(data)
             .map_err(|_| EthApiError::FailedToDecodeSignedTransaction)?;
 
+        // Blob transactions need their blobs from the DA layer
+        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
+        if transaction.tx_type() == TxType::EIP4844 {
+            return Err(RpcInvalidTransactionError::TxTypeNotSupported.into());
+        }
+
         Ok(transaction.into())
     }
 }
 
+/// Checks that the transaction type is enabled by the given spec:
+/// EIP-2930 transactions require Berlin and EIP-1559 transactions require London.
+pub(crate) fn ensure_tx_type_supported(
+    tx_type: TxType,
+    spec_id: SpecId,
+) -> Result<(), RpcInvalidTransactionError> {
+    let supported = match tx_type {
+        TxType::Legacy => true,
+        TxType::EIP2930 => spec_id >= SpecId::BERLIN,
+        TxType::EIP1559 => spec_id >= SpecId::LONDON,
+        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
+        TxType::EIP4844 => false,
+    };
+
+    if supported {
+        Ok(())
+    } else {
+        Err(RpcInvalidTransactionError::TxTypeNotSupported)
+    }
+}
+
 impl TryFrom<RlpEvmTransaction> for TransactionSignedEcRecovered {
     type Error = EthApiError;
 
@@ -10,19 +10,25 @@ use super::DbAccount;
 
 pub(crate) struct EvmDb<'a, C: sov_modules_api::Context> {
     pub(crate) accounts: sov_modules_api::StateMap<Address, DbAccount, BcsCodec>,
+    pub(crate) account_incarnations: sov_modules_api::StateMap<Address, u64, BcsCodec>,
     pub(crate) code: sov_modules_api::StateMap<H256, Bytes, BcsCodec>,
+    pub(crate) latest_block_hashes: sov_modules_api::StateMap<U256, H256, BcsCodec>,
     pub(crate) working_set: &'a mut WorkingSet<C>,
 }
 
 impl<'a, C: sov_modules_api::Context> EvmDb<'a, C> {
     pub(crate) fn new(
         accounts: sov_modules_api::StateMap<Address, DbAccount, BcsCodec>,
+        account_incarnations: sov_modules_api::StateMap<Address, u64, BcsCodec>,
         code: sov_modules_api::StateMap<H256, Bytes, BcsCodec>,
+        latest_block_hashes: sov_modules_api::StateMap<U256, H256, BcsCodec>,
         working_set: &'a mut WorkingSet<C>,
     ) -> Self {
         Self {
             accounts,
+            account_incarnations,
             code,
+            latest_block_hashes,
             working_set,
         }
     }
@@ -60,7 +66,13 @@ impl<'a, C: sov_modules_api::Context> Database for EvmDb<'a, C> {
         Ok(storage_value)
     }
 
-    fn block_hash(&mut self, _number: U256) -> Result<B256, Self::Error> {
-        todo!("block_hash not yet implemented")
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        // revm only asks for the hashes of the 256 blocks before the current one.
+        let block_hash = self
+            .latest_block_hashes
+            .get(&number, self.working_set)
+            .unwrap_or_default();
+
+        Ok(block_hash)
     }
 }
@@ -1,4 +1,4 @@
-use revm::primitives::{Account, HashMap, B160};
+use revm::primitives::{Account, AccountStatus, HashMap, SpecId, B160};
 use revm::DatabaseCommit;
 use sov_modules_api::StateMapAccessor;
 
@@ -8,18 +8,13 @@ use super::DbAccount;
 impl<'a, C: sov_modules_api::Context> DatabaseCommit for EvmDb<'a, C> {
     fn commit(&mut self, changes: HashMap<B160, Account>) {
         for (address, account) in changes {
-            // TODO figure out what to do when account is destroyed.
-            // https://github.com/Sovereign-Labs/sovereign-sdk/issues/425
+            // The changes of a transaction are filtered by `apply_eip6780` under Cancun.
             if account.is_selfdestructed() {
-                todo!("Account destruction not supported")
+                self.destroy_account(address);
+                continue;
             }
 
-            let accounts_prefix = self.accounts.prefix();
-
-            let mut db_account = self
-                .accounts
-                .get(&address, self.working_set)
-                .unwrap_or_else(|| DbAccount::new(accounts_prefix, address));
+            let mut db_account = self.get_or_create_account(address);
 
             let account_info = account.info;
 
@@ -37,11 +32,57 @@ impl<'a, C: sov_modules_api::Context> DatabaseCommit for EvmDb<'a, C> {
             db_account.info = account_info.into();
 
             for (key, value) in account.storage.into_iter() {
-                let value = value.present_value();
-                db_account.storage.set(&key, &value, self.working_set);
+                db_account
+                    .storage
+                    .set(&key, &value.present_value(), self.working_set);
             }
 
             self.accounts.set(&address, &db_account, self.working_set)
         }
     }
 }
+
+impl<'a, C: sov_modules_api::Context> EvmDb<'a, C> {
+    fn get_or_create_account(&mut self, address: B160) -> DbAccount {
+        self.accounts
+            .get(&address, self.working_set)
+            .unwrap_or_else(|| {
+                let incarnation = self
+                    .account_incarnations
+                    .get(&address, self.working_set)
+                    .unwrap_or_default();
+                DbAccount::new(self.accounts.prefix(), address, incarnation)
+            })
+    }
+
+    /// Removes the account. Its storage isn't cleared, which would cost as much as the number of
+    /// its slots: the next incarnation of the account gets a storage of its own instead.
+    /// Its code is kept, because it's shared by hash.
+    fn destroy_account(&mut self, address: B160) {
+        if self.accounts.get(&address, self.working_set).is_none() {
+            return;
+        }
+
+        let incarnation = self
+            .account_incarnations
+            .get(&address, self.working_set)
+            .unwrap_or_default();
+        self.account_incarnations
+            .set(&address, &(incarnation + 1), self.working_set);
+        self.accounts.delete(&address, self.working_set);
+    }
+}
+
+/// Applies EIP-6780 to the changes of a transaction executed under `spec_id`: from Cancun on,
+/// `SELFDESTRUCT` only destroys the accounts created in the same transaction, and the others
+/// just lose their balance. It's checked here whatever the EIP-6780 support of revm.
+pub(crate) fn apply_eip6780(changes: &mut HashMap<B160, Account>, spec_id: SpecId) {
+    if spec_id < SpecId::CANCUN {
+        return;
+    }
+    for account in changes.values_mut() {
+        if account.is_selfdestructed() && !account.status.contains(AccountStatus::Created) {
+            account.status.remove(AccountStatus::SelfDestructed);
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::{bail, ensure, Result};
+use reth_primitives::{keccak256, Address, U256};
+use revm::primitives::{Bytes, Log, B256};
+use sov_bank::{Amount, Coins};
+use sov_modules_api::WorkingSet;
+
+use super::executor::{PrecompileOutput, MODULE_CALL_BASE_GAS};
+use crate::{rollup_address, Erc20Token, Evm};
+
+/// The EVM gas charged for the calls to the ERC-20 precompiles which only read the bank,
+/// as much as a cold storage read.
+pub(crate) const ERC20_READ_GAS: u64 = 2_600;
+
+/// The runtime code of the accounts of the ERC-20 precompiles, which reverts. The calls to the
+/// precompiles never run it, but contracts check that the tokens they call have code.
+pub(crate) const ERC20_PRECOMPILE_CODE: &str = "60006000fd";
+
+/// The ERC-20 precompiles of the bank tokens bridged to the EVM.
+///
+/// The balance of an EVM account is the bank balance of its rollup address, see
+/// [`rollup_address`], so the tokens never leave the bank. The allowances are kept by the module.
+/// The precompiles implement `totalSupply`, `balanceOf`, `transfer`, `allowance`, `approve`,
+/// `transferFrom` and `decimals` (always 0, like bank amounts), and emit the standard `Transfer`
+/// and `Approval` logs. `withdraw(bytes32 recipient, uint256 amount)` transfers the tokens of the
+/// caller to a rollup address, and emits a `Withdrawal(bytes32 indexed recipient, uint256 amount)` log.
+pub(crate) struct Erc20Precompiles<'m, C: sov_modules_api::Context> {
+    evm: &'m Evm<C>,
+    tokens: Vec<Erc20Token<C>>,
+}
+
+impl<'m, C: sov_modules_api::Context> Erc20Precompiles<'m, C> {
+    pub(crate) fn new(evm: &'m Evm<C>, tokens: Vec<Erc20Token<C>>) -> Self {
+        Self { evm, tokens }
+    }
+
+    /// The bank token of the precompile at `address`, if any.
+    pub(crate) fn token_at(&self, address: Address) -> Option<C::Address> {
+        self.tokens
+            .iter()
+            .find(|token| token.contract_address == address)
+            .map(|token| token.token_address.clone())
+    }
+
+    /// Executes the ABI encoded call of `caller` to the precompile at `contract`.
+    pub(crate) fn call(
+        &self,
+        token_address: &C::Address,
+        contract: Address,
+        caller: Address,
+        input: &[u8],
+        is_static: bool,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<PrecompileOutput> {
+        ensure!(input.len() >= 4, "The call data has no function selector");
+        let (selector, args) = input.split_at(4);
+        let bank = &self.evm.bank;
+
+        let read = |value: U256| PrecompileOutput {
+            gas: ERC20_READ_GAS,
+            output: Bytes::copy_from_slice(&value.to_be_bytes::<32>()),
+            logs: vec![],
+        };
+        // The transfers and approvals return `true`, as they revert when they fail.
+        let write = |output: Option<U256>, logs: Vec<Log>| PrecompileOutput {
+            gas: MODULE_CALL_BASE_GAS,
+            output: output
+                .map(|output| Bytes::copy_from_slice(&output.to_be_bytes::<32>()))
+                .unwrap_or_default(),
+            logs,
+        };
+
+        if selector == function_selector("totalSupply()") {
+            let supply = bank
+                .get_total_supply_of(token_address, working_set)
+                .unwrap_or_default();
+            return Ok(read(U256::from(supply)));
+        }
+        if selector == function_selector("decimals()") {
+            return Ok(read(U256::ZERO));
+        }
+        if selector == function_selector("balanceOf(address)") {
+            let holder = rollup_address::<C>(address_arg(args, 0)?);
+            let balance = bank
+                .get_balance_of(holder, token_address.clone(), working_set)
+                .unwrap_or_default();
+            return Ok(read(U256::from(balance)));
+        }
+        if selector == function_selector("allowance(address,address)") {
+            let key = (contract, address_arg(args, 0)?, address_arg(args, 1)?);
+            let allowance = self
+                .evm
+                .erc20_allowances
+                .get(&key, working_set)
+                .unwrap_or_default();
+            return Ok(read(allowance));
+        }
+
+        ensure!(
+            !is_static,
+            "ERC-20 transfers can't be made in a static call"
+        );
+        if selector == function_selector("transfer(address,uint256)") {
+            let (to, value) = (address_arg(args, 0)?, uint_arg(args, 1)?);
+            self.transfer(token_address, caller, to, value, working_set)?;
+            return Ok(write(
+                Some(U256::from(1)),
+                vec![transfer_log(contract, caller, to, value)],
+            ));
+        }
+        if selector == function_selector("approve(address,uint256)") {
+            let (spender, value) = (address_arg(args, 0)?, uint_arg(args, 1)?);
+            self.evm
+                .erc20_allowances
+                .set(&(contract, caller, spender), &value, working_set);
+            let log = Log {
+                address: contract,
+                topics: vec![
+                    keccak256("Approval(address,address,uint256)"),
+                    address_topic(caller),
+                    address_topic(spender),
+                ],
+                data: Bytes::copy_from_slice(&value.to_be_bytes::<32>()),
+            };
+            return Ok(write(Some(U256::from(1)), vec![log]));
+        }
+        if selector == function_selector("transferFrom(address,address,uint256)") {
+            let (from, to, value) = (
+                address_arg(args, 0)?,
+                address_arg(args, 1)?,
+                uint_arg(args, 2)?,
+            );
+            let key = (contract, from, caller);
+            let allowance = self
+                .evm
+                .erc20_allowances
+                .get(&key, working_set)
+                .unwrap_or_default();
+            // An allowance of `type(uint256).max` is never spent, as in the common ERC-20 contracts.
+            if allowance != U256::MAX {
+                let Some(allowance) = allowance.checked_sub(value) else {
+                    bail!("Insufficient allowance of {} from {}", caller, from);
+                };
+                self.evm.erc20_allowances.set(&key, &allowance, working_set);
+            }
+            self.transfer(token_address, from, to, value, working_set)?;
+            return Ok(write(
+                Some(U256::from(1)),
+                vec![transfer_log(contract, from, to, value)],
+            ));
+        }
+        if selector == function_selector("withdraw(bytes32,uint256)") {
+            let (recipient, value) = (word_arg(args, 0)?, uint_arg(args, 1)?);
+            bank.transfer_from(
+                &rollup_address::<C>(caller),
+                &C::Address::try_from(&recipient[..])?,
+                Coins {
+                    amount: bank_amount(value)?,
+                    token_address: token_address.clone(),
+                },
+                working_set,
+            )?;
+            let log = Log {
+                address: contract,
+                topics: vec![crate::bridge::withdrawal_topic(), B256::from(recipient)],
+                data: Bytes::copy_from_slice(&value.to_be_bytes::<32>()),
+            };
+            return Ok(write(None, vec![log]));
+        }
+        bail!("Unknown function selector {}", hex::encode(selector))
+    }
+
+    fn transfer(
+        &self,
+        token_address: &C::Address,
+        from: Address,
+        to: Address,
+        value: U256,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.evm.bank.transfer_from(
+            &rollup_address::<C>(from),
+            &rollup_address::<C>(to),
+            Coins {
+                amount: bank_amount(value)?,
+                token_address: token_address.clone(),
+            },
+            working_set,
+        )?;
+        Ok(())
+    }
+}
+
+fn function_selector(signature: &str) -> [u8; 4] {
+    keccak256(signature)[..4]
+        .try_into()
+        .expect("The selector has the right length")
+}
+
+/// The `index`th 32 bytes word of the arguments.
+fn word_arg(args: &[u8], index: usize) -> Result<[u8; 32]> {
+    match args.get(index * 32..(index + 1) * 32) {
+        Some(word) => Ok(word.try_into().expect("The word has the right length")),
+        None => bail!("Missing argument {}", index),
+    }
+}
+
+fn uint_arg(args: &[u8], index: usize) -> Result<U256> {
+    Ok(U256::from_be_bytes(word_arg(args, index)?))
+}
+
+fn address_arg(args: &[u8], index: usize) -> Result<Address> {
+    let word = word_arg(args, index)?;
+    ensure!(
+        word[..12].iter().all(|byte| *byte == 0),
+        "Argument {} is not an address",
+        index
+    );
+    Ok(Address::from_slice(&word[12..]))
+}
+
+fn address_topic(address: Address) -> B256 {
+    let mut topic = B256::zero();
+    topic[12..].copy_from_slice(address.as_bytes());
+    topic
+}
+
+fn transfer_log(contract: Address, from: Address, to: Address, value: U256) -> Log {
+    Log {
+        address: contract,
+        topics: vec![
+            keccak256("Transfer(address,address,uint256)"),
+            address_topic(from),
+            address_topic(to),
+        ],
+        data: Bytes::copy_from_slice(&value.to_be_bytes::<32>()),
+    }
+}
+
+fn bank_amount(value: U256) -> Result<Amount> {
+    match Amount::try_from(value) {
+        Ok(amount) => Ok(amount),
+        Err(_) => bail!("Amount {} exceeds the bank amounts", value),
+    }
+}
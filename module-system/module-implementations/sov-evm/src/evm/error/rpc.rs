@@ -147,6 +147,40 @@ impl From<EthApiError> for RpcError {
         RpcError::Call(error.into())
     }
 }
+/// Errors that can occur when querying logs or polling filters.
+#[derive(Debug, thiserror::Error)]
+pub enum EthFilterError {
+    /// The filter doesn't exist, or it expired.
+    #[error("filter not found")]
+    FilterNotFound,
+    /// The query spans more blocks than allowed.
+    #[error("query exceeds max block range {0}")]
+    QueryExceedsMaxBlocks(u64),
+    /// The query matches more logs than allowed.
+    #[error("query exceeds max results {0}")]
+    QueryExceedsMaxResults(usize),
+    /// Any other error of the `eth_` namespace.
+    #[error(transparent)]
+    EthApiError(#[from] EthApiError),
+}
+
+impl From<EthFilterError> for ErrorObject<'static> {
+    fn from(error: EthFilterError) -> Self {
+        match error {
+            EthFilterError::EthApiError(err) => err.into(),
+            err @ (EthFilterError::FilterNotFound
+            | EthFilterError::QueryExceedsMaxBlocks(_)
+            | EthFilterError::QueryExceedsMaxResults(_)) => invalid_params_rpc_err(err.to_string()),
+        }
+    }
+}
+
+impl From<EthFilterError> for RpcError {
+    fn from(error: EthFilterError) -> Self {
+        RpcError::Call(error.into())
+    }
+}
+
 impl From<JsInspectorError> for EthApiError {
     fn from(error: JsInspectorError) -> Self {
         match error {
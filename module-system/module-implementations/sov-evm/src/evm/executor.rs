@@ -1,12 +1,37 @@
 use std::convert::Infallible;
+use std::rc::Rc;
 
-use reth_primitives::TransactionSignedEcRecovered;
-use reth_revm::tracing::{TracingInspector, TracingInspectorConfig};
-use revm::primitives::{CfgEnv, EVMError, Env, ExecutionResult, ResultAndState, TxEnv};
-use revm::{self, Database, DatabaseCommit};
+use borsh::BorshDeserialize;
+use reth_primitives::hex_literal::hex;
+use reth_primitives::{Address, TransactionSignedEcRecovered};
+use revm::interpreter::{
+    return_ok, CallInputs, CallScheme, CreateInputs, Gas, InstructionResult, Interpreter,
+};
+use revm::primitives::{
+    Account, Bytes, CfgEnv, EVMError, Env, ExecutionResult, HashMap, Log, ResultAndState, SpecId,
+    TxEnv, B160, B256, U256,
+};
+use revm::{self, Database, DatabaseCommit, EVMData, Inspector};
+use sov_modules_api::{Module, WorkingSet, WorkingSetSnapshot};
 
 use super::conversions::create_tx_env;
+use super::db::EvmDb;
+use super::db_commit::apply_eip6780;
+use super::erc20::Erc20Precompiles;
 use super::primitive_types::BlockEnv;
+use crate::rollup_address;
+
+/// The address of the precompile dispatching borsh encoded `CallMessage`s to the bank module.
+pub const BANK_PRECOMPILE_ADDRESS: Address =
+    Address(hex!("0000000000000000000000000000000000000b01"));
+/// The address of the precompile dispatching borsh encoded `CallMessage`s to the NFT module.
+pub const NFT_PRECOMPILE_ADDRESS: Address =
+    Address(hex!("0000000000000000000000000000000000000b02"));
+
+/// The EVM gas charged for every call to a module precompile.
+pub(crate) const MODULE_CALL_BASE_GAS: u64 = 20_000;
+/// The EVM gas charged for every unit of gas funds the module charges in the working set.
+pub(crate) const EVM_GAS_PER_MODULE_GAS: u64 = 100;
 
 pub(crate) fn execute_tx<DB: Database<Error = Infallible> + DatabaseCommit>(
     db: DB,
@@ -16,6 +41,7 @@ pub(crate) fn execute_tx<DB: Database<Error = Infallible> + DatabaseCommit>(
 ) -> Result<ExecutionResult, EVMError<Infallible>> {
     let mut evm = revm::new();
 
+    let spec_id = config_env.spec_id;
     let env = Env {
         block: block_env.into(),
         cfg: config_env,
@@ -24,15 +50,60 @@ pub(crate) fn execute_tx<DB: Database<Error = Infallible> + DatabaseCommit>(
 
     evm.env = env;
     evm.database(db);
-    evm.transact_commit()
+    let ResultAndState { result, state } = evm.transact()?;
+    commit(&mut evm, state, spec_id);
+    Ok(result)
 }
 
-pub(crate) fn inspect<DB: Database<Error = Infallible> + DatabaseCommit>(
-    db: DB,
+/// Executes the transaction and commits it, dispatching the calls to the module precompiles.
+pub(crate) fn execute_tx_with_precompiles<'a, C: sov_modules_api::Context>(
+    db: EvmDb<'a, C>,
+    block_env: &BlockEnv,
+    tx: &TransactionSignedEcRecovered,
+    config_env: CfgEnv,
+    precompiles: &mut ModulePrecompiles<'_, C>,
+) -> Result<ExecutionResult, EVMError<Infallible>> {
+    let mut evm = revm::new();
+
+    let spec_id = config_env.spec_id;
+    let env = Env {
+        block: block_env.into(),
+        cfg: config_env,
+        tx: create_tx_env(tx),
+    };
+
+    evm.env = env;
+    evm.database(db);
+    let ResultAndState { result, state } = evm.inspect(precompiles)?;
+    commit(&mut evm, state, spec_id);
+    Ok(result)
+}
+
+/// Commits the changes of a transaction executed under `spec_id`.
+fn commit<DB: Database + DatabaseCommit>(
+    evm: &mut revm::EVM<DB>,
+    mut changes: HashMap<B160, Account>,
+    spec_id: SpecId,
+) {
+    apply_eip6780(&mut changes, spec_id);
+    evm.db().expect("The database was set").commit(changes);
+}
+
+/// Executes the transaction without committing it, dispatching the calls to the module
+/// precompiles and recording its execution in the `inspector`. The changes of the module calls
+/// are left in the working set.
+pub(crate) fn inspect<'a, C, I>(
+    db: EvmDb<'a, C>,
     block_env: &BlockEnv,
     tx: TxEnv,
     config_env: CfgEnv,
-) -> Result<ResultAndState, EVMError<Infallible>> {
+    precompiles: &mut ModulePrecompiles<'_, C>,
+    inspector: I,
+) -> Result<ResultAndState, EVMError<Infallible>>
+where
+    C: sov_modules_api::Context,
+    I: Inspector<EvmDb<'a, C>>,
+{
     let mut evm = revm::new();
 
     let env = Env {
@@ -44,9 +115,333 @@ pub(crate) fn inspect<DB: Database<Error = Infallible> + DatabaseCommit>(
     evm.env = env;
     evm.database(db);
 
-    let config = TracingInspectorConfig::all();
+    evm.inspect(WithModulePrecompiles {
+        precompiles,
+        inspector,
+    })
+}
+
+/// A runtime module which contracts call through a precompile, with a borsh encoded `CallMessage`.
+pub(crate) trait ModuleCall<C: sov_modules_api::Context> {
+    fn dispatch(
+        &self,
+        message: &[u8],
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()>;
+}
+
+impl<C: sov_modules_api::Context, M: Module<Context = C>> ModuleCall<C> for M {
+    fn dispatch(
+        &self,
+        message: &[u8],
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let message = M::CallMessage::try_from_slice(message)?;
+        self.call(message, context, working_set)?;
+        Ok(())
+    }
+}
+
+/// The outcome of a successful precompile call.
+#[derive(Default)]
+pub(crate) struct PrecompileOutput {
+    /// The EVM gas to charge for the call.
+    pub(crate) gas: u64,
+    /// The data returned to the caller.
+    pub(crate) output: Bytes,
+    /// The logs emitted by the call.
+    pub(crate) logs: Vec<Log>,
+}
+
+/// Dispatches the calls to the module precompiles and the ERC-20 precompiles of a transaction.
+///
+/// The modules run in the working set of the transaction, with the caller's mapped rollup
+/// address as sender, see [`rollup_address`]. The working set is snapshotted before the first
+/// precompile call of every frame, so that the module changes of a frame are discarded along with
+/// its EVM changes when it reverts.
+pub(crate) struct ModulePrecompiles<'m, C: sov_modules_api::Context> {
+    modules: Vec<(Address, &'m dyn ModuleCall<C>)>,
+    erc20: Erc20Precompiles<'m, C>,
+    context: &'m C,
+    /// The active frames, from the outermost one, with the state of the working set when they
+    /// started if they made module calls. The frames without a snapshot are always the innermost.
+    frames: Vec<Option<Rc<WorkingSetSnapshot>>>,
+}
+
+impl<'m, C: sov_modules_api::Context> ModulePrecompiles<'m, C> {
+    pub(crate) fn new(
+        modules: Vec<(Address, &'m dyn ModuleCall<C>)>,
+        erc20: Erc20Precompiles<'m, C>,
+        context: &'m C,
+    ) -> Self {
+        Self {
+            modules,
+            erc20,
+            context,
+            frames: Vec::new(),
+        }
+    }
+
+    fn module_at(&self, address: B160) -> Option<&'m dyn ModuleCall<C>> {
+        self.modules
+            .iter()
+            .find(|(precompile, _)| *precompile == address)
+            .map(|(_, module)| *module)
+    }
+
+    /// Calls the module.
+    fn dispatch(
+        &mut self,
+        module: &dyn ModuleCall<C>,
+        inputs: &CallInputs,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<PrecompileOutput> {
+        // The module only sees the direct caller, so it can't act on behalf of anyone else.
+        anyhow::ensure!(
+            inputs.context.scheme == CallScheme::Call && inputs.transfer.value == U256::ZERO,
+            "Module precompiles only accept plain calls without value"
+        );
+        self.snapshot_frames(working_set);
+
+        let context = C::new(
+            rollup_address::<C>(inputs.context.caller),
+            self.context.sequencer().clone(),
+            self.context.slot_height(),
+        );
+        let remaining_funds = working_set.gas_remaining_funds();
+        let result = module.dispatch(&inputs.input, &context, working_set);
+        // The module gas is paid with EVM gas, out of the gas limit of the transaction.
+        let module_gas = remaining_funds.saturating_sub(working_set.gas_remaining_funds());
+        working_set.refund_gas(module_gas);
+        result?;
+
+        Ok(PrecompileOutput {
+            gas: MODULE_CALL_BASE_GAS
+                .saturating_add(module_gas.saturating_mul(EVM_GAS_PER_MODULE_GAS)),
+            ..Default::default()
+        })
+    }
+
+    /// Calls the ERC-20 precompile of the bank token.
+    fn dispatch_erc20(
+        &mut self,
+        token_address: &C::Address,
+        inputs: &CallInputs,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<PrecompileOutput> {
+        // Delegate calls and call codes run the code of the precompile address instead, which reverts.
+        anyhow::ensure!(
+            inputs.transfer.value == U256::ZERO,
+            "ERC-20 precompiles don't accept value"
+        );
+        self.snapshot_frames(working_set);
+
+        self.erc20.call(
+            token_address,
+            inputs.contract,
+            inputs.context.caller,
+            &inputs.input,
+            inputs.is_static,
+            working_set,
+        )
+    }
+
+    /// Snapshots the working set for the frames which didn't make module calls yet, as it is
+    /// still in the state they started with.
+    fn snapshot_frames(&mut self, working_set: &WorkingSet<C>) {
+        if !matches!(self.frames.last(), Some(None)) {
+            return;
+        }
+        let snapshot = Rc::new(working_set.snapshot());
+        for frame in self.frames.iter_mut().rev() {
+            if frame.is_some() {
+                break;
+            }
+            *frame = Some(snapshot.clone());
+        }
+    }
+
+    /// Closes the frame, discarding the changes of its module calls if it didn't succeed.
+    fn end_frame(&mut self, ret: InstructionResult, working_set: &mut WorkingSet<C>) {
+        if let Some(Some(snapshot)) = self.frames.pop() {
+            if !matches!(ret, return_ok!()) {
+                working_set.revert_to_snapshot(&snapshot);
+            }
+        }
+    }
+}
+
+impl<'m, 'a, C: sov_modules_api::Context> Inspector<EvmDb<'a, C>> for ModulePrecompiles<'m, C> {
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.frames.push(None);
+        let result = if let Some(module) = self.module_at(inputs.contract) {
+            self.dispatch(module, inputs, data.db.working_set)
+        } else if let Some(token_address) = self.erc20.token_at(inputs.contract) {
+            self.dispatch_erc20(&token_address, inputs, data.db.working_set)
+        } else {
+            return (InstructionResult::Continue, Gas::new(0), Bytes::new());
+        };
+
+        let mut gas = Gas::new(inputs.gas_limit);
+        match result {
+            Ok(outcome) if gas.record_cost(outcome.gas) => {
+                // The call has no journal checkpoint of its own, so its logs are only kept
+                // once it succeeded.
+                for log in outcome.logs {
+                    data.journaled_state.log(log);
+                }
+                (InstructionResult::Return, gas, outcome.output)
+            }
+            Ok(_) => (InstructionResult::OutOfGas, gas, Bytes::new()),
+            Err(e) => {
+                tracing::debug!("Precompile call failed: {}", e);
+                (InstructionResult::Revert, gas, Bytes::from(e.to_string()))
+            }
+        }
+    }
+
+    fn call_end(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        _inputs: &CallInputs,
+        remaining_gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+    ) -> (InstructionResult, Gas, Bytes) {
+        self.end_frame(ret, data.db.working_set);
+        (ret, remaining_gas, out)
+    }
+
+    fn create(
+        &mut self,
+        _data: &mut EVMData<'_, EvmDb<'a, C>>,
+        _inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        self.frames.push(None);
+        (InstructionResult::Continue, None, Gas::new(0), Bytes::new())
+    }
+
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        _inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B160>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        self.end_frame(ret, data.db.working_set);
+        (ret, address, remaining_gas, out)
+    }
+}
+
+/// Runs the module precompiles under another inspector, such as a tracer.
+struct WithModulePrecompiles<'p, 'm, C: sov_modules_api::Context, I> {
+    precompiles: &'p mut ModulePrecompiles<'m, C>,
+    inspector: I,
+}
+
+impl<'p, 'm, 'a, C, I> Inspector<EvmDb<'a, C>> for WithModulePrecompiles<'p, 'm, C, I>
+where
+    C: sov_modules_api::Context,
+    I: Inspector<EvmDb<'a, C>>,
+{
+    fn initialize_interp(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+    ) -> InstructionResult {
+        self.inspector.initialize_interp(interp, data)
+    }
+
+    fn step(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+    ) -> InstructionResult {
+        self.inspector.step(interp, data)
+    }
+
+    fn log(
+        &mut self,
+        evm_data: &mut EVMData<'_, EvmDb<'a, C>>,
+        address: &B160,
+        topics: &[B256],
+        data: &Bytes,
+    ) {
+        self.inspector.log(evm_data, address, topics, data)
+    }
+
+    fn step_end(
+        &mut self,
+        interp: &mut Interpreter,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        eval: InstructionResult,
+    ) -> InstructionResult {
+        self.inspector.step_end(interp, data, eval)
+    }
+
+    // The inspector sees the calls to the precompiles before they are dispatched.
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        inputs: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let outcome = self.inspector.call(data, inputs);
+        if outcome.0 != InstructionResult::Continue {
+            self.precompiles.frames.push(None);
+            return outcome;
+        }
+        self.precompiles.call(data, inputs)
+    }
+
+    fn call_end(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        inputs: &CallInputs,
+        remaining_gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let (ret, remaining_gas, out) =
+            self.precompiles
+                .call_end(data, inputs, remaining_gas, ret, out);
+        self.inspector
+            .call_end(data, inputs, remaining_gas, ret, out)
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        self.precompiles.create(data, inputs);
+        self.inspector.create(data, inputs)
+    }
 
-    let mut inspector = TracingInspector::new(config);
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, EvmDb<'a, C>>,
+        inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<B160>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<B160>, Gas, Bytes) {
+        let (ret, address, remaining_gas, out) =
+            self.precompiles
+                .create_end(data, inputs, ret, address, remaining_gas, out);
+        self.inspector
+            .create_end(data, inputs, ret, address, remaining_gas, out)
+    }
 
-    evm.inspect(&mut inspector)
+    fn selfdestruct(&mut self, contract: B160, target: B160, value: U256) {
+        self.inspector.selfdestruct(contract, target, value)
+    }
 }
@@ -7,8 +7,9 @@ use sov_state::Prefix;
 pub(crate) mod call;
 pub(crate) mod conversions;
 pub(crate) mod db;
-mod db_commit;
+pub(crate) mod db_commit;
 pub(crate) mod db_init;
+pub(crate) mod erc20;
 pub(crate) mod error;
 pub(crate) mod executor;
 pub(crate) mod primitive_types;
@@ -16,6 +17,7 @@ pub(crate) mod primitive_types;
 mod tests;
 
 pub(crate) use call::prepare_call_env;
+pub use executor::{BANK_PRECOMPILE_ADDRESS, NFT_PRECOMPILE_ADDRESS};
 pub use primitive_types::RlpEvmTransaction;
 use sov_state::codec::BcsCodec;
 
@@ -35,8 +37,10 @@ pub(crate) struct DbAccount {
 }
 
 impl DbAccount {
-    fn new(parent_prefix: &Prefix, address: Address) -> Self {
-        let prefix = Self::create_storage_prefix(parent_prefix, address);
+    /// Creates an empty account. `incarnation` is the number of times the account was destroyed
+    /// before, and its storage is apart from the storage of the previous incarnations.
+    pub(crate) fn new(parent_prefix: &Prefix, address: Address, incarnation: u64) -> Self {
+        let prefix = Self::create_storage_prefix(parent_prefix, address, incarnation);
         Self {
             info: Default::default(),
             storage: StateMap::with_codec(prefix, BcsCodec {}),
@@ -48,16 +52,19 @@ impl DbAccount {
         address: Address,
         info: AccountInfo,
     ) -> Self {
-        let prefix = Self::create_storage_prefix(parent_prefix, address);
         Self {
             info,
-            storage: StateMap::with_codec(prefix, BcsCodec {}),
+            ..Self::new(parent_prefix, address, 0)
         }
     }
 
-    fn create_storage_prefix(parent_prefix: &Prefix, address: Address) -> Prefix {
+    fn create_storage_prefix(parent_prefix: &Prefix, address: Address, incarnation: u64) -> Prefix {
         let mut prefix = parent_prefix.as_aligned_vec().clone().into_inner();
         prefix.extend_from_slice(&address.0);
+        // The first incarnation keeps the prefix of the accounts created before they were counted.
+        if incarnation > 0 {
+            prefix.extend_from_slice(&incarnation.to_be_bytes());
+        }
         Prefix::new(prefix)
     }
 }
@@ -76,9 +83,13 @@ pub struct EvmChainConfig {
     /// List of EVM hardforks by block number
     pub spec: Vec<(u64, SpecId)>,
 
-    /// Coinbase where all the fees go
+    /// Coinbase receiving the priority fees of the transactions applied outside of a blob
+    /// with a registered sequencer
     pub coinbase: Address,
 
+    /// Treasury receiving the base fees. They are burned if it's not set.
+    pub base_fee_recipient: Option<Address>,
+
     /// Gas limit for single block
     pub block_gas_limit: u64,
 
@@ -96,6 +107,7 @@ impl Default for EvmChainConfig {
             limit_contract_code_size: None,
             spec: vec![(0, SpecId::SHANGHAI)],
             coinbase: Address::zero(),
+            base_fee_recipient: None,
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
             block_timestamp_delta: 1,
             base_fee_params: BaseFeeParams::ethereum(),
@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+
+// Source of the native bridge system contract deployed at genesis, see `src/bridge.rs`.
+// NativeBridge.bin-runtime holds its runtime code, and NativeBridge.abi its interface:
+// solc --abi --bin-runtime NativeBridge.sol -o . --overwrite
+// The runtime code is assembled by hand to keep it minimal, and must be kept in sync with this
+// source: same selectors, same logs, and no other entry point.
+pragma solidity ^0.8.0;
+
+contract NativeBridge {
+    event Withdrawal(bytes32 indexed recipient, uint256 amount);
+
+    // Withdraws the value of the call to `recipient`, a rollup address, once the transaction is
+    // settled by the EVM module.
+    function withdraw(bytes32 recipient) external payable {
+        emit Withdrawal(recipient, msg.value);
+    }
+}
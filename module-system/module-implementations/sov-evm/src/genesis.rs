@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{ensure, Result};
 use reth_primitives::constants::{EMPTY_RECEIPTS, EMPTY_TRANSACTIONS};
 use reth_primitives::{Address, Bloom, Bytes, EMPTY_OMMER_ROOT, H256, KECCAK_EMPTY, U256};
 use revm::primitives::SpecId;
@@ -10,7 +10,7 @@ use sov_modules_api::WorkingSet;
 use crate::evm::db_init::InitEvmDb;
 use crate::evm::primitive_types::Block;
 use crate::evm::{AccountInfo, EvmChainConfig};
-use crate::Evm;
+use crate::{BridgeConfig, Evm};
 
 /// Evm account.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
@@ -41,7 +41,8 @@ impl AccountData {
 
 /// Genesis configuration.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
-pub struct EvmConfig {
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct EvmConfig<C: sov_modules_api::Context> {
     /// Genesis accounts.
     pub data: Vec<AccountData>,
     /// Chain id.
@@ -50,8 +51,12 @@ pub struct EvmConfig {
     pub limit_contract_code_size: Option<usize>,
     /// List of EVM hardforks by block number
     pub spec: HashMap<u64, SpecId>,
-    /// Coinbase where all the fees go
+    /// Coinbase receiving the priority fees of the transactions applied outside of a blob
+    /// with a registered sequencer
     pub coinbase: Address,
+    /// Treasury receiving the base fees. They are burned if it's not set.
+    #[serde(default)]
+    pub base_fee_recipient: Option<Address>,
     /// Starting base fee.
     pub starting_base_fee: u64,
     /// Gas limit for single block
@@ -62,9 +67,18 @@ pub struct EvmConfig {
     pub block_timestamp_delta: u64,
     /// Base fee params.
     pub base_fee_params: reth_primitives::BaseFeeParams,
+    /// Bridge between the bank tokens and the EVM, if any.
+    /// The genesis accounts can't have a native balance then, as it wouldn't be backed by the bank.
+    #[serde(default)]
+    pub bridge: Option<BridgeConfig<C>>,
+    /// Rollup address allowed to control the EVM state with [`crate::DevCallMessage`].
+    /// It must only be set on local chains.
+    #[cfg(feature = "dev")]
+    #[serde(default)]
+    pub dev_authority: Option<C::Address>,
 }
 
-impl Default for EvmConfig {
+impl<C: sov_modules_api::Context> Default for EvmConfig<C> {
     fn default() -> Self {
         Self {
             data: vec![],
@@ -72,11 +86,15 @@ impl Default for EvmConfig {
             limit_contract_code_size: None,
             spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
             coinbase: Address::zero(),
+            base_fee_recipient: None,
             starting_base_fee: reth_primitives::constants::MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
             block_timestamp_delta: reth_primitives::constants::SLOT_DURATION.as_secs(),
             genesis_timestamp: 0,
             base_fee_params: reth_primitives::BaseFeeParams::ethereum(),
+            bridge: None,
+            #[cfg(feature = "dev")]
+            dev_authority: None,
         }
     }
 }
@@ -107,14 +125,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let mut spec = config
             .spec
             .iter()
-            .map(|(k, v)| {
-                // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
-                if *v == SpecId::CANCUN {
-                    panic!("Cancun is not supported");
-                }
-
-                (*k, *v)
-            })
+            .map(|(k, v)| (*k, *v))
             .collect::<Vec<_>>();
 
         spec.sort_by(|a, b| a.0.cmp(&b.0));
@@ -130,6 +141,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
             limit_contract_code_size: config.limit_contract_code_size,
             spec,
             coinbase: config.coinbase,
+            base_fee_recipient: config.base_fee_recipient,
             block_gas_limit: config.block_gas_limit,
             block_timestamp_delta: config.block_timestamp_delta,
             base_fee_params: config.base_fee_params,
@@ -137,6 +149,20 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         self.cfg.set(&chain_cfg, working_set);
 
+        if let Some(bridge) = &config.bridge {
+            // The native balances must be backed by tokens locked in the account of the module.
+            ensure!(
+                config.data.iter().all(|acc| acc.balance == U256::ZERO),
+                "Genesis EVM accounts can't have a native balance when a bridge is configured, deposit the tokens instead"
+            );
+            self.init_bridge(bridge, working_set)?;
+        }
+
+        #[cfg(feature = "dev")]
+        if let Some(dev_authority) = &config.dev_authority {
+            self.dev_authority.set(dev_authority, working_set);
+        }
+
         let header = reth_primitives::Header {
             parent_hash: H256::default(),
             ommers_hash: EMPTY_OMMER_ROOT,
@@ -184,6 +210,7 @@ mod tests {
 
     use reth_primitives::Bytes;
     use revm::primitives::{Address, SpecId};
+    use sov_modules_api::default_context::DefaultContext;
 
     use crate::{AccountData, EvmConfig};
 
@@ -231,7 +258,7 @@ mod tests {
                 }
         }"#;
 
-        let parsed_config: EvmConfig = serde_json::from_str(data).unwrap();
+        let parsed_config: EvmConfig<DefaultContext> = serde_json::from_str(data).unwrap();
         assert_eq!(config, parsed_config)
     }
 }
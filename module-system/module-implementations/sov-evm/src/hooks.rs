@@ -4,7 +4,7 @@ use sov_modules_api::{AccessoryWorkingSet, Spec, WorkingSet};
 use sov_state::Storage;
 
 use crate::evm::primitive_types::{Block, BlockEnv};
-use crate::experimental::PendingTransaction;
+use crate::experimental::{PendingTransaction, BLOCK_HASHES_TO_KEEP};
 use crate::Evm;
 
 impl<C: sov_modules_api::Context> Evm<C>
@@ -18,13 +18,7 @@ where
         pre_state_root: &<<C as Spec>::Storage as Storage>::Root,
         working_set: &mut WorkingSet<C>,
     ) {
-        let mut parent_block = self
-            .head
-            .get(working_set)
-            .expect("Head block should always be set");
-
-        parent_block.header.state_root = H256(pre_state_root.clone().into());
-        self.head.set(&parent_block, working_set);
+        let parent_block = self.seal_head(H256(pre_state_root.clone().into()), working_set);
 
         let cfg = self.cfg.get(working_set).unwrap_or_default();
         let new_pending_env = BlockEnv {
@@ -41,6 +35,22 @@ where
         self.block_env.set(&new_pending_env, working_set);
     }
 
+    /// Logic executed at the beginning of each blob. Here we record the rollup address of the sequencer
+    /// of the blob, as resolved by the sequencer registry, whose EVM account (see [`crate::evm_address`])
+    /// receives the priority fees of the transactions of the blob.
+    /// Without a sequencer, they go to the coinbase of the chain config.
+    pub fn begin_blob_hook(&self, sequencer: Option<C::Address>, working_set: &mut WorkingSet<C>) {
+        match sequencer {
+            Some(sequencer) => self.blob_sequencer.set(&sequencer, working_set),
+            None => self.blob_sequencer.delete(working_set),
+        }
+    }
+
+    /// Logic executed at the end of each blob. Here we forget the sequencer of the blob.
+    pub fn end_blob_hook(&self, working_set: &mut WorkingSet<C>) {
+        self.blob_sequencer.delete(working_set);
+    }
+
     /// Logic executed at the end of the slot. Here, we generate an authenticated block and set it as the new head of the chain.
     /// It's important to note that the state root hash is not known at this moment, so we postpone setting this field until the begin_slot_hook of the next slot.
     pub fn end_slot_hook(&self, working_set: &mut WorkingSet<C>) {
@@ -182,6 +192,8 @@ where
         let sealed_block = block.seal();
 
         self.blocks.push(&sealed_block, accessory_working_set);
+        self.logs_blooms
+            .push(&sealed_block.header.logs_bloom, accessory_working_set);
         self.block_hashes.set(
             &sealed_block.header.hash,
             &sealed_block.header.number,
@@ -190,3 +202,31 @@ where
         self.pending_head.delete(accessory_working_set);
     }
 }
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Sets the state root of the head, now that it's known, and makes its hash available to
+    /// the `BLOCKHASH` opcode. Returns the sealed head, the parent of the pending block.
+    pub(crate) fn seal_head(&self, state_root: H256, working_set: &mut WorkingSet<C>) -> Block {
+        let mut parent_block = self
+            .head
+            .get(working_set)
+            .expect("Head block should always be set");
+
+        parent_block.header.state_root = state_root;
+        self.head.set(&parent_block, working_set);
+
+        let parent_number = parent_block.header.number;
+        self.latest_block_hashes.set(
+            &U256::from(parent_number),
+            &parent_block.header.hash_slow(),
+            working_set,
+        );
+        if parent_number >= BLOCK_HASHES_TO_KEEP {
+            self.latest_block_hashes.delete(
+                &U256::from(parent_number - BLOCK_HASHES_TO_KEEP),
+                working_set,
+            );
+        }
+        parent_block
+    }
+}
@@ -1,7 +1,11 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 #[cfg(feature = "experimental")]
+mod bridge;
+#[cfg(feature = "experimental")]
 mod call;
+#[cfg(feature = "dev")]
+mod dev;
 #[cfg(feature = "experimental")]
 mod evm;
 #[cfg(feature = "experimental")]
@@ -9,6 +13,10 @@ mod genesis;
 #[cfg(feature = "experimental")]
 mod hooks;
 #[cfg(feature = "experimental")]
+pub use bridge::{evm_address, rollup_address, BridgeConfig, Erc20Token};
+#[cfg(feature = "dev")]
+pub use dev::DevCallMessage;
+#[cfg(feature = "experimental")]
 pub use {call::*, error::rpc::*, evm::*, genesis::*};
 #[cfg(feature = "native")]
 #[cfg(feature = "experimental")]
@@ -16,6 +24,18 @@ mod query;
 #[cfg(feature = "native")]
 #[cfg(feature = "experimental")]
 pub use query::*;
+#[cfg(feature = "native")]
+#[cfg(feature = "experimental")]
+mod proof;
+#[cfg(feature = "native")]
+#[cfg(feature = "experimental")]
+pub use sov_evm_proof_verifier::{EvmAccountProof, EvmStorageProof, JmtProof};
+#[cfg(feature = "native")]
+#[cfg(feature = "experimental")]
+mod trace;
+#[cfg(feature = "native")]
+#[cfg(feature = "experimental")]
+pub use trace::{BlockReplay, TransactionTrace};
 #[cfg(feature = "experimental")]
 mod signer;
 #[cfg(feature = "experimental")]
@@ -35,20 +55,31 @@ pub use revm::primitives::SpecId;
 #[cfg(feature = "experimental")]
 mod experimental {
 
-    use reth_primitives::Address;
-    use sov_modules_api::{Error, ModuleInfo, WorkingSet};
+    use std::convert::Infallible;
+
+    use reth_primitives::{Address, Bloom};
+    use revm::primitives::{CfgEnv, EVMError, ResultAndState, TxEnv};
+    use revm::Inspector;
+    use sov_modules_api::{Error, ModuleInfo, StateMapAccessor, WorkingSet};
     use sov_state::codec::BcsCodec;
 
     use super::evm::db::EvmDb;
-    use super::evm::{DbAccount, EvmChainConfig};
+    use super::evm::erc20::Erc20Precompiles;
+    use super::evm::executor::{self, ModuleCall, ModulePrecompiles};
+    use super::evm::{DbAccount, EvmChainConfig, BANK_PRECOMPILE_ADDRESS, NFT_PRECOMPILE_ADDRESS};
     use crate::evm::primitive_types::{
         Block, BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered,
     };
-    use crate::EvmConfig;
+    use crate::trace::{BlockCall, BlockCallKind};
+    #[cfg(feature = "dev")]
+    use crate::DevCallMessage;
+    use crate::{rollup_address, BridgeConfig, CallMessage, EvmConfig};
 
     // Gas per transaction not creating a contract.
     pub(crate) const MIN_TRANSACTION_GAS: u64 = 21_000u64;
     pub(crate) const MIN_CREATE_GAS: u64 = 53_000u64;
+    // The number of blocks the `BLOCKHASH` opcode can look back at.
+    pub(crate) const BLOCK_HASHES_TO_KEEP: u64 = 256;
 
     #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
     pub(crate) struct PendingTransaction {
@@ -65,10 +96,24 @@ mod experimental {
         #[address]
         pub(crate) address: C::Address,
 
+        /// Reference to the Bank module, which holds the tokens bridged to the EVM.
+        #[module]
+        pub(crate) bank: sov_bank::Bank<C>,
+
+        /// Reference to the NFT module, which contracts call through a precompile.
+        #[module]
+        pub(crate) nft: sov_nft_module::NonFungibleToken<C>,
+
         /// Mapping from account address to account state.
         #[state]
         pub(crate) accounts: sov_modules_api::StateMap<Address, DbAccount, BcsCodec>,
 
+        /// Mapping from account address to the number of times the account was destroyed by `SELFDESTRUCT`.
+        /// The storage of a destroyed account is left behind, and the next incarnation of the account
+        /// gets a storage prefix of its own, so destroying an account costs the same whatever its storage.
+        #[state]
+        pub(crate) account_incarnations: sov_modules_api::StateMap<Address, u64, BcsCodec>,
+
         /// Mapping from code hash to code. Used for lazy-loading code into a contract account.
         #[state]
         pub(crate) code:
@@ -78,6 +123,27 @@ mod experimental {
         #[state]
         pub(crate) cfg: sov_modules_api::StateValue<EvmChainConfig, BcsCodec>,
 
+        /// Bridge between the bank tokens and the EVM. This field is set in genesis.
+        #[state]
+        pub(crate) bridge: sov_modules_api::StateValue<BridgeConfig<C>, BcsCodec>,
+
+        /// Allowances of the ERC-20 precompiles, by precompile, owner and spender.
+        #[state]
+        pub(crate) erc20_allowances:
+            sov_modules_api::StateMap<(Address, Address, Address), reth_primitives::U256, BcsCodec>,
+
+        /// Rollup address allowed to send the developer controls of the `dev` feature, if any.
+        /// This field is set in genesis.
+        #[state]
+        #[cfg_attr(not(feature = "dev"), allow(dead_code))]
+        pub(crate) dev_authority: sov_modules_api::StateValue<C::Address>,
+
+        /// Rollup address of the sequencer of the blob being applied, which receives the priority fees
+        /// of its transactions. This field is set in `begin_blob_hook` and removed in `end_blob_hook`,
+        /// so it never outlives its blob.
+        #[state]
+        pub(crate) blob_sequencer: sov_modules_api::StateValue<C::Address>,
+
         /// Block environment used by the evm. This field is set in `begin_slot_hook`.
         #[state]
         pub(crate) block_env: sov_modules_api::StateValue<BlockEnv, BcsCodec>,
@@ -92,6 +158,12 @@ mod experimental {
         #[state]
         pub(crate) head: sov_modules_api::StateValue<Block, BcsCodec>,
 
+        /// The hashes of the last [`BLOCK_HASHES_TO_KEEP`] blocks by block number, for the `BLOCKHASH` opcode.
+        /// The hash of a block is added in `begin_slot_hook` of the next block, once its `state_root` is known.
+        #[state]
+        pub(crate) latest_block_hashes:
+            sov_modules_api::StateMap<reth_primitives::U256, reth_primitives::H256, BcsCodec>,
+
         /// Used only by the RPC: This represents the head of the chain and is set in two distinct stages:
         /// 1. `end_slot_hook`: the pending head is populated with data from pending_transactions.
         /// 2. `finalize_hook` the `root_hash` is populated.
@@ -103,6 +175,11 @@ mod experimental {
         #[state]
         pub(crate) blocks: sov_modules_api::AccessoryStateVec<SealedBlock, BcsCodec>,
 
+        /// Used only by the RPC: the logs bloom of every block, indexed by block number.
+        /// Log queries check it to skip the blocks without matching logs, without loading them.
+        #[state]
+        pub(crate) logs_blooms: sov_modules_api::AccessoryStateVec<Bloom, BcsCodec>,
+
         /// Used only by the RPC: block_hash => block_number mapping,
         #[state]
         pub(crate) block_hashes:
@@ -121,14 +198,19 @@ mod experimental {
         /// Used only by the RPC: Receipts.
         #[state]
         pub(crate) receipts: sov_modules_api::AccessoryStateVec<Receipt, BcsCodec>,
+
+        /// Used only by the RPC: block_number => the calls applied in the block, in order.
+        /// The transactions of a block are traced by replaying them on top of its parent.
+        #[state]
+        pub(crate) block_calls: sov_modules_api::AccessoryStateMap<u64, Vec<BlockCall<C>>>,
     }
 
     impl<C: sov_modules_api::Context> sov_modules_api::Module for Evm<C> {
         type Context = C;
 
-        type Config = EvmConfig;
+        type Config = EvmConfig<C>;
 
-        type CallMessage = super::call::CallMessage;
+        type CallMessage = CallMessage<C>;
 
         type Event = ();
 
@@ -146,13 +228,124 @@ mod experimental {
             context: &Self::Context,
             working_set: &mut WorkingSet<C>,
         ) -> Result<sov_modules_api::CallResponse, Error> {
-            Ok(self.execute_call(msg.tx, context, working_set)?)
+            let replayed = match &msg {
+                CallMessage::Transact { .. } => false,
+                // Impersonated transactions are recorded as transactions, and the blocks are
+                // replayed with their final timestamp.
+                #[cfg(feature = "dev")]
+                CallMessage::Dev { call } => !matches!(
+                    call,
+                    DevCallMessage::Impersonate { .. } | DevCallMessage::IncreaseTime { .. }
+                ),
+                CallMessage::Deposit { .. } | CallMessage::Withdraw { .. } => true,
+            };
+            if replayed {
+                self.record_block_call(BlockCallKind::Call(msg.clone()), context, working_set);
+            }
+            Ok(self.execute_module_call(msg, context, working_set)?)
         }
     }
 
     impl<C: sov_modules_api::Context> Evm<C> {
+        /// The modules contracts can call through precompiles, and the ERC-20 precompiles of the
+        /// bridged bank tokens.
+        pub(crate) fn module_precompiles<'m>(
+            &'m self,
+            context: &'m C,
+            working_set: &mut WorkingSet<C>,
+        ) -> ModulePrecompiles<'m, C> {
+            let erc20_tokens = self
+                .bridge
+                .get(working_set)
+                .map(|bridge| bridge.erc20_tokens)
+                .unwrap_or_default();
+            ModulePrecompiles::new(
+                vec![
+                    (BANK_PRECOMPILE_ADDRESS, &self.bank as &dyn ModuleCall<C>),
+                    (NFT_PRECOMPILE_ADDRESS, &self.nft as &dyn ModuleCall<C>),
+                ],
+                Erc20Precompiles::new(self, erc20_tokens),
+                context,
+            )
+        }
+
+        /// Executes a call without committing it, dispatching its calls to the module
+        /// precompiles as transactions do, and recording its execution in the `inspector`.
+        /// The changes of the module calls are discarded afterwards.
+        pub(crate) fn inspect_call<I>(
+            &self,
+            block_env: &BlockEnv,
+            tx_env: TxEnv,
+            cfg_env: CfgEnv,
+            inspector: I,
+            working_set: &mut WorkingSet<C>,
+        ) -> Result<ResultAndState, EVMError<Infallible>>
+        where
+            I: for<'a> Inspector<EvmDb<'a, C>>,
+        {
+            // Calls have no sequencer, the caller stands in for it.
+            let caller = rollup_address::<C>(tx_env.caller);
+            let context = C::new(caller.clone(), caller, block_env.number);
+            let mut precompiles = self.module_precompiles(&context, working_set);
+
+            let snapshot = working_set.snapshot();
+            let result = executor::inspect(
+                self.get_db(working_set),
+                block_env,
+                tx_env,
+                cfg_env,
+                &mut precompiles,
+                inspector,
+            );
+            working_set.revert_to_snapshot(&snapshot);
+            result
+        }
+
+        pub(crate) fn execute_module_call(
+            &self,
+            msg: CallMessage<C>,
+            context: &C,
+            working_set: &mut WorkingSet<C>,
+        ) -> anyhow::Result<sov_modules_api::CallResponse> {
+            match msg {
+                CallMessage::Transact { tx } => self.execute_call(tx, context, working_set),
+                CallMessage::Deposit {
+                    token_address,
+                    to,
+                    amount,
+                } => self.deposit(token_address, to.into(), amount, context, working_set),
+                CallMessage::Withdraw {
+                    token_address,
+                    amount,
+                } => self.withdraw(token_address, amount, context, working_set),
+                #[cfg(feature = "dev")]
+                CallMessage::Dev { call } => self.execute_dev_call(call, context, working_set),
+            }
+        }
+
         pub(crate) fn get_db<'a>(&self, working_set: &'a mut WorkingSet<C>) -> EvmDb<'a, C> {
-            EvmDb::new(self.accounts.clone(), self.code.clone(), working_set)
+            EvmDb::new(
+                self.accounts.clone(),
+                self.account_incarnations.clone(),
+                self.code.clone(),
+                self.latest_block_hashes.clone(),
+                working_set,
+            )
+        }
+
+        /// Returns the account with the given address, or a new empty one if it doesn't exist.
+        pub(crate) fn get_or_create_account(
+            &self,
+            address: Address,
+            working_set: &mut WorkingSet<C>,
+        ) -> DbAccount {
+            self.accounts.get(&address, working_set).unwrap_or_else(|| {
+                let incarnation = self
+                    .account_incarnations
+                    .get(&address, working_set)
+                    .unwrap_or_default();
+                DbAccount::new(self.accounts.prefix(), address, incarnation)
+            })
         }
     }
 }
@@ -0,0 +1,95 @@
+//! Proofs of the EVM accounts and storage slots against the rollup state root, for `eth_getProof`.
+
+use std::collections::HashMap;
+
+use reth_interfaces::RethError;
+use reth_primitives::{Address, Bytes, KECCAK_EMPTY, U256};
+use sov_evm_proof_verifier::{
+    account_key, storage_key, EvmAccountProof, EvmStorageProof, JmtProof,
+};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::trace::BlockReplay;
+use crate::{EthApiError, Evm};
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Proves the account with the given address and the given storage slots of it, as of the
+    /// block of `replay`. The `working_set` must be on top of the state after the block.
+    pub fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<U256>,
+        replay: &BlockReplay<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<EvmAccountProof, EthApiError> {
+        working_set.record_reads();
+        let account = self.accounts.get(&address, working_set);
+        let db_account = match &account {
+            Some(account) => account.clone(),
+            None => self.get_or_create_account(address, working_set),
+        };
+        let values = keys
+            .iter()
+            .map(|key| db_account.storage.get(key, working_set).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let proven = working_set.prove_recorded_reads(()).map_err(proof_error)?;
+
+        let state_root = replay.state_root();
+        if proven.state_root != state_root.as_bytes() {
+            return Err(proof_error(format!(
+                "The state of block {} was proven against an unexpected root",
+                replay.number()
+            )));
+        }
+
+        // Reads are only recorded once, so the proofs are looked up by key.
+        let proofs = proven
+            .proofs
+            .into_iter()
+            .map(|proof| (proof.key.key().to_vec(), proof))
+            .collect::<HashMap<_, _>>();
+        let jmt_proof = |key: Vec<u8>| {
+            let proof = proofs
+                .get(&key)
+                .ok_or_else(|| proof_error("A read entry of the account wasn't proven"))?;
+            Ok::<_, EthApiError>(JmtProof {
+                key: Bytes::from(key),
+                value: proof
+                    .value
+                    .as_ref()
+                    .map(|value| Bytes::from(value.value().to_vec())),
+                proof: Bytes::from(proof.proof.clone()),
+            })
+        };
+
+        let account_proof = jmt_proof(account_key(address))?;
+        let storage_proof = keys
+            .into_iter()
+            .zip(values)
+            .map(|(key, value)| {
+                Ok(EvmStorageProof {
+                    key,
+                    value,
+                    proof: jmt_proof(storage_key(address, key))?,
+                })
+            })
+            .collect::<Result<Vec<_>, EthApiError>>()?;
+
+        let info = account.map(|account| account.info);
+        Ok(EvmAccountProof {
+            address,
+            balance: info.as_ref().map(|info| info.balance).unwrap_or_default(),
+            nonce: info.as_ref().map(|info| info.nonce).unwrap_or_default(),
+            code_hash: info.map(|info| info.code_hash).unwrap_or(KECCAK_EMPTY),
+            block_number: replay.number(),
+            state_root,
+            account_proof,
+            storage_proof,
+        })
+    }
+}
+
+fn proof_error(error: impl ToString) -> EthApiError {
+    EthApiError::Internal(RethError::Custom(error.to_string()))
+}
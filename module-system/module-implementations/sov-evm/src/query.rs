@@ -1,10 +1,18 @@
 use std::array::TryFromSliceError;
+use std::ops::RangeInclusive;
 
 use ethereum_types::U64;
 use jsonrpsee::core::RpcResult;
 use reth_primitives::contract::create_address;
 use reth_primitives::TransactionKind::{Call, Create};
-use reth_primitives::{TransactionSignedEcRecovered, U128, U256};
+use reth_primitives::{
+    AccessListWithGasUsed, Address, BlockNumberOrTag, TransactionSignedEcRecovered, H256, U128,
+    U256,
+};
+use reth_revm::access_list::AccessListInspector;
+use reth_rpc_types::{Filter, FilterBlockOption, FilteredParams};
+use revm::inspectors::NoOpInspector;
+use revm::precompile::{Precompiles, SpecId as PrecompileSpecId};
 use revm::primitives::{
     EVMError, ExecutionResult, Halt, InvalidTransaction, TransactTo, KECCAK_EMPTY,
 };
@@ -14,12 +22,19 @@ use sov_modules_api::WorkingSet;
 use tracing::info;
 
 use crate::call::get_cfg_env;
-use crate::error::rpc::{ensure_success, RevertError, RpcInvalidTransactionError};
-use crate::evm::db::EvmDb;
+use crate::error::rpc::{ensure_success, EthFilterError, RevertError, RpcInvalidTransactionError};
 use crate::evm::primitive_types::{BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered};
-use crate::evm::{executor, prepare_call_env};
+use crate::evm::{prepare_call_env, BANK_PRECOMPILE_ADDRESS, NFT_PRECOMPILE_ADDRESS};
 use crate::experimental::{MIN_CREATE_GAS, MIN_TRANSACTION_GAS};
-use crate::{EthApiError, Evm};
+use crate::trace::last_coinbase;
+use crate::{evm_address, EthApiError, Evm};
+
+/// The maximum number of blocks a log query can span.
+pub const MAX_BLOCKS_PER_LOG_QUERY: u64 = 100_000;
+/// The maximum number of logs a log query can return.
+pub const MAX_LOGS_PER_RESPONSE: usize = 20_000;
+/// The maximum number of blocks a fee history query can return.
+pub const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
 
 #[rpc_gen(client, server)]
 impl<C: sov_modules_api::Context> Evm<C> {
@@ -138,13 +153,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_balance(
         &self,
         address: reth_primitives::Address,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         info!("evm module: eth_getBalance");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
+        self.ensure_latest_block(block_number.as_deref(), working_set)?;
 
         let balance = self
             .accounts
@@ -161,13 +175,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         address: reth_primitives::Address,
         index: reth_primitives::U256,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         info!("evm module: eth_getStorageAt");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
+        self.ensure_latest_block(block_number.as_deref(), working_set)?;
 
         let storage_slot = self
             .accounts
@@ -178,40 +191,17 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(storage_slot)
     }
 
-    /// Handler for: `eth_getTransactionCount`
-    #[rpc_method(name = "eth_getTransactionCount")]
-    pub fn get_transaction_count(
-        &self,
-        address: reth_primitives::Address,
-        _block_number: Option<String>,
-        working_set: &mut WorkingSet<C>,
-    ) -> RpcResult<reth_primitives::U64> {
-        info!("evm module: eth_getTransactionCount");
-
-        // TODO: Implement block_number once we have archival state #882
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/882
-
-        let nonce = self
-            .accounts
-            .get(&address, working_set)
-            .map(|account| account.info.nonce)
-            .unwrap_or_default();
-
-        Ok(nonce.into())
-    }
-
     /// Handler for: `eth_getCode`
     #[rpc_method(name = "eth_getCode")]
     pub fn get_code(
         &self,
         address: reth_primitives::Address,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         info!("evm module: eth_getCode");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
+        self.ensure_latest_block(block_number.as_deref(), working_set)?;
 
         let code = self
             .accounts
@@ -223,18 +213,103 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 
     /// Handler for: `eth_feeHistory`
-    // TODO https://github.com/Sovereign-Labs/sovereign-sdk/issues/502
+    ///
+    /// Returns the base fees and gas usage of up to [`MAX_FEE_HISTORY_BLOCKS`] blocks ending at
+    /// `newest_block`, and the base fee of the block after it. The rewards are the priority fees
+    /// per gas paid at the given percentiles of the gas used in each block.
     #[rpc_method(name = "eth_feeHistory")]
     pub fn fee_history(
         &self,
-        _working_set: &mut WorkingSet<C>,
+        block_count: reth_primitives::U64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+        working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_rpc_types::FeeHistory> {
         info!("evm module: eth_feeHistory");
+
+        if let Some(percentiles) = &reward_percentiles {
+            if percentiles.iter().any(|p| !(0.0..=100.0).contains(p))
+                || percentiles.windows(2).any(|w| w[0] > w[1])
+            {
+                return Err(EthApiError::InvalidRewardPercentiles.into());
+            }
+        }
+
+        let block_count = block_count.as_u64().min(MAX_FEE_HISTORY_BLOCKS);
+        if block_count == 0 {
+            return Ok(reth_rpc_types::FeeHistory::default());
+        }
+
+        let cfg = self
+            .cfg
+            .get(working_set)
+            .expect("EVM config must be set at genesis");
+        let mut accessory_state = working_set.accessory_state();
+        let Some(head) = (self.blocks.len(&mut accessory_state) as u64).checked_sub(1) else {
+            return Ok(reth_rpc_types::FeeHistory::default());
+        };
+        let newest = resolve_block_number(Some(newest_block), head)?;
+        if newest > head {
+            return Err(EthApiError::UnknownBlockNumber.into());
+        }
+        let oldest = (newest + 1).saturating_sub(block_count);
+
+        let mut base_fee_per_gas = vec![];
+        let mut gas_used_ratio = vec![];
+        let mut reward = vec![];
+        for block_number in oldest..=newest {
+            let block = self
+                .blocks
+                .get(block_number as usize, &mut accessory_state)
+                .expect("Block must be set");
+            let base_fee = block.header.base_fee_per_gas.unwrap_or_default();
+            base_fee_per_gas.push(U256::from(base_fee));
+            gas_used_ratio.push(block.header.gas_used as f64 / block.header.gas_limit as f64);
+
+            if let Some(percentiles) = &reward_percentiles {
+                // The priority fee per gas and the gas used of the transactions, cheapest first.
+                let mut transactions = block
+                    .transactions
+                    .clone()
+                    .map(|tx_number| {
+                        let tx = self
+                            .transactions
+                            .get(tx_number as usize, &mut accessory_state)
+                            .expect("Transaction of a known block must be set");
+                        let receipt = self
+                            .receipts
+                            .get(tx_number as usize, &mut accessory_state)
+                            .expect("Receipt of a known transaction must be set");
+                        let priority_fee = tx
+                            .signed_transaction
+                            .effective_gas_price(Some(base_fee))
+                            .saturating_sub(base_fee as u128);
+                        (priority_fee, receipt.gas_used)
+                    })
+                    .collect::<Vec<_>>();
+                transactions.sort_unstable();
+                reward.push(reward_percentiles_of_block(
+                    &transactions,
+                    block.header.gas_used,
+                    percentiles,
+                ));
+            }
+
+            if block_number == newest {
+                base_fee_per_gas.push(U256::from(
+                    block
+                        .header
+                        .next_block_base_fee(cfg.base_fee_params)
+                        .unwrap_or_default(),
+                ));
+            }
+        }
+
         Ok(reth_rpc_types::FeeHistory {
-            base_fee_per_gas: Default::default(),
-            gas_used_ratio: Default::default(),
-            oldest_block: Default::default(),
-            reward: Default::default(),
+            base_fee_per_gas,
+            gas_used_ratio,
+            oldest_block: U256::from(oldest),
+            reward: reward_percentiles.map(|_| reward),
         })
     }
 
@@ -319,6 +394,18 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(receipt)
     }
 
+    /// Handler for: `eth_getLogs`
+    #[rpc_method(name = "eth_getLogs")]
+    pub fn get_logs(
+        &self,
+        filter: Filter,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<reth_rpc_types::Log>> {
+        info!("evm module: eth_getLogs");
+
+        Ok(self.logs_for_filter(&filter, working_set)?)
+    }
+
     /// Handler for: `eth_call`
     //https://github.com/paradigmxyz/reth/blob/f577e147807a783438a3f16aad968b4396274483/crates/rpc/rpc/src/eth/api/transactions.rs#L502
     //https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-types/src/eth/call.rs#L7
@@ -332,27 +419,18 @@ impl<C: sov_modules_api::Context> Evm<C> {
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         info!("evm module: eth_call");
-        let block_env = match block_number {
-            Some(ref block_number) if block_number == "pending" => {
-                self.block_env.get(working_set).unwrap_or_default().clone()
-            }
-            _ => {
-                let block = self.get_sealed_block_by_number(block_number, working_set);
-                BlockEnv::from(&block)
-            }
-        };
+        let block_env = self.call_block_env(block_number, working_set);
 
-        let tx_env = prepare_call_env(&block_env, request.clone()).unwrap();
+        let tx_env = prepare_call_env(&block_env, request.clone())?;
 
         let cfg = self.cfg.get(working_set).unwrap_or_default();
         let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
 
-        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
-
-        let result = match executor::inspect(evm_db, &block_env, tx_env, cfg_env) {
-            Ok(result) => result.result,
-            Err(err) => return Err(EthApiError::from(err).into()),
-        };
+        let result =
+            match self.inspect_call(&block_env, tx_env, cfg_env, NoOpInspector, working_set) {
+                Ok(result) => result.result,
+                Err(err) => return Err(EthApiError::from(err).into()),
+            };
 
         Ok(ensure_success(result)?)
     }
@@ -383,17 +461,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U64> {
         info!("evm module: eth_estimateGas");
-        let mut block_env = match block_number {
-            Some(ref block_number) if block_number == "pending" => {
-                self.block_env.get(working_set).unwrap_or_default().clone()
-            }
-            _ => {
-                let block = self.get_sealed_block_by_number(block_number, working_set);
-                BlockEnv::from(&block)
-            }
-        };
+        let mut block_env = self.call_block_env(block_number, working_set);
 
-        let tx_env = prepare_call_env(&block_env, request.clone()).unwrap();
+        let tx_env = prepare_call_env(&block_env, request.clone())?;
 
         let cfg = self.cfg.get(working_set).unwrap_or_default();
         let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
@@ -412,8 +482,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
             .map(|account| account.info)
             .unwrap_or_default();
 
-        // if the request is a simple transfer we can optimize
-        if tx_env.data.is_empty() {
+        // if the request is a simple transfer we can optimize, unless its access list adds
+        // to the intrinsic gas
+        if tx_env.data.is_empty() && tx_env.access_list.is_empty() {
             if let TransactTo::Call(to) = tx_env.transact_to {
                 let to_account = self
                     .accounts
@@ -447,10 +518,14 @@ impl<C: sov_modules_api::Context> Evm<C> {
         let gas_limit = std::cmp::min(U256::from(tx_env.gas_limit), highest_gas_limit);
         block_env.gas_limit = convert_u256_to_u64(gas_limit).unwrap();
 
-        let evm_db = self.get_db(working_set);
-
         // execute the call without writing to db
-        let result = executor::inspect(evm_db, &block_env, tx_env.clone(), cfg_env.clone());
+        let result = self.inspect_call(
+            &block_env,
+            tx_env.clone(),
+            cfg_env.clone(),
+            NoOpInspector,
+            working_set,
+        );
 
         // Exceptional case: init used too much gas, we need to increase the gas limit and try
         // again
@@ -459,8 +534,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
             // if price or limit was included in the request then we can execute the request
             // again with the block's gas limit to check if revert is gas related or not
             if request_gas.is_some() || request_gas_price.is_some() {
-                let evm_db = self.get_db(working_set);
-                return Err(map_out_of_gas_err(block_env, tx_env, cfg_env, evm_db).into());
+                return Err(self
+                    .map_out_of_gas_err(block_env, tx_env, cfg_env, working_set)
+                    .into());
             }
         }
 
@@ -474,8 +550,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     // if price or limit was included in the request then we can execute the request
                     // again with the block's gas limit to check if revert is gas related or not
                     return if request_gas.is_some() || request_gas_price.is_some() {
-                        let evm_db = self.get_db(working_set);
-                        Err(map_out_of_gas_err(block_env, tx_env, cfg_env, evm_db).into())
+                        Err(self
+                            .map_out_of_gas_err(block_env, tx_env, cfg_env, working_set)
+                            .into())
                     } else {
                         // the transaction did revert
                         Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
@@ -507,8 +584,13 @@ impl<C: sov_modules_api::Context> Evm<C> {
             let mut tx_env = tx_env.clone();
             tx_env.gas_limit = mid_gas_limit;
 
-            let evm_db = self.get_db(working_set);
-            let result = executor::inspect(evm_db, &block_env, tx_env.clone(), cfg_env.clone());
+            let result = self.inspect_call(
+                &block_env,
+                tx_env.clone(),
+                cfg_env.clone(),
+                NoOpInspector,
+                working_set,
+            );
 
             // Exceptional case: init used too much gas, we need to increase the gas limit and try
             // again
@@ -557,6 +639,106 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(U64::from(highest_gas_limit))
     }
 
+    /// Handler for: `eth_createAccessList`
+    // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/call.rs#L338
+    #[rpc_method(name = "eth_createAccessList")]
+    pub fn create_access_list(
+        &self,
+        mut request: reth_rpc_types::CallRequest,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AccessListWithGasUsed> {
+        info!("evm module: eth_createAccessList");
+        let block_env = self.call_block_env(block_number.clone(), working_set);
+
+        let tx_env = prepare_call_env(&block_env, request.clone())?;
+
+        let cfg = self.cfg.get(working_set).unwrap_or_default();
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        let from = tx_env.caller;
+        let to = match tx_env.transact_to {
+            TransactTo::Call(to) => to,
+            TransactTo::Create(_) => {
+                let nonce = self
+                    .accounts
+                    .get(&from, working_set)
+                    .map(|account| account.info.nonce)
+                    .unwrap_or_default();
+                create_address(from, nonce)
+            }
+        };
+
+        // the sender, the recipient and the precompiles are always warm
+        let precompiles = Precompiles::new(PrecompileSpecId::from_spec_id(cfg_env.spec_id))
+            .addresses()
+            .into_iter()
+            .map(|address| Address::from(*address))
+            .chain([BANK_PRECOMPILE_ADDRESS, NFT_PRECOMPILE_ADDRESS]);
+        let mut inspector = AccessListInspector::new(
+            request.access_list.take().unwrap_or_default(),
+            from,
+            to,
+            precompiles,
+        );
+
+        let result = self
+            .inspect_call(&block_env, tx_env, cfg_env, &mut inspector, working_set)
+            .map_err(EthApiError::from)?;
+
+        match result.result {
+            ExecutionResult::Success { .. } => {}
+            ExecutionResult::Halt { reason, gas_used } => {
+                return Err(RpcInvalidTransactionError::halt(reason, gas_used).into())
+            }
+            ExecutionResult::Revert { output, .. } => {
+                return Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
+            }
+        }
+
+        // the gas used depends on the access list, so it is estimated with the access list
+        let access_list = inspector.into_access_list();
+        request.access_list = Some(access_list.clone());
+        let gas_used = self.eth_estimate_gas(request, block_number, working_set)?;
+
+        Ok(AccessListWithGasUsed {
+            access_list,
+            gas_used: U256::from(gas_used.as_u64()),
+        })
+    }
+
+    /// The environment `eth_call` and its variants execute calls in. A call is executed as the next
+    /// transaction of the block, so its priority fees go to the sequencer of the blob being applied
+    /// or, once the block is sealed, to the sequencer of its last transaction.
+    fn call_block_env(
+        &self,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> BlockEnv {
+        let (mut block_env, sequencer) = match block_number {
+            Some(ref block_number) if block_number == "pending" => (
+                self.block_env.get(working_set).unwrap_or_default(),
+                self.blob_sequencer.get(working_set),
+            ),
+            _ => {
+                let block = self.get_sealed_block_by_number(block_number, working_set);
+                (BlockEnv::from(&block), None)
+            }
+        };
+
+        let coinbase = match sequencer {
+            Some(sequencer) => Some(evm_address::<C>(&sequencer)),
+            None => self
+                .block_calls
+                .get(&block_env.number, &mut working_set.accessory_state())
+                .and_then(|calls| last_coinbase(&calls)),
+        };
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        block_env
+    }
+
     fn get_sealed_block_by_number(
         &self,
         block_number: Option<String>,
@@ -588,7 +770,249 @@ impl<C: sov_modules_api::Context> Evm<C> {
     }
 }
 
-fn get_cfg_env_template() -> revm::primitives::CfgEnv {
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// The nonce of an account for `eth_getTransactionCount`. The endpoint itself is served by
+    /// `sov-ethereum`, which adds the transactions of its pool for the `pending` block.
+    pub fn get_transaction_count(
+        &self,
+        address: reth_primitives::Address,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<reth_primitives::U64> {
+        info!("evm module: eth_getTransactionCount");
+
+        self.ensure_latest_block(block_number.as_deref(), working_set)?;
+
+        let nonce = self
+            .accounts
+            .get(&address, working_set)
+            .map(|account| account.info.nonce)
+            .unwrap_or_default();
+
+        Ok(nonce.into())
+    }
+
+    /// Fails unless `block_number` selects the latest state, which is the only one these queries
+    /// read by block: historical states are read through the `state_id` argument of the module
+    /// RPC methods instead.
+    fn ensure_latest_block(
+        &self,
+        block_number: Option<&str>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), EthApiError> {
+        let block_number = match block_number {
+            None | Some("latest") | Some("pending") => return Ok(()),
+            Some(block_number) => block_number,
+        };
+        let head = self
+            .blocks
+            .len(&mut working_set.accessory_state())
+            .checked_sub(1)
+            .map(|head| head as u64);
+        let is_head = match block_number {
+            "earliest" => head == Some(0),
+            number => {
+                let number = u64::from_str_radix(number.trim_start_matches("0x"), 16)
+                    .map_err(|e| EthApiError::InvalidParams(e.to_string()))?;
+                head == Some(number)
+            }
+        };
+        if is_head {
+            Ok(())
+        } else {
+            Err(EthApiError::Unsupported(
+                "Only the latest block can be queried by number, use the `state_id` argument to query a historical state",
+            ))
+        }
+    }
+
+    /// Returns the logs matching the `filter`, in the blocks selected by its block hash or range.
+    ///
+    /// As go-ethereum does, a range ending after the head block ends at the head block.
+    /// The range can't span more than [`MAX_BLOCKS_PER_LOG_QUERY`] blocks, and the query fails
+    /// if more than [`MAX_LOGS_PER_RESPONSE`] logs match.
+    pub fn logs_for_filter(
+        &self,
+        filter: &Filter,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Vec<reth_rpc_types::Log>, EthFilterError> {
+        let mut accessory_state = working_set.accessory_state();
+        let block_count = self.blocks.len(&mut accessory_state) as u64;
+        if block_count == 0 {
+            return Ok(vec![]);
+        }
+        let head = block_count - 1;
+
+        let block_numbers = match filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                let block_number = self
+                    .block_hashes
+                    .get(&block_hash, &mut accessory_state)
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                block_number..=block_number
+            }
+            FilterBlockOption::Range {
+                from_block,
+                to_block,
+            } => {
+                let from = resolve_block_number(from_block, head)?;
+                let to = resolve_block_number(to_block, head)?.min(head);
+                if from > head {
+                    return Ok(vec![]);
+                }
+                if from > to {
+                    return Err(EthApiError::InvalidBlockRange.into());
+                }
+                if to - from >= MAX_BLOCKS_PER_LOG_QUERY {
+                    return Err(EthFilterError::QueryExceedsMaxBlocks(
+                        MAX_BLOCKS_PER_LOG_QUERY,
+                    ));
+                }
+                from..=to
+            }
+        };
+
+        self.logs_in_blocks(filter, block_numbers, working_set)
+    }
+
+    /// Returns the logs matching the addresses and topics of the `filter` in the given blocks,
+    /// ignoring the block hash or range of the `filter`. The blocks which don't exist are skipped.
+    pub fn logs_in_blocks(
+        &self,
+        filter: &Filter,
+        block_numbers: RangeInclusive<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Vec<reth_rpc_types::Log>, EthFilterError> {
+        let mut accessory_state = working_set.accessory_state();
+        let params = FilteredParams::new(Some(filter.clone()));
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let mut logs = vec![];
+        for block_number in block_numbers {
+            let Some(bloom) = self
+                .logs_blooms
+                .get(block_number as usize, &mut accessory_state)
+            else {
+                break;
+            };
+            if !FilteredParams::matches_address(bloom, &address_filter)
+                || !FilteredParams::matches_topics(bloom, &topics_filter)
+            {
+                continue;
+            }
+
+            let block = self
+                .blocks
+                .get(block_number as usize, &mut accessory_state)
+                .expect("Block with a logs bloom must be set");
+            for tx_number in block.transactions.clone() {
+                let tx = self
+                    .transactions
+                    .get(tx_number as usize, &mut accessory_state)
+                    .expect("Transaction of a known block must be set");
+                let receipt = self
+                    .receipts
+                    .get(tx_number as usize, &mut accessory_state)
+                    .expect("Receipt of a known transaction must be set");
+
+                for (idx, log) in receipt.receipt.logs.into_iter().enumerate() {
+                    let log = build_rpc_log(
+                        &block,
+                        tx.signed_transaction.hash,
+                        tx_number - block.transactions.start,
+                        receipt.log_index_start + idx as u64,
+                        log,
+                    );
+                    if !params.filter_address(&log) || !params.filter_topics(&log) {
+                        continue;
+                    }
+                    if logs.len() == MAX_LOGS_PER_RESPONSE {
+                        return Err(EthFilterError::QueryExceedsMaxResults(
+                            MAX_LOGS_PER_RESPONSE,
+                        ));
+                    }
+                    logs.push(log);
+                }
+            }
+        }
+
+        Ok(logs)
+    }
+
+    fn map_out_of_gas_err(
+        &self,
+        block_env: BlockEnv,
+        mut tx_env: revm::primitives::TxEnv,
+        cfg_env: revm::primitives::CfgEnv,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthApiError {
+        let req_gas_limit = tx_env.gas_limit;
+        tx_env.gas_limit = block_env.gas_limit;
+        let res = self
+            .inspect_call(&block_env, tx_env, cfg_env, NoOpInspector, working_set)
+            .unwrap();
+        match res.result {
+            ExecutionResult::Success { .. } => {
+                // transaction succeeded by manually increasing the gas limit to
+                // highest, which means the caller lacks funds to pay for the tx
+                RpcInvalidTransactionError::BasicOutOfGas(U256::from(req_gas_limit)).into()
+            }
+            ExecutionResult::Revert { output, .. } => {
+                // reverted again after bumping the limit
+                RpcInvalidTransactionError::Revert(RevertError::new(output)).into()
+            }
+            ExecutionResult::Halt { reason, .. } => {
+                RpcInvalidTransactionError::EvmHalt(reason).into()
+            }
+        }
+    }
+}
+
+/// The priority fees per gas at the given percentiles of the gas used in a block, from its
+/// transactions sorted by priority fee, as `(priority fee per gas, gas used)`.
+// modified from: https://github.com/paradigmxyz/reth/blob/cc576bc8690a3e16e6e5bf1cbbbfdd029e85e3d4/crates/rpc/rpc/src/eth/api/fees.rs#L166
+fn reward_percentiles_of_block(
+    transactions: &[(u128, u64)],
+    block_gas_used: u64,
+    percentiles: &[f64],
+) -> Vec<U256> {
+    let Some(&(_, first_gas_used)) = transactions.first() else {
+        return vec![U256::ZERO; percentiles.len()];
+    };
+
+    let mut tx_index = 0;
+    let mut cumulative_gas_used = first_gas_used;
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = (block_gas_used as f64 * percentile / 100.0) as u64;
+            while cumulative_gas_used < threshold && tx_index < transactions.len() - 1 {
+                tx_index += 1;
+                cumulative_gas_used += transactions[tx_index].1;
+            }
+            U256::from(transactions[tx_index].0)
+        })
+        .collect()
+}
+
+/// Resolves the number of a block of a query, `None` standing for the head block.
+pub(crate) fn resolve_block_number(
+    block: Option<BlockNumberOrTag>,
+    head: u64,
+) -> Result<u64, EthApiError> {
+    match block {
+        None | Some(BlockNumberOrTag::Latest) | Some(BlockNumberOrTag::Pending) => Ok(head),
+        Some(BlockNumberOrTag::Earliest) => Ok(0),
+        Some(BlockNumberOrTag::Number(number)) => Ok(number),
+        // safe and finalized are not supported
+        Some(BlockNumberOrTag::Safe) | Some(BlockNumberOrTag::Finalized) => {
+            Err(EthApiError::UnknownSafeOrFinalizedBlock)
+        }
+    }
+}
+
+pub(crate) fn get_cfg_env_template() -> revm::primitives::CfgEnv {
     let mut cfg_env = revm::primitives::CfgEnv::default();
     // Reth sets this to true and uses only timeout, but other clients use this as a part of DOS attacks protection, with 100mln gas limit
     // https://github.com/paradigmxyz/reth/blob/62f39a5a151c5f4ddc9bf0851725923989df0412/crates/rpc/rpc/src/eth/revm_utils.rs#L215
@@ -654,41 +1078,36 @@ pub(crate) fn build_rpc_receipt(
             .logs
             .into_iter()
             .enumerate()
-            .map(|(idx, log)| reth_rpc_types::Log {
-                address: log.address,
-                topics: log.topics,
-                data: log.data,
-                block_hash,
-                block_number,
-                transaction_hash,
-                transaction_index: Some(U256::from(transaction_index)),
-                log_index: Some(U256::from(receipt.log_index_start + idx as u64)),
-                removed: false,
+            .map(|(idx, log)| {
+                build_rpc_log(
+                    &block,
+                    transaction.hash,
+                    transaction_index,
+                    receipt.log_index_start + idx as u64,
+                    log,
+                )
             })
             .collect(),
     }
 }
 
-fn map_out_of_gas_err<C: sov_modules_api::Context>(
-    block_env: BlockEnv,
-    mut tx_env: revm::primitives::TxEnv,
-    cfg_env: revm::primitives::CfgEnv,
-    db: EvmDb<'_, C>,
-) -> EthApiError {
-    let req_gas_limit = tx_env.gas_limit;
-    tx_env.gas_limit = block_env.gas_limit;
-    let res = executor::inspect(db, &block_env, tx_env, cfg_env).unwrap();
-    match res.result {
-        ExecutionResult::Success { .. } => {
-            // transaction succeeded by manually increasing the gas limit to
-            // highest, which means the caller lacks funds to pay for the tx
-            RpcInvalidTransactionError::BasicOutOfGas(U256::from(req_gas_limit)).into()
-        }
-        ExecutionResult::Revert { output, .. } => {
-            // reverted again after bumping the limit
-            RpcInvalidTransactionError::Revert(RevertError::new(output)).into()
-        }
-        ExecutionResult::Halt { reason, .. } => RpcInvalidTransactionError::EvmHalt(reason).into(),
+pub(crate) fn build_rpc_log(
+    block: &SealedBlock,
+    transaction_hash: H256,
+    transaction_index: u64,
+    log_index: u64,
+    log: reth_primitives::Log,
+) -> reth_rpc_types::Log {
+    reth_rpc_types::Log {
+        address: log.address,
+        topics: log.topics,
+        data: log.data,
+        block_hash: Some(block.header.hash),
+        block_number: Some(U256::from(block.header.number)),
+        transaction_hash: Some(transaction_hash),
+        transaction_index: Some(U256::from(transaction_index)),
+        log_index: Some(U256::from(log_index)),
+        removed: false,
     }
 }
 
@@ -0,0 +1,147 @@
+use reth_primitives::contract::create_address;
+use reth_primitives::{AccessList, AccessListItem, Address, Bytes, TransactionKind, H256};
+use reth_rpc_types::CallRequest;
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, StateVecAccessor, WorkingSet};
+
+use crate::call::CallMessage;
+use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::genesis_tests::get_evm;
+use crate::tests::logs_tests::{produce_block, sign_message};
+use crate::tests::test_signer::TestSigner;
+use crate::{AccountData, Evm, EvmConfig};
+type C = DefaultContext;
+
+const SET_ARG: u32 = 999;
+
+fn config(dev_signer: &TestSigner, spec: SpecId) -> EvmConfig<C> {
+    EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, spec)].into_iter().collect(),
+        ..Default::default()
+    }
+}
+
+/// Produces a block deploying the simple storage contract.
+fn setup(dev_signer: &TestSigner) -> (Evm<C>, WorkingSet<C>, Address) {
+    let (evm, mut working_set) = get_evm(&config(dev_signer, SpecId::SHANGHAI));
+    let contract = SimpleStorageContract::default();
+    produce_block(
+        &evm,
+        vec![sign_message(
+            dev_signer,
+            TransactionKind::Create,
+            contract.byte_code().to_vec(),
+            0,
+        )],
+        &mut working_set,
+    );
+
+    (evm, working_set, create_address(dev_signer.address(), 0))
+}
+
+fn storage_access_list(contract_addr: Address) -> AccessList {
+    AccessList(vec![AccessListItem {
+        address: contract_addr,
+        storage_keys: vec![H256::zero()],
+    }])
+}
+
+/// Sets the stored value on a freshly deployed contract and returns the gas used.
+fn set_gas_used(access_list: impl FnOnce(Address) -> AccessList) -> u64 {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, contract_addr) = setup(&dev_signer);
+
+    let tx = dev_signer
+        .sign_transaction_with_access_list(
+            TransactionKind::Call(contract_addr),
+            SimpleStorageContract::default()
+                .set_call_data(SET_ARG)
+                .to_vec(),
+            access_list(contract_addr),
+            1,
+        )
+        .unwrap();
+    produce_block(&evm, vec![CallMessage::Transact { tx }], &mut working_set);
+
+    let receipt = evm
+        .receipts
+        .last(&mut working_set.accessory_state())
+        .unwrap();
+    assert!(receipt.receipt.success);
+    receipt.gas_used
+}
+
+#[test]
+fn access_lists_warm_their_storage_slots() {
+    let without_list = set_gas_used(|_| AccessList::default());
+    let with_list = set_gas_used(storage_access_list);
+
+    // The list costs 2400 for the address and 1900 for the slot,
+    // and saves the 2100 of the cold storage access.
+    assert_eq!(with_list, without_list + 2400 + 1900 - 2100);
+}
+
+#[test]
+fn transaction_types_are_checked_against_the_spec() {
+    let dev_signer = TestSigner::new_random();
+    // EIP-1559 transactions are only supported from London on.
+    let (evm, mut working_set) = get_evm(&config(&dev_signer, SpecId::BERLIN));
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+
+    let context = C::new(
+        generate_address::<C>("sender"),
+        generate_address::<C>("sequencer"),
+        1,
+    );
+    let message = sign_message(
+        &dev_signer,
+        TransactionKind::Create,
+        SimpleStorageContract::default().byte_code().to_vec(),
+        0,
+    );
+    assert!(evm.call(message, &context, &mut working_set).is_err());
+    assert!(evm.pending_transactions.last(&mut working_set).is_none());
+}
+
+#[test]
+fn created_access_lists_contain_the_accessed_slots() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, contract_addr) = setup(&dev_signer);
+
+    let request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: Some(Bytes::from(
+            SimpleStorageContract::default()
+                .set_call_data(SET_ARG)
+                .to_vec(),
+        ))
+        .into(),
+        ..Default::default()
+    };
+    let created = evm
+        .create_access_list(request.clone(), None, &mut working_set)
+        .unwrap();
+    assert_eq!(created.access_list, storage_access_list(contract_addr));
+
+    let estimated = evm
+        .eth_estimate_gas(
+            CallRequest {
+                access_list: Some(created.access_list),
+                ..request
+            },
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(created.gas_used, U256::from(estimated.as_u64()));
+}
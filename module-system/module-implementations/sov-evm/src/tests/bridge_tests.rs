@@ -0,0 +1,355 @@
+use reth_primitives::{keccak256, Address, Bytes, TransactionKind, U256};
+use reth_rpc_types::CallRequest;
+use secp256k1::SecretKey;
+use sov_bank::{get_genesis_token_address, BankConfig, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::prelude::*;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+use crate::call::CallMessage;
+use crate::tests::logs_tests::produce_block;
+use crate::tests::test_signer::TestSigner;
+use crate::{evm_address, rollup_address, AccountData, BridgeConfig, Erc20Token, Evm, EvmConfig};
+type C = DefaultContext;
+
+const INITIAL_BALANCE: u64 = 1_000_000_000;
+const NATIVE_BRIDGE: Address = Address::repeat_byte(0xb0);
+const ERC20_CONTRACT: Address = Address::repeat_byte(0xe2);
+
+struct Setup {
+    evm: Evm<C>,
+    working_set: WorkingSet<C>,
+    context: C,
+    native_token: <C as Spec>::Address,
+    erc20_token: <C as Spec>::Address,
+}
+
+/// Creates a native token and another token owned by the sender, both bridged to the EVM.
+fn setup() -> Setup {
+    let sender = generate_address::<C>("bridge_user");
+    let token = |token_name: &str| TokenConfig {
+        token_name: token_name.to_owned(),
+        address_and_balances: vec![(sender, INITIAL_BALANCE)],
+        authorized_minters: vec![],
+        salt: 0,
+    };
+    let bank_config = BankConfig {
+        tokens: vec![token("native"), token("erc20")],
+    };
+    let native_token = get_genesis_token_address::<C>("native", 0);
+    let erc20_token = get_genesis_token_address::<C>("erc20", 0);
+
+    let config = EvmConfig {
+        bridge: Some(BridgeConfig {
+            native_token_address: native_token,
+            native_bridge_address: NATIVE_BRIDGE,
+            erc20_tokens: vec![Erc20Token {
+                token_address: erc20_token,
+                contract_address: ERC20_CONTRACT,
+            }],
+        }),
+        ..Default::default()
+    };
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let evm = Evm::<C>::default();
+    evm.bank.genesis(&bank_config, &mut working_set).unwrap();
+    evm.genesis(&config, &mut working_set).unwrap();
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+
+    Setup {
+        evm,
+        working_set,
+        context: C::new(sender, generate_address::<C>("sequencer"), 1),
+        native_token,
+        erc20_token,
+    }
+}
+
+impl Setup {
+    fn deposit(&mut self, token: <C as Spec>::Address, to: Address, amount: u64) {
+        let message = CallMessage::Deposit {
+            token_address: token,
+            to: to.0,
+            amount,
+        };
+        self.evm
+            .call(message, &self.context, &mut self.working_set)
+            .unwrap();
+    }
+
+    fn withdraw(&mut self, token: <C as Spec>::Address, amount: u64) -> anyhow::Result<()> {
+        let message = CallMessage::Withdraw {
+            token_address: token,
+            amount,
+        };
+        self.evm
+            .call(message, &self.context, &mut self.working_set)
+            .map(|_| ())
+            .map_err(Into::into)
+    }
+
+    fn bank_balance(&mut self, owner: <C as Spec>::Address, token: <C as Spec>::Address) -> u64 {
+        self.evm
+            .bank
+            .get_balance_of(owner, token, &mut self.working_set)
+            .unwrap_or_default()
+    }
+
+    fn evm_balance(&mut self, address: Address) -> U256 {
+        self.evm
+            .accounts
+            .get(&address, &mut self.working_set)
+            .map(|account| account.info.balance)
+            .unwrap_or_default()
+    }
+
+    /// The balance of the EVM account in the ERC-20 precompile, held by its rollup address.
+    fn erc20_balance(&mut self, address: Address) -> u64 {
+        self.bank_balance(rollup_address::<C>(address), self.erc20_token)
+    }
+
+    /// Calls the ERC-20 precompile without committing the call, and returns the output.
+    fn call_erc20(&mut self, from: Address, input: Vec<u8>) -> U256 {
+        let request = CallRequest {
+            from: Some(from),
+            to: Some(ERC20_CONTRACT),
+            input: Some(Bytes::from(input)).into(),
+            ..Default::default()
+        };
+        let output = self
+            .evm
+            .get_call(request, None, None, None, &mut self.working_set)
+            .unwrap();
+        U256::try_from_be_slice(&output).unwrap()
+    }
+}
+
+fn calldata(signature: &str, args: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = keccak256(signature)[..4].to_vec();
+    for arg in args {
+        data.extend_from_slice(arg);
+    }
+    data
+}
+
+fn word(address: Address) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[12..].copy_from_slice(address.as_bytes());
+    word
+}
+
+#[test]
+fn deposits_and_withdrawals_conserve_supply() {
+    let mut setup = setup();
+    let sender = *setup.context.sender();
+    let evm_sender = evm_address::<C>(&sender);
+    let (native, erc20, module) = (setup.native_token, setup.erc20_token, setup.evm.address);
+
+    setup.deposit(native, evm_sender, 1000);
+    setup.deposit(erc20, evm_sender, 50);
+    assert_eq!(setup.evm_balance(evm_sender), U256::from(1000));
+    assert_eq!(setup.erc20_balance(evm_sender), 50);
+    assert_eq!(setup.bank_balance(module, native), 1000);
+    assert_eq!(setup.bank_balance(module, erc20), 0);
+    assert_eq!(setup.bank_balance(sender, native), INITIAL_BALANCE - 1000);
+
+    setup.withdraw(native, 400).unwrap();
+    setup.withdraw(erc20, 20).unwrap();
+    assert_eq!(setup.evm_balance(evm_sender), U256::from(600));
+    assert_eq!(setup.erc20_balance(evm_sender), 30);
+    assert_eq!(setup.bank_balance(module, native), 600);
+    assert_eq!(setup.bank_balance(sender, native), INITIAL_BALANCE - 600);
+    assert_eq!(setup.bank_balance(sender, erc20), INITIAL_BALANCE - 30);
+
+    assert!(setup.withdraw(native, 601).is_err());
+    assert!(setup.withdraw(erc20, 31).is_err());
+    let unknown_token = generate_address::<C>("unknown");
+    assert!(setup.withdraw(unknown_token, 1).is_err());
+}
+
+#[test]
+fn genesis_balances_must_be_backed_by_the_bridge() {
+    let native_token = get_genesis_token_address::<C>("native", 0);
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: Address::repeat_byte(0x01),
+            balance: AccountData::balance(1000),
+            code_hash: AccountData::empty_code(),
+            code: Default::default(),
+            nonce: 0,
+        }],
+        bridge: Some(BridgeConfig {
+            native_token_address: native_token,
+            native_bridge_address: NATIVE_BRIDGE,
+            erc20_tokens: vec![],
+        }),
+        ..Default::default()
+    };
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let evm = Evm::<C>::default();
+    assert!(evm.genesis(&config, &mut working_set).is_err());
+}
+
+#[test]
+fn withdrawals_from_the_evm_are_settled() {
+    let mut setup = setup();
+    let dev_signer = TestSigner::new_random();
+    let other = Address::repeat_byte(0x0f);
+    let recipient = generate_address::<C>("recipient");
+    let (native, erc20, module) = (setup.native_token, setup.erc20_token, setup.evm.address);
+
+    setup.deposit(native, dev_signer.address(), 100_000_000);
+    setup.deposit(erc20, dev_signer.address(), 50);
+
+    let recipient_word: [u8; 32] = recipient.as_ref().try_into().unwrap();
+    let messages = vec![
+        dev_signer
+            .sign_transaction_with_value(
+                TransactionKind::Call(NATIVE_BRIDGE),
+                calldata("withdraw(bytes32)", &[recipient_word]),
+                30_000,
+                0,
+            )
+            .unwrap(),
+        dev_signer
+            .sign_default_transaction(
+                TransactionKind::Call(ERC20_CONTRACT),
+                calldata(
+                    "transfer(address,uint256)",
+                    &[word(other), U256::from(10).to_be_bytes()],
+                ),
+                1,
+            )
+            .unwrap(),
+        dev_signer
+            .sign_default_transaction(
+                TransactionKind::Call(ERC20_CONTRACT),
+                calldata(
+                    "withdraw(bytes32,uint256)",
+                    &[recipient_word, U256::from(15).to_be_bytes()],
+                ),
+                2,
+            )
+            .unwrap(),
+    ];
+    produce_block(
+        &setup.evm,
+        messages
+            .into_iter()
+            .map(|tx| CallMessage::Transact { tx })
+            .collect(),
+        &mut setup.working_set,
+    );
+
+    let mut accessory_state = setup.working_set.accessory_state();
+    for index in 0..3 {
+        let receipt = setup.evm.receipts.get(index, &mut accessory_state).unwrap();
+        assert!(receipt.receipt.success);
+    }
+
+    // The tokens backing the burned base fees are burned too.
+    let block = setup.evm.blocks.get(1usize, &mut accessory_state).unwrap();
+    let burned = block.header.base_fee_per_gas.unwrap() * block.header.gas_used;
+    assert_eq!(setup.bank_balance(recipient, native), 30_000);
+    assert_eq!(
+        setup.bank_balance(module, native),
+        100_000_000 - 30_000 - burned
+    );
+    assert_eq!(setup.evm_balance(NATIVE_BRIDGE), U256::ZERO);
+
+    assert_eq!(setup.bank_balance(recipient, erc20), 15);
+    assert_eq!(setup.erc20_balance(dev_signer.address()), 25);
+    assert_eq!(setup.erc20_balance(other), 10);
+    assert_eq!(
+        setup.call_erc20(
+            other,
+            calldata("balanceOf(address)", &[word(dev_signer.address())])
+        ),
+        U256::from(25)
+    );
+}
+
+#[test]
+fn erc20_precompiles_spend_allowances() {
+    let mut setup = setup();
+    let owner = TestSigner::new_random();
+    let spender = TestSigner::new(SecretKey::from_slice(&[7; 32]).unwrap());
+    let other = Address::repeat_byte(0x0f);
+    let (native, erc20) = (setup.native_token, setup.erc20_token);
+
+    setup.deposit(native, owner.address(), 100_000_000);
+    setup.deposit(native, spender.address(), 100_000_000);
+    setup.deposit(erc20, owner.address(), 50);
+
+    let transfer_from = |value: u64, nonce: u64| {
+        spender
+            .sign_default_transaction(
+                TransactionKind::Call(ERC20_CONTRACT),
+                calldata(
+                    "transferFrom(address,address,uint256)",
+                    &[
+                        word(owner.address()),
+                        word(other),
+                        U256::from(value).to_be_bytes(),
+                    ],
+                ),
+                nonce,
+            )
+            .unwrap()
+    };
+    let messages = vec![
+        owner
+            .sign_default_transaction(
+                TransactionKind::Call(ERC20_CONTRACT),
+                calldata(
+                    "approve(address,uint256)",
+                    &[word(spender.address()), U256::from(20).to_be_bytes()],
+                ),
+                0,
+            )
+            .unwrap(),
+        transfer_from(15, 0),
+        // Exceeds the remaining allowance.
+        transfer_from(10, 1),
+    ];
+    produce_block(
+        &setup.evm,
+        messages
+            .into_iter()
+            .map(|tx| CallMessage::Transact { tx })
+            .collect(),
+        &mut setup.working_set,
+    );
+
+    let mut accessory_state = setup.working_set.accessory_state();
+    let succeeded: Vec<_> = (0..3)
+        .map(|index| {
+            let receipt = setup.evm.receipts.get(index, &mut accessory_state).unwrap();
+            receipt.receipt.success
+        })
+        .collect();
+    assert_eq!(succeeded, vec![true, true, false]);
+
+    assert_eq!(setup.erc20_balance(owner.address()), 35);
+    assert_eq!(setup.erc20_balance(other), 15);
+    assert_eq!(
+        setup.call_erc20(
+            other,
+            calldata(
+                "allowance(address,address)",
+                &[word(owner.address()), word(spender.address())]
+            )
+        ),
+        U256::from(5)
+    );
+    assert_eq!(
+        setup.call_erc20(other, calldata("totalSupply()", &[])),
+        U256::from(INITIAL_BALANCE)
+    );
+}
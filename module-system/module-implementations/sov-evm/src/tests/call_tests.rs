@@ -1,15 +1,18 @@
+use reth_primitives::contract::create_address;
 use reth_primitives::{Address, Bytes, TransactionKind};
 use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address;
-use sov_modules_api::{Context, Module, StateMapAccessor, StateValueAccessor, StateVecAccessor};
+use sov_modules_api::{
+    Context, Module, StateMapAccessor, StateValueAccessor, StateVecAccessor, WorkingSet,
+};
 
 use crate::call::CallMessage;
 use crate::evm::primitive_types::Receipt;
 use crate::smart_contracts::SimpleStorageContract;
 use crate::tests::genesis_tests::get_evm;
 use crate::tests::test_signer::TestSigner;
-use crate::{AccountData, EvmConfig};
+use crate::{AccountData, Evm, EvmConfig};
 type C = DefaultContext;
 
 #[test]
@@ -123,7 +126,262 @@ fn failed_transaction_test() {
     assert_eq!(block.transactions.end, 0);
 }
 
-fn create_contract_message(dev_signer: &TestSigner, nonce: u64) -> CallMessage {
+/// Deploys a contract which stores the first word of the call data in slot 0,
+/// and self-destructs, sending its balance to the caller, when called without data.
+const DESTRUCTIBLE_INIT_CODE: &str = "600e600c600039600e6000f33660065733ff5b60003560005500";
+
+/// Self-destructs while deploying the contract, sending its balance to the caller.
+const SELFDESTRUCTING_INIT_CODE: &str = "33ff";
+
+/// Deploys a contract which stores the hash of the block whose number is the first word
+/// of the call data in slot 0.
+pub(crate) const BLOCK_HASH_INIT_CODE: &str = "6008600c60003960086000f36000354060005500";
+
+#[test]
+fn selfdestruct_removes_account_and_storage() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let (evm, mut working_set) = get_evm(&funded_config(&dev_signer));
+    let contract_addr = create_address(dev_signer.address(), 0);
+
+    produce_block(
+        &evm,
+        vec![
+            raw_message(
+                &dev_signer,
+                TransactionKind::Create,
+                DESTRUCTIBLE_INIT_CODE,
+                0,
+            ),
+            call_message(&dev_signer, contract_addr, U256::from(7), 1),
+        ],
+        &mut working_set,
+    );
+
+    let db_account = evm.accounts.get(&contract_addr, &mut working_set).unwrap();
+    assert_eq!(
+        db_account.storage.get(&U256::ZERO, &mut working_set),
+        Some(U256::from(7))
+    );
+
+    produce_block(
+        &evm,
+        vec![raw_message(
+            &dev_signer,
+            TransactionKind::Call(contract_addr),
+            "",
+            2,
+        )],
+        &mut working_set,
+    );
+
+    assert!(all_receipts_succeeded(&evm, &mut working_set));
+    assert_eq!(evm.accounts.get(&contract_addr, &mut working_set), None);
+    // The storage of the destroyed account is left behind, apart from the next incarnation.
+    let next_incarnation = evm.get_or_create_account(contract_addr, &mut working_set);
+    assert_ne!(next_incarnation.storage, db_account.storage);
+    assert_eq!(
+        next_incarnation.storage.get(&U256::ZERO, &mut working_set),
+        None
+    );
+}
+
+#[test]
+fn selfdestruct_keeps_existing_account_after_cancun() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        spec: vec![(0, SpecId::CANCUN)].into_iter().collect(),
+        ..funded_config(&dev_signer)
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    let contract_addr = create_address(dev_signer.address(), 0);
+
+    produce_block(
+        &evm,
+        vec![
+            raw_message(
+                &dev_signer,
+                TransactionKind::Create,
+                DESTRUCTIBLE_INIT_CODE,
+                0,
+            ),
+            call_message(&dev_signer, contract_addr, U256::from(7), 1),
+        ],
+        &mut working_set,
+    );
+    let code_hash = evm
+        .accounts
+        .get(&contract_addr, &mut working_set)
+        .unwrap()
+        .info
+        .code_hash;
+
+    produce_block(
+        &evm,
+        vec![raw_message(
+            &dev_signer,
+            TransactionKind::Call(contract_addr),
+            "",
+            2,
+        )],
+        &mut working_set,
+    );
+
+    // EIP-6780: the contract wasn't created in the same transaction, so it keeps its code and storage.
+    assert!(all_receipts_succeeded(&evm, &mut working_set));
+    let db_account = evm.accounts.get(&contract_addr, &mut working_set).unwrap();
+    assert_eq!(db_account.info.code_hash, code_hash);
+    assert_eq!(
+        db_account.storage.get(&U256::ZERO, &mut working_set),
+        Some(U256::from(7))
+    );
+    assert_eq!(
+        evm.account_incarnations
+            .get(&contract_addr, &mut working_set),
+        None
+    );
+}
+
+#[test]
+fn selfdestruct_removes_account_created_in_same_transaction_after_cancun() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        spec: vec![(0, SpecId::CANCUN)].into_iter().collect(),
+        ..funded_config(&dev_signer)
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    let contract_addr = create_address(dev_signer.address(), 0);
+
+    // The init code self-destructs, sending the balance to the caller.
+    produce_block(
+        &evm,
+        vec![raw_message(
+            &dev_signer,
+            TransactionKind::Create,
+            SELFDESTRUCTING_INIT_CODE,
+            0,
+        )],
+        &mut working_set,
+    );
+
+    assert!(all_receipts_succeeded(&evm, &mut working_set));
+    assert_eq!(evm.accounts.get(&contract_addr, &mut working_set), None);
+}
+
+#[test]
+fn blockhash_returns_latest_block_hashes() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let (evm, mut working_set) = get_evm(&funded_config(&dev_signer));
+    let contract_addr = create_address(dev_signer.address(), 0);
+
+    produce_block(
+        &evm,
+        vec![raw_message(
+            &dev_signer,
+            TransactionKind::Create,
+            BLOCK_HASH_INIT_CODE,
+            0,
+        )],
+        &mut working_set,
+    );
+    produce_block(&evm, vec![], &mut working_set);
+    // Block 3 reads the hash of block 1.
+    produce_block(
+        &evm,
+        vec![call_message(&dev_signer, contract_addr, U256::from(1), 1)],
+        &mut working_set,
+    );
+
+    let block_hash = evm
+        .blocks
+        .get(1usize, &mut working_set.accessory_state())
+        .unwrap()
+        .header
+        .hash;
+    let db_account = evm.accounts.get(&contract_addr, &mut working_set).unwrap();
+    assert_eq!(
+        db_account.storage.get(&U256::ZERO, &mut working_set),
+        Some(U256::from_be_bytes(block_hash.0))
+    );
+
+    // The hash of the current block is unknown.
+    produce_block(
+        &evm,
+        vec![call_message(&dev_signer, contract_addr, U256::from(4), 2)],
+        &mut working_set,
+    );
+    assert_eq!(
+        db_account.storage.get(&U256::ZERO, &mut working_set),
+        Some(U256::ZERO)
+    );
+    assert!(all_receipts_succeeded(&evm, &mut working_set));
+}
+
+fn funded_config(dev_signer: &TestSigner) -> EvmConfig<C> {
+    EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    }
+}
+
+/// Executes the messages in a block, using the same state root for the block hash in
+/// the provable state and in the accessory state.
+fn produce_block(evm: &Evm<C>, messages: Vec<CallMessage<C>>, working_set: &mut WorkingSet<C>) {
+    let state_root = [10u8; 32].into();
+    evm.begin_slot_hook([5u8; 32], &state_root, working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+        for message in messages {
+            evm.call(message, &context, working_set).unwrap();
+        }
+    }
+    evm.end_slot_hook(working_set);
+    evm.finalize_hook(&state_root, &mut working_set.accessory_state());
+}
+
+fn all_receipts_succeeded(evm: &Evm<C>, working_set: &mut WorkingSet<C>) -> bool {
+    evm.receipts
+        .iter(&mut working_set.accessory_state())
+        .all(|receipt| receipt.receipt.success)
+}
+
+fn raw_message(
+    dev_signer: &TestSigner,
+    to: TransactionKind,
+    data: &str,
+    nonce: u64,
+) -> CallMessage<C> {
+    let signed_tx = dev_signer
+        .sign_default_transaction(to, hex::decode(data).unwrap(), nonce)
+        .unwrap();
+    CallMessage::Transact { tx: signed_tx }
+}
+
+fn call_message(
+    dev_signer: &TestSigner,
+    contract_addr: Address,
+    arg: U256,
+    nonce: u64,
+) -> CallMessage<C> {
+    let signed_tx = dev_signer
+        .sign_default_transaction(
+            TransactionKind::Call(contract_addr),
+            arg.to_be_bytes::<32>().to_vec(),
+            nonce,
+        )
+        .unwrap();
+    CallMessage::Transact { tx: signed_tx }
+}
+
+fn create_contract_message(dev_signer: &TestSigner, nonce: u64) -> CallMessage<C> {
     let contract = SimpleStorageContract::default();
     let signed_tx = dev_signer
         .sign_default_transaction(
@@ -132,7 +390,7 @@ fn create_contract_message(dev_signer: &TestSigner, nonce: u64) -> CallMessage {
             nonce,
         )
         .unwrap();
-    CallMessage { tx: signed_tx }
+    CallMessage::Transact { tx: signed_tx }
 }
 
 fn set_arg_message(
@@ -140,7 +398,7 @@ fn set_arg_message(
     dev_signer: &TestSigner,
     nonce: u64,
     set_arg: u32,
-) -> CallMessage {
+) -> CallMessage<C> {
     let contract = SimpleStorageContract::default();
     let signed_tx = dev_signer
         .sign_default_transaction(
@@ -150,5 +408,5 @@ fn set_arg_message(
         )
         .unwrap();
 
-    CallMessage { tx: signed_tx }
+    CallMessage::Transact { tx: signed_tx }
 }
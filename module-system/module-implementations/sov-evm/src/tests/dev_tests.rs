@@ -0,0 +1,205 @@
+use reth_primitives::{
+    keccak256, Address, Bytes, Transaction, TransactionKind, TxEip1559, KECCAK_EMPTY, U256,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::prelude::*;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, Spec, WorkingSet};
+
+use crate::call::CallMessage;
+use crate::tests::genesis_tests::get_evm;
+use crate::{DevCallMessage, Evm, EvmConfig, RlpEvmTransaction};
+type C = DefaultContext;
+
+const ACCOUNT: Address = Address::repeat_byte(0xaa);
+const RECIPIENT: Address = Address::repeat_byte(0x11);
+// PUSH1 0x2a PUSH1 0x00 SSTORE STOP
+const CODE: [u8; 6] = [0x60, 0x2a, 0x60, 0x00, 0x55, 0x00];
+
+fn setup(dev_authority: Option<<C as Spec>::Address>) -> (Evm<C>, WorkingSet<C>) {
+    get_evm(&EvmConfig {
+        dev_authority,
+        ..Default::default()
+    })
+}
+
+fn authority() -> C {
+    C::new(
+        generate_address::<C>("dev_authority"),
+        generate_address::<C>("sequencer"),
+        1,
+    )
+}
+
+fn dev_call(
+    evm: &Evm<C>,
+    call: DevCallMessage,
+    context: &C,
+    working_set: &mut WorkingSet<C>,
+) -> anyhow::Result<()> {
+    evm.call(CallMessage::Dev { call }, context, working_set)
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+fn word(value: u64) -> [u8; 32] {
+    U256::from(value).to_be_bytes()
+}
+
+#[test]
+fn dev_calls_set_balances_code_and_storage() {
+    let context = authority();
+    let (evm, mut working_set) = setup(Some(*context.sender()));
+
+    for call in [
+        DevCallMessage::SetBalance {
+            address: ACCOUNT.0,
+            balance: word(1_000),
+        },
+        DevCallMessage::SetCode {
+            address: ACCOUNT.0,
+            code: CODE.to_vec(),
+        },
+        DevCallMessage::SetStorageAt {
+            address: ACCOUNT.0,
+            slot: word(1),
+            value: word(42),
+        },
+    ] {
+        dev_call(&evm, call, &context, &mut working_set).unwrap();
+    }
+
+    let account = evm.accounts.get(&ACCOUNT, &mut working_set).unwrap();
+    assert_eq!(account.info.balance, U256::from(1_000));
+    assert_eq!(account.info.code_hash, keccak256(CODE));
+    assert_eq!(
+        evm.code.get(&keccak256(CODE), &mut working_set),
+        Some(Bytes::from(CODE.to_vec()))
+    );
+    assert_eq!(
+        account.storage.get(&U256::from(1), &mut working_set),
+        Some(U256::from(42))
+    );
+
+    // Empty code removes the code of the account.
+    dev_call(
+        &evm,
+        DevCallMessage::SetCode {
+            address: ACCOUNT.0,
+            code: vec![],
+        },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+    let account = evm.accounts.get(&ACCOUNT, &mut working_set).unwrap();
+    assert_eq!(account.info.code_hash, KECCAK_EMPTY);
+    assert_eq!(account.info.balance, U256::from(1_000));
+}
+
+#[test]
+fn dev_calls_are_only_accepted_from_the_dev_authority() {
+    let set_balance = DevCallMessage::SetBalance {
+        address: ACCOUNT.0,
+        balance: word(1_000),
+    };
+    let other = C::new(
+        generate_address::<C>("other"),
+        generate_address::<C>("sequencer"),
+        1,
+    );
+
+    let (evm, mut working_set) = setup(Some(*authority().sender()));
+    assert!(dev_call(&evm, set_balance.clone(), &other, &mut working_set).is_err());
+
+    // Chains without a dev authority reject them all.
+    let (evm, mut working_set) = setup(None);
+    assert!(dev_call(&evm, set_balance, &authority(), &mut working_set).is_err());
+    assert!(evm.accounts.get(&ACCOUNT, &mut working_set).is_none());
+}
+
+#[test]
+fn impersonated_accounts_send_transactions_without_signatures() {
+    let context = authority();
+    let (evm, mut working_set) = setup(Some(*context.sender()));
+    let transfer = RlpEvmTransaction::unsigned(Transaction::Eip1559(TxEip1559 {
+        chain_id: 1,
+        nonce: 0,
+        gas_limit: 21_000,
+        max_fee_per_gas: u128::from(reth_primitives::constants::MIN_PROTOCOL_BASE_FEE * 2),
+        to: TransactionKind::Call(RECIPIENT),
+        value: 100,
+        ..Default::default()
+    }));
+
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+    dev_call(
+        &evm,
+        DevCallMessage::SetBalance {
+            address: ACCOUNT.0,
+            balance: word(1_000_000_000),
+        },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+    dev_call(
+        &evm,
+        DevCallMessage::Impersonate {
+            from: ACCOUNT.0,
+            tx: transfer.clone(),
+        },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+    // The unsigned transaction can't be sent as is.
+    assert!(evm
+        .call(
+            CallMessage::Transact { tx: transfer },
+            &context,
+            &mut working_set
+        )
+        .is_err());
+    evm.end_slot_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let recipient = evm.accounts.get(&RECIPIENT, &mut working_set).unwrap();
+    assert_eq!(recipient.info.balance, U256::from(100));
+    let account = evm.accounts.get(&ACCOUNT, &mut working_set).unwrap();
+    assert_eq!(account.info.nonce, 1);
+
+    let transaction = evm
+        .transactions
+        .last(&mut working_set.accessory_state())
+        .unwrap();
+    assert_eq!(transaction.signer, ACCOUNT);
+}
+
+#[test]
+fn increased_time_carries_over_to_the_following_blocks() {
+    let context = authority();
+    let (evm, mut working_set) = setup(Some(*context.sender()));
+    let delta = EvmConfig::<C>::default().block_timestamp_delta;
+
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+    let timestamp = evm.block_env.get(&mut working_set).unwrap().timestamp;
+    dev_call(
+        &evm,
+        DevCallMessage::IncreaseTime { seconds: 3_600 },
+        &context,
+        &mut working_set,
+    )
+    .unwrap();
+    evm.end_slot_hook(&mut working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+
+    let head = evm.head.get(&mut working_set).unwrap();
+    assert_eq!(head.header.timestamp, timestamp + 3_600);
+
+    evm.begin_slot_hook([6u8; 32], &[11u8; 32].into(), &mut working_set);
+    assert_eq!(
+        evm.block_env.get(&mut working_set).unwrap().timestamp,
+        timestamp + 3_600 + delta
+    );
+}
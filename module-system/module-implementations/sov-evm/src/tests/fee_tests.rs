@@ -0,0 +1,264 @@
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, TransactionKind, U64};
+use reth_rpc_types::CallRequest;
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::prelude::*;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, Spec, WorkingSet};
+
+use crate::call::CallMessage;
+use crate::tests::genesis_tests::get_evm;
+use crate::tests::test_signer::TestSigner;
+use crate::{evm_address, AccountData, Evm, EvmConfig};
+type C = DefaultContext;
+
+const INITIAL_BALANCE: u64 = 1_000_000_000;
+const RECIPIENT: Address = Address::repeat_byte(0x11);
+const COINBASE: Address = Address::repeat_byte(0xc0);
+const TREASURY: Address = Address::repeat_byte(0x7e);
+const TRANSFER_GAS: u64 = 21_000;
+/// `COINBASE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN`: returns the coinbase when created.
+const COINBASE_INIT_CODE: &str = "4160005260206000f3";
+
+fn setup(dev_signer: &TestSigner, base_fee_recipient: Option<Address>) -> (Evm<C>, WorkingSet<C>) {
+    get_evm(&EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(INITIAL_BALANCE),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        coinbase: COINBASE,
+        base_fee_recipient,
+        ..Default::default()
+    })
+}
+
+/// Produces a block of transfers with the given priority fees, applied in a blob of `sequencer`,
+/// and returns the base fee of the block.
+fn produce_block(
+    evm: &Evm<C>,
+    dev_signer: &TestSigner,
+    priority_fees: &[u128],
+    first_nonce: u64,
+    sequencer: Option<<C as Spec>::Address>,
+    working_set: &mut WorkingSet<C>,
+) -> u64 {
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), working_set);
+    evm.begin_blob_hook(sequencer, working_set);
+    let context = C::new(
+        generate_address::<C>("sender"),
+        generate_address::<C>("sequencer"),
+        1,
+    );
+    for (nonce, priority_fee) in (first_nonce..).zip(priority_fees) {
+        let tx = dev_signer
+            .sign_transaction_with_priority_fee(
+                TransactionKind::Call(RECIPIENT),
+                *priority_fee,
+                nonce,
+            )
+            .unwrap();
+        evm.call(CallMessage::Transact { tx }, &context, working_set)
+            .unwrap();
+    }
+    evm.end_blob_hook(working_set);
+    let base_fee = evm.block_env.get(working_set).unwrap().basefee;
+    evm.end_slot_hook(working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+    base_fee
+}
+
+fn balance(evm: &Evm<C>, address: Address, working_set: &mut WorkingSet<C>) -> U256 {
+    evm.accounts
+        .get(&address, working_set)
+        .map(|account| account.info.balance)
+        .unwrap_or_default()
+}
+
+#[test]
+fn priority_fees_go_to_the_blob_sequencer_and_base_fees_to_the_treasury() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set) = setup(&dev_signer, Some(TREASURY));
+    let sequencer = generate_address::<C>("blob_sequencer");
+
+    let base_fee = produce_block(
+        &evm,
+        &dev_signer,
+        &[3],
+        0,
+        Some(sequencer),
+        &mut working_set,
+    );
+    let gas = U256::from(TRANSFER_GAS);
+    let base_fees = gas * U256::from(base_fee);
+    assert_eq!(
+        balance(&evm, evm_address::<C>(&sequencer), &mut working_set),
+        gas * U256::from(3)
+    );
+    assert_eq!(balance(&evm, TREASURY, &mut working_set), base_fees);
+    assert_eq!(balance(&evm, COINBASE, &mut working_set), U256::ZERO);
+    assert_eq!(
+        balance(&evm, dev_signer.address(), &mut working_set),
+        U256::from(INITIAL_BALANCE) - base_fees - gas * U256::from(3)
+    );
+
+    // Outside of a blob with a sequencer, the priority fees go to the coinbase.
+    produce_block(&evm, &dev_signer, &[2], 1, None, &mut working_set);
+    assert_eq!(
+        balance(&evm, COINBASE, &mut working_set),
+        gas * U256::from(2)
+    );
+    assert_eq!(
+        balance(&evm, evm_address::<C>(&sequencer), &mut working_set),
+        gas * U256::from(3)
+    );
+}
+
+#[test]
+fn calls_are_executed_with_the_coinbase_of_the_last_blob() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set) = setup(&dev_signer, None);
+    let sequencer = generate_address::<C>("blob_sequencer");
+    let request = CallRequest {
+        from: Some(dev_signer.address()),
+        input: Some(Bytes::from(hex::decode(COINBASE_INIT_CODE).unwrap())).into(),
+        ..Default::default()
+    };
+    let coinbase = |block: &str, working_set: &mut WorkingSet<C>| {
+        let output = evm
+            .get_call(
+                request.clone(),
+                Some(block.to_owned()),
+                None,
+                None,
+                working_set,
+            )
+            .unwrap();
+        Address::from_slice(&output[12..])
+    };
+
+    produce_block(
+        &evm,
+        &dev_signer,
+        &[3],
+        0,
+        Some(sequencer),
+        &mut working_set,
+    );
+    assert_eq!(
+        coinbase("latest", &mut working_set),
+        evm_address::<C>(&sequencer)
+    );
+
+    // The next block has no transaction yet.
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+    assert_eq!(coinbase("pending", &mut working_set), COINBASE);
+
+    // While a blob is applied, calls on the pending block pay its sequencer.
+    evm.begin_blob_hook(Some(sequencer), &mut working_set);
+    assert_eq!(
+        coinbase("pending", &mut working_set),
+        evm_address::<C>(&sequencer)
+    );
+}
+
+#[test]
+fn base_fees_are_burned_without_treasury() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set) = setup(&dev_signer, None);
+    let sequencer = generate_address::<C>("blob_sequencer");
+
+    let base_fee = produce_block(
+        &evm,
+        &dev_signer,
+        &[3],
+        0,
+        Some(sequencer),
+        &mut working_set,
+    );
+    let gas = U256::from(TRANSFER_GAS);
+    let total = balance(&evm, dev_signer.address(), &mut working_set)
+        + balance(&evm, evm_address::<C>(&sequencer), &mut working_set)
+        + balance(&evm, COINBASE, &mut working_set)
+        + balance(&evm, RECIPIENT, &mut working_set);
+    assert_eq!(
+        total,
+        U256::from(INITIAL_BALANCE) - gas * U256::from(base_fee)
+    );
+}
+
+#[test]
+fn fee_history_reports_base_fees_gas_usage_and_rewards() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set) = setup(&dev_signer, None);
+
+    let first_base_fee = produce_block(&evm, &dev_signer, &[3, 1], 0, None, &mut working_set);
+    let second_base_fee = produce_block(&evm, &dev_signer, &[], 2, None, &mut working_set);
+
+    let history = evm
+        .fee_history(
+            U64::from(10),
+            BlockNumberOrTag::Latest,
+            Some(vec![0.0, 50.0, 100.0]),
+            &mut working_set,
+        )
+        .unwrap();
+    // The history starts at genesis, since fewer blocks than requested exist.
+    assert_eq!(history.oldest_block, U256::ZERO);
+    assert_eq!(history.base_fee_per_gas.len(), 4);
+    assert_eq!(history.base_fee_per_gas[1], U256::from(first_base_fee));
+    assert_eq!(history.base_fee_per_gas[2], U256::from(second_base_fee));
+
+    let gas_limit = evm.cfg.get(&mut working_set).unwrap().block_gas_limit;
+    assert_eq!(
+        history.gas_used_ratio,
+        vec![0.0, (2 * TRANSFER_GAS) as f64 / gas_limit as f64, 0.0]
+    );
+    // The rewards are the priority fees, at the percentiles of the gas used in the block.
+    let rewards = |fees: [u64; 3]| fees.map(U256::from).to_vec();
+    assert_eq!(
+        history.reward,
+        Some(vec![
+            rewards([0, 0, 0]),
+            rewards([1, 1, 3]),
+            rewards([0, 0, 0])
+        ])
+    );
+
+    let history = evm
+        .fee_history(
+            U64::from(1),
+            BlockNumberOrTag::Number(1),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(history.oldest_block, U256::from(1));
+    assert_eq!(
+        history.base_fee_per_gas,
+        vec![U256::from(first_base_fee), U256::from(second_base_fee)]
+    );
+    assert_eq!(history.reward, None);
+
+    for percentiles in [vec![50.0, 10.0], vec![101.0]] {
+        assert!(evm
+            .fee_history(
+                U64::from(1),
+                BlockNumberOrTag::Latest,
+                Some(percentiles),
+                &mut working_set
+            )
+            .is_err());
+    }
+    assert!(evm
+        .fee_history(
+            U64::from(1),
+            BlockNumberOrTag::Number(3),
+            None,
+            &mut working_set
+        )
+        .is_err());
+}
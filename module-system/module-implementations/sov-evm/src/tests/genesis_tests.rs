@@ -17,7 +17,7 @@ use crate::{AccountData, Evm, EvmConfig};
 type C = DefaultContext;
 
 lazy_static! {
-    pub(crate) static ref TEST_CONFIG: EvmConfig = EvmConfig {
+    pub(crate) static ref TEST_CONFIG: EvmConfig<C> = EvmConfig {
         data: vec![AccountData {
             address: Address::from([1u8; 20]),
             balance: U256::from(1000000000),
@@ -34,8 +34,12 @@ lazy_static! {
         genesis_timestamp: 50,
         coinbase: Address::from([3u8; 20]),
         limit_contract_code_size: Some(5000),
+        base_fee_recipient: Some(Address::from([4u8; 20])),
         starting_base_fee: 70,
         base_fee_params: BaseFeeParams::ethereum(),
+        bridge: None,
+        #[cfg(feature = "dev")]
+        dev_authority: None,
     };
 }
 
@@ -91,6 +95,7 @@ fn genesis_cfg() {
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
             block_timestamp_delta: 2,
             coinbase: Address::from([3u8; 20]),
+            base_fee_recipient: Some(Address::from([4u8; 20])),
             limit_contract_code_size: Some(5000),
             base_fee_params: BaseFeeParams::ethereum(),
         }
@@ -117,12 +122,14 @@ fn genesis_empty_spec_defaults_to_shanghai() {
 }
 
 #[test]
-#[should_panic(expected = "Cancun is not supported")]
 fn genesis_cfg_cancun() {
-    get_evm(&EvmConfig {
+    let (evm, mut working_set) = get_evm(&EvmConfig {
         spec: vec![(0, SpecId::CANCUN)].into_iter().collect(),
         ..Default::default()
     });
+
+    let cfg = evm.cfg.get(&mut working_set).unwrap();
+    assert_eq!(cfg.spec, vec![(0, SpecId::CANCUN)]);
 }
 
 #[test]
@@ -212,7 +219,31 @@ fn genesis_head() {
     );
 }
 
-pub(crate) fn get_evm(config: &EvmConfig) -> (Evm<C>, WorkingSet<DefaultContext>) {
+#[test]
+fn genesis_balance_by_block_number() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let address = Address::from([1u8; 20]);
+
+    for block_number in [
+        None,
+        Some("latest"),
+        Some("pending"),
+        Some("earliest"),
+        Some("0x0"),
+    ] {
+        assert_eq!(
+            evm.get_balance(address, block_number.map(Into::into), &mut working_set)
+                .unwrap(),
+            U256::from(1000000000)
+        );
+    }
+    // Only the latest state can be read by block number.
+    assert!(evm
+        .get_balance(address, Some("0x1".into()), &mut working_set)
+        .is_err());
+}
+
+pub(crate) fn get_evm(config: &EvmConfig<C>) -> (Evm<C>, WorkingSet<DefaultContext>) {
     let tmpdir = tempfile::tempdir().unwrap();
     let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
     let evm = Evm::<C>::default();
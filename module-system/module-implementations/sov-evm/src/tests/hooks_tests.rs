@@ -196,6 +196,7 @@ fn finalize_hook_creates_final_block() {
     let mut accessory_state = working_set.accessory_state();
     evm.finalize_hook(&root_hash, &mut accessory_state);
     assert_eq!(evm.blocks.len(&mut accessory_state), 2);
+    assert_eq!(evm.logs_blooms.len(&mut accessory_state), 2);
 
     evm.begin_slot_hook(DA_ROOT_HASH.0, &root_hash, &mut working_set);
 
@@ -253,4 +254,46 @@ fn finalize_hook_creates_final_block() {
     );
 
     assert_eq!(evm.pending_head.get(&mut accessory_state), None);
+
+    // The block hash available to the `BLOCKHASH` opcode is the hash of the final block.
+    assert_eq!(
+        evm.latest_block_hashes
+            .get(&U256::from(1), &mut working_set)
+            .unwrap(),
+        block.header.hash
+    );
+}
+
+#[test]
+fn begin_slot_hook_keeps_latest_block_hashes() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    for _ in 0..257 {
+        evm.begin_slot_hook(DA_ROOT_HASH.0, &[10u8; 32].into(), &mut working_set);
+        evm.end_slot_hook(&mut working_set);
+    }
+    evm.begin_slot_hook(DA_ROOT_HASH.0, &[10u8; 32].into(), &mut working_set);
+
+    // Block 258 is pending: only the hashes of blocks 2 to 257 are kept.
+    for number in [0u64, 1] {
+        assert_eq!(
+            evm.latest_block_hashes
+                .get(&U256::from(number), &mut working_set),
+            None
+        );
+    }
+    for number in 2u64..=257 {
+        assert!(evm
+            .latest_block_hashes
+            .get(&U256::from(number), &mut working_set)
+            .is_some());
+    }
+
+    let head = evm.head.get(&mut working_set).unwrap();
+    assert_eq!(head.header.number, 257);
+    assert_eq!(
+        evm.latest_block_hashes
+            .get(&U256::from(257), &mut working_set)
+            .unwrap(),
+        head.header.hash_slow()
+    );
 }
@@ -0,0 +1,202 @@
+use reth_primitives::contract::create_address;
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, TransactionKind, H256};
+use reth_rpc_types::Filter;
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, WorkingSet};
+
+use crate::call::CallMessage;
+use crate::tests::genesis_tests::get_evm;
+use crate::tests::test_signer::TestSigner;
+use crate::{AccountData, EthFilterError, Evm, EvmConfig, MAX_BLOCKS_PER_LOG_QUERY};
+type C = DefaultContext;
+
+/// Deploys a contract which emits a log with no data, whose only topic is the first word
+/// of the call data.
+pub(crate) const LOGGER_INIT_CODE: &str = "6009600c60003960096000f360003560006000a100";
+
+pub(crate) const TOPIC_A: H256 = H256([0xaa; 32]);
+const TOPIC_B: H256 = H256([0xbb; 32]);
+
+pub(crate) fn sign_message(
+    dev_signer: &TestSigner,
+    to: TransactionKind,
+    data: Vec<u8>,
+    nonce: u64,
+) -> CallMessage<C> {
+    CallMessage::Transact {
+        tx: dev_signer
+            .sign_default_transaction(to, data, nonce)
+            .unwrap(),
+    }
+}
+
+pub(crate) fn produce_block(
+    evm: &Evm<C>,
+    messages: Vec<CallMessage<C>>,
+    working_set: &mut WorkingSet<C>,
+) {
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), working_set);
+    let context = C::new(
+        generate_address::<C>("sender"),
+        generate_address::<C>("sequencer"),
+        1,
+    );
+    for message in messages {
+        evm.call(message, &context, working_set).unwrap();
+    }
+    evm.end_slot_hook(working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+}
+
+/// Produces a block deploying the logger and emitting logs with topics A and B,
+/// then a block emitting a log with topic A, and an empty block.
+fn setup_logs() -> (Evm<C>, WorkingSet<C>, Address) {
+    let dev_signer = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    let logger = create_address(dev_signer.address(), 0);
+
+    produce_block(
+        &evm,
+        vec![
+            sign_message(
+                &dev_signer,
+                TransactionKind::Create,
+                hex::decode(LOGGER_INIT_CODE).unwrap(),
+                0,
+            ),
+            sign_message(
+                &dev_signer,
+                TransactionKind::Call(logger),
+                TOPIC_A.0.to_vec(),
+                1,
+            ),
+            sign_message(
+                &dev_signer,
+                TransactionKind::Call(logger),
+                TOPIC_B.0.to_vec(),
+                2,
+            ),
+        ],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![sign_message(
+            &dev_signer,
+            TransactionKind::Call(logger),
+            TOPIC_A.0.to_vec(),
+            3,
+        )],
+        &mut working_set,
+    );
+    produce_block(&evm, vec![], &mut working_set);
+
+    (evm, working_set, logger)
+}
+
+#[test]
+fn logs_are_filtered_by_address_and_topics() {
+    let (evm, mut working_set, logger) = setup_logs();
+
+    let logs = evm
+        .logs_for_filter(
+            &Filter::new().from_block(0).event_signature(TOPIC_A),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(logs.len(), 2);
+    assert_eq!(logs[0].block_number, Some(U256::from(1)));
+    assert_eq!(logs[0].transaction_index, Some(U256::from(1)));
+    assert_eq!(logs[0].log_index, Some(U256::from(0)));
+    assert_eq!(logs[1].block_number, Some(U256::from(2)));
+    assert!(logs
+        .iter()
+        .all(|log| log.address == logger && log.topics == vec![TOPIC_A]));
+
+    let logs = evm
+        .logs_for_filter(
+            &Filter::new().from_block(0).address(logger),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(logs.len(), 3);
+    // The log index counts the logs of the block.
+    assert_eq!(logs[1].topics, vec![TOPIC_B]);
+    assert_eq!(logs[1].log_index, Some(U256::from(1)));
+
+    let logs = evm
+        .logs_for_filter(
+            &Filter::new().from_block(0).address(Address::repeat_byte(1)),
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(logs.is_empty());
+}
+
+#[test]
+fn logs_are_filtered_by_block() {
+    let (evm, mut working_set, _) = setup_logs();
+
+    // The range defaults to the head block.
+    let logs = evm
+        .logs_for_filter(&Filter::new(), &mut working_set)
+        .unwrap();
+    assert!(logs.is_empty());
+
+    let logs = evm
+        .logs_for_filter(&Filter::new().from_block(2).to_block(100), &mut working_set)
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].block_number, Some(U256::from(2)));
+
+    let block_hash = logs[0].block_hash.unwrap();
+    let logs = evm
+        .logs_for_filter(&Filter::new().at_block_hash(block_hash), &mut working_set)
+        .unwrap();
+    assert_eq!(logs.len(), 1);
+
+    assert!(evm
+        .logs_for_filter(&Filter::new().from_block(2).to_block(1), &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn ranges_outside_of_the_chain_are_handled() {
+    let (evm, mut working_set, _) = setup_logs();
+
+    // The range ends at the head block.
+    let filter = Filter::new()
+        .from_block(0)
+        .to_block(MAX_BLOCKS_PER_LOG_QUERY * 2);
+    assert_eq!(
+        evm.logs_for_filter(&filter, &mut working_set)
+            .unwrap()
+            .len(),
+        3
+    );
+
+    let filter = Filter::new().from_block(4).to_block(10);
+    assert!(evm
+        .logs_for_filter(&filter, &mut working_set)
+        .unwrap()
+        .is_empty());
+
+    let filter = Filter::new().from_block(BlockNumberOrTag::Finalized);
+    assert!(matches!(
+        evm.logs_for_filter(&filter, &mut working_set),
+        Err(EthFilterError::EthApiError(_))
+    ));
+}
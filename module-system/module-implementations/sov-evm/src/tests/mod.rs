@@ -1,6 +1,22 @@
+#[cfg(feature = "native")]
+mod access_list_tests;
+#[cfg(feature = "native")]
+mod bridge_tests;
 mod call_tests;
 mod cfg_tests;
+#[cfg(feature = "dev")]
+mod dev_tests;
+#[cfg(feature = "native")]
+mod fee_tests;
 mod genesis_tests;
 mod hooks_tests;
+#[cfg(feature = "native")]
+mod logs_tests;
+#[cfg(feature = "native")]
+mod precompile_tests;
+#[cfg(feature = "native")]
+mod proof_tests;
 pub(crate) mod test_signer;
+#[cfg(feature = "native")]
+mod trace_tests;
 mod tx_tests;
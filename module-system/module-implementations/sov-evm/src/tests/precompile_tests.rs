@@ -0,0 +1,259 @@
+use borsh::BorshSerialize;
+use reth_primitives::contract::create_address;
+use reth_primitives::{Address, Bytes, TransactionKind};
+use reth_rpc_types::CallRequest;
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_bank::{get_genesis_token_address, BankConfig, Coins, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::prelude::*;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Module, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+use crate::call::CallMessage;
+use crate::evm::executor::MODULE_CALL_BASE_GAS;
+use crate::tests::logs_tests::{produce_block, sign_message};
+use crate::tests::test_signer::TestSigner;
+use crate::{rollup_address, AccountData, Evm, EvmConfig};
+type C = DefaultContext;
+
+/// Deploys a contract which forwards its call data to the bank precompile, and reverts if the
+/// precompile call fails or if the call has a value.
+const FORWARDER_INIT_CODE: &str = "6024600c60003960246000f3366000600037600060003660006000610b015af11561001f573461001f57005b600080fd";
+
+/// Deploys a contract which calls the address in the first word of its call data with the rest of
+/// it and a value of 1, ignoring the result of the call.
+const WRAPPER_INIT_CODE: &str =
+    "601a600c600039601a6000f3602036038060206000376000600082600060016000355af15000";
+
+const INITIAL_BALANCE: u64 = 1_000;
+
+/// Deploys the forwarder, whose rollup address owns `INITIAL_BALANCE` of a token.
+fn setup(dev_signer: &TestSigner) -> (Evm<C>, WorkingSet<C>, Address, <C as Spec>::Address) {
+    let forwarder = create_address(dev_signer.address(), 0);
+    let bank_config = BankConfig {
+        tokens: vec![TokenConfig {
+            token_name: "token".to_owned(),
+            address_and_balances: vec![(rollup_address::<C>(forwarder), INITIAL_BALANCE)],
+            authorized_minters: vec![],
+            salt: 0,
+        }],
+    };
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1_000_000_000_000u64),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let evm = Evm::<C>::default();
+    evm.bank.genesis(&bank_config, &mut working_set).unwrap();
+    evm.genesis(&config, &mut working_set).unwrap();
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+
+    produce_block(
+        &evm,
+        vec![sign_message(
+            dev_signer,
+            TransactionKind::Create,
+            hex::decode(FORWARDER_INIT_CODE).unwrap(),
+            0,
+        )],
+        &mut working_set,
+    );
+
+    let token = get_genesis_token_address::<C>("token", 0);
+    (evm, working_set, forwarder, token)
+}
+
+fn transfer_message(to: <C as Spec>::Address, token: <C as Spec>::Address, amount: u64) -> Vec<u8> {
+    sov_bank::CallMessage::<C>::Transfer {
+        to,
+        coins: Coins {
+            amount,
+            token_address: token,
+        },
+    }
+    .try_to_vec()
+    .unwrap()
+}
+
+#[test]
+fn contracts_call_modules_as_their_rollup_address() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, forwarder, token) = setup(&dev_signer);
+    let recipient = generate_address::<C>("recipient");
+
+    produce_block(
+        &evm,
+        vec![sign_message(
+            &dev_signer,
+            TransactionKind::Call(forwarder),
+            transfer_message(recipient, token, 100),
+            1,
+        )],
+        &mut working_set,
+    );
+
+    let receipt = evm
+        .receipts
+        .get(1usize, &mut working_set.accessory_state())
+        .unwrap();
+    assert!(receipt.receipt.success);
+    assert!(receipt.gas_used > MODULE_CALL_BASE_GAS);
+
+    assert_eq!(balance_of(&evm, recipient, token, &mut working_set), 100);
+    assert_eq!(
+        balance_of(
+            &evm,
+            rollup_address::<C>(forwarder),
+            token,
+            &mut working_set
+        ),
+        INITIAL_BALANCE - 100
+    );
+}
+
+#[test]
+fn reverted_module_calls_are_rolled_back() {
+    let recipient = generate_address::<C>("recipient");
+
+    // The forwarder reverts after the module call when the call has a value,
+    // and the module call fails when the forwarder doesn't own enough tokens.
+    for (value, amount) in [(1, 100), (0, INITIAL_BALANCE + 1)] {
+        let dev_signer = TestSigner::new_random();
+        let (evm, mut working_set, forwarder, token) = setup(&dev_signer);
+
+        let tx = dev_signer
+            .sign_transaction_with_value(
+                TransactionKind::Call(forwarder),
+                transfer_message(recipient, token, amount),
+                value,
+                1,
+            )
+            .unwrap();
+        produce_block(&evm, vec![CallMessage::Transact { tx }], &mut working_set);
+
+        let receipt = evm
+            .receipts
+            .get(1usize, &mut working_set.accessory_state())
+            .unwrap();
+        assert!(!receipt.receipt.success);
+        assert_eq!(balance_of(&evm, recipient, token, &mut working_set), 0);
+        assert_eq!(
+            balance_of(
+                &evm,
+                rollup_address::<C>(forwarder),
+                token,
+                &mut working_set
+            ),
+            INITIAL_BALANCE
+        );
+    }
+}
+
+#[test]
+fn module_calls_of_reverted_frames_are_rolled_back() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, forwarder, token) = setup(&dev_signer);
+    let recipient = generate_address::<C>("recipient");
+    let wrapper = create_address(dev_signer.address(), 1);
+    produce_block(
+        &evm,
+        vec![sign_message(
+            &dev_signer,
+            TransactionKind::Create,
+            hex::decode(WRAPPER_INIT_CODE).unwrap(),
+            1,
+        )],
+        &mut working_set,
+    );
+
+    // The forwarder reverts after the module call, and the wrapper ignores it.
+    let mut calldata = [0u8; 12].to_vec();
+    calldata.extend_from_slice(forwarder.as_bytes());
+    calldata.extend(transfer_message(recipient, token, 100));
+    let tx = dev_signer
+        .sign_transaction_with_value(TransactionKind::Call(wrapper), calldata, 1, 2)
+        .unwrap();
+    produce_block(&evm, vec![CallMessage::Transact { tx }], &mut working_set);
+
+    let receipt = evm
+        .receipts
+        .get(2usize, &mut working_set.accessory_state())
+        .unwrap();
+    assert!(receipt.receipt.success);
+    assert_eq!(balance_of(&evm, recipient, token, &mut working_set), 0);
+    assert_eq!(
+        balance_of(
+            &evm,
+            rollup_address::<C>(forwarder),
+            token,
+            &mut working_set
+        ),
+        INITIAL_BALANCE
+    );
+}
+
+#[test]
+fn calls_dispatch_to_module_precompiles_without_committing() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, forwarder, token) = setup(&dev_signer);
+    let recipient = generate_address::<C>("recipient");
+
+    let request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(forwarder),
+        input: Some(Bytes::from(transfer_message(recipient, token, 100))).into(),
+        ..Default::default()
+    };
+    evm.get_call(request.clone(), None, None, None, &mut working_set)
+        .unwrap();
+    let estimated = evm
+        .eth_estimate_gas(request.clone(), None, &mut working_set)
+        .unwrap();
+    assert!(estimated.as_u64() > MODULE_CALL_BASE_GAS);
+
+    // The module call fails without the tokens.
+    let request = CallRequest {
+        input: Some(Bytes::from(transfer_message(
+            recipient,
+            token,
+            INITIAL_BALANCE + 1,
+        )))
+        .into(),
+        ..request
+    };
+    assert!(evm
+        .get_call(request, None, None, None, &mut working_set)
+        .is_err());
+
+    assert_eq!(balance_of(&evm, recipient, token, &mut working_set), 0);
+    assert_eq!(
+        balance_of(
+            &evm,
+            rollup_address::<C>(forwarder),
+            token,
+            &mut working_set
+        ),
+        INITIAL_BALANCE
+    );
+}
+
+fn balance_of(
+    evm: &Evm<C>,
+    owner: <C as Spec>::Address,
+    token: <C as Spec>::Address,
+    working_set: &mut WorkingSet<C>,
+) -> u64 {
+    evm.bank
+        .get_balance_of(owner, token, working_set)
+        .unwrap_or_default()
+}
@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use reth_primitives::contract::create_address;
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, TransactionKind, H256};
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_evm_proof_verifier::{JmtProof, EVM_ACCOUNTS_PREFIX};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
+
+use crate::smart_contracts::SimpleStorageContract;
+use crate::tests::logs_tests::sign_message;
+use crate::tests::test_signer::TestSigner;
+use crate::{AccountData, Evm, EvmAccountProof, EvmConfig};
+type C = DefaultContext;
+
+const SET_ARG: u32 = 999;
+
+/// Commits a block deploying the simple storage contract and setting its value, with the
+/// state root of the block recorded as in the rollup.
+fn setup(path: &Path, dev_signer: &TestSigner) -> (Evm<C>, WorkingSet<C>, Address) {
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+    let storage = new_orphan_storage(path).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+    let evm = Evm::<C>::default();
+    evm.genesis(&config, &mut working_set).unwrap();
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+
+    let contract = SimpleStorageContract::default();
+    let contract_addr = create_address(dev_signer.address(), 0);
+    let context = C::new(
+        generate_address::<C>("sender"),
+        generate_address::<C>("sequencer"),
+        1,
+    );
+    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+    for message in [
+        sign_message(
+            dev_signer,
+            TransactionKind::Create,
+            contract.byte_code().to_vec(),
+            0,
+        ),
+        sign_message(
+            dev_signer,
+            TransactionKind::Call(contract_addr),
+            contract.set_call_data(SET_ARG).to_vec(),
+            1,
+        ),
+    ] {
+        evm.call(message, &context, &mut working_set).unwrap();
+    }
+    evm.end_slot_hook(&mut working_set);
+
+    let mut checkpoint = working_set.checkpoint();
+    let (cache_log, witness) = checkpoint.freeze();
+    let (root, node_batch) = storage.compute_state_update(cache_log, &witness).unwrap();
+    let mut working_set = checkpoint.to_revertable();
+    evm.finalize_hook(&root, &mut working_set.accessory_state());
+    let accessory_writes = working_set.checkpoint().freeze_non_provable();
+    storage.commit(&node_batch, &accessory_writes);
+
+    (evm, WorkingSet::new(storage), contract_addr)
+}
+
+fn prove(
+    evm: &Evm<C>,
+    address: Address,
+    working_set: &mut WorkingSet<C>,
+) -> (EvmAccountProof, H256) {
+    let replay = evm
+        .block_replay(BlockNumberOrTag::Latest, working_set)
+        .unwrap();
+    let proof = evm
+        .get_proof(
+            address,
+            vec![U256::ZERO, U256::from(1)],
+            &replay,
+            working_set,
+        )
+        .unwrap();
+    (proof, replay.state_root())
+}
+
+fn jmt_proofs(proof: &EvmAccountProof) -> impl Iterator<Item = &JmtProof> {
+    std::iter::once(&proof.account_proof).chain(proof.storage_proof.iter().map(|slot| &slot.proof))
+}
+
+#[test]
+fn accounts_are_stored_under_the_verifier_prefix() {
+    let evm = Evm::<C>::default();
+    assert_eq!(
+        evm.accounts.prefix().as_aligned_vec().clone().into_inner(),
+        EVM_ACCOUNTS_PREFIX
+    );
+}
+
+#[test]
+fn account_and_storage_proofs_verify_against_the_block_state_root() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, contract_addr) = setup(tmpdir.path(), &dev_signer);
+
+    let (proof, state_root) = prove(&evm, contract_addr, &mut working_set);
+    assert_eq!(proof.block_number, 1);
+    assert_eq!(proof.state_root, state_root);
+    assert_eq!(proof.nonce, 1);
+    assert_ne!(proof.code_hash, KECCAK_EMPTY);
+    assert_eq!(proof.storage_proof[0].value, U256::from(SET_ARG));
+    // The second slot is not set, so it's proven absent.
+    assert_eq!(proof.storage_proof[1].value, U256::ZERO);
+    assert!(proof.storage_proof[1].proof.value.is_none());
+
+    proof.verify(state_root).unwrap();
+    for jmt_proof in jmt_proofs(&proof) {
+        jmt_proof.verify(state_root).unwrap();
+        jmt_proof.to_solidity().unwrap().verify(state_root).unwrap();
+    }
+}
+
+#[test]
+fn absent_accounts_are_proven_absent() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, _) = setup(tmpdir.path(), &dev_signer);
+
+    let (proof, state_root) = prove(&evm, Address::from([0x42; 20]), &mut working_set);
+    assert_eq!((proof.balance, proof.nonce), (U256::ZERO, 0));
+    assert_eq!(proof.code_hash, KECCAK_EMPTY);
+    assert!(proof.account_proof.value.is_none());
+
+    proof.verify(state_root).unwrap();
+    for jmt_proof in jmt_proofs(&proof) {
+        jmt_proof.to_solidity().unwrap().verify(state_root).unwrap();
+    }
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, contract_addr) = setup(tmpdir.path(), &dev_signer);
+    let (proof, state_root) = prove(&evm, contract_addr, &mut working_set);
+
+    assert!(proof.verify(H256::repeat_byte(1)).is_err());
+
+    let mut tampered = proof.clone();
+    tampered.balance += U256::from(1);
+    assert!(tampered.verify(state_root).is_err());
+
+    let mut tampered = proof.clone();
+    tampered.storage_proof[0].value = U256::from(SET_ARG + 1);
+    assert!(tampered.verify(state_root).is_err());
+
+    // The proof of another slot can't be passed off as the proof of the requested one.
+    let mut tampered = proof.clone();
+    tampered.storage_proof[1].proof = proof.storage_proof[0].proof.clone();
+    assert!(tampered.verify(state_root).is_err());
+
+    let solidity_proof = proof.storage_proof[0].proof.to_solidity().unwrap();
+    let mut tampered = solidity_proof.clone();
+    tampered.value_hash = H256::zero();
+    assert!(tampered.verify(state_root).is_err());
+
+    // An existing slot can't be proven absent.
+    let mut tampered = solidity_proof;
+    tampered.exists = false;
+    assert!(tampered.verify(state_root).is_err());
+}
@@ -1,7 +1,7 @@
 use ethers_core::rand::rngs::StdRng;
 use ethers_core::rand::SeedableRng;
 use reth_primitives::{
-    Address, Bytes as RethBytes, Transaction as RethTransaction, TransactionKind,
+    AccessList, Address, Bytes as RethBytes, Transaction as RethTransaction, TransactionKind,
     TxEip1559 as RethTxEip1559,
 };
 use secp256k1::{PublicKey, SecretKey};
@@ -45,16 +45,59 @@ impl TestSigner {
         data: Vec<u8>,
         nonce: u64,
     ) -> Result<RlpEvmTransaction, SignError> {
-        let reth_tx = RethTxEip1559 {
+        self.sign_transaction_with_value(to, data, 0, nonce)
+    }
+
+    /// Signs default Eip1559 transaction with to, data, value and nonce overridden.
+    pub(crate) fn sign_transaction_with_value(
+        &self,
+        to: TransactionKind,
+        data: Vec<u8>,
+        value: u128,
+        nonce: u64,
+    ) -> Result<RlpEvmTransaction, SignError> {
+        self.sign_transaction(RethTxEip1559 {
+            to,
+            input: RethBytes::from(data),
+            value,
+            nonce,
+            ..default_transaction()
+        })
+    }
+
+    /// Signs default Eip1559 transaction with to, priority fee and nonce overridden.
+    pub(crate) fn sign_transaction_with_priority_fee(
+        &self,
+        to: TransactionKind,
+        max_priority_fee_per_gas: u128,
+        nonce: u64,
+    ) -> Result<RlpEvmTransaction, SignError> {
+        self.sign_transaction(RethTxEip1559 {
+            to,
+            max_priority_fee_per_gas,
+            nonce,
+            ..default_transaction()
+        })
+    }
+
+    /// Signs default Eip1559 transaction with to, data, access list and nonce overridden.
+    pub(crate) fn sign_transaction_with_access_list(
+        &self,
+        to: TransactionKind,
+        data: Vec<u8>,
+        access_list: AccessList,
+        nonce: u64,
+    ) -> Result<RlpEvmTransaction, SignError> {
+        self.sign_transaction(RethTxEip1559 {
             to,
             input: RethBytes::from(data),
+            access_list,
             nonce,
-            chain_id: 1,
-            gas_limit: 1_000_000u64,
-            max_fee_per_gas: u128::from(reth_primitives::constants::MIN_PROTOCOL_BASE_FEE * 2),
-            ..Default::default()
-        };
+            ..default_transaction()
+        })
+    }
 
+    fn sign_transaction(&self, reth_tx: RethTxEip1559) -> Result<RlpEvmTransaction, SignError> {
         let reth_tx = RethTransaction::Eip1559(reth_tx);
         let signed = self.signer.sign_transaction(reth_tx, self.address)?;
 
@@ -63,3 +106,12 @@ impl TestSigner {
         })
     }
 }
+
+fn default_transaction() -> RethTxEip1559 {
+    RethTxEip1559 {
+        chain_id: 1,
+        gas_limit: 1_000_000u64,
+        max_fee_per_gas: u128::from(reth_primitives::constants::MIN_PROTOCOL_BASE_FEE * 2),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,239 @@
+use reth_primitives::contract::create_address;
+use reth_primitives::{Address, BlockNumberOrTag, Bytes, TransactionKind};
+use reth_rpc_types::trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions, GethTrace,
+    PreStateFrame, PreStateMode,
+};
+use reth_rpc_types::CallRequest;
+use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{StateVecAccessor, WorkingSet};
+
+use crate::tests::call_tests::BLOCK_HASH_INIT_CODE;
+use crate::tests::genesis_tests::get_evm;
+use crate::tests::logs_tests::{produce_block, sign_message, LOGGER_INIT_CODE, TOPIC_A};
+use crate::tests::test_signer::TestSigner;
+use crate::{AccountData, CallMessage, EthApiError, Evm, EvmConfig};
+type C = DefaultContext;
+
+const TOPIC: [u8; 32] = [0x11; 32];
+
+/// Produces the first `blocks` blocks of a chain where the first block deploys the logger,
+/// and the second block calls it twice.
+fn setup_chain(dev_signer: &TestSigner, blocks: usize) -> (Evm<C>, WorkingSet<C>, Address) {
+    let logger = create_address(dev_signer.address(), 0);
+    let chain = vec![
+        vec![sign_message(
+            dev_signer,
+            TransactionKind::Create,
+            hex::decode(LOGGER_INIT_CODE).unwrap(),
+            0,
+        )],
+        vec![
+            sign_message(
+                dev_signer,
+                TransactionKind::Call(logger),
+                TOPIC_A.0.to_vec(),
+                1,
+            ),
+            sign_message(dev_signer, TransactionKind::Call(logger), TOPIC.to_vec(), 2),
+        ],
+    ];
+    let (evm, working_set) = produce_chain(dev_signer, chain.into_iter().take(blocks));
+
+    (evm, working_set, logger)
+}
+
+/// Produces a block for each list of messages, on a chain funding the `dev_signer`.
+fn produce_chain(
+    dev_signer: &TestSigner,
+    chain: impl IntoIterator<Item = Vec<CallMessage<C>>>,
+) -> (Evm<C>, WorkingSet<C>) {
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    for messages in chain {
+        produce_block(&evm, messages, &mut working_set);
+    }
+
+    (evm, working_set)
+}
+
+fn tracer_options(tracer: GethDebugBuiltInTracerType) -> GethDebugTracingOptions {
+    GethDebugTracingOptions {
+        tracer: Some(GethDebugTracerType::BuiltInTracer(tracer)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn transactions_are_traced_on_top_of_the_parent_block() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, logger) = setup_chain(&dev_signer, 2);
+
+    let tx_hash = evm
+        .transactions
+        .get(2usize, &mut working_set.accessory_state())
+        .unwrap()
+        .signed_transaction
+        .hash;
+    let receipt = evm
+        .receipts
+        .get(2usize, &mut working_set.accessory_state())
+        .unwrap();
+    let (replay, index) = evm.transaction_replay(tx_hash, &mut working_set).unwrap();
+    assert_eq!(replay.number(), 2);
+    assert_eq!(index, 1);
+
+    // The first transaction of the block is executed before the traced one.
+    let (_, mut parent_working_set, _) = setup_chain(&dev_signer, 1);
+    let trace = evm
+        .trace_transaction(
+            &replay,
+            index,
+            &tracer_options(GethDebugBuiltInTracerType::CallTracer),
+            &mut parent_working_set,
+        )
+        .unwrap();
+
+    let GethTrace::CallTracer(frame) = trace else {
+        panic!("Expected a call frame, got {:?}", trace);
+    };
+    assert_eq!(frame.to, Some(logger));
+    assert_eq!(frame.input, Bytes::from(TOPIC.to_vec()));
+    assert_eq!(frame.gas_used, U256::from(receipt.gas_used));
+}
+
+#[test]
+fn blocks_are_traced_with_the_struct_logger() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, _) = setup_chain(&dev_signer, 2);
+    let replay = evm
+        .block_replay(BlockNumberOrTag::Latest, &mut working_set)
+        .unwrap();
+
+    let (_, mut parent_working_set, _) = setup_chain(&dev_signer, 1);
+    let traces = evm
+        .trace_block(
+            &replay,
+            None,
+            &GethDebugTracingOptions::default(),
+            &mut parent_working_set,
+        )
+        .unwrap();
+
+    assert_eq!(traces.len(), 2);
+    for trace in traces {
+        let GethTrace::Default(frame) = trace.result else {
+            panic!("Expected a struct logger frame, got {:?}", trace.result);
+        };
+        assert!(!frame.failed);
+        assert!(frame.struct_logs.iter().any(|log| log.op == "LOG1"));
+    }
+}
+
+#[test]
+fn transactions_are_traced_with_the_hash_of_the_parent_block() {
+    let dev_signer = TestSigner::new_random();
+    let contract = create_address(dev_signer.address(), 0);
+    let chain = vec![
+        vec![sign_message(
+            &dev_signer,
+            TransactionKind::Create,
+            hex::decode(BLOCK_HASH_INIT_CODE).unwrap(),
+            0,
+        )],
+        // Stores the hash of block 1 with `BLOCKHASH`.
+        vec![sign_message(
+            &dev_signer,
+            TransactionKind::Call(contract),
+            U256::from(1).to_be_bytes::<32>().to_vec(),
+            1,
+        )],
+    ];
+    let (evm, mut working_set) = produce_chain(&dev_signer, chain.clone());
+    let parent_hash = evm
+        .blocks
+        .get(1usize, &mut working_set.accessory_state())
+        .unwrap()
+        .header
+        .hash;
+    let tx_hash = evm
+        .transactions
+        .get(1usize, &mut working_set.accessory_state())
+        .unwrap()
+        .signed_transaction
+        .hash;
+    let (replay, index) = evm.transaction_replay(tx_hash, &mut working_set).unwrap();
+
+    let (_, mut parent_working_set) = produce_chain(&dev_signer, chain.into_iter().take(1));
+    let trace = evm
+        .trace_transaction(
+            &replay,
+            index,
+            &GethDebugTracingOptions::default(),
+            &mut parent_working_set,
+        )
+        .unwrap();
+
+    let GethTrace::Default(frame) = trace else {
+        panic!("Expected a struct logger frame, got {:?}", trace);
+    };
+    let sstore = frame
+        .struct_logs
+        .iter()
+        .find(|log| log.op == "SSTORE")
+        .unwrap();
+    assert!(sstore
+        .stack
+        .as_ref()
+        .unwrap()
+        .contains(&U256::from_be_bytes(parent_hash.0)));
+}
+
+#[test]
+fn calls_are_traced_on_top_of_the_block() {
+    let dev_signer = TestSigner::new_random();
+    let (evm, mut working_set, logger) = setup_chain(&dev_signer, 1);
+    let replay = evm
+        .block_replay(BlockNumberOrTag::Latest, &mut working_set)
+        .unwrap();
+    let request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(logger),
+        input: Some(Bytes::from(TOPIC.to_vec())).into(),
+        ..Default::default()
+    };
+
+    let trace = evm
+        .trace_call(
+            request.clone(),
+            &replay,
+            &tracer_options(GethDebugBuiltInTracerType::PreStateTracer),
+            &mut working_set,
+        )
+        .unwrap();
+    let GethTrace::PreStateTracer(PreStateFrame::Default(PreStateMode(accounts))) = trace else {
+        panic!("Expected a prestate frame, got {:?}", trace);
+    };
+    assert!(accounts[&logger].code.is_some());
+    assert!(accounts.contains_key(&dev_signer.address()));
+
+    let opts = GethDebugTracingOptions {
+        tracer: Some(GethDebugTracerType::JsTracer("{}".to_owned())),
+        ..Default::default()
+    };
+    assert!(matches!(
+        evm.trace_call(request, &replay, &opts, &mut working_set),
+        Err(EthApiError::Unsupported(_))
+    ));
+}
@@ -5,10 +5,11 @@ use ethers_core::types::transaction::eip2718::TypedTransaction;
 use ethers_core::types::{Bytes, Eip1559TransactionRequest};
 use ethers_core::utils::rlp::Rlp;
 use ethers_signers::{LocalWallet, Signer};
-use reth_primitives::{Address, TransactionSignedEcRecovered, U256, U8};
+use reth_primitives::{Address, TransactionSignedEcRecovered, H256, U256, U8};
 use reth_rpc_types::{CallInput, CallRequest};
 use revm::primitives::{TransactTo, TxEnv};
 
+use crate::error::rpc::{EthApiError, RpcInvalidTransactionError};
 use crate::evm::prepare_call_env;
 use crate::evm::primitive_types::TransactionSignedAndRecovered;
 use crate::primitive_types::{Block, BlockEnv};
@@ -123,6 +124,23 @@ fn prepare_call_env_conversion() {
     assert_eq!(tx_env.access_list, expected.access_list);
 }
 
+#[test]
+fn prepare_call_env_rejects_blob_transactions() {
+    let request = CallRequest {
+        max_fee_per_blob_gas: Some(U256::from(1u64)),
+        blob_versioned_hashes: vec![H256::random()],
+        transaction_type: Some(U8::from(3u8)),
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        prepare_call_env(&BlockEnv::default(), request),
+        Err(EthApiError::InvalidTransaction(
+            RpcInvalidTransactionError::TxTypeNotSupported
+        ))
+    ));
+}
+
 #[test]
 fn prepare_call_block_env() {
     let block = Block {
@@ -0,0 +1,463 @@
+//! Re-execution of transactions and calls with the tracers of the `debug_` namespace.
+
+use reth_interfaces::RethError;
+use reth_primitives::{Address, BlockNumberOrTag, TransactionSignedEcRecovered, H256};
+use reth_revm::tracing::{TracingInspector, TracingInspectorConfig};
+use reth_rpc_types::trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions, GethTrace, NoopFrame,
+};
+use reth_rpc_types::CallRequest;
+use revm::db::{CacheDB, EmptyDB};
+use revm::primitives::{CfgEnv, ExecutionResult, ResultAndState, State, TxEnv, KECCAK_EMPTY};
+use revm::{Database, DatabaseCommit};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::call::get_cfg_env;
+use crate::evm::conversions::create_tx_env;
+use crate::evm::db_commit::apply_eip6780;
+use crate::evm::primitive_types::BlockEnv;
+use crate::evm::{executor, prepare_call_env};
+use crate::query::{get_cfg_env_template, resolve_block_number};
+use crate::{rollup_address, CallMessage, EthApiError, Evm};
+
+/// A block loaded to be re-executed with a tracer.
+///
+/// Blocks and transactions are only kept in the accessory state, so they are loaded from the
+/// latest state before being re-executed on top of an archival state. The calls to the module
+/// are replayed in the order they were applied, but not the calls to the other modules made
+/// in the same slot.
+#[derive(Debug, Clone)]
+pub struct BlockReplay<C: sov_modules_api::Context> {
+    block_env: BlockEnv,
+    state_root: H256,
+    parent_state_root: Option<H256>,
+    transactions: Vec<TransactionSignedEcRecovered>,
+    calls: Vec<BlockCall<C>>,
+}
+
+impl<C: sov_modules_api::Context> BlockReplay<C> {
+    /// The number of the block.
+    pub fn number(&self) -> u64 {
+        self.block_env.number
+    }
+
+    /// The state root reached after the block. Calls are traced on top of this state.
+    pub fn state_root(&self) -> H256 {
+        self.state_root
+    }
+
+    /// The state root reached after the parent block, or `None` for the genesis block.
+    /// The transactions of the block are traced on top of this state.
+    pub fn parent_state_root(&self) -> Option<H256> {
+        self.parent_state_root
+    }
+
+    /// The environment calls are traced in, with the coinbase of the last transaction of the block.
+    fn call_block_env(&self) -> BlockEnv {
+        BlockEnv {
+            coinbase: last_coinbase(&self.calls).unwrap_or(self.block_env.coinbase),
+            ..self.block_env.clone()
+        }
+    }
+}
+
+/// A call to the module applied in a block, recorded to replay the block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Address: Serialize + serde::de::DeserializeOwned")]
+pub(crate) struct BlockCall<C: sov_modules_api::Context> {
+    sender: C::Address,
+    sequencer: C::Address,
+    height: u64,
+    kind: BlockCallKind<C>,
+}
+
+/// The kind of a [`BlockCall`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "C::Address: Serialize + serde::de::DeserializeOwned")]
+pub(crate) enum BlockCallKind<C: sov_modules_api::Context> {
+    /// The next transaction of the block, executed with the coinbase of its blob.
+    Transaction { coinbase: Address },
+    /// A call which isn't an Ethereum transaction, such as a deposit.
+    Call(CallMessage<C>),
+}
+
+/// The coinbase the last transaction of the calls was executed with, if any.
+pub(crate) fn last_coinbase<C: sov_modules_api::Context>(
+    calls: &[BlockCall<C>],
+) -> Option<Address> {
+    calls.iter().rev().find_map(|call| match call.kind {
+        BlockCallKind::Transaction { coinbase } => Some(coinbase),
+        BlockCallKind::Call(_) => None,
+    })
+}
+
+/// The trace of a transaction of a block, as returned by `debug_traceBlockByNumber`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTrace {
+    /// The hash of the transaction.
+    pub tx_hash: H256,
+    /// The output of the tracer.
+    pub result: GethTrace,
+}
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Loads the block with the given number or tag from the latest state.
+    pub fn block_replay(
+        &self,
+        block: BlockNumberOrTag,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<BlockReplay<C>, EthApiError> {
+        let mut accessory_state = working_set.accessory_state();
+        let head = (self.blocks.len(&mut accessory_state) as u64).saturating_sub(1);
+        let number = resolve_block_number(Some(block), head)?;
+
+        let block = self
+            .blocks
+            .get(number as usize, &mut accessory_state)
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let parent_state_root = number.checked_sub(1).map(|parent| {
+            self.blocks
+                .get(parent as usize, &mut accessory_state)
+                .expect("Parent block must be set")
+                .header
+                .state_root
+        });
+        let transactions: Vec<TransactionSignedEcRecovered> = block
+            .transactions
+            .clone()
+            .map(|tx_number| {
+                self.transactions
+                    .get(tx_number as usize, &mut accessory_state)
+                    .expect("Transaction must be set")
+                    .into()
+            })
+            .collect();
+        // The blocks applied before the calls were recorded only had transactions, with the
+        // coinbase of the chain.
+        let calls = self
+            .block_calls
+            .get(&number, &mut accessory_state)
+            .unwrap_or_else(|| {
+                transactions
+                    .iter()
+                    .map(|tx| BlockCall {
+                        sender: rollup_address::<C>(tx.signer()),
+                        sequencer: rollup_address::<C>(tx.signer()),
+                        height: number,
+                        kind: BlockCallKind::Transaction {
+                            coinbase: block.header.beneficiary,
+                        },
+                    })
+                    .collect()
+            });
+
+        Ok(BlockReplay {
+            block_env: BlockEnv::from(&block),
+            state_root: block.header.state_root,
+            parent_state_root,
+            transactions,
+            calls,
+        })
+    }
+
+    /// Loads the block of the transaction with the given hash from the latest state,
+    /// along with the index of the transaction in the block.
+    pub fn transaction_replay(
+        &self,
+        hash: H256,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(BlockReplay<C>, usize), EthApiError> {
+        let mut accessory_state = working_set.accessory_state();
+        let tx_number = self
+            .transaction_hashes
+            .get(&hash, &mut accessory_state)
+            .ok_or(EthApiError::TransactionNotFound)?;
+        let block_number = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Transaction must be set")
+            .block_number;
+
+        let replay = self.block_replay(BlockNumberOrTag::Number(block_number), working_set)?;
+        let index = replay
+            .transactions
+            .iter()
+            .position(|tx| tx.hash() == hash)
+            .expect("Transaction must be in its block");
+        Ok((replay, index))
+    }
+
+    /// Traces the transactions of the block, up to the one at index `last` if given.
+    /// The `working_set` must be on top of the state after the parent block.
+    pub fn trace_block(
+        &self,
+        replay: &BlockReplay<C>,
+        last: Option<usize>,
+        opts: &GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<Vec<TransactionTrace>, EthApiError> {
+        ensure_supported_tracer(opts)?;
+        let transactions = match last {
+            Some(last) => replay
+                .transactions
+                .get(..=last)
+                .ok_or(EthApiError::UnknownBlockOrTxIndex)?,
+            None => &replay.transactions[..],
+        };
+
+        let cfg = self.cfg.get(working_set).expect("Evm config must be set");
+        let base_fee_recipient = cfg.base_fee_recipient;
+        let cfg_env = get_cfg_env(&replay.block_env, cfg, None);
+
+        // The state of the block before its first call, as left by `begin_slot_hook`.
+        if let Some(parent_state_root) = replay.parent_state_root {
+            self.seal_head(parent_state_root, working_set);
+        }
+        self.block_env.set(&replay.block_env, working_set);
+
+        let mut transactions = transactions.iter().enumerate();
+        let mut traces = Vec::with_capacity(transactions.len());
+        for call in &replay.calls {
+            let context = C::new(call.sender.clone(), call.sequencer.clone(), call.height);
+            let coinbase = match &call.kind {
+                BlockCallKind::Transaction { coinbase } => *coinbase,
+                BlockCallKind::Call(msg) => {
+                    self.execute_module_call(msg.clone(), &context, working_set)
+                        .map_err(replay_error)?;
+                    continue;
+                }
+            };
+            let Some((index, tx)) = transactions.next() else {
+                break;
+            };
+            let block_env = BlockEnv {
+                coinbase,
+                ..replay.block_env.clone()
+            };
+
+            // When tracing a single transaction, the ones before it are only executed.
+            if last.is_some_and(|last| index < last) {
+                self.apply_transaction(tx, &block_env, &context, working_set)
+                    .map_err(replay_error)??;
+                continue;
+            }
+
+            let (result, mut res) = self.trace_tx(
+                &block_env,
+                create_tx_env(tx),
+                cfg_env.clone(),
+                &context,
+                opts,
+                working_set,
+            )?;
+            // The next transactions of the block are executed on top of this one.
+            apply_eip6780(&mut res.state, cfg_env.spec_id);
+            self.get_db(working_set).commit(res.state);
+            self.settle_transaction(&res.result, &block_env, base_fee_recipient, working_set)
+                .map_err(replay_error)?;
+            traces.push(TransactionTrace {
+                tx_hash: tx.hash(),
+                result,
+            });
+            if last == Some(index) {
+                break;
+            }
+        }
+        Ok(traces)
+    }
+
+    /// Records a call applied in the pending block, to replay the block.
+    pub(crate) fn record_block_call(
+        &self,
+        kind: BlockCallKind<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let number = self
+            .block_env
+            .get(working_set)
+            .expect("Pending block must be set")
+            .number;
+        let call = BlockCall {
+            sender: context.sender().clone(),
+            sequencer: context.sequencer().clone(),
+            height: context.slot_height(),
+            kind,
+        };
+
+        let mut accessory_state = working_set.accessory_state();
+        let mut calls = self
+            .block_calls
+            .get(&number, &mut accessory_state)
+            .unwrap_or_default();
+        calls.push(call);
+        self.block_calls.set(&number, &calls, &mut accessory_state);
+    }
+
+    /// Traces the transaction at `index` in the block.
+    /// The `working_set` must be on top of the state after the parent block.
+    pub fn trace_transaction(
+        &self,
+        replay: &BlockReplay<C>,
+        index: usize,
+        opts: &GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<GethTrace, EthApiError> {
+        let mut traces = self.trace_block(replay, Some(index), opts, working_set)?;
+        Ok(traces
+            .pop()
+            .expect("The traced transaction must be the last one")
+            .result)
+    }
+
+    /// Traces a call executed in the environment of the block, as `eth_call` executes it.
+    /// The `working_set` must be on top of the state after the block.
+    pub fn trace_call(
+        &self,
+        request: CallRequest,
+        replay: &BlockReplay<C>,
+        opts: &GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<GethTrace, EthApiError> {
+        ensure_supported_tracer(opts)?;
+        let block_env = replay.call_block_env();
+        let tx_env = prepare_call_env(&block_env, request)?;
+
+        let cfg = self.cfg.get(working_set).expect("Evm config must be set");
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        // Calls have no sequencer, the caller stands in for it.
+        let caller = rollup_address::<C>(tx_env.caller);
+        let context = C::new(caller.clone(), caller, block_env.number);
+        let snapshot = working_set.snapshot();
+        let traced = self.trace_tx(&block_env, tx_env, cfg_env, &context, opts, working_set);
+        working_set.revert_to_snapshot(&snapshot);
+        Ok(traced?.0)
+    }
+
+    /// Executes the transaction without committing it, and returns its trace and its changes.
+    /// The changes of its module calls, made on behalf of `context`, are left in the working set.
+    // Adopted from https://github.com/paradigmxyz/reth/blob/e83d3aa/crates/rpc/rpc/src/debug.rs
+    fn trace_tx(
+        &self,
+        block_env: &BlockEnv,
+        tx_env: TxEnv,
+        cfg_env: CfgEnv,
+        context: &C,
+        opts: &GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(GethTrace, ResultAndState), EthApiError> {
+        let GethDebugTracingOptions {
+            config,
+            tracer,
+            tracer_config,
+            ..
+        } = opts.clone();
+
+        let mut inspector =
+            TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+        let mut precompiles = self.module_precompiles(context, working_set);
+        let res = executor::inspect(
+            self.get_db(working_set),
+            block_env,
+            tx_env,
+            cfg_env,
+            &mut precompiles,
+            &mut inspector,
+        )?;
+        let gas_used = res.result.gas_used();
+
+        let trace = match tracer {
+            // The default struct logger
+            None => {
+                let return_value = match &res.result {
+                    ExecutionResult::Success { output, .. } => output.clone().into_data(),
+                    ExecutionResult::Revert { output, .. } => output.clone(),
+                    ExecutionResult::Halt { .. } => Default::default(),
+                };
+                inspector
+                    .into_geth_builder()
+                    .geth_traces(gas_used, return_value.into(), config)
+                    .into()
+            }
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer)) => {
+                let call_config = tracer_config
+                    .into_call_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                inspector
+                    .into_geth_builder()
+                    .geth_call_traces(call_config, gas_used)
+                    .into()
+            }
+            Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::PreStateTracer,
+            )) => {
+                let prestate_config = tracer_config
+                    .into_pre_state_config()
+                    .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                let prestate = self.prestate_snapshot(&res.state, working_set);
+                inspector
+                    .into_geth_builder()
+                    .geth_prestate_traces(&res, prestate_config, prestate)
+                    .unwrap_or_else(|never| match never {})
+                    .into()
+            }
+            Some(_) => NoopFrame::default().into(),
+        };
+
+        Ok((trace, res))
+    }
+
+    /// Copies the accounts and storage slots touched by a transaction, as they were before it.
+    fn prestate_snapshot(
+        &self,
+        state: &State,
+        working_set: &mut WorkingSet<C>,
+    ) -> CacheDB<EmptyDB> {
+        let mut evm_db = self.get_db(working_set);
+        let mut snapshot = CacheDB::new(EmptyDB::default());
+
+        for (address, account) in state {
+            let Some(mut info) = evm_db.basic(*address).unwrap() else {
+                continue;
+            };
+            if info.code_hash != KECCAK_EMPTY {
+                info.code = Some(evm_db.code_by_hash(info.code_hash).unwrap());
+            }
+            snapshot.insert_account_info(*address, info);
+
+            for slot in account.storage.keys() {
+                let value = evm_db.storage(*address, *slot).unwrap();
+                snapshot
+                    .insert_account_storage(*address, *slot, value)
+                    .unwrap();
+            }
+        }
+        snapshot
+    }
+}
+
+/// Only the default struct logger and the call, prestate and noop tracers are supported.
+fn ensure_supported_tracer(opts: &GethDebugTracingOptions) -> Result<(), EthApiError> {
+    match &opts.tracer {
+        None
+        | Some(GethDebugTracerType::BuiltInTracer(
+            GethDebugBuiltInTracerType::CallTracer
+            | GethDebugBuiltInTracerType::PreStateTracer
+            | GethDebugBuiltInTracerType::NoopTracer,
+        )) => Ok(()),
+        Some(_) => Err(EthApiError::Unsupported(
+            "Only the default, callTracer, prestateTracer and noopTracer tracers are supported",
+        )),
+    }
+}
+
+fn replay_error(error: impl ToString) -> EthApiError {
+    EthApiError::Internal(RethError::Custom(format!(
+        "The block can't be replayed: {}",
+        error.to_string()
+    )))
+}
@@ -1,10 +1,10 @@
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 #[cfg(feature = "native")]
 use sov_modules_api::macros::CliWalletArg;
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
-use crate::SequencerRegistry;
+use crate::{PendingExit, SequencerRegistry, SlashingRecord};
 
 /// This enumeration represents the available call messages for interacting with
 /// the `sov-sequencer-registry` module.
@@ -25,11 +25,27 @@ pub enum CallMessage {
         /// The raw Da address of the sequencer you're registering.
         da_address: Vec<u8>,
     },
-    /// Remove a sequencer from the sequencer registry.
+    /// Start removing a sequencer from the sequencer registry.
+    ///
+    /// The sequencer stays registered, and its bond can still be slashed,
+    /// until the unbonding period has elapsed and the bond is withdrawn.
     Exit {
         /// The raw Da address of the sequencer you're removing.
         da_address: Vec<u8>,
     },
+    /// Remove a sequencer whose unbonding period has elapsed from the
+    /// sequencer registry and return its bond.
+    Withdraw {
+        /// The raw Da address of the sequencer you're removing.
+        da_address: Vec<u8>,
+    },
+    /// Lock additional coins in the bond of a registered sequencer.
+    IncreaseBond {
+        /// The raw Da address of the sequencer.
+        da_address: Vec<u8>,
+        /// The amount of coins to add to the bond.
+        amount: u64,
+    },
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
@@ -50,8 +66,6 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<CallResponse> {
-        let locker = &self.address;
-        let coins = self.coins_to_lock.get_or_err(working_set)?;
         let sequencer = context.sender();
 
         let belongs_to = self
@@ -62,17 +76,163 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
             bail!("Unauthorized exit attempt");
         }
 
-        self.delete(da_address, working_set);
+        if self.pending_exits.get(da_address, working_set).is_some() {
+            bail!("sequencer {} is already exiting", da_address);
+        }
+
+        let requested_at = context.slot_height();
+        let unbonding_period = self.get_unbonding_period(working_set);
+        self.pending_exits.set(
+            da_address,
+            &PendingExit {
+                requested_at,
+                withdrawable_at: requested_at.saturating_add(unbonding_period),
+            },
+            working_set,
+        );
+
+        // An exiting sequencer should not keep its priority while unbonding.
+        self.remove_preferred_sequencer(da_address, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn increase_bond(
+        &self,
+        da_address: &Da::Address,
+        amount: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let locker = &self.address;
+        let sequencer = context.sender();
+
+        let belongs_to = self
+            .allowed_sequencers
+            .get_or_err(da_address, working_set)?;
+
+        if sequencer != &belongs_to {
+            bail!("Unauthorized bond increase attempt");
+        }
+
+        if self.pending_exits.get(da_address, working_set).is_some() {
+            bail!("sequencer {} is exiting", da_address);
+        }
+
+        let mut coins = self.coins_to_lock.get_or_err(working_set)?;
+        coins.amount = amount;
+        self.bank
+            .transfer_from(sequencer, locker, coins, working_set)?;
+
+        let bond = self.bonds.get_or_err(da_address, working_set)?;
+        self.bonds
+            .set(da_address, &bond.saturating_add(amount), working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn withdraw(
+        &self,
+        da_address: &Da::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let locker = &self.address;
+        let coins = self
+            .get_bond(da_address, working_set)
+            .ok_or_else(|| anyhow!("sequencer {} is not registered", da_address))?;
+        let sequencer = context.sender();
+
+        let belongs_to = self
+            .allowed_sequencers
+            .get_or_err(da_address, working_set)?;
+
+        if sequencer != &belongs_to {
+            bail!("Unauthorized withdraw attempt");
+        }
+
+        let pending_exit = self
+            .pending_exits
+            .get(da_address, working_set)
+            .ok_or_else(|| anyhow!("sequencer {} has not requested an exit", da_address))?;
+
+        if context.slot_height() < pending_exit.withdrawable_at {
+            bail!(
+                "bond of sequencer {} is locked until slot {}",
+                da_address,
+                pending_exit.withdrawable_at
+            );
+        }
 
         self.bank
             .transfer_from(locker, sequencer, coins, working_set)?;
 
+        self.delete(da_address, working_set);
+
         Ok(CallResponse::default())
     }
 
+    /// Removes the sequencer from the registry and burns its bond, or sends
+    /// it to the configured recipient.
+    pub(crate) fn slash(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let rollup_address = match self.allowed_sequencers.get(da_address, working_set) {
+            Some(rollup_address) => rollup_address,
+            // Nothing to slash.
+            None => return Ok(()),
+        };
+        let was_unbonding = self.pending_exits.get(da_address, working_set).is_some();
+        let coins = self
+            .get_bond(da_address, working_set)
+            .ok_or_else(|| anyhow!("sequencer {} has no bond", da_address))?;
+
+        // The bond is moved before the sequencer is removed, so that a failed transfer
+        // leaves its stake record in place.
+        let locker = &self.address;
+        let recipient = self.slashed_bond_recipient.get(working_set);
+        match &recipient {
+            Some(recipient) => {
+                self.bank
+                    .transfer_from(locker, recipient, coins.clone(), working_set)?;
+            }
+            None => self.bank.burn(coins.clone(), locker, working_set)?,
+        }
+
+        self.delete(da_address, working_set);
+
+        let mut history = self.get_slashing_history(da_address, working_set);
+        history.push(SlashingRecord {
+            rollup_address,
+            amount: coins.amount,
+            recipient,
+            was_unbonding,
+        });
+        self.slashing_history.set(da_address, &history, working_set);
+
+        Ok(())
+    }
+
     pub(crate) fn delete(&self, da_address: &Da::Address, working_set: &mut WorkingSet<C>) {
         self.allowed_sequencers.delete(da_address, working_set);
+        self.pending_exits.delete(da_address, working_set);
+        self.bonds.delete(da_address, working_set);
+        self.leader_credits.delete(da_address, working_set);
 
+        let mut sequencers = self.sequencers.get(working_set).unwrap_or_default();
+        sequencers.retain(|sequencer| sequencer != da_address);
+        self.sequencers.set(&sequencers, working_set);
+
+        self.remove_preferred_sequencer(da_address, working_set);
+    }
+
+    fn remove_preferred_sequencer(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
         if let Some(preferred_sequencer) = self.preferred_sequencer.get(working_set) {
             if da_address == &preferred_sequencer {
                 self.preferred_sequencer.delete(working_set);
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
 
-use crate::SequencerRegistry;
+use crate::{LeaderScheduleConfig, SequencerRegistry};
 
 /// Genesis configuration for the [`SequencerRegistry`] module.
 ///
@@ -43,6 +43,11 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
         self.coins_to_lock.set(&config.coins_to_lock, working_set);
+        self.unbonding_period
+            .set(&config.unbonding_period, working_set);
+        if let Some(recipient) = &config.slashed_bond_recipient {
+            self.slashed_bond_recipient.set(recipient, working_set);
+        }
         self.register_sequencer(
             &config.seq_da_address,
             &config.seq_rollup_address,
@@ -52,6 +57,15 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
             self.preferred_sequencer
                 .set(&config.seq_da_address, working_set);
         }
+        if let Some(leader_schedule) = &config.leader_schedule {
+            self.leader_schedule_config
+                .set(leader_schedule, working_set);
+            if self.preferred_sequencer.get(working_set).is_none() {
+                if let Some(leader) = self.elect_leader(None, working_set) {
+                    self.preferred_sequencer.set(&leader, working_set);
+                }
+            }
+        }
 
         Ok(())
     }
@@ -66,7 +80,7 @@ mod tests {
     use sov_modules_api::default_context::DefaultContext;
     use sov_modules_api::{AddressBech32, Spec};
 
-    use crate::SequencerConfig;
+    use crate::{LeaderScheduleConfig, SequencerConfig};
 
     #[test]
     fn test_config_serialization() {
@@ -97,6 +111,12 @@ mod tests {
             seq_da_address: seq_da_addreess,
             coins_to_lock: coins,
             is_preferred_sequencer: true,
+            unbonding_period: 10,
+            slashed_bond_recipient: None,
+            leader_schedule: Some(LeaderScheduleConfig {
+                epoch_length: 100,
+                max_missed_slots: 3,
+            }),
         };
 
         let data = r#"
@@ -107,7 +127,13 @@ mod tests {
                 "amount":50,
                 "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
             },
-            "is_preferred_sequencer":true
+            "is_preferred_sequencer":true,
+            "unbonding_period":10,
+            "slashed_bond_recipient":null,
+            "leader_schedule":{
+                "epoch_length":100,
+                "max_missed_slots":3
+            }
         }"#;
 
         let parsed_config: SequencerConfig<DefaultContext, MockDaSpec> =
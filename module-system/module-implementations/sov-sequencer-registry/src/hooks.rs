@@ -37,7 +37,7 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ApplyBlobHooks<Da::BlobTransaction
         match result {
             SequencerOutcome::Completed => (),
             SequencerOutcome::Slashed { sequencer } => {
-                self.delete(&sequencer, working_set);
+                self.slash(&sequencer, working_set)?;
             }
         }
         Ok(())
@@ -3,6 +3,13 @@
 //! sequencer is supported. The sequencer's address and bond are registered
 //! during the rollup deployment.
 //!
+//! Optionally, the preferred sequencer rotates among the registered
+//! sequencers according to a [`LeaderScheduleConfig`].
+//!
+//! Exiting is a two-step process: a sequencer first requests an exit and can
+//! only withdraw its bond once the configured unbonding period has elapsed.
+//! Until then the sequencer can still be slashed.
+//!
 //! The module implements the [`sov_modules_api::hooks::ApplyBlobHooks`] trait.
 
 #![deny(missing_docs)]
@@ -11,14 +18,40 @@ mod genesis;
 mod hooks;
 #[cfg(feature = "native")]
 mod query;
+mod schedule;
 pub use call::*;
 pub use genesis::*;
 #[cfg(feature = "native")]
 pub use query::*;
+pub use schedule::*;
+use serde::{Deserialize, Serialize};
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
 use sov_state::codec::BcsCodec;
 
+/// An exit that has been requested by a sequencer but not finalized yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingExit {
+    /// The slot height at which the exit was requested.
+    pub requested_at: u64,
+    /// The first slot height at which the bond can be withdrawn.
+    pub withdrawable_at: u64,
+}
+
+/// A record of a sequencer being slashed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct SlashingRecord<C: sov_modules_api::Context> {
+    /// The rollup address the slashed sequencer was registered with.
+    pub rollup_address: C::Address,
+    /// The amount of the bond that was slashed.
+    pub amount: u64,
+    /// The address the slashed bond was sent to, or [`None`] if it was burned.
+    pub recipient: Option<C::Address>,
+    /// Whether the sequencer was unbonding when it was slashed.
+    pub was_unbonding: bool,
+}
+
 /// The `sov-sequencer-registry` module `struct`.
 #[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
 #[derive(Clone, ModuleInfo)]
@@ -53,6 +86,49 @@ pub struct SequencerRegistry<C: sov_modules_api::Context, Da: sov_modules_api::D
     /// allowed to exit.
     #[state]
     pub(crate) coins_to_lock: StateValue<sov_bank::Coins<C>>,
+
+    /// The amount of tokens each sequencer has locked. This is at least
+    /// [`SequencerRegistry::coins_to_lock`], but sequencers can increase it
+    /// to get a bigger share of the leader schedule.
+    #[state]
+    pub(crate) bonds: StateMap<Da::Address, u64, BcsCodec>,
+
+    /// The DA addresses of all registered sequencers, in registration order.
+    #[state]
+    pub(crate) sequencers: StateValue<Vec<Da::Address>, BcsCodec>,
+
+    /// Optional configuration for rotating the preferred sequencer.
+    /// If not set, the preferred sequencer is static.
+    #[state]
+    pub(crate) leader_schedule_config: StateValue<LeaderScheduleConfig>,
+
+    /// The epoch of the leader schedule that is currently in progress.
+    #[state]
+    pub(crate) current_epoch: StateValue<Epoch>,
+
+    /// The credits of each sequencer in the weighted round-robin leader election.
+    #[state]
+    pub(crate) leader_credits: StateMap<Da::Address, i128, BcsCodec>,
+
+    /// The number of slots a sequencer has to wait after requesting an exit
+    /// before it can withdraw its bond.
+    #[state]
+    pub(crate) unbonding_period: StateValue<u64>,
+
+    /// Sequencers that have requested an exit and are waiting for the
+    /// unbonding period to elapse. They remain in
+    /// [`SequencerRegistry::allowed_sequencers`] and can still be slashed.
+    #[state]
+    pub(crate) pending_exits: StateMap<Da::Address, PendingExit, BcsCodec>,
+
+    /// Optional recipient of slashed bonds.
+    /// If not set, slashed bonds are burned.
+    #[state]
+    pub(crate) slashed_bond_recipient: StateValue<C::Address>,
+
+    /// All the slashings that happened to a given sequencer.
+    #[state]
+    pub(crate) slashing_history: StateMap<Da::Address, Vec<SlashingRecord<C>>, BcsCodec>,
 }
 
 /// Result of applying a blob, from sequencer's point of view.
@@ -97,6 +173,14 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> sov_modules_api::
                 let da_address = Da::Address::try_from(&da_address)?;
                 self.exit(&da_address, context, working_set)?
             }
+            CallMessage::Withdraw { da_address } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.withdraw(&da_address, context, working_set)?
+            }
+            CallMessage::IncreaseBond { da_address, amount } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.increase_bond(&da_address, amount, context, working_set)?
+            }
         })
     }
 }
@@ -123,14 +207,34 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         let locker = &self.address;
         let coins = self.coins_to_lock.get_or_err(working_set)?;
         self.bank
-            .transfer_from(rollup_address, locker, coins, working_set)?;
+            .transfer_from(rollup_address, locker, coins.clone(), working_set)?;
 
         self.allowed_sequencers
             .set(da_address, rollup_address, working_set);
+        self.bonds.set(da_address, &coins.amount, working_set);
+
+        let mut sequencers = self.sequencers.get(working_set).unwrap_or_default();
+        sequencers.push(da_address.clone());
+        self.sequencers.set(&sequencers, working_set);
 
         Ok(())
     }
 
+    /// Returns the bond locked by the sequencer with the given DA address, or
+    /// [`None`] if it isn't registered.
+    pub fn get_bond(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<sov_bank::Coins<C>> {
+        let amount = self.bonds.get(da_address, working_set)?;
+        let token_address = self.coins_to_lock.get(working_set)?.token_address;
+        Some(sov_bank::Coins {
+            amount,
+            token_address,
+        })
+    }
+
     /// Returns the preferred sequencer, or [`None`] it wasn't set.
     ///
     /// Read about [`SequencerConfig::is_preferred_sequencer`] to learn about
@@ -154,8 +258,47 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         })
     }
 
+    /// Returns the rollup address of the registered sequencer with the given DA address,
+    /// or [`None`] if it isn't registered.
+    pub fn get_sequencer_rollup_address(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<C::Address> {
+        self.allowed_sequencers.get(da_address, working_set)
+    }
+
     /// Checks whether `sender` is a registered sequencer.
+    ///
+    /// Sequencers that are unbonding are still allowed to submit blobs, since
+    /// their bond remains slashable until it is withdrawn.
     pub fn is_sender_allowed(&self, sender: &Da::Address, working_set: &mut WorkingSet<C>) -> bool {
         self.allowed_sequencers.get(sender, working_set).is_some()
     }
+
+    /// Returns the number of slots between an exit request and the bond withdrawal.
+    pub fn get_unbonding_period(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.unbonding_period.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the pending exit of the sequencer with the given DA address, if any.
+    pub fn get_pending_exit(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<PendingExit> {
+        self.pending_exits.get(da_address, working_set)
+    }
+
+    /// Returns all the slashings of the sequencer with the given DA address,
+    /// oldest first.
+    pub fn get_slashing_history(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<SlashingRecord<C>> {
+        self.slashing_history
+            .get(da_address, working_set)
+            .unwrap_or_default()
+    }
 }
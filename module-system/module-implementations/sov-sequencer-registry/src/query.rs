@@ -3,7 +3,7 @@ use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
 
-use crate::SequencerRegistry;
+use crate::{Epoch, LeaderScheduleConfig, SequencerRegistry, SlashingRecord};
 
 /// The response type to the `getSequencerDddress` RPC method.
 #[cfg_attr(
@@ -16,6 +16,85 @@ pub struct SequencerAddressResponse<C: Context> {
     pub address: Option<C::Address>,
 }
 
+/// The bond status of a sequencer.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone)
+)]
+#[cfg_attr(
+    feature = "native",
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(Debug, Eq, PartialEq)]
+pub enum SequencerBondStatus<C: Context> {
+    /// The sequencer is not registered and has no bond locked.
+    NotBonded,
+    /// The sequencer is registered and its bond is locked.
+    Active {
+        /// The rollup address of the sequencer.
+        address: C::Address,
+        /// The locked bond.
+        bond: sov_bank::Coins<C>,
+    },
+    /// The sequencer has requested an exit and is waiting for the unbonding period to elapse.
+    Unbonding {
+        /// The rollup address of the sequencer.
+        address: C::Address,
+        /// The locked bond.
+        bond: sov_bank::Coins<C>,
+        /// The slot height at which the exit was requested.
+        requested_at: u64,
+        /// The first slot height at which the bond can be withdrawn.
+        withdrawable_at: u64,
+    },
+}
+
+/// The response type to the `getSequencerBondStatus` RPC method.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone)
+)]
+#[cfg_attr(
+    feature = "native",
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct SequencerBondStatusResponse<C: Context> {
+    /// The bond status of the requested sequencer.
+    pub status: SequencerBondStatus<C>,
+}
+
+/// The response type to the `getSlashingHistory` RPC method.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone)
+)]
+#[cfg_attr(
+    feature = "native",
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct SlashingHistoryResponse<C: Context> {
+    /// All the slashings of the requested sequencer, oldest first.
+    pub slashings: Vec<SlashingRecord<C>>,
+}
+
+/// The response type to the `getLeaderSchedule` RPC method.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize, Clone)
+)]
+#[cfg_attr(feature = "native", serde(bound = ""))]
+#[derive(Debug, Eq, PartialEq)]
+pub struct LeaderScheduleResponse<Da: sov_modules_api::DaSpec> {
+    /// The current preferred sequencer.
+    pub leader: Option<Da::Address>,
+    /// The epoch in progress, if the leader schedule is configured and has started.
+    pub epoch: Option<Epoch>,
+    /// The leader schedule configuration, or [`None`] if the preferred sequencer is static.
+    pub config: Option<LeaderScheduleConfig>,
+}
+
 #[rpc_gen(client, server, namespace = "sequencer")]
 impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
     /// Returns the rollup address of the sequencer with the given DA address.
@@ -31,4 +110,56 @@ impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
             address: self.allowed_sequencers.get(&da_address, working_set),
         })
     }
+
+    /// Returns the bond status of the sequencer with the given DA address.
+    #[rpc_method(name = "getSequencerBondStatus")]
+    pub fn sequencer_bond_status(
+        &self,
+        da_address: Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<SequencerBondStatusResponse<C>> {
+        let status = match self.allowed_sequencers.get(&da_address, working_set) {
+            None => SequencerBondStatus::NotBonded,
+            Some(address) => {
+                let bond = self
+                    .get_bond(&da_address, working_set)
+                    .expect("Registered sequencer must have a bond");
+                match self.pending_exits.get(&da_address, working_set) {
+                    None => SequencerBondStatus::Active { address, bond },
+                    Some(pending_exit) => SequencerBondStatus::Unbonding {
+                        address,
+                        bond,
+                        requested_at: pending_exit.requested_at,
+                        withdrawable_at: pending_exit.withdrawable_at,
+                    },
+                }
+            }
+        };
+        Ok(SequencerBondStatusResponse { status })
+    }
+
+    /// Returns all the slashings of the sequencer with the given DA address.
+    #[rpc_method(name = "getSlashingHistory")]
+    pub fn slashing_history(
+        &self,
+        da_address: Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<SlashingHistoryResponse<C>> {
+        Ok(SlashingHistoryResponse {
+            slashings: self.get_slashing_history(&da_address, working_set),
+        })
+    }
+
+    /// Returns the current preferred sequencer and the state of the leader schedule.
+    #[rpc_method(name = "getLeaderSchedule")]
+    pub fn leader_schedule(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<LeaderScheduleResponse<Da>> {
+        Ok(LeaderScheduleResponse {
+            leader: self.get_preferred_sequencer(working_set),
+            epoch: self.get_current_epoch(working_set),
+            config: self.get_leader_schedule_config(working_set),
+        })
+    }
 }
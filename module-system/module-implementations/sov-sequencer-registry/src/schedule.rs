@@ -0,0 +1,164 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+use tracing::info;
+
+use crate::SequencerRegistry;
+
+/// Configuration of the rotating preferred sequencer ("leader") schedule.
+///
+/// The registered sequencers take turns as the preferred sequencer. Each turn
+/// ("epoch") lasts [`LeaderScheduleConfig::epoch_length`] DA slots, and
+/// sequencers are elected with a frequency proportional to their bond.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct LeaderScheduleConfig {
+    /// The number of DA slots in an epoch.
+    pub epoch_length: u64,
+    /// The number of consecutive slots the preferred sequencer can miss
+    /// before the leadership is handed over to the next sequencer.
+    /// If `0`, the leader is never replaced before the end of its epoch.
+    pub max_missed_slots: u64,
+}
+
+/// An epoch of the leader schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct Epoch {
+    /// The number of the epoch, starting from `0`.
+    pub number: u64,
+    /// The first slot height of the epoch.
+    pub start_slot: u64,
+    /// The number of consecutive slots in which the leader did not submit a blob.
+    pub missed_slots: u64,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
+    /// Returns the leader schedule configuration, or [`None`] if the preferred
+    /// sequencer is static.
+    pub fn get_leader_schedule_config(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<LeaderScheduleConfig> {
+        self.leader_schedule_config.get(working_set)
+    }
+
+    /// Returns the epoch of the leader schedule that is currently in progress.
+    pub fn get_current_epoch(&self, working_set: &mut WorkingSet<C>) -> Option<Epoch> {
+        self.current_epoch.get(working_set)
+    }
+
+    /// Advances the leader schedule at the end of the slot with the given height.
+    ///
+    /// `leader_submitted_blob` tells whether the current preferred sequencer
+    /// submitted a blob in this slot. A new leader is elected for the next slot if:
+    /// 1. The current epoch is over.
+    /// 2. The leader missed [`LeaderScheduleConfig::max_missed_slots`] slots in a row.
+    /// 3. There is no leader, for example because it exited or was slashed.
+    ///
+    /// Does nothing if the leader schedule is not configured.
+    pub fn advance_leader_schedule(
+        &self,
+        slot_height: u64,
+        leader_submitted_blob: bool,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let config = match self.leader_schedule_config.get(working_set) {
+            Some(config) => config,
+            None => return,
+        };
+
+        let mut epoch = self.current_epoch.get(working_set).unwrap_or(Epoch {
+            number: 0,
+            start_slot: slot_height,
+            missed_slots: 0,
+        });
+
+        let leader = self.preferred_sequencer.get(working_set);
+        if leader.is_some() {
+            if leader_submitted_blob {
+                epoch.missed_slots = 0;
+            } else {
+                epoch.missed_slots += 1;
+            }
+        }
+
+        let epoch_is_over =
+            slot_height.saturating_add(1) >= epoch.start_slot.saturating_add(config.epoch_length);
+        let leader_is_absent =
+            config.max_missed_slots > 0 && epoch.missed_slots >= config.max_missed_slots;
+
+        if leader.is_none() || epoch_is_over || leader_is_absent {
+            // A leader that keeps missing slots shouldn't be re-elected right away.
+            let excluded = if leader_is_absent {
+                leader.as_ref()
+            } else {
+                None
+            };
+
+            if let Some(new_leader) = self.elect_leader(excluded, working_set) {
+                info!(
+                    "Sequencer {} is the preferred sequencer of epoch {}",
+                    new_leader,
+                    epoch.number + 1
+                );
+                self.preferred_sequencer.set(&new_leader, working_set);
+                epoch = Epoch {
+                    number: epoch.number + 1,
+                    start_slot: slot_height + 1,
+                    missed_slots: 0,
+                };
+            }
+        }
+
+        self.current_epoch.set(&epoch, working_set);
+    }
+
+    /// Elects the next leader among the eligible sequencers using smooth
+    /// weighted round-robin, so that each sequencer leads a share of the epochs
+    /// proportional to its bond. Sequencers that are unbonding are not eligible.
+    ///
+    /// `excluded` is skipped unless it is the only eligible sequencer.
+    pub(crate) fn elect_leader(
+        &self,
+        excluded: Option<&Da::Address>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Da::Address> {
+        let mut candidates = Vec::new();
+        for sequencer in self.sequencers.get(working_set).unwrap_or_default() {
+            if self.pending_exits.get(&sequencer, working_set).is_some() {
+                continue;
+            }
+            if let Some(bond) = self.bonds.get(&sequencer, working_set) {
+                candidates.push((sequencer, bond));
+            }
+        }
+
+        if candidates.len() > 1 {
+            if let Some(excluded) = excluded {
+                candidates.retain(|(sequencer, _)| sequencer != excluded);
+            }
+        }
+
+        let total_weight: i128 = candidates.iter().map(|(_, bond)| *bond as i128).sum();
+        let mut elected: Option<(Da::Address, i128)> = None;
+        for (sequencer, bond) in candidates {
+            let credits = self
+                .leader_credits
+                .get(&sequencer, working_set)
+                .unwrap_or_default()
+                + bond as i128;
+            self.leader_credits.set(&sequencer, &credits, working_set);
+
+            // Ties are broken by registration order.
+            match &elected {
+                Some((_, max_credits)) if *max_credits >= credits => {}
+                _ => elected = Some((sequencer, credits)),
+            }
+        }
+
+        let (leader, credits) = elected?;
+        self.leader_credits
+            .set(&leader, &(credits - total_weight), working_set);
+        Some(leader)
+    }
+}
@@ -21,6 +21,7 @@ pub const UNKNOWN_SEQUENCER_DA_ADDRESS: [u8; 32] = [3; 32];
 pub const LOW_FUND_KEY: &str = "zero_funds";
 pub const INITIAL_BALANCE: u64 = 210;
 pub const LOCKED_AMOUNT: u64 = 200;
+pub const UNBONDING_PERIOD: u64 = 5;
 
 pub struct TestSequencer {
     pub bank: sov_bank::Bank<C>,
@@ -63,6 +64,17 @@ impl TestSequencer {
             working_set,
         )
     }
+
+    #[allow(dead_code)]
+    pub fn query_total_supply(&mut self, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.bank
+            .supply_of(
+                self.sequencer_config.coins_to_lock.token_address,
+                working_set,
+            )
+            .unwrap()
+            .amount
+    }
 }
 
 pub fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
@@ -100,6 +112,9 @@ pub fn create_sequencer_config(
             token_address,
         },
         is_preferred_sequencer: false,
+        unbonding_period: UNBONDING_PERIOD,
+        slashed_bond_recipient: None,
+        leader_schedule: None,
     }
 }
 
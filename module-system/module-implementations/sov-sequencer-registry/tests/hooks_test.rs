@@ -1,9 +1,9 @@
 use helpers::*;
 use sov_mock_da::{MockAddress, MockBlob};
 use sov_modules_api::hooks::ApplyBlobHooks;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::{Context, Module, ModuleInfo, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
-use sov_sequencer_registry::{SequencerOutcome, SequencerRegistry};
+use sov_sequencer_registry::{CallMessage, SequencerOutcome, SequencerRegistry, SlashingRecord};
 
 mod helpers;
 
@@ -245,3 +245,168 @@ fn end_blob_hook_slash_unknown_sequencer() {
         .unwrap();
     assert!(resp.address.is_none());
 }
+
+#[test]
+fn end_blob_hook_slash_burns_bond() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    let supply_before = test_sequencer.query_total_supply(working_set).unwrap();
+
+    let genesis_sequencer_da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    <SequencerRegistry<C, Da> as ApplyBlobHooks<MockBlob>>::end_blob_hook(
+        &test_sequencer.registry,
+        SequencerOutcome::Slashed {
+            sequencer: genesis_sequencer_da_address,
+        },
+        working_set,
+    )
+    .unwrap();
+
+    let supply_after = test_sequencer.query_total_supply(working_set).unwrap();
+    assert_eq!(supply_before - LOCKED_AMOUNT, supply_after);
+
+    let resp = test_sequencer
+        .registry
+        .slashing_history(genesis_sequencer_da_address, working_set)
+        .unwrap();
+    assert_eq!(
+        vec![SlashingRecord {
+            rollup_address: test_sequencer.sequencer_config.seq_rollup_address,
+            amount: LOCKED_AMOUNT,
+            recipient: None,
+            was_unbonding: false,
+        }],
+        resp.slashings
+    );
+}
+
+#[test]
+fn end_blob_hook_slash_redistributes_bond() {
+    let mut test_sequencer = create_test_sequencer();
+    let recipient = generate_address("slashing_recipient");
+    test_sequencer.sequencer_config.slashed_bond_recipient = Some(recipient);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    let supply_before = test_sequencer.query_total_supply(working_set).unwrap();
+
+    <SequencerRegistry<C, Da> as ApplyBlobHooks<MockBlob>>::end_blob_hook(
+        &test_sequencer.registry,
+        SequencerOutcome::Slashed {
+            sequencer: MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+        },
+        working_set,
+    )
+    .unwrap();
+
+    let supply_after = test_sequencer.query_total_supply(working_set).unwrap();
+    assert_eq!(supply_before, supply_after);
+    let resp = test_sequencer
+        .query_balance(recipient, working_set)
+        .unwrap();
+    assert_eq!(Some(LOCKED_AMOUNT), resp.amount);
+}
+
+#[test]
+fn end_blob_hook_slash_unbonding_sequencer() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let genesis_sequencer_da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
+    let reward_address = generate_address(REWARD_SEQUENCER_KEY);
+    let sender_context = C::new(sequencer_address, reward_address, 1);
+    test_sequencer
+        .registry
+        .call(
+            CallMessage::Exit {
+                da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Sequencer exit has failed");
+
+    // A blob from the exiting sequencer is still processed and can be slashed
+    let mut test_blob = MockBlob::new(Vec::new(), genesis_sequencer_da_address, [0_u8; 32]);
+    test_sequencer
+        .registry
+        .begin_blob_hook(&mut test_blob, working_set)
+        .unwrap();
+    <SequencerRegistry<C, Da> as ApplyBlobHooks<MockBlob>>::end_blob_hook(
+        &test_sequencer.registry,
+        SequencerOutcome::Slashed {
+            sequencer: genesis_sequencer_da_address,
+        },
+        working_set,
+    )
+    .unwrap();
+
+    assert!(test_sequencer
+        .registry
+        .get_pending_exit(&genesis_sequencer_da_address, working_set)
+        .is_none());
+    let history = test_sequencer
+        .registry
+        .get_slashing_history(&genesis_sequencer_da_address, working_set);
+    assert_eq!(1, history.len());
+    assert!(history[0].was_unbonding);
+
+    // The bond is gone, so the withdrawal fails
+    let withdraw_context = C::new(sequencer_address, reward_address, 1 + UNBONDING_PERIOD);
+    let result = test_sequencer.registry.call(
+        CallMessage::Withdraw {
+            da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
+        },
+        &withdraw_context,
+        working_set,
+    );
+    assert!(result.is_err());
+    let resp = test_sequencer.query_balance_via_bank(working_set).unwrap();
+    assert_eq!(INITIAL_BALANCE - LOCKED_AMOUNT, resp.amount.unwrap());
+}
+
+#[test]
+fn end_blob_hook_failed_slash_keeps_sequencer() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    // Empty the locker, so that the bond can't be burned.
+    let locker = *test_sequencer.registry.address();
+    let mut coins = test_sequencer.sequencer_config.coins_to_lock.clone();
+    coins.amount = LOCKED_AMOUNT;
+    test_sequencer
+        .bank
+        .transfer_from(&locker, &generate_address("drain"), coins, working_set)
+        .unwrap();
+
+    let genesis_sequencer_da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let result = <SequencerRegistry<C, Da> as ApplyBlobHooks<MockBlob>>::end_blob_hook(
+        &test_sequencer.registry,
+        SequencerOutcome::Slashed {
+            sequencer: genesis_sequencer_da_address,
+        },
+        working_set,
+    );
+    assert!(result.is_err());
+
+    let resp = test_sequencer
+        .registry
+        .sequencer_address(genesis_sequencer_da_address, working_set)
+        .unwrap();
+    assert_eq!(
+        Some(test_sequencer.sequencer_config.seq_rollup_address),
+        resp.address
+    );
+    let resp = test_sequencer
+        .registry
+        .slashing_history(genesis_sequencer_da_address, working_set)
+        .unwrap();
+    assert!(resp.slashings.is_empty());
+}
@@ -0,0 +1,260 @@
+use helpers::*;
+use sov_mock_da::MockAddress;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_sequencer_registry::{CallMessage, LeaderScheduleConfig};
+
+mod helpers;
+
+const WEIGHTED_SEQUENCER_KEY: &str = "weighted_sequencer";
+const WEIGHTED_SEQUENCER_DA_ADDRESS: [u8; 32] = [5; 32];
+
+fn create_scheduled_sequencer(leader_schedule: LeaderScheduleConfig) -> TestSequencer {
+    let mut test_sequencer = create_test_sequencer();
+    test_sequencer.sequencer_config.leader_schedule = Some(leader_schedule);
+    test_sequencer.bank_config.tokens[0]
+        .address_and_balances
+        .push((generate_address(WEIGHTED_SEQUENCER_KEY), LOCKED_AMOUNT * 3));
+    test_sequencer
+}
+
+fn register(
+    test_sequencer: &TestSequencer,
+    key: &str,
+    da_address: [u8; 32],
+    working_set: &mut WorkingSet<C>,
+) {
+    let sender_context = C::new(
+        generate_address(key),
+        generate_address(REWARD_SEQUENCER_KEY),
+        1,
+    );
+    test_sequencer
+        .registry
+        .call(
+            CallMessage::Register {
+                da_address: da_address.to_vec(),
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Sequencer registration has failed");
+}
+
+#[test]
+fn test_static_preferred_sequencer_without_schedule() {
+    let mut test_sequencer = create_test_sequencer();
+    test_sequencer.sequencer_config.is_preferred_sequencer = true;
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    register(
+        &test_sequencer,
+        ANOTHER_SEQUENCER_KEY,
+        ANOTHER_SEQUENCER_DA_ADDRESS,
+        working_set,
+    );
+
+    for slot in 1..10 {
+        test_sequencer
+            .registry
+            .advance_leader_schedule(slot, false, working_set);
+        assert_eq!(
+            Some(MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS)),
+            test_sequencer.registry.get_preferred_sequencer(working_set)
+        );
+    }
+    assert!(test_sequencer
+        .registry
+        .get_current_epoch(working_set)
+        .is_none());
+}
+
+#[test]
+fn test_leader_rotates_every_epoch() {
+    let mut test_sequencer = create_scheduled_sequencer(LeaderScheduleConfig {
+        epoch_length: 3,
+        max_missed_slots: 0,
+    });
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    // The genesis sequencer is the only one, so it is elected at genesis
+    let genesis_sequencer = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let another_sequencer = MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS);
+    assert_eq!(
+        Some(genesis_sequencer),
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+
+    register(
+        &test_sequencer,
+        ANOTHER_SEQUENCER_KEY,
+        ANOTHER_SEQUENCER_DA_ADDRESS,
+        working_set,
+    );
+
+    let mut leaders = Vec::new();
+    for slot in 1..=12 {
+        leaders.push(
+            test_sequencer
+                .registry
+                .get_preferred_sequencer(working_set)
+                .unwrap(),
+        );
+        test_sequencer
+            .registry
+            .advance_leader_schedule(slot, true, working_set);
+    }
+
+    let g = genesis_sequencer;
+    let a = another_sequencer;
+    // Both sequencers have the same bond, so they alternate after the genesis sequencer's first term
+    assert_eq!(vec![g, g, g, g, g, g, a, a, a, g, g, g], leaders);
+    let epoch = test_sequencer
+        .registry
+        .get_current_epoch(working_set)
+        .unwrap();
+    assert_eq!(4, epoch.number);
+    assert_eq!(13, epoch.start_slot);
+}
+
+#[test]
+fn test_leader_schedule_is_weighted_by_bond() {
+    let mut test_sequencer = create_scheduled_sequencer(LeaderScheduleConfig {
+        epoch_length: 1,
+        max_missed_slots: 0,
+    });
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    register(
+        &test_sequencer,
+        WEIGHTED_SEQUENCER_KEY,
+        WEIGHTED_SEQUENCER_DA_ADDRESS,
+        working_set,
+    );
+    let sender_context = C::new(
+        generate_address(WEIGHTED_SEQUENCER_KEY),
+        generate_address(REWARD_SEQUENCER_KEY),
+        1,
+    );
+    test_sequencer
+        .registry
+        .call(
+            CallMessage::IncreaseBond {
+                da_address: WEIGHTED_SEQUENCER_DA_ADDRESS.to_vec(),
+                amount: LOCKED_AMOUNT,
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Bond increase has failed");
+
+    let weighted_sequencer = MockAddress::from(WEIGHTED_SEQUENCER_DA_ADDRESS);
+    let mut weighted_epochs = 0;
+    for slot in 1..=30 {
+        test_sequencer
+            .registry
+            .advance_leader_schedule(slot, true, working_set);
+        if test_sequencer.registry.get_preferred_sequencer(working_set) == Some(weighted_sequencer)
+        {
+            weighted_epochs += 1;
+        }
+    }
+
+    // The weighted sequencer has twice the bond, so it leads two epochs out of three
+    assert_eq!(20, weighted_epochs);
+}
+
+#[test]
+fn test_leader_replaced_after_missed_slots() {
+    let mut test_sequencer = create_scheduled_sequencer(LeaderScheduleConfig {
+        epoch_length: 100,
+        max_missed_slots: 2,
+    });
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    register(
+        &test_sequencer,
+        ANOTHER_SEQUENCER_KEY,
+        ANOTHER_SEQUENCER_DA_ADDRESS,
+        working_set,
+    );
+
+    let genesis_sequencer = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    test_sequencer
+        .registry
+        .advance_leader_schedule(1, true, working_set);
+    test_sequencer
+        .registry
+        .advance_leader_schedule(2, false, working_set);
+    assert_eq!(
+        Some(genesis_sequencer),
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+
+    test_sequencer
+        .registry
+        .advance_leader_schedule(3, false, working_set);
+    assert_eq!(
+        Some(MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS)),
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+    let epoch = test_sequencer
+        .registry
+        .get_current_epoch(working_set)
+        .unwrap();
+    assert_eq!(1, epoch.number);
+    assert_eq!(4, epoch.start_slot);
+    assert_eq!(0, epoch.missed_slots);
+}
+
+#[test]
+fn test_exiting_leader_is_replaced() {
+    let mut test_sequencer = create_scheduled_sequencer(LeaderScheduleConfig {
+        epoch_length: 100,
+        max_missed_slots: 0,
+    });
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    register(
+        &test_sequencer,
+        ANOTHER_SEQUENCER_KEY,
+        ANOTHER_SEQUENCER_DA_ADDRESS,
+        working_set,
+    );
+
+    let sender_context = C::new(
+        generate_address(GENESIS_SEQUENCER_KEY),
+        generate_address(REWARD_SEQUENCER_KEY),
+        1,
+    );
+    test_sequencer
+        .registry
+        .call(
+            CallMessage::Exit {
+                da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Sequencer exit has failed");
+    assert!(test_sequencer
+        .registry
+        .get_preferred_sequencer(working_set)
+        .is_none());
+
+    // Unbonding sequencers are not eligible
+    test_sequencer
+        .registry
+        .advance_leader_schedule(1, false, working_set);
+    assert_eq!(
+        Some(MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS)),
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+}
@@ -2,7 +2,7 @@ use helpers::*;
 use sov_mock_da::MockAddress;
 use sov_modules_api::{Context, Error, Module, ModuleInfo, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
-use sov_sequencer_registry::{CallMessage, SequencerRegistry};
+use sov_sequencer_registry::{CallMessage, SequencerBondStatus, SequencerRegistry};
 
 mod helpers;
 
@@ -10,7 +10,8 @@ mod helpers;
 // This test checks:
 //  - genesis sequencer is present after genesis
 //  - registration works, and funds are deducted
-//  - exit works and funds are returned
+//  - exit works, but funds are locked until the end of the unbonding period
+//  - withdrawal works and funds are returned
 #[test]
 fn test_registration_lifecycle() {
     let mut test_sequencer = create_test_sequencer();
@@ -26,6 +27,13 @@ fn test_registration_lifecycle() {
             .sequencer_address(MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS), working_set)
             .unwrap();
         assert_eq!(Some(sequencer_address), registry_response.address);
+        assert_eq!(
+            Some(sequencer_address),
+            test_sequencer.registry.get_sequencer_rollup_address(
+                &MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+                working_set
+            )
+        );
     }
 
     // Check normal lifecycle
@@ -47,6 +55,10 @@ fn test_registration_lifecycle() {
         .sequencer_address(da_address, working_set)
         .unwrap();
     assert!(registry_response_before.address.is_none());
+    assert!(test_sequencer
+        .registry
+        .get_sequencer_rollup_address(&da_address, working_set)
+        .is_none());
 
     let register_message = CallMessage::Register {
         da_address: da_address.as_ref().to_vec(),
@@ -85,13 +97,131 @@ fn test_registration_lifecycle() {
         .unwrap()
         .amount
         .unwrap();
-    assert_eq!(balance_before, balance_after_exit);
+    assert_eq!(balance_after_registration, balance_after_exit);
+
+    let status_after_exit = test_sequencer
+        .registry
+        .sequencer_bond_status(da_address, working_set)
+        .unwrap()
+        .status;
+    assert!(matches!(
+        status_after_exit,
+        SequencerBondStatus::Unbonding {
+            requested_at: 1,
+            withdrawable_at,
+            ..
+        } if withdrawable_at == 1 + UNBONDING_PERIOD
+    ));
+
+    let withdraw_context = C::new(sequencer_address, reward_address, 1 + UNBONDING_PERIOD);
+    let withdraw_message = CallMessage::Withdraw {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    test_sequencer
+        .registry
+        .call(withdraw_message, &withdraw_context, working_set)
+        .expect("Sequencer withdrawal has failed");
+
+    let balance_after_withdrawal = test_sequencer
+        .query_balance(sequencer_address, working_set)
+        .unwrap()
+        .amount
+        .unwrap();
+    assert_eq!(balance_before, balance_after_withdrawal);
 
-    let registry_response_after_exit = test_sequencer
+    let registry_response_after_withdrawal = test_sequencer
         .registry
         .sequencer_address(da_address, working_set)
         .unwrap();
-    assert!(registry_response_after_exit.address.is_none());
+    assert!(registry_response_after_withdrawal.address.is_none());
+    assert_eq!(
+        SequencerBondStatus::NotBonded,
+        test_sequencer
+            .registry
+            .sequencer_bond_status(da_address, working_set)
+            .unwrap()
+            .status
+    );
+}
+
+#[test]
+fn test_withdraw_before_unbonding_period() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
+    let reward_address = generate_address(REWARD_SEQUENCER_KEY);
+    let sender_context = C::new(sequencer_address, reward_address, 1);
+
+    let withdraw_message = CallMessage::Withdraw {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    let response =
+        test_sequencer
+            .registry
+            .call(withdraw_message.clone(), &sender_context, working_set);
+    assert_eq!(
+        format!("sequencer {} has not requested an exit", da_address),
+        response.err().unwrap().to_string()
+    );
+
+    let exit_message = CallMessage::Exit {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    test_sequencer
+        .registry
+        .call(exit_message, &sender_context, working_set)
+        .expect("Sequencer exit has failed");
+
+    let early_context = C::new(sequencer_address, reward_address, UNBONDING_PERIOD);
+    let response = test_sequencer
+        .registry
+        .call(withdraw_message, &early_context, working_set);
+    assert_eq!(
+        format!(
+            "bond of sequencer {} is locked until slot {}",
+            da_address,
+            1 + UNBONDING_PERIOD
+        ),
+        response.err().unwrap().to_string()
+    );
+
+    // The sequencer is still registered while unbonding
+    assert!(test_sequencer
+        .registry
+        .is_sender_allowed(&da_address, working_set));
+}
+
+#[test]
+fn test_exit_twice() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
+    let reward_address = generate_address(REWARD_SEQUENCER_KEY);
+    let sender_context = C::new(sequencer_address, reward_address, 1);
+
+    let exit_message = CallMessage::Exit {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    test_sequencer
+        .registry
+        .call(exit_message.clone(), &sender_context, working_set)
+        .expect("Sequencer exit has failed");
+
+    let response = test_sequencer
+        .registry
+        .call(exit_message, &sender_context, working_set);
+    assert_eq!(
+        format!("sequencer {} is already exiting", da_address),
+        response.err().unwrap().to_string()
+    );
 }
 
 #[test]
@@ -131,6 +131,7 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
                     &client,
                     account.address.clone(),
                     token_address.clone(),
+                    None,
                 )
                 .await
                 .context(BAD_RPC_URL)?;
@@ -189,6 +190,7 @@ async fn get_nonce_for_account<C: sov_modules_api::Context + Send + Sync + Seria
     Ok(match AccountsRpcClient::<C>::get_account(
         client,
         account.pub_key.clone(),
+        None,
     )
     .await
     .context(
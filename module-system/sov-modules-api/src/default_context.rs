@@ -7,7 +7,9 @@ use sov_modules_core::{Address, Context, PublicKey, Spec, TupleGasUnit};
 use sov_rollup_interface::RollupAddress;
 #[cfg(feature = "native")]
 use sov_state::ProverStorage;
-use sov_state::{ArrayWitness, DefaultStorageSpec, ZkStorage};
+use sov_state::{
+    ArrayWitness, CompressedStorageSpec, CompressedWitness, DefaultStorageSpec, ZkStorage,
+};
 
 #[cfg(feature = "native")]
 use crate::default_signature::private_key::DefaultPrivateKey;
@@ -103,6 +105,99 @@ impl Context for ZkDefaultContext {
     }
 }
 
+/// A native context whose storage produces a [`CompressedWitness`].
+#[cfg(feature = "native")]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompressedContext {
+    pub sender: Address,
+    pub sequencer: Address,
+    /// The height to report. This is set by the kernel when the context is created
+    visible_height: u64,
+}
+
+#[cfg(feature = "native")]
+impl Spec for CompressedContext {
+    type Address = Address;
+    type Storage =
+        ProverStorage<CompressedStorageSpec, sov_prover_storage_manager::SnapshotManager>;
+    type PrivateKey = DefaultPrivateKey;
+    type PublicKey = DefaultPublicKey;
+    type Hasher = sha2::Sha256;
+    type Signature = DefaultSignature;
+    type Witness = CompressedWitness;
+}
+
+#[cfg(feature = "native")]
+impl Context for CompressedContext {
+    type GasUnit = TupleGasUnit<2>;
+
+    fn sender(&self) -> &Self::Address {
+        &self.sender
+    }
+
+    fn sequencer(&self) -> &Self::Address {
+        &self.sequencer
+    }
+
+    fn new(sender: Self::Address, sequencer: Self::Address, height: u64) -> Self {
+        Self {
+            sender,
+            sequencer,
+            visible_height: height,
+        }
+    }
+
+    fn slot_height(&self) -> u64 {
+        self.visible_height
+    }
+}
+
+/// The zk counterpart of [`CompressedContext`], verifying a [`CompressedWitness`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZkCompressedContext {
+    pub sender: Address,
+    pub sequencer: Address,
+    /// The height to report. This is set by the kernel when the context is created
+    visible_height: u64,
+}
+
+impl Spec for ZkCompressedContext {
+    type Address = Address;
+    type Storage = ZkStorage<CompressedStorageSpec>;
+    #[cfg(feature = "native")]
+    type PrivateKey = DefaultPrivateKey;
+    type PublicKey = DefaultPublicKey;
+    type Hasher = sha2::Sha256;
+    type Signature = DefaultSignature;
+    type Witness = CompressedWitness;
+}
+
+impl Context for ZkCompressedContext {
+    type GasUnit = TupleGasUnit<2>;
+
+    fn sender(&self) -> &Self::Address {
+        &self.sender
+    }
+
+    fn sequencer(&self) -> &Self::Address {
+        &self.sequencer
+    }
+
+    fn new(sender: Self::Address, sequencer: Self::Address, height: u64) -> Self {
+        Self {
+            sender,
+            sequencer,
+            visible_height: height,
+        }
+    }
+
+    fn slot_height(&self) -> u64 {
+        self.visible_height
+    }
+}
+
 impl PublicKey for DefaultPublicKey {
     fn to_address<A: RollupAddress>(&self) -> A {
         let pub_key_hash = {
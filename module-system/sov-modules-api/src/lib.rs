@@ -35,7 +35,8 @@ pub use sov_modules_core::{
     archival_state, runtime, AccessoryWorkingSet, Address, AddressBech32, CallResponse, Context,
     DispatchCall, EncodeCall, GasUnit, Genesis, KernelModule, KernelWorkingSet, Module,
     ModuleCallJsonSchema, ModuleError, ModuleError as Error, ModuleInfo, ModulePrefix, PublicKey,
-    Signature, Spec, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet, WorkingSet,
+    Signature, Spec, StateCheckpoint, StateReaderAndWriter, ValueWithProof, VersionedWorkingSet,
+    WorkingSet, WorkingSetSnapshot,
 };
 pub use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 pub use sov_rollup_interface::services::da::SlotData;
@@ -57,6 +58,11 @@ pub mod da {
     pub use sov_rollup_interface::da::{BlockHeaderTrait, NanoSeconds, Time};
 }
 
+#[cfg(feature = "native")]
+pub mod rpc {
+    pub use sov_rollup_interface::rpc::{StateIdentifier, StateVersionProvider};
+}
+
 pub mod storage {
     pub use sov_rollup_interface::storage::HierarchicalStorageManager;
 }
@@ -1,6 +1,7 @@
 use jsonrpsee::types::ErrorObjectOwned;
-use sov_modules_core::{Context, Spec};
+use sov_modules_core::{Context, Spec, WorkingSet};
 use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::rpc::{StateIdentifier, StateVersionProvider};
 
 pub fn generate_address<C: Context>(key: &str) -> <C as Spec>::Address {
     let hash: [u8; 32] = <C as Spec>::Hasher::digest(key.as_bytes()).into();
@@ -14,3 +15,21 @@ pub fn to_jsonrpsee_error_object(err: impl ToString, message: &str) -> ErrorObje
         Some(err.to_string()),
     )
 }
+
+/// Creates a working set on top of the state identified by `state_id`,
+/// or on top of the latest state if `state_id` is `None`.
+pub fn working_set_at<C: Context>(
+    storage: <C as Spec>::Storage,
+    state_versions: &dyn StateVersionProvider,
+    state_id: Option<StateIdentifier>,
+) -> Result<WorkingSet<C>, ErrorObjectOwned> {
+    let mut working_set = WorkingSet::new(storage);
+    if let Some(state_id) = state_id {
+        let version = state_versions
+            .get_state_version(&state_id)
+            .map_err(|e| to_jsonrpsee_error_object(e, "Failed to resolve the state version"))?
+            .ok_or_else(|| to_jsonrpsee_error_object(format!("{:?}", state_id), "Unknown state"))?;
+        working_set.set_archival_version(version);
+    }
+    Ok(working_set)
+}
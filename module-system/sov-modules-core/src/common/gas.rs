@@ -91,4 +91,9 @@ where
 
         Ok(())
     }
+
+    /// Adds the provided funds back to the remaining funds.
+    pub fn refund(&mut self, funds: u64) {
+        self.remaining_funds = self.remaining_funds.saturating_add(funds);
+    }
 }
@@ -175,6 +175,59 @@ pub struct StorageProof<P> {
     pub proof: P,
 }
 
+/// A value computed from the provable state, along with proofs of all the
+/// storage entries it was computed from.
+///
+/// The proofs are borsh-encoded [`Storage::Proof`]s, so that this type doesn't
+/// depend on a particular [`Storage`] implementation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "sync", derive(Serialize, serde::Deserialize))]
+pub struct ValueWithProof<T> {
+    /// The value
+    pub value: T,
+    /// The proofs of the storage entries which the value was computed from
+    pub proofs: Vec<StorageProof<Vec<u8>>>,
+    /// The version of the state against which the entries are proven
+    pub version: Version,
+    /// The root of the state against which the entries are proven
+    #[cfg_attr(
+        feature = "sync",
+        serde(with = "sov_rollup_interface::rpc::utils::rpc_hex")
+    )]
+    pub state_root: Vec<u8>,
+}
+
+impl<T> ValueWithProof<T> {
+    /// Verifies all the proofs against the trusted `state_root`, returning the
+    /// proven storage entries. The value itself isn't checked: callers are
+    /// expected to recompute it from the returned entries.
+    pub fn verify<S: Storage>(
+        &self,
+        state_root: &S::Root,
+    ) -> Result<Vec<(StorageKey, Option<StorageValue>)>, anyhow::Error> {
+        anyhow::ensure!(
+            self.state_root.as_slice() == state_root.as_ref(),
+            "The proofs were generated against a different state root"
+        );
+
+        self.proofs
+            .iter()
+            .map(|storage_proof| {
+                let proof = S::Proof::try_from_slice(&storage_proof.proof)
+                    .map_err(|e| anyhow::anyhow!("Invalid proof encoding: {}", e))?;
+                S::open_proof(
+                    state_root.clone(),
+                    StorageProof {
+                        key: storage_proof.key.clone(),
+                        value: storage_proof.value.clone(),
+                        proof,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
 /// An interface for storing and retrieving values in the storage.
 pub trait Storage: Clone {
     /// The witness type for this storage instance.
@@ -274,6 +327,23 @@ pub trait Storage: Clone {
         proof: StorageProof<Self::Proof>,
     ) -> Result<(StorageKey, Option<StorageValue>), anyhow::Error>;
 
+    /// Proves the values of `keys` against the root of the state at `version`,
+    /// or at the latest committed version if `None`. Returns the proven version,
+    /// its root and the proofs, in the order of `keys`.
+    ///
+    /// # About proofs
+    /// This method is blanket-implemented to return an error. **Only native
+    /// execution environments** (i.e. outside of the zkVM), which have access
+    /// to the historical state, **SHOULD** override this method.
+    #[allow(clippy::type_complexity)]
+    fn prove_keys(
+        &self,
+        _keys: &[StorageKey],
+        _version: Option<Version>,
+    ) -> Result<(Version, Self::Root, Vec<StorageProof<Self::Proof>>), anyhow::Error> {
+        anyhow::bail!("This storage does not support proofs")
+    }
+
     /// Indicates if storage is empty or not.
     /// Useful during initialization.
     fn is_empty(&self) -> bool;
@@ -3,6 +3,7 @@
 use alloc::vec::Vec;
 use core::{fmt, mem};
 
+use borsh::BorshSerialize;
 pub use kernel_state::{KernelWorkingSet, VersionedWorkingSet};
 use sov_rollup_interface::maybestd::collections::HashMap;
 use sov_rollup_interface::stf::Event;
@@ -13,6 +14,7 @@ use crate::module::{Context, Spec};
 use crate::storage::{
     CacheKey, CacheValue, EncodeKeyLike, NativeStorage, OrderedReadsAndWrites, StateCodec,
     StateValueCodec, Storage, StorageInternalCache, StorageKey, StorageProof, StorageValue,
+    ValueWithProof,
 };
 use crate::Version;
 
@@ -307,6 +309,7 @@ impl<C: Context> StateCheckpoint<C> {
             gas_meter: self.gas_meter,
             archival_working_set: None,
             archival_accessory_working_set: None,
+            recorded_reads: None,
         }
     }
 
@@ -346,6 +349,7 @@ pub struct WorkingSet<C: Context> {
     gas_meter: GasMeter<C::GasUnit>,
     archival_working_set: Option<ArchivalJmtWorkingSet<C>>,
     archival_accessory_working_set: Option<ArchivalAccessoryWorkingSet<C>>,
+    recorded_reads: Option<Vec<StorageKey>>,
 }
 
 impl<C: Context> WorkingSet<C> {
@@ -439,6 +443,24 @@ impl<C: Context> WorkingSet<C> {
         }
     }
 
+    /// Records the changes made so far, so that the ones made afterwards can be discarded with
+    /// [`WorkingSet::revert_to_snapshot`] while keeping the working set.
+    pub fn snapshot(&self) -> WorkingSetSnapshot {
+        WorkingSetSnapshot {
+            writes: self.delta.writes.clone(),
+            accessory_writes: self.accessory_delta.writes.clone(),
+            events: self.events.len(),
+        }
+    }
+
+    /// Discards the changes and the events made since the `snapshot` was taken.
+    /// The gas charged in the meantime isn't given back.
+    pub fn revert_to_snapshot(&mut self, snapshot: &WorkingSetSnapshot) {
+        self.delta.writes = snapshot.writes.clone();
+        self.accessory_delta.writes = snapshot.accessory_writes.clone();
+        self.events.truncate(snapshot.events);
+    }
+
     /// Adds an event to the working set.
     pub fn add_event(&mut self, key: &str, value: &str) {
         self.events.push(Event::new(key, value));
@@ -471,6 +493,11 @@ impl<C: Context> WorkingSet<C> {
         self.gas_meter.charge_gas(gas)
     }
 
+    /// Gives back gas funds charged from the gas meter, when they are paid for otherwise.
+    pub fn refund_gas(&mut self, funds: u64) {
+        self.gas_meter.refund(funds)
+    }
+
     /// Fetches given value and provides a proof of it presence/absence.
     pub fn get_with_proof(
         &mut self,
@@ -482,10 +509,51 @@ impl<C: Context> WorkingSet<C> {
         // First inner is `RevertableWriter` and second inner is actually a `Storage` instance
         self.delta.inner.inner.get_with_proof(key)
     }
+
+    /// Starts recording the keys read from the provable state, so that they
+    /// can later be proven with [`WorkingSet::prove_recorded_reads`].
+    pub fn record_reads(&mut self) {
+        self.recorded_reads = Some(Vec::new());
+    }
+
+    /// Proves the values of the keys read since [`WorkingSet::record_reads`] was
+    /// called, against the root of the state this working set reads from, and
+    /// attaches the proofs to `value`.
+    pub fn prove_recorded_reads<T>(&mut self, value: T) -> anyhow::Result<ValueWithProof<T>> {
+        let keys = self.recorded_reads.take().unwrap_or_default();
+        let version = self
+            .archival_working_set
+            .as_ref()
+            .map(|archival_working_set| archival_working_set.version());
+        let (version, state_root, proofs) = self.delta.inner.inner.prove_keys(&keys, version)?;
+
+        let proofs = proofs
+            .into_iter()
+            .map(|StorageProof { key, value, proof }| {
+                let proof = proof
+                    .try_to_vec()
+                    .map_err(|e| anyhow::anyhow!("Failed to encode the proof: {}", e))?;
+                Ok(StorageProof { key, value, proof })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(ValueWithProof {
+            value,
+            proofs,
+            version,
+            state_root: state_root.as_ref().to_vec(),
+        })
+    }
 }
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        if let Some(recorded_reads) = &mut self.recorded_reads {
+            if !recorded_reads.contains(key) {
+                recorded_reads.push(key.clone());
+            }
+        }
+
         match &mut self.archival_working_set {
             None => self.delta.get(key),
             Some(ref mut archival_working_set) => archival_working_set.get(key),
@@ -547,6 +615,7 @@ pub mod archival_state {
     /// Archival JMT
     pub struct ArchivalJmtWorkingSet<C: Context> {
         delta: RevertableWriter<Delta<C::Storage>>,
+        version: Version,
     }
 
     impl<C: Context> ArchivalJmtWorkingSet<C> {
@@ -557,8 +626,14 @@ pub mod archival_state {
                     Delta::new(inner.clone(), Some(version)),
                     Some(version),
                 ),
+                version,
             }
         }
+
+        /// The version of the state this working set reads from
+        pub fn version(&self) -> Version {
+            self.version
+        }
     }
 
     /// Archival Accessory
@@ -714,6 +789,14 @@ pub mod kernel_state {
     }
 }
 
+/// The changes of a [`WorkingSet`] at some point, see [`WorkingSet::snapshot`].
+#[derive(Debug, Clone)]
+pub struct WorkingSetSnapshot {
+    writes: HashMap<CacheKey, Option<CacheValue>>,
+    accessory_writes: HashMap<CacheKey, Option<CacheValue>>,
+    events: usize,
+}
+
 struct RevertableWriter<T> {
     inner: T,
     writes: HashMap<CacheKey, Option<CacheValue>>,
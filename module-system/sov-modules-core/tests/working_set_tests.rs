@@ -59,3 +59,29 @@ fn test_kernel_workingset_get() {
 
     assert_eq!(Some(storage_value), working_set.get(&storage_key));
 }
+
+#[test]
+fn test_workingset_revert_to_snapshot() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let codec = BcsCodec {};
+    let storage = new_orphan_storage(tempdir.path()).unwrap();
+
+    let prefix = sov_modules_core::Prefix::new(vec![1, 2, 3]);
+    let kept_key = StorageKey::new(&prefix, &vec![4, 5, 6], &codec);
+    let reverted_key = StorageKey::new(&prefix, &vec![7, 8, 9], &codec);
+    let storage_value = StorageValue::new(&vec![7, 8, 9], &codec);
+
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
+    working_set.set(&kept_key, storage_value.clone());
+    working_set.add_event("kept", "event");
+    let snapshot = working_set.snapshot();
+
+    working_set.set(&reverted_key, storage_value.clone());
+    working_set.delete(&kept_key);
+    working_set.add_event("reverted", "event");
+    working_set.revert_to_snapshot(&snapshot);
+
+    assert_eq!(Some(storage_value), working_set.get(&kept_key));
+    assert_eq!(None, working_set.get(&reverted_key));
+    assert_eq!(1, working_set.events().len());
+}
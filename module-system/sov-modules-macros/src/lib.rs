@@ -122,6 +122,14 @@ pub fn config_constant(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// 2. `#[rpc_gen]` is applied to an `impl` block instead of a trait
 /// 3. `#[method]` is renamed to with `#[rpc_method]` to avoid import confusion and clarify the purpose of the annotation
 ///
+/// Methods which take a `WorkingSet` also accept an optional trailing `state_id: Option<StateIdentifier>`
+/// parameter, which selects the historical state to query. The latest state is queried if it is `None`.
+///
+/// If the `with_proofs` flag is set (i.e. `#[rpc_gen(client, server, namespace = "myNamespace", with_proofs)]`),
+/// each of these methods also gets a `*_with_proof` twin, exposed as `<name>WithProof`. The twin returns a
+/// [`ValueWithProof`](sov_modules_api::ValueWithProof) holding the value along with proofs of all the storage entries
+/// it was computed from, the version of the state and its root. The methods must return `RpcResult<T>`.
+///
 /// ## Example
 /// ```
 /// use sov_modules_api::{Context, ModuleInfo};
@@ -190,8 +198,11 @@ pub fn config_constant(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// pub trait MyModuleRpcImpl<C: sov_modules_api::Context> {
 ///     fn get_backing_impl(&self) -> &TestStruct<C>;
 ///     fn get_working_set(&self) -> ::sov_modules_api::WorkingSet<C>;
-///     fn my_method(&self, param: u32) -> u32 {
-///         Self::get_backing_impl(self).my_method(self, &mut Self::get_working_set(self), param)
+///     fn get_working_set_at(&self, state_id: Option<StateIdentifier>) -> RpcResult<::sov_modules_api::WorkingSet<C>> {
+///         // Returns `get_working_set()` for `None`, and an error otherwise
+///     }
+///     fn my_method(&self, param: u32, state_id: Option<StateIdentifier>) -> RpcResult<u32> {
+///         Self::get_backing_impl(self).my_method(self, &mut Self::get_working_set_at(self, state_id)?, param)
 ///     }
 /// }
 /// ```
@@ -48,6 +48,7 @@ impl ExposeRpcMacro {
         let rpc_storage_struct = quote! {
             struct RpcStorage #impl_generics #where_clause {
                 storage: #context_type::Storage,
+                state_versions: ::std::sync::Arc<dyn ::sov_modules_api::rpc::StateVersionProvider>,
                 // Function pointers are always Send + Sync, regardless of
                 // whether the return type is. The alternative would be to
                 // `unsafe impl Send/Sync` for `RpcStorage`, but this seems
@@ -60,6 +61,7 @@ impl ExposeRpcMacro {
                 fn clone(&self) -> Self {
                     Self {
                         storage: self.storage.clone(),
+                        state_versions: self.state_versions.clone(),
                         _phantom: ::std::marker::PhantomData,
                     }
                  }
@@ -108,6 +110,12 @@ impl ExposeRpcMacro {
                     {
                         ::sov_modules_api::WorkingSet::new(self.storage.clone())
                     }
+
+                    /// Get a working set on top of the state identified by `state_id`, resolved through the ledger
+                    fn get_working_set_at(&self, state_id: ::core::option::Option<::sov_modules_api::rpc::StateIdentifier>) -> ::jsonrpsee::core::RpcResult<::sov_modules_api::WorkingSet<#context_type>>
+                    {
+                        ::sov_modules_api::utils::working_set_at(self.storage.clone(), self.state_versions.as_ref(), state_id)
+                    }
                 }
             };
 
@@ -115,11 +123,13 @@ impl ExposeRpcMacro {
         }
 
         let get_rpc_methods: proc_macro2::TokenStream = quote! {
-            /// Returns a [`jsonrpsee::RpcModule`] with all the rpc methods exposed by the module
-            pub fn get_rpc_methods #impl_generics (storage: <#context_type as ::sov_modules_api::Spec>::Storage) -> ::jsonrpsee::RpcModule<()> #where_clause {
+            /// Returns a [`jsonrpsee::RpcModule`] with all the rpc methods exposed by the module.
+            /// Historical states requested by the rpc methods are resolved through `state_versions`
+            pub fn get_rpc_methods #impl_generics (storage: <#context_type as ::sov_modules_api::Spec>::Storage, state_versions: ::std::sync::Arc<dyn ::sov_modules_api::rpc::StateVersionProvider>) -> ::jsonrpsee::RpcModule<()> #where_clause {
                 let mut module = ::jsonrpsee::RpcModule::new(());
                 let r = RpcStorage:: #ty_generics  {
                     storage: storage.clone(),
+                    state_versions,
                     _phantom: ::std::marker::PhantomData
                 };
 
@@ -25,6 +25,59 @@ fn get_method_attribute(attributes: &[Attribute]) -> Option<(Attribute, usize)>
     None
 }
 
+/// Returns the `#[method]` attribute of the `*_with_proof` twin of a method,
+/// whose name is the name of the original method with a `WithProof` suffix.
+fn with_proof_method_attribute(attribute: &Attribute) -> Result<Attribute, syn::Error> {
+    if let Ok(Meta::List(MetaList { nested, .. })) = attribute.parse_meta() {
+        for meta in nested.iter() {
+            if let syn::NestedMeta::Meta(Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(name),
+                ..
+            })) = meta
+            {
+                if path.is_ident("name") {
+                    let name = format!("{}WithProof", name.value());
+                    return Ok(syn::parse_quote! { #[method(name = #name)] });
+                }
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(
+        attribute,
+        "Expected the method name to be set with `name = \"...\"`",
+    ))
+}
+
+/// Extracts `T` from the `RpcResult<T>` returned by a method.
+fn rpc_result_type(output: &syn::ReturnType) -> Result<syn::Type, syn::Error> {
+    if let syn::ReturnType::Type(_, ty) = output {
+        if let syn::Type::Path(syn::TypePath { path, .. }) = ty.as_ref() {
+            if let Some(segment) = path.segments.last() {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    if let Some(syn::GenericArgument::Type(ty)) = args.args.first() {
+                        return Ok(ty.clone());
+                    }
+                }
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(
+        output,
+        "Methods with proofs must return `RpcResult<T>`",
+    ))
+}
+
+/// Removes the `with_proofs` flag, which is not understood by `jsonrpsee`, from the
+/// attribute arguments. Returns whether the flag was present.
+fn take_with_proofs_flag(attrs: &mut Vec<syn::NestedMeta>) -> bool {
+    let len = attrs.len();
+    attrs.retain(|attr| {
+        !matches!(attr, syn::NestedMeta::Meta(Meta::Path(path)) if path.is_ident("with_proofs"))
+    });
+    attrs.len() != len
+}
+
 fn jsonrpsee_rpc_macro_path() -> Path {
     let segments = vec![
         Ident::new("jsonrpsee", proc_macro2::Span::call_site()),
@@ -61,6 +114,14 @@ fn find_working_set_argument(sig: &Signature) -> Option<(usize, syn::Type)> {
     None
 }
 
+/// The optional trailing argument added to every method which reads from a working set,
+/// used to query the state at a given slot height or state root instead of the latest state.
+fn state_id_argument() -> FnArg {
+    syn::parse_quote! {
+        state_id: ::core::option::Option<::sov_modules_api::rpc::StateIdentifier>
+    }
+}
+
 struct RpcImplBlock {
     pub(crate) type_name: Ident,
     pub(crate) methods: Vec<RpcEnabledMethod>,
@@ -73,6 +134,8 @@ struct RpcEnabledMethod {
     pub(crate) method_signature: Signature,
     pub(crate) docs: Vec<Attribute>,
     pub(crate) idx_of_working_set_arg: Option<usize>,
+    /// The return type of the `*_with_proof` twin of the method, if it has one
+    pub(crate) proof_output: Option<syn::ReturnType>,
 }
 
 impl RpcImplBlock {
@@ -122,13 +185,43 @@ impl RpcImplBlock {
                     .filter(|arg| arg.to_string() != quote! { self }.to_string());
                 let mut inputs: Vec<syn::FnArg> = signature.inputs.clone().into_iter().collect();
                 inputs.remove(idx);
+                inputs.push(state_id_argument());
 
                 signature.inputs = inputs.into_iter().collect();
 
+                if let Some(proof_output) = &method.proof_output {
+                    let mut proof_signature = signature.clone();
+                    proof_signature.ident = format_ident!("{}_with_proof", method_name);
+                    proof_signature.output = proof_output.clone();
+                    let proof_method_name = &proof_signature.ident;
+                    let pre_working_set_args = pre_working_set_args.clone();
+                    let post_working_set_args = post_working_set_args.clone();
+                    let all_args = arg_values
+                        .clone()
+                        .take(idx)
+                        .chain(arg_values.clone().skip(idx + 1));
+
+                    impl_trait_methods.push(quote! {
+                        #proof_signature {
+                            let mut working_set = Self::get_working_set_at(self, state_id)?;
+                            working_set.record_reads();
+                            let value = <#type_name #ty_generics as ::std::default::Default>::default().#method_name(#(#pre_working_set_args,)* &mut working_set, #(#post_working_set_args),* )?;
+                            working_set.prove_recorded_reads(value).map_err(|e| {
+                                ::sov_modules_api::utils::to_jsonrpsee_error_object(e, "Failed to prove the storage reads")
+                            })
+                        }
+                    });
+                    blanket_impl_methods.push(quote! {
+                        #proof_signature {
+                            <Self as #impl_trait_name #ty_generics >::#proof_method_name(#(#all_args,)* state_id)
+                        }
+                    });
+                }
+
                 quote! {
                     #( #docs )*
                     #signature {
-                        <#type_name #ty_generics as ::std::default::Default>::default().#method_name(#(#pre_working_set_args,)* &mut Self::get_working_set(self), #(#post_working_set_args),* )
+                        <#type_name #ty_generics as ::std::default::Default>::default().#method_name(#(#pre_working_set_args,)* &mut Self::get_working_set_at(self, state_id)?, #(#post_working_set_args),* )
                     }
                 }
             } else {
@@ -152,7 +245,7 @@ impl RpcImplBlock {
                 quote! {
                     #( #docs )*
                     #signature {
-                        <Self as #impl_trait_name #ty_generics >::#method_name(#(#pre_working_set_args,)* #(#post_working_set_args),* )
+                        <Self as #impl_trait_name #ty_generics >::#method_name(#(#pre_working_set_args,)* #(#post_working_set_args,)* state_id)
                     }
                 }
             } else {
@@ -174,6 +267,19 @@ impl RpcImplBlock {
                 pub trait #impl_trait_name #generics #where_clause {
                     /// Get a clean working set on top of the latest state
                     fn get_working_set(&self) -> #working_set_type;
+
+                    /// Get a clean working set on top of the state identified by `state_id`,
+                    /// or on top of the latest state if `state_id` is `None`.
+                    /// Historical states are not supported unless this method is overridden.
+                    fn get_working_set_at(&self, state_id: ::core::option::Option<::sov_modules_api::rpc::StateIdentifier>) -> ::jsonrpsee::core::RpcResult<#working_set_type> {
+                        match state_id {
+                            None => Ok(Self::get_working_set(self)),
+                            Some(state_id) => Err(::sov_modules_api::utils::to_jsonrpsee_error_object(
+                                format!("{:?}", state_id),
+                                "Historical state queries are not supported",
+                            )),
+                        }
+                    }
                     #(#impl_trait_methods)*
                 }
             }
@@ -231,6 +337,8 @@ fn build_rpc_trait(
     mut input: syn::ItemImpl,
 ) -> Result<proc_macro2::TokenStream, syn::Error> {
     let intermediate_trait_name = format_ident!("{}Rpc", type_name);
+    // Methods reading from a working set get a `*_with_proof` twin if requested
+    let with_proofs = take_with_proofs_flag(&mut attrs);
     // If the user hasn't directly provided trait bounds, override jsonrpsee's defaults
     // with an empty bound. This prevents spurious compilation errors like `Context does not implement DeserializeOwned`
     add_server_bounds_attr_if_missing(&mut attrs);
@@ -271,6 +379,8 @@ fn build_rpc_trait(
                     let mut inputs: Vec<syn::FnArg> =
                         intermediate_trait_inputs.into_iter().collect();
                     inputs.remove(idx);
+                    // Let callers pick the state to query, defaulting to the latest one
+                    inputs.push(state_id_argument());
                     intermediate_trait_inputs = inputs.into_iter().collect();
 
                     // Store the type of the working set argument for later reference
@@ -285,17 +395,42 @@ fn build_rpc_trait(
                     .filter(|attr| attr.path.is_ident("doc"))
                     .cloned()
                     .collect::<Vec<_>>();
+                let proof_output = if with_proofs && idx_of_working_set_arg.is_some() {
+                    let value_type = rpc_result_type(&method.sig.output)?;
+                    Some(syn::parse_quote! {
+                        -> ::jsonrpsee::core::RpcResult<::sov_modules_api::ValueWithProof<#value_type>>
+                    })
+                } else {
+                    None
+                };
                 rpc_info.methods.push(RpcEnabledMethod {
                     method_name: method.sig.ident.clone(),
                     method_signature: method.sig.clone(),
                     docs: docs.clone(),
                     idx_of_working_set_arg,
+                    proof_output: proof_output.clone(),
                 });
 
                 // Remove the working set argument from the signature
                 let mut intermediate_signature = method.sig.clone();
                 intermediate_signature.inputs = intermediate_trait_inputs;
 
+                if let Some(proof_output) = proof_output {
+                    let proof_attr = with_proof_method_attribute(&attr)?;
+                    let mut proof_signature = intermediate_signature.clone();
+                    proof_signature.ident = format_ident!("{}_with_proof", method.sig.ident);
+                    proof_signature.output = proof_output;
+                    let proof_doc = format!(
+                        "Same as `{}`, along with proofs of the storage entries it read.",
+                        method.sig.ident
+                    );
+                    intermediate_trait_items.push(quote! {
+                        #[doc = #proof_doc]
+                        #proof_attr
+                        #proof_signature;
+                    });
+                }
+
                 // Build the annotated signature for the intermediate trait
                 let annotated_signature = quote! {
                     #( #docs )*
@@ -67,7 +67,7 @@ fn main() {
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::first_method(
-                &r,
+                &r, None,
             )
             .unwrap();
         assert_eq!(result, 11);
@@ -76,12 +76,23 @@ fn main() {
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::second_method(
-                &r, 22,
+                &r, 22, None,
             )
             .unwrap();
         assert_eq!(result, 22);
     }
 
+    {
+        // Historical state queries are rejected unless `get_working_set_at` is overridden
+        let result =
+            <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::second_method(
+                &r,
+                22,
+                Some(sov_modules_api::rpc::StateIdentifier::SlotHeight(1)),
+            );
+        assert!(result.is_err());
+    }
+
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::third_method(
@@ -94,7 +105,7 @@ fn main() {
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::fourth_method(
-                &r, 44,
+                &r, 44, None,
             )
             .unwrap();
         assert_eq!(result, 44);
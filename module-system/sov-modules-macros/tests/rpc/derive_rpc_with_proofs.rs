@@ -0,0 +1,68 @@
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::default_context::ZkDefaultContext;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, ModuleInfo, WorkingSet};
+use sov_state::ZkStorage;
+
+#[derive(ModuleInfo)]
+pub struct TestStruct<C: ::sov_modules_api::Context> {
+    #[address]
+    pub(crate) address: C::Address,
+}
+
+#[rpc_gen(client, server, namespace = "test", with_proofs)]
+impl<C: sov_modules_api::Context> TestStruct<C> {
+    #[rpc_method(name = "firstMethod")]
+    pub fn first_method(&self, result: u32, _working_set: &mut WorkingSet<C>) -> RpcResult<u32> {
+        Ok(result)
+    }
+
+    #[rpc_method(name = "secondMethod")]
+    pub fn second_method(&self, result: u32) -> RpcResult<u32> {
+        Ok(result)
+    }
+}
+
+struct RpcStorage<C: Context> {
+    pub storage: C::Storage,
+}
+
+impl TestStructRpcImpl<ZkDefaultContext> for RpcStorage<ZkDefaultContext> {
+    fn get_working_set(&self) -> ::sov_modules_api::WorkingSet<ZkDefaultContext> {
+        ::sov_modules_api::WorkingSet::new(self.storage.clone())
+    }
+}
+
+fn main() {
+    let storage = ZkStorage::new();
+    let r: RpcStorage<ZkDefaultContext> = RpcStorage {
+        storage: storage.clone(),
+    };
+    {
+        let result =
+            <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::first_method(
+                &r, 11, None,
+            )
+            .unwrap();
+        assert_eq!(result, 11);
+    }
+
+    {
+        // The zk storage can't prove historical reads
+        let result = <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::first_method_with_proof(
+            &r, 11, None,
+        );
+        assert!(result.is_err());
+    }
+
+    {
+        let result =
+            <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext>>::second_method(
+                &r, 22,
+            )
+            .unwrap();
+        assert_eq!(result, 22);
+    }
+
+    println!("All tests passed!");
+}
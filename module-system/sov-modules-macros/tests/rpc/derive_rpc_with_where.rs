@@ -80,7 +80,7 @@ fn main() {
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext, u32>>::first_method(
-                &r,
+                &r, None,
             )
             .unwrap();
         assert_eq!(result, 11);
@@ -89,7 +89,7 @@ fn main() {
     {
         let result =
             <RpcStorage<ZkDefaultContext> as TestStructRpcServer<ZkDefaultContext, u32>>::second_method(
-                &r, 22,
+                &r, 22, None,
             )
             .unwrap();
         assert_eq!(result, (22, 15733059416522709050));
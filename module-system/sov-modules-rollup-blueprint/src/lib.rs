@@ -1,11 +1,15 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod replay;
 mod runtime_rpc;
 mod wallet;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
+use anyhow::Context as _;
 use async_trait::async_trait;
+pub use replay::*;
 pub use runtime_rpc::*;
 use sov_db::ledger_db::LedgerDB;
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
@@ -13,11 +17,12 @@ use sov_modules_api::{Context, DaSpec, Spec};
 use sov_modules_stf_blueprint::{GenesisParams, Runtime as RuntimeTrait, StfBlueprint};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
-use sov_rollup_interface::zk::ZkvmHost;
+use sov_rollup_interface::zk::{Zkvm, ZkvmHost};
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{
-    InitVariant, ProverService, RollupConfig, RollupProverConfig, StateTransitionRunner,
+    InitVariant, ProverService, RemoteProver, RollupConfig, RollupProverConfig,
+    StateTransitionRunner, ZkProofVerifier,
 };
 use tokio::sync::oneshot;
 pub use wallet::*;
@@ -59,10 +64,12 @@ pub trait RollupBlueprint: Sized + Send + Sync {
 
     /// Prover service.
     type ProverService: ProverService<
-        StateRoot = <<Self::NativeContext as Spec>::Storage as Storage>::Root,
-        Witness = <<Self::NativeContext as Spec>::Storage as Storage>::Witness,
-        DaService = Self::DaService,
-    >;
+            StateRoot = <<Self::NativeContext as Spec>::Storage as Storage>::Root,
+            Witness = <<Self::NativeContext as Spec>::Storage as Storage>::Witness,
+            DaService = Self::DaService,
+        > + Send
+        + Sync
+        + 'static;
 
     /// Creates RPC methods for the rollup.
     fn create_rpc_methods(
@@ -99,6 +106,10 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         })
     }
 
+    /// Returns the commitment to the zkVM program proving the slots of the rollup. The proofs
+    /// submitted by a [`RemoteProver`] are verified against it.
+    fn code_commitment(&self) -> <Self::Vm as Zkvm>::CodeCommitment;
+
     /// Creates instance of [`DaService`].
     async fn create_da_service(
         &self,
@@ -113,6 +124,27 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         da_service: &Self::DaService,
     ) -> Self::ProverService;
 
+    /// Creates a [`RemoteProver`], which proves the slots executed by the full node serving
+    /// RPC at `node_url` and posts the proofs to the DA configured in `rollup_config`.
+    /// The proofs are submitted with the `remote_prover_token` of the `rollup_config`.
+    async fn create_remote_prover(
+        &self,
+        node_url: &str,
+        rollup_config: &RollupConfig<Self::DaConfig>,
+        prover_config: RollupProverConfig,
+    ) -> Result<RemoteProver<Self::ProverService>, anyhow::Error> {
+        let da_service = self.create_da_service(rollup_config).await;
+        let prover_service = self
+            .create_prover_service(prover_config, rollup_config, &da_service)
+            .await;
+        let token = rollup_config
+            .runner
+            .remote_prover_token
+            .clone()
+            .context("The remote prover needs a `remote_prover_token` in the runner config")?;
+        RemoteProver::new(node_url, token, Arc::new(prover_service))
+    }
+
     /// Creates instance of [`Self::StorageManager`].
     /// Panics if initialization fails.
     fn create_storage_manager(
@@ -138,6 +170,10 @@ pub trait RollupBlueprint: Sized + Send + Sync {
     ) -> Result<Rollup<Self>, anyhow::Error>
     where
         <Self::NativeContext as Spec>::Storage: NativeStorage,
+        Self::Vm: 'static,
+        Self::DaSpec: 'static,
+        <<Self::NativeContext as Spec>::Storage as Storage>::Root: 'static,
+        <<Self::NativeContext as Spec>::Storage as Storage>::Witness: 'static,
     {
         let da_service = self.create_da_service(&rollup_config).await;
         // TODO: Double check what kind of storage needed here.
@@ -184,7 +220,13 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             storage_manager,
             init_variant,
             prover_service,
-        )?;
+        )?
+        .with_remote_proof_verifier(ZkProofVerifier::<
+            Self::Vm,
+            Self::DaSpec,
+            <<Self::NativeContext as Spec>::Storage as Storage>::Root,
+            <<Self::NativeContext as Spec>::Storage as Storage>::Witness,
+        >::new(self.code_commitment()));
 
         Ok(Rollup {
             runner,
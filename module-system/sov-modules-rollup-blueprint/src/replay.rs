@@ -0,0 +1,305 @@
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use sov_chain_state::ChainState;
+use sov_db::native_db::NativeDB;
+use sov_db::schema::types::JmtValue;
+use sov_db::state_db::StateDB;
+use sov_modules_api::{Spec, WorkingSet};
+use sov_modules_stf_blueprint::StfBlueprint;
+use sov_prover_storage_manager::{ProverStorageManager, SnapshotManager};
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::rpc::{StateIdentifier, StateVersionProvider};
+use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+use sov_rollup_interface::zk::StateTransitionData;
+use sov_state::storage::NativeStorage;
+use sov_state::{MerkleProofSpec, ProverStorage, Storage};
+use sov_stf_runner::RollupConfig;
+use tracing::{debug, info};
+
+use crate::RollupBlueprint;
+
+/// Every slot writes the state at a new version, so the state after slot `n`
+/// is at version `n + GENESIS_STATE_VERSION`.
+const GENESIS_STATE_VERSION: u64 = 1;
+
+/// Where the block of a replayed slot was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockSource {
+    /// The state transition data of the slot, still stored in the ledger for proving.
+    Ledger,
+    /// The DA layer.
+    Da,
+}
+
+/// A state root recorded by the node for a slot, which doesn't match the replayed one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RootMismatch {
+    /// The root of the node's state after the slot.
+    State {
+        /// The recorded root.
+        recorded: Vec<u8>,
+    },
+    /// The ledger doesn't know the replayed root as the root reached by the slot.
+    Ledger {
+        /// The slot the ledger associates with the replayed root, if any.
+        recorded_slot: Option<u64>,
+    },
+    /// The post-state root of the slot in the `historical_transitions` of the chain state.
+    ChainState {
+        /// The recorded root.
+        recorded: Vec<u8>,
+    },
+}
+
+/// A state key the replayed slot and the node wrote differently.
+/// `None` means that the slot didn't write the key, `Some(None)` that it deleted it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteDiff {
+    /// The state key.
+    pub key: Vec<u8>,
+    /// The value written by the replayed slot.
+    pub replayed: Option<JmtValue>,
+    /// The value written by the node.
+    pub recorded: Option<JmtValue>,
+}
+
+/// The first replayed slot whose state root differs from the one recorded by the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// The number of the slot.
+    pub slot_number: u64,
+    /// The height of the slot's block on the DA layer.
+    pub da_height: u64,
+    /// The state root reached by the replay.
+    pub replayed_root: Vec<u8>,
+    /// The recorded roots that don't match the replayed one.
+    pub mismatches: Vec<RootMismatch>,
+    /// The state writes of the slot that differ, ordered by key.
+    pub writes: Vec<WriteDiff>,
+}
+
+/// The outcome of [`replay_slots`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// The replayed slots which matched the recorded state, along with the source of their block.
+    pub matching_slots: Vec<(u64, BlockSource)>,
+    /// The first divergence found. The replay stops on it.
+    pub divergence: Option<Divergence>,
+}
+
+/// Re-executes the `slots` of the rollup stored under `rollup_config.storage.path` and audits
+/// their post-state roots against the node's state, its ledger and the `historical_transitions`
+/// of the chain state module.
+///
+/// The slots are executed natively on a copy of the node's state made in `scratch_path`,
+/// which must not exist yet. The blocks are taken from the state transition data stored in the
+/// ledger for proving when it's still there, and fetched from the DA layer otherwise.
+/// The replay stops on the first slot whose state root differs, and reports the state writes
+/// of that slot which differ from the node's.
+///
+/// The node must be stopped, as its databases are opened by the replay.
+pub async fn replay_slots<R, Ss>(
+    blueprint: &R,
+    rollup_config: &RollupConfig<R::DaConfig>,
+    slots: RangeInclusive<u64>,
+    scratch_path: &Path,
+) -> anyhow::Result<ReplayReport>
+where
+    R: RollupBlueprint,
+    Ss: MerkleProofSpec,
+    R::NativeContext: Spec<Storage = ProverStorage<Ss, SnapshotManager>>,
+{
+    let node_path = &rollup_config.storage.path;
+    let ledger_db = blueprint.create_ledger_db(rollup_config);
+    let head_slot = ledger_db
+        .get_head_slot()?
+        .map(|(number, _)| number.0)
+        .unwrap_or_default();
+    anyhow::ensure!(
+        *slots.start() > 0 && slots.start() <= slots.end() && *slots.end() <= head_slot,
+        "Cannot replay slots {:?}: the node has executed slots 1 to {}",
+        slots,
+        head_slot
+    );
+    anyhow::ensure!(
+        !scratch_path.exists(),
+        "The scratch path {} already exists",
+        scratch_path.display()
+    );
+
+    // The state before the first replayed slot. The accessory state of a slot is written
+    // one version behind its provable state.
+    let start_version = slots.start() - 1 + GENESIS_STATE_VERSION;
+    StateDB::<SnapshotManager>::copy_at_version(node_path, scratch_path, start_version)?;
+    NativeDB::<SnapshotManager>::copy_at_version(node_path, scratch_path, start_version - 1)?;
+
+    let mut node_storage_manager =
+        ProverStorageManager::<R::DaSpec, Ss>::new(sov_state::config::Config {
+            path: node_path.clone(),
+        })?;
+    let node_storage = node_storage_manager.create_finalized_storage()?;
+    let node_state_db = StateDB::<SnapshotManager>::setup_read_only_schema_db(node_path)?;
+    let mut scratch_storage_manager =
+        ProverStorageManager::<R::DaSpec, Ss>::new(sov_state::config::Config {
+            path: scratch_path.to_path_buf(),
+        })?;
+
+    let mut pre_state_root = node_storage.get_root_hash(start_version)?;
+    anyhow::ensure!(
+        scratch_storage_manager
+            .create_finalized_storage()?
+            .get_root_hash(start_version)?
+            == pre_state_root,
+        "The copy of the state doesn't match the node's state at version {}",
+        start_version
+    );
+
+    let da_service = blueprint.create_da_service(rollup_config).await;
+    let stf =
+        StfBlueprint::<R::NativeContext, R::DaSpec, R::Vm, R::NativeRuntime, R::NativeKernel>::new(
+        );
+    let chain_state = ChainState::<R::NativeContext, R::DaSpec>::default();
+    let genesis_height = chain_state.get_genesis_height(&mut WorkingSet::new(node_storage.clone()));
+
+    let mut matching_slots = Vec::new();
+    for slot_number in slots {
+        let version = slot_number + GENESIS_STATE_VERSION;
+        let da_height = rollup_config.runner.start_height + slot_number - 1;
+
+        // The transition of a slot is stored in the chain state during the next slot,
+        // so it's missing for the last executed slot.
+        let recorded_transition = genesis_height.and_then(|genesis_height| {
+            chain_state.get_historical_transitions(
+                genesis_height + slot_number,
+                &mut WorkingSet::new(node_storage.clone()),
+            )
+        });
+
+        let cached_block = match (
+            ledger_db.get_next_proving_job(da_height)?,
+            &recorded_transition,
+        ) {
+            (Some((height, _, job)), Some(transition)) if height == da_height => {
+                let transition_data: StateTransitionData<
+                    <ProverStorage<Ss, SnapshotManager> as Storage>::Root,
+                    Ss::Witness,
+                    R::DaSpec,
+                > = bincode::deserialize(&job)?;
+                (&transition_data.da_block_header.hash() == transition.da_block_hash()).then(|| {
+                    (
+                        transition_data.da_block_header,
+                        *transition.validity_condition(),
+                        transition_data.blobs,
+                    )
+                })
+            }
+            _ => None,
+        };
+        let (header, validity_condition, mut blobs, source) = match cached_block {
+            Some((header, validity_condition, blobs)) => {
+                (header, validity_condition, blobs, BlockSource::Ledger)
+            }
+            None => {
+                let filtered_block = da_service.get_block_at(da_height).await?;
+                let blobs = da_service.extract_relevant_blobs(&filtered_block);
+                (
+                    filtered_block.header().clone(),
+                    filtered_block.validity_condition(),
+                    blobs,
+                    BlockSource::Da,
+                )
+            }
+        };
+        debug!(
+            "Replaying slot {} at height {} from {:?}",
+            slot_number, da_height, source
+        );
+
+        let pre_state = scratch_storage_manager.create_storage_on(&header)?;
+        let slot_result = stf.apply_slot(
+            &pre_state_root,
+            pre_state,
+            Default::default(),
+            &header,
+            &validity_condition,
+            &mut blobs,
+        );
+        let replayed_root = slot_result.state_root;
+
+        let mut mismatches = Vec::new();
+        let recorded_root = node_storage.get_root_hash(version)?;
+        if recorded_root != replayed_root {
+            mismatches.push(RootMismatch::State {
+                recorded: recorded_root.as_ref().to_vec(),
+            });
+        }
+        let ledger_version = ledger_db
+            .get_state_version(&StateIdentifier::StateRoot(replayed_root.as_ref().to_vec()))?;
+        if ledger_version != Some(version) {
+            mismatches.push(RootMismatch::Ledger {
+                recorded_slot: ledger_version.map(|version| version - GENESIS_STATE_VERSION),
+            });
+        }
+        if let Some(transition) = &recorded_transition {
+            if transition.post_state_root() != &replayed_root {
+                mismatches.push(RootMismatch::ChainState {
+                    recorded: transition.post_state_root().as_ref().to_vec(),
+                });
+            }
+        }
+
+        if !mismatches.is_empty() {
+            info!(
+                "Slot {} at height {} diverges from the recorded state",
+                slot_number, da_height
+            );
+            let (state_snapshot, _) = slot_result.change_set.freeze()?;
+            let replayed_writes = StateDB::<SnapshotManager>::get_snapshot_values_written_at(
+                &state_snapshot,
+                version,
+            )?;
+            let recorded_writes =
+                StateDB::<SnapshotManager>::get_values_written_at(&node_state_db, version)?;
+            let writes = replayed_writes
+                .keys()
+                .chain(recorded_writes.keys())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .filter_map(|key| {
+                    let replayed = replayed_writes.get(key);
+                    let recorded = recorded_writes.get(key);
+                    (replayed != recorded).then(|| WriteDiff {
+                        key: key.clone(),
+                        replayed: replayed.cloned(),
+                        recorded: recorded.cloned(),
+                    })
+                })
+                .collect();
+
+            return Ok(ReplayReport {
+                matching_slots,
+                divergence: Some(Divergence {
+                    slot_number,
+                    da_height,
+                    replayed_root: replayed_root.as_ref().to_vec(),
+                    mismatches,
+                    writes,
+                }),
+            });
+        }
+
+        scratch_storage_manager.save_change_set(&header, slot_result.change_set)?;
+        scratch_storage_manager.finalize(&header)?;
+        matching_slots.push((slot_number, source));
+        pre_state_root = replayed_root;
+    }
+
+    Ok(ReplayReport {
+        matching_slots,
+        divergence: None,
+    })
+}
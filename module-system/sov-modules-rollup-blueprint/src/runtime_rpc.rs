@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::Context as _;
 use sov_db::ledger_db::LedgerDB;
 use sov_modules_api::{Context, Spec};
@@ -18,7 +20,7 @@ where
     Da: DaService + Clone,
 {
     // runtime rpc.
-    let mut rpc_methods = RT::rpc_methods(storage.clone());
+    let mut rpc_methods = RT::rpc_methods(storage.clone(), Arc::new(ledger_db.clone()));
 
     // ledger rpc.
     {
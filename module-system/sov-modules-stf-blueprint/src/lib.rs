@@ -56,8 +56,11 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     type GenesisPaths: Send + Sync;
 
     #[cfg(feature = "native")]
-    /// Default rpc methods.
-    fn rpc_methods(storage: <C as Spec>::Storage) -> jsonrpsee::RpcModule<()>;
+    /// Default rpc methods. Queries of historical states are resolved through `state_versions`.
+    fn rpc_methods(
+        storage: <C as Spec>::Storage,
+        state_versions: std::sync::Arc<dyn sov_modules_api::rpc::StateVersionProvider>,
+    ) -> jsonrpsee::RpcModule<()>;
 
     #[cfg(feature = "native")]
     /// Reads genesis configs.
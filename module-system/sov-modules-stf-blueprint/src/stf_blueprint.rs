@@ -177,6 +177,9 @@ where
         let gas_elastic_price = [0, 0];
         let mut sequencer_reward = 0u64;
 
+        // Transactions observe the height at which they appear to be executing
+        let slot_height = self.kernel.visible_height(&mut batch_workspace);
+
         // Dispatching transactions
         let mut tx_receipts = Vec::with_capacity(txs.len());
         for (TransactionAndRawHash { tx, raw_tx_hash }, msg) in
@@ -191,7 +194,7 @@ where
             // Pre dispatch hook
             // TODO set the sequencer pubkey
             let hook = RuntimeTxHook {
-                height: 1,
+                height: slot_height,
                 sequencer: tx.pub_key().clone(),
             };
             let ctx = match self
@@ -217,6 +220,8 @@ where
             // Commit changes after pre_dispatch_tx_hook
             batch_workspace = batch_workspace.checkpoint().to_revertable();
 
+            // Events are attributed to the module handling the call message
+            let module_address = self.runtime.module_address(&msg).as_ref().to_vec();
             let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
 
             let remaining_gas = batch_workspace.gas_remaining_funds();
@@ -231,7 +236,11 @@ where
                 gas_reward
             );
 
-            let events = batch_workspace.take_events();
+            let events = batch_workspace
+                .take_events()
+                .into_iter()
+                .map(|event| event.with_module(&module_address))
+                .collect();
             let tx_effect = match tx_result {
                 Ok(_) => TxEffect::Successful,
                 Err(e) => {
@@ -268,12 +277,15 @@ where
         // TODO: calculate the amount based of gas and fees
         let sequencer_outcome = SequencerOutcome::Rewarded(sequencer_reward);
 
+        // A failed hook only reverts its own changes, the transactions stay applied.
+        batch_workspace = batch_workspace.checkpoint().to_revertable();
         if let Err(e) = self
             .runtime
             .end_blob_hook(sequencer_outcome.clone(), &mut batch_workspace)
         {
             // TODO: will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
             error!("Failed on `end_blob_hook`: {}", e);
+            batch_workspace = batch_workspace.revert().to_revertable();
         };
 
         (
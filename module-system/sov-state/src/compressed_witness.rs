@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sov_modules_core::Witness;
+
+/// The shortest run of bytes encoded as a reference to previous hints.
+/// It fits a JMT node hash, so the sibling nodes shared by the proofs of a slot are stored once.
+const MIN_MATCH: usize = 32;
+/// The maximum size of the decoded hints. A copy expands a few bytes of encoding into any
+/// number of bytes, so the output is bounded to keep a malformed witness from exhausting the
+/// memory. Witnesses this large can't be proven anyway.
+const MAX_DECODED_LEN: usize = 1 << 30;
+
+/// A [`Witness`] which stores the hints in a compact, deduplicated encoding.
+///
+/// When serialized, the hints are concatenated, and every run of at least 32 bytes which
+/// already appeared earlier in the hints is replaced by a reference to it. This stores
+/// the sibling nodes shared by the JMT proofs of a slot, as well as the values and the proofs
+/// read repeatedly, only once. The hints are decoded when the witness is deserialized,
+/// and are then read in the same order they were added, as with [`ArrayWitness`](crate::ArrayWitness).
+///
+/// # Example
+///
+/// ```
+/// use sov_state::{CompressedWitness, Witness};
+///
+/// let witness = CompressedWitness::default();
+///
+/// witness.add_hint([7u8; 64]);
+/// witness.add_hint([7u8; 64]);
+///
+/// let witness: CompressedWitness =
+///     bincode::deserialize(&bincode::serialize(&witness).unwrap()).unwrap();
+///
+/// assert_eq!(witness.get_hint::<[u8; 64]>(), [7u8; 64]);
+/// assert_eq!(witness.get_hint::<[u8; 64]>(), [7u8; 64]);
+/// ```
+#[derive(Default, Debug)]
+pub struct CompressedWitness {
+    next_idx: AtomicUsize,
+    hints: Mutex<Vec<Vec<u8>>>,
+}
+
+impl Witness for CompressedWitness {
+    fn add_hint<T: BorshSerialize>(&self, hint: T) {
+        self.hints.lock().unwrap().push(hint.try_to_vec().unwrap())
+    }
+
+    fn get_hint<T: BorshDeserialize>(&self) -> T {
+        let idx = self.next_idx.fetch_add(1, Ordering::SeqCst);
+        let hints_lock = self.hints.lock().unwrap();
+        T::deserialize_reader(&mut std::io::Cursor::new(&hints_lock[idx]))
+            .expect("Hint deserialization should never fail")
+    }
+
+    fn merge(&self, rhs: &Self) {
+        let rhs_next_idx = rhs.next_idx.load(Ordering::SeqCst);
+        let mut lhs_hints_lock = self.hints.lock().unwrap();
+        let mut rhs_hints_lock = rhs.hints.lock().unwrap();
+        lhs_hints_lock.extend(rhs_hints_lock.drain(rhs_next_idx..))
+    }
+}
+
+/// The serialized form of [`CompressedWitness`].
+#[derive(Serialize, Deserialize)]
+struct EncodedWitness {
+    next_idx: u64,
+    hint_count: u64,
+    /// For every hint, its length followed by the operations producing it. An operation is either
+    /// literal bytes, or a copy of bytes located at some distance back in the concatenated hints.
+    data: Vec<u8>,
+}
+
+impl Serialize for CompressedWitness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let hints = self.hints.lock().unwrap();
+        EncodedWitness {
+            next_idx: self.next_idx.load(Ordering::SeqCst) as u64,
+            hint_count: hints.len() as u64,
+            data: encode(&hints),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CompressedWitness {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = EncodedWitness::deserialize(deserializer)?;
+        let hints = decode(&encoded.data, encoded.hint_count).map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            next_idx: AtomicUsize::new(encoded.next_idx as usize),
+            hints: Mutex::new(hints),
+        })
+    }
+}
+
+fn encode(hints: &[Vec<u8>]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut stream: Vec<u8> = Vec::with_capacity(hints.iter().map(Vec::len).sum());
+    // The latest position of every run of `MIN_MATCH` bytes seen in the stream.
+    let mut runs: HashMap<[u8; MIN_MATCH], usize> = HashMap::new();
+
+    for hint in hints {
+        write_varint(&mut data, hint.len() as u64);
+        let start = stream.len();
+        stream.extend_from_slice(hint);
+        let end = stream.len();
+
+        let mut literal_start = start;
+        let mut pos = start;
+        while pos + MIN_MATCH <= end {
+            let run: [u8; MIN_MATCH] = stream[pos..pos + MIN_MATCH]
+                .try_into()
+                .expect("The run has the right length");
+            match runs.get(&run) {
+                Some(&candidate) => {
+                    let mut len = MIN_MATCH;
+                    while pos + len < end && stream[candidate + len] == stream[pos + len] {
+                        len += 1;
+                    }
+                    write_literal(&mut data, &stream[literal_start..pos]);
+                    write_varint(&mut data, ((len as u64) << 1) | 1);
+                    write_varint(&mut data, (pos - candidate) as u64);
+                    runs.insert(run, pos);
+                    pos += len;
+                    literal_start = pos;
+                }
+                None => {
+                    runs.insert(run, pos);
+                    pos += 1;
+                }
+            }
+        }
+        write_literal(&mut data, &stream[literal_start..end]);
+    }
+    data
+}
+
+fn decode(data: &[u8], hint_count: u64) -> anyhow::Result<Vec<Vec<u8>>> {
+    let mut cursor = data;
+    let mut stream: Vec<u8> = Vec::new();
+    let mut hint_ends = Vec::new();
+
+    for _ in 0..hint_count {
+        let len = read_varint(&mut cursor)?;
+        anyhow::ensure!(
+            len <= (MAX_DECODED_LEN - stream.len()) as u64,
+            "The hints exceed the maximum witness size"
+        );
+        let end = stream.len() + len as usize;
+        while stream.len() < end {
+            let op = read_varint(&mut cursor)?;
+            // Both operations produce bytes of the current hint only, so their length is
+            // bounded by the remaining size of the hint, itself bounded by the remaining output.
+            let remaining = (end - stream.len()) as u64;
+            let op_len = op >> 1;
+            anyhow::ensure!(
+                op_len > 0 && op_len <= remaining,
+                "Operation exceeds the hint"
+            );
+            let op_len = op_len as usize;
+            if op & 1 == 0 {
+                anyhow::ensure!(op_len <= cursor.len(), "Truncated literal");
+                let (literal, rest) = cursor.split_at(op_len);
+                stream.extend_from_slice(literal);
+                cursor = rest;
+            } else {
+                let distance = read_varint(&mut cursor)?;
+                anyhow::ensure!(
+                    distance > 0 && distance <= stream.len() as u64,
+                    "Copy distance out of bounds"
+                );
+                let from = stream.len() - distance as usize;
+                // The copied bytes may overlap with the ones being produced.
+                for i in 0..op_len {
+                    stream.push(stream[from + i]);
+                }
+            }
+        }
+        hint_ends.push(end);
+    }
+    anyhow::ensure!(cursor.is_empty(), "Trailing bytes after the last hint");
+
+    let mut start = 0;
+    Ok(hint_ends
+        .into_iter()
+        .map(|end| {
+            let hint = stream[start..end].to_vec();
+            start = end;
+            hint
+        })
+        .collect())
+}
+
+fn write_literal(data: &mut Vec<u8>, literal: &[u8]) {
+    if !literal.is_empty() {
+        write_varint(data, (literal.len() as u64) << 1);
+        data.extend_from_slice(literal);
+    }
+}
+
+fn write_varint(data: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        data.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    data.push(value as u8);
+}
+
+fn read_varint(cursor: &mut &[u8]) -> anyhow::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = cursor
+            .split_first()
+            .ok_or_else(|| anyhow::anyhow!("Truncated varint"))?;
+        *cursor = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    anyhow::bail!("Varint overflow")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArrayWitness;
+
+    fn roundtrip(witness: &CompressedWitness) -> CompressedWitness {
+        bincode::deserialize(&bincode::serialize(witness).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn hints_are_read_in_order() {
+        let witness = CompressedWitness::default();
+        witness.add_hint(1u64);
+        witness.add_hint(vec![3u8; 100]);
+        witness.add_hint(Option::<Vec<u8>>::None);
+        witness.add_hint(vec![3u8; 100]);
+
+        let witness = roundtrip(&witness);
+        assert_eq!(witness.get_hint::<u64>(), 1);
+        assert_eq!(witness.get_hint::<Vec<u8>>(), vec![3u8; 100]);
+        assert_eq!(witness.get_hint::<Option<Vec<u8>>>(), None);
+        assert_eq!(witness.get_hint::<Vec<u8>>(), vec![3u8; 100]);
+    }
+
+    #[test]
+    fn shared_nodes_are_stored_once() {
+        let nodes: Vec<[u8; 32]> = (0..16u8).map(|i| [i; 32]).collect();
+        let compressed = CompressedWitness::default();
+        let array = ArrayWitness::default();
+        // Proofs sharing all of their upper nodes, as the proofs of a slot do.
+        for leaf in 0..50u8 {
+            let mut proof = vec![[leaf.wrapping_mul(31); 32]];
+            proof.extend_from_slice(&nodes);
+            compressed.add_hint(proof.clone());
+            array.add_hint(proof);
+        }
+
+        let compressed_size = bincode::serialize(&compressed).unwrap().len();
+        let array_size = bincode::serialize(&array).unwrap().len();
+        assert!(compressed_size * 5 < array_size);
+
+        let compressed = roundtrip(&compressed);
+        for leaf in 0..50u8 {
+            let proof = compressed.get_hint::<Vec<[u8; 32]>>();
+            assert_eq!(proof[0], [leaf.wrapping_mul(31); 32]);
+            assert_eq!(&proof[1..], &nodes[..]);
+        }
+    }
+
+    #[test]
+    fn malformed_encoding_is_rejected() {
+        let mut data = Vec::new();
+        write_varint(&mut data, 40);
+        write_varint(&mut data, (40 << 1) | 1);
+        write_varint(&mut data, 1);
+        assert!(decode(&data, 1).is_err());
+    }
+
+    #[test]
+    fn oversized_copy_is_rejected() {
+        // A few bytes copying the first byte of the hint over and over.
+        let mut data = Vec::new();
+        write_varint(&mut data, u64::MAX >> 1);
+        write_literal(&mut data, &[7]);
+        write_varint(&mut data, ((u64::MAX >> 2) << 1) | 1);
+        write_varint(&mut data, 1);
+        assert!(decode(&data, 1).is_err());
+
+        let mut data = Vec::new();
+        write_varint(&mut data, MAX_DECODED_LEN as u64);
+        write_literal(&mut data, &[7]);
+        write_varint(&mut data, u64::MAX | 1);
+        write_varint(&mut data, 1);
+        assert!(decode(&data, 1).is_err());
+    }
+
+    #[test]
+    fn merge_keeps_unread_hints() {
+        let lhs = CompressedWitness::default();
+        lhs.add_hint(1u64);
+        let rhs = CompressedWitness::default();
+        rhs.add_hint(2u64);
+        rhs.add_hint(3u64);
+        assert_eq!(rhs.get_hint::<u64>(), 2);
+
+        lhs.merge(&rhs);
+        let lhs = roundtrip(&lhs);
+        assert_eq!(lhs.get_hint::<u64>(), 1);
+        assert_eq!(lhs.get_hint::<u64>(), 3);
+    }
+}
@@ -4,6 +4,11 @@
 
 pub mod codec;
 
+mod compressed_witness;
+
+#[cfg(feature = "multiproof")]
+mod multiproof;
+
 #[cfg(feature = "native")]
 mod prover_storage;
 
@@ -22,6 +27,9 @@ pub use sov_modules_core::{
 };
 use sov_rollup_interface::digest::Digest;
 
+pub use crate::compressed_witness::CompressedWitness;
+#[cfg(feature = "multiproof")]
+pub use crate::multiproof::MultiProof;
 pub use crate::witness::ArrayWitness;
 
 /// A trait specifying the hash function and format of the witness used in
@@ -46,3 +54,16 @@ impl MerkleProofSpec for DefaultStorageSpec {
 
     type Hasher = Sha256;
 }
+
+/// A [`MerkleProofSpec`] using the [`CompressedWitness`], which deduplicates the JMT nodes
+/// shared by the proofs of a slot to reduce the size of the witness.
+///
+/// This is the storage spec of the `CompressedContext` and `ZkCompressedContext` of `sov-modules-api`.
+#[derive(Clone)]
+pub struct CompressedStorageSpec;
+
+impl MerkleProofSpec for CompressedStorageSpec {
+    type Witness = CompressedWitness;
+
+    type Hasher = Sha256;
+}
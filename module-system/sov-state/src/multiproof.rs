@@ -0,0 +1,400 @@
+#[cfg(feature = "native")]
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+#[cfg(feature = "native")]
+use std::sync::Mutex;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::storage::{LeafNode, Nibble, NibblePath, Node, NodeKey, TreeReader};
+use jmt::{JellyfishMerkleTree, KeyHash, OwnedValue, RootHash, SimpleHasher, ValueHash, Version};
+
+/// The number of nibbles in a [`KeyHash`], which bounds the depth of the JMT.
+const MAX_DEPTH: usize = 64;
+
+/// A single proof for all the keys read and written in the JMT during a slot.
+///
+/// It holds the JMT nodes on the paths from the root to the leaves of the keys, and every node
+/// is stored once, even when it is shared by the paths of several keys. Reads are verified by
+/// hashing each node once and walking the path of every key from the root, instead of verifying
+/// one [`SparseMerkleProof`](jmt::proof::SparseMerkleProof) per read. Writes are applied to the
+/// nodes of the proof reachable from the root, which computes the new root without an
+/// [`UpdateMerkleProof`](jmt::proof::UpdateMerkleProof).
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize)]
+pub struct MultiProof {
+    /// The version of the tree the nodes were read from.
+    version: Version,
+    nodes: Vec<Node>,
+}
+
+impl MultiProof {
+    /// The number of distinct JMT nodes in the proof.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns true if the proof contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Verifies that each of the `reads` has the given value in the tree with the given `root`,
+    /// where `None` means that the key is absent from the tree.
+    pub fn verify<'a, H: SimpleHasher>(
+        &self,
+        root: RootHash,
+        reads: impl IntoIterator<Item = (KeyHash, Option<&'a [u8]>)>,
+    ) -> anyhow::Result<()> {
+        let nodes: HashMap<[u8; 32], &Node> = self
+            .nodes
+            .iter()
+            .map(|node| (node.hash::<H>(), node))
+            .collect();
+
+        for (key_hash, value) in reads {
+            let found = Self::find_leaf(&nodes, root, key_hash)?;
+            match (found, value) {
+                (Some(value_hash), Some(value)) => anyhow::ensure!(
+                    value_hash == ValueHash::with::<H>(value),
+                    "The multiproof contains a different value for key {:?}",
+                    key_hash
+                ),
+                (None, None) => {}
+                (Some(_), None) => {
+                    anyhow::bail!("The multiproof proves that key {:?} exists", key_hash)
+                }
+                (None, Some(_)) => {
+                    anyhow::bail!("The multiproof proves that key {:?} is absent", key_hash)
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies the `writes` to the tree with the given `root`, and returns the root of the
+    /// updated tree. A write with a `None` value deletes the key.
+    pub fn apply_writes<H: SimpleHasher>(
+        &self,
+        root: RootHash,
+        writes: impl IntoIterator<Item = (KeyHash, Option<OwnedValue>)>,
+    ) -> anyhow::Result<RootHash> {
+        let reader = ProvenTreeReader::new::<H>(&self.nodes, root, self.version);
+        let (new_root, _) =
+            JellyfishMerkleTree::<_, H>::new(&reader).put_value_set(writes, self.version + 1)?;
+        Ok(new_root)
+    }
+
+    /// Walks the path of `key_hash` from the root, returning the hash of its value
+    /// or `None` if the path ends before reaching its leaf.
+    fn find_leaf(
+        nodes: &HashMap<[u8; 32], &Node>,
+        root: RootHash,
+        key_hash: KeyHash,
+    ) -> anyhow::Result<Option<ValueHash>> {
+        let mut node_hash = root.0;
+        for depth in 0..=MAX_DEPTH {
+            let node = nodes.get(&node_hash).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The multiproof is missing a node on the path of key {:?}",
+                    key_hash
+                )
+            })?;
+            match node {
+                Node::Null => return Ok(None),
+                Node::Leaf(leaf) => {
+                    return Ok((leaf.key_hash() == key_hash).then(|| leaf.value_hash()))
+                }
+                Node::Internal(internal) => {
+                    anyhow::ensure!(depth < MAX_DEPTH, "The multiproof path is too deep");
+                    match internal.child(nibble_at(&key_hash, depth)) {
+                        Some(child) => node_hash = child.hash,
+                        None => return Ok(None),
+                    }
+                }
+            }
+        }
+        unreachable!("Every iteration at the maximum depth returns")
+    }
+}
+
+fn nibble_at(key_hash: &KeyHash, depth: usize) -> Nibble {
+    let byte = key_hash.0[depth / 2];
+    let nibble = if depth % 2 == 0 {
+        byte >> 4
+    } else {
+        byte & 0x0f
+    };
+    Nibble::from(nibble)
+}
+
+/// A [`TreeReader`] serving the nodes of a [`MultiProof`] which are reachable from a root.
+/// Every node it serves is authenticated by the hash stored in its parent.
+struct ProvenTreeReader {
+    nodes: HashMap<NodeKey, Node>,
+}
+
+impl ProvenTreeReader {
+    fn new<H: SimpleHasher>(nodes: &[Node], root: RootHash, version: Version) -> Self {
+        let nodes_by_hash: HashMap<[u8; 32], &Node> =
+            nodes.iter().map(|node| (node.hash::<H>(), node)).collect();
+
+        let mut reachable = HashMap::new();
+        let mut pending = vec![(NodeKey::new(version, NibblePath::new_even(vec![])), root.0)];
+        while let Some((node_key, node_hash)) = pending.pop() {
+            // The nodes which aren't in the proof are off the paths of the written keys.
+            let node = match nodes_by_hash.get(&node_hash) {
+                Some(node) => *node,
+                None => continue,
+            };
+            if let Node::Internal(internal) = node {
+                for nibble in 0..16u8 {
+                    if let Some(child) = internal.child(Nibble::from(nibble)) {
+                        let mut nibble_path = node_key.nibble_path().clone();
+                        nibble_path.push(Nibble::from(nibble));
+                        pending.push((NodeKey::new(child.version, nibble_path), child.hash));
+                    }
+                }
+            }
+            reachable.insert(node_key, node.clone());
+        }
+        Self { nodes: reachable }
+    }
+}
+
+impl TreeReader for ProvenTreeReader {
+    fn get_node_option(&self, node_key: &NodeKey) -> anyhow::Result<Option<Node>> {
+        Ok(self.nodes.get(node_key).cloned())
+    }
+
+    fn get_value_option(
+        &self,
+        _max_version: Version,
+        _key_hash: KeyHash,
+    ) -> anyhow::Result<Option<OwnedValue>> {
+        anyhow::bail!("The multiproof doesn't hold values")
+    }
+
+    fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+        anyhow::bail!("The multiproof doesn't hold the rightmost leaf")
+    }
+}
+
+/// A [`TreeReader`] which records every node it reads, to build a [`MultiProof`]
+/// of the keys looked up or updated through it.
+#[cfg(feature = "native")]
+pub(crate) struct MultiProofRecorder<'a, R> {
+    reader: &'a R,
+    nodes: Mutex<Vec<Node>>,
+}
+
+#[cfg(feature = "native")]
+impl<'a, R: TreeReader> MultiProofRecorder<'a, R> {
+    pub(crate) fn new(reader: &'a R) -> Self {
+        Self {
+            reader,
+            nodes: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns a proof of all the keys read so far from the tree at `version`, with the nodes
+    /// ordered by hash.
+    pub(crate) fn into_multiproof<H: SimpleHasher>(self, version: Version) -> MultiProof {
+        let nodes: BTreeMap<[u8; 32], Node> = self
+            .nodes
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .map(|node| (node.hash::<H>(), node))
+            .collect();
+        MultiProof {
+            version,
+            nodes: nodes.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "native")]
+impl<'a, R: TreeReader> TreeReader for MultiProofRecorder<'a, R> {
+    fn get_node_option(&self, node_key: &NodeKey) -> anyhow::Result<Option<Node>> {
+        let node = self.reader.get_node_option(node_key)?;
+        if let Some(node) = &node {
+            self.nodes.lock().unwrap().push(node.clone());
+        }
+        Ok(node)
+    }
+
+    fn get_value_option(
+        &self,
+        version: jmt::Version,
+        key_hash: KeyHash,
+    ) -> anyhow::Result<Option<jmt::OwnedValue>> {
+        self.reader.get_value_option(version, key_hash)
+    }
+
+    fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+        self.reader.get_rightmost_leaf()
+    }
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use jmt::storage::{NodeBatch, TreeWriter};
+    use sha2::Sha256;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MemoryTree {
+        batch: Mutex<NodeBatch>,
+    }
+
+    impl TreeReader for MemoryTree {
+        fn get_node_option(&self, node_key: &NodeKey) -> anyhow::Result<Option<Node>> {
+            Ok(self.batch.lock().unwrap().nodes().get(node_key).cloned())
+        }
+
+        fn get_value_option(
+            &self,
+            max_version: Version,
+            key_hash: KeyHash,
+        ) -> anyhow::Result<Option<OwnedValue>> {
+            Ok(self
+                .batch
+                .lock()
+                .unwrap()
+                .values()
+                .iter()
+                .filter(|((version, hash), _)| *hash == key_hash && *version <= max_version)
+                .max_by_key(|((version, _), _)| *version)
+                .and_then(|(_, value)| value.clone()))
+        }
+
+        // The leaf with the largest key hash among the nodes of the latest version.
+        fn get_rightmost_leaf(&self) -> anyhow::Result<Option<(NodeKey, LeafNode)>> {
+            Ok(self
+                .batch
+                .lock()
+                .unwrap()
+                .nodes()
+                .iter()
+                .filter_map(|(node_key, node)| match node {
+                    Node::Leaf(leaf) => Some((node_key.clone(), leaf.clone())),
+                    _ => None,
+                })
+                .max_by_key(|(node_key, leaf)| (node_key.version(), leaf.key_hash().0)))
+        }
+    }
+
+    impl TreeWriter for MemoryTree {
+        fn write_node_batch(&self, node_batch: &NodeBatch) -> anyhow::Result<()> {
+            self.batch
+                .lock()
+                .unwrap()
+                .extend(node_batch.nodes().clone(), node_batch.values().clone());
+            Ok(())
+        }
+    }
+
+    fn key_hash(i: u8) -> KeyHash {
+        KeyHash::with::<Sha256>([i])
+    }
+
+    fn prove_reads(tree: &MemoryTree, keys: &[u8]) -> MultiProof {
+        let recorder = MultiProofRecorder::new(tree);
+        let jmt = JellyfishMerkleTree::<_, Sha256>::new(&recorder);
+        for &key in keys {
+            jmt.get(key_hash(key), 0).unwrap();
+        }
+        recorder.into_multiproof::<Sha256>(0)
+    }
+
+    fn setup_tree() -> (MemoryTree, RootHash) {
+        let tree = MemoryTree::default();
+        let (root, update) = JellyfishMerkleTree::<_, Sha256>::new(&tree)
+            .put_value_set((0..50u8).map(|i| (key_hash(i), Some(vec![i; 8]))), 0)
+            .unwrap();
+        tree.write_node_batch(&update.node_batch).unwrap();
+        (tree, root)
+    }
+
+    #[test]
+    fn reads_are_verified_against_the_root() {
+        let (tree, root) = setup_tree();
+        let proof = prove_reads(&tree, &[1, 2, 3, 2, 100]);
+
+        let values: Vec<Vec<u8>> = (1..=3u8).map(|i| vec![i; 8]).collect();
+        let reads = vec![
+            (key_hash(1), Some(values[0].as_slice())),
+            (key_hash(2), Some(values[1].as_slice())),
+            (key_hash(3), Some(values[2].as_slice())),
+            (key_hash(100), None),
+        ];
+        proof.verify::<Sha256>(root, reads.clone()).unwrap();
+
+        // The upper nodes shared by the paths are stored once.
+        let single_proofs: usize = [1, 2, 3, 100]
+            .iter()
+            .map(|&key| prove_reads(&tree, &[key]).len())
+            .sum();
+        assert!(proof.len() < single_proofs);
+
+        assert!(proof.verify::<Sha256>(RootHash([0; 32]), reads).is_err());
+    }
+
+    #[test]
+    fn incorrect_reads_are_rejected() {
+        let (tree, root) = setup_tree();
+        let proof = prove_reads(&tree, &[1, 100]);
+        let value = vec![1u8; 8];
+
+        let wrong_value = vec![2u8; 8];
+        assert!(proof
+            .verify::<Sha256>(root, [(key_hash(1), Some(wrong_value.as_slice()))])
+            .is_err());
+        assert!(proof.verify::<Sha256>(root, [(key_hash(1), None)]).is_err());
+        assert!(proof
+            .verify::<Sha256>(root, [(key_hash(100), Some(value.as_slice()))])
+            .is_err());
+        // The proof doesn't cover the path of a key which wasn't read.
+        assert!(proof
+            .verify::<Sha256>(root, [(key_hash(4), Some(vec![4u8; 8].as_slice()))])
+            .is_err());
+    }
+
+    #[test]
+    fn writes_are_applied_to_the_root() {
+        let (tree, root) = setup_tree();
+        // Updates, deletes and inserts.
+        let writes = vec![
+            (key_hash(1), Some(vec![7u8; 8])),
+            (key_hash(2), None),
+            (key_hash(100), Some(vec![100u8; 8])),
+        ];
+
+        let recorder = MultiProofRecorder::new(&tree);
+        let (new_root, _) = JellyfishMerkleTree::<_, Sha256>::new(&recorder)
+            .put_value_set(writes.clone(), 1)
+            .unwrap();
+        let proof = recorder.into_multiproof::<Sha256>(0);
+
+        assert_eq!(
+            new_root,
+            proof.apply_writes::<Sha256>(root, writes.clone()).unwrap()
+        );
+        // The proof doesn't start from another root.
+        assert!(proof
+            .apply_writes::<Sha256>(RootHash([0; 32]), writes)
+            .is_err());
+        // The proof doesn't cover the path of a key which wasn't written.
+        assert!(proof
+            .apply_writes::<Sha256>(root, [(key_hash(4), Some(vec![7u8; 8]))])
+            .is_err());
+    }
+
+    #[test]
+    fn rightmost_leaf_is_the_largest_key() {
+        let (tree, _) = setup_tree();
+        let (_, leaf) = tree.get_rightmost_leaf().unwrap().unwrap();
+        let largest_key = (0..50u8).map(key_hash).max_by_key(|key| key.0).unwrap();
+        assert_eq!(largest_key, leaf.key_hash());
+    }
+}
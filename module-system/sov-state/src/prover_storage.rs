@@ -12,6 +12,8 @@ use sov_modules_core::{
 };
 
 use crate::config::Config;
+#[cfg(feature = "multiproof")]
+use crate::multiproof::MultiProofRecorder;
 use crate::MerkleProofSpec;
 
 /// A [`Storage`] implementation to be used by the prover in a native execution
@@ -126,9 +128,9 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
         witness.add_hint(prev_root.0);
 
         // For each value that's been read from the tree, read it from the logged JMT to populate hints
+        #[cfg(not(feature = "multiproof"))]
         for (key, read_value) in state_accesses.ordered_reads {
             let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            // TODO: Switch to the batch read API once it becomes available
             let (result, proof) = jmt.get_with_proof(key_hash, latest_version)?;
             if result.as_ref() != read_value.as_ref().map(|f| f.value.as_ref()) {
                 anyhow::bail!("Bug! Incorrect value read from jmt");
@@ -136,6 +138,22 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
             witness.add_hint(proof);
         }
 
+        // Read all the values through a recorder of the visited nodes, and prove them all at once
+        // with the nodes visited by the update below
+        #[cfg(feature = "multiproof")]
+        let recorder = MultiProofRecorder::new(&self.db);
+        #[cfg(feature = "multiproof")]
+        {
+            let recording_jmt = JellyfishMerkleTree::<_, S::Hasher>::new(&recorder);
+            for (key, read_value) in state_accesses.ordered_reads {
+                let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
+                let result = recording_jmt.get(key_hash, latest_version)?;
+                if result.as_ref() != read_value.as_ref().map(|f| f.value.as_ref()) {
+                    anyhow::bail!("Bug! Incorrect value read from jmt");
+                }
+            }
+        }
+
         let mut key_preimages = Vec::with_capacity(state_accesses.ordered_writes.len());
 
         // Compute the jmt update from the write batch
@@ -153,12 +171,26 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
 
         let next_version = self.db.get_next_version();
 
-        let (new_root, update_proof, tree_update) = jmt
-            .put_value_set_with_proof(batch, next_version)
-            .expect("JMT update must succeed");
+        #[cfg(not(feature = "multiproof"))]
+        let (new_root, tree_update) = {
+            let (new_root, update_proof, tree_update) = jmt
+                .put_value_set_with_proof(batch, next_version)
+                .expect("JMT update must succeed");
 
-        witness.add_hint(update_proof);
-        witness.add_hint(new_root.0);
+            witness.add_hint(update_proof);
+            witness.add_hint(new_root.0);
+            (new_root, tree_update)
+        };
+
+        #[cfg(feature = "multiproof")]
+        let (new_root, tree_update) = {
+            let (new_root, tree_update) = JellyfishMerkleTree::<_, S::Hasher>::new(&recorder)
+                .put_value_set(batch, next_version)
+                .expect("JMT update must succeed");
+
+            witness.add_hint(recorder.into_multiproof::<S::Hasher>(latest_version));
+            (new_root, tree_update)
+        };
 
         let state_update = ProverStateUpdate {
             node_batch: tree_update.node_batch,
@@ -211,6 +243,37 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
         Ok((key, value))
     }
 
+    fn prove_keys(
+        &self,
+        keys: &[StorageKey],
+        version: Option<Version>,
+    ) -> Result<(Version, Self::Root, Vec<StorageProof<Self::Proof>>), anyhow::Error> {
+        let version = match version {
+            Some(version) => version,
+            None => self
+                .db
+                .get_latest_version()?
+                .ok_or_else(|| anyhow::anyhow!("No state has been committed yet"))?,
+        };
+        let merkle = JellyfishMerkleTree::<StateDB<Q>, S::Hasher>::new(&self.db);
+        let state_root = merkle.get_root_hash(version)?;
+
+        let proofs = keys
+            .iter()
+            .map(|key| {
+                let (value, proof) =
+                    merkle.get_with_proof(KeyHash::with::<S::Hasher>(key.as_ref()), version)?;
+                Ok(StorageProof {
+                    key: key.clone(),
+                    value: value.map(StorageValue::from),
+                    proof,
+                })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?;
+
+        Ok((version, state_root, proofs))
+    }
+
     // Based on assumption `validate_and_commit` increments version.
     fn is_empty(&self) -> bool {
         self.db.get_next_version() <= 1
@@ -61,9 +61,9 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
         let prev_state_root = witness.get_hint();
 
         // For each value that's been read from the tree, verify the provided smt proof
+        #[cfg(not(feature = "multiproof"))]
         for (key, read_value) in state_accesses.ordered_reads {
             let key_hash = KeyHash::with::<S::Hasher>(key.key.as_ref());
-            // TODO: Switch to the batch read API once it becomes available
             let proof: jmt::proof::SparseMerkleProof<S::Hasher> = witness.get_hint();
             match read_value {
                 Some(val) => proof.verify_existence(
@@ -75,6 +75,26 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
             }
         }
 
+        // Verify all the values read from the tree against a single proof, which also holds the
+        // nodes updated by the writes
+        #[cfg(feature = "multiproof")]
+        let multiproof: crate::MultiProof = witness.get_hint();
+        #[cfg(feature = "multiproof")]
+        {
+            multiproof.verify::<S::Hasher>(
+                jmt::RootHash(prev_state_root),
+                state_accesses
+                    .ordered_reads
+                    .iter()
+                    .map(|(key, read_value)| {
+                        (
+                            KeyHash::with::<S::Hasher>(key.key.as_ref()),
+                            read_value.as_ref().map(|v| v.value.as_slice()),
+                        )
+                    }),
+            )?;
+        }
+
         // Compute the jmt update from the write batch
         let batch = state_accesses
             .ordered_writes
@@ -88,8 +108,17 @@ impl<S: MerkleProofSpec> Storage for ZkStorage<S> {
             })
             .collect::<Vec<_>>();
 
+        #[cfg(feature = "multiproof")]
+        let new_root = multiproof
+            .apply_writes::<S::Hasher>(jmt::RootHash(prev_state_root), batch)
+            .expect("Updates must be valid")
+            .0;
+
+        #[cfg(not(feature = "multiproof"))]
         let update_proof: jmt::proof::UpdateMerkleProof<S::Hasher> = witness.get_hint();
+        #[cfg(not(feature = "multiproof"))]
         let new_root: [u8; 32] = witness.get_hint();
+        #[cfg(not(feature = "multiproof"))]
         update_proof
             .verify_update(
                 jmt::RootHash(prev_state_root),
@@ -0,0 +1,169 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+mod solidity;
+
+use anyhow::ensure;
+use borsh::BorshDeserialize;
+use jmt::proof::SparseMerkleProof;
+use jmt::{KeyHash, RootHash};
+use reth_primitives::{Address, Bytes, H256, KECCAK_EMPTY, U256};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+pub use solidity::{SolidityJmtProof, PLACEHOLDER_HASH};
+
+/// The prefix of the `accounts` map of the EVM module, under which the accounts are stored
+/// by address. The storage slots of an account are stored under this prefix followed by
+/// the 20 bytes of its address.
+pub const EVM_ACCOUNTS_PREFIX: &[u8] = b"sov_evm::experimental/Evm/accounts/";
+
+/// A proof that a key of the rollup state has a value, or is absent, against a state root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JmtProof {
+    /// The key in the rollup state.
+    pub key: Bytes,
+    /// The value stored under the key, or `None` if the key is absent.
+    pub value: Option<Bytes>,
+    /// The borsh encoded JMT [`SparseMerkleProof`], with SHA-256 as the hasher.
+    pub proof: Bytes,
+}
+
+impl JmtProof {
+    /// Checks the proof against the state root with the JMT implementation.
+    pub fn verify(&self, state_root: H256) -> anyhow::Result<()> {
+        let proof = self.decode_proof()?;
+        proof.verify(
+            RootHash(state_root.0),
+            KeyHash::with::<Sha256>(&self.key),
+            self.value.as_ref(),
+        )
+    }
+
+    /// Converts the proof to the flat format checked by [`SolidityJmtProof::verify`].
+    pub fn to_solidity(&self) -> anyhow::Result<SolidityJmtProof> {
+        let proof = self.decode_proof()?;
+        Ok(SolidityJmtProof::new(
+            &self.key,
+            self.value.as_ref().map(|value| &value[..]),
+            &proof,
+        ))
+    }
+
+    fn decode_proof(&self) -> anyhow::Result<SparseMerkleProof<Sha256>> {
+        SparseMerkleProof::try_from_slice(&self.proof)
+            .map_err(|e| anyhow::anyhow!("Invalid proof encoding: {}", e))
+    }
+}
+
+/// The proof of a storage slot of an [`EvmAccountProof`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvmStorageProof {
+    /// The storage slot.
+    pub key: U256,
+    /// The value of the slot, zero if it isn't set.
+    pub value: U256,
+    /// The proof of the slot in the rollup state.
+    pub proof: JmtProof,
+}
+
+/// The response of `eth_getProof`: an EVM account and some of its storage slots,
+/// with the proofs of their records in the rollup state.
+///
+/// Unlike EIP-1186, the EVM state isn't kept in Merkle Patricia tries: the account and each
+/// slot are proven separately against the rollup state root recorded for the block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmAccountProof {
+    /// The address of the account.
+    pub address: Address,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The hash of the code of the account, [`KECCAK_EMPTY`] if the account doesn't exist.
+    pub code_hash: H256,
+    /// The number of the block whose state is proven.
+    pub block_number: u64,
+    /// The rollup state root recorded for the block, against which the proofs are checked.
+    pub state_root: H256,
+    /// The proof of the account record.
+    pub account_proof: JmtProof,
+    /// The proofs of the requested storage slots.
+    pub storage_proof: Vec<EvmStorageProof>,
+}
+
+impl EvmAccountProof {
+    /// Checks the account and the storage slots against a trusted rollup state root, such as
+    /// one proven to a light client. The keys of the proofs are checked to belong to the
+    /// account, and the returned values to be the proven ones.
+    pub fn verify(&self, trusted_state_root: H256) -> anyhow::Result<()> {
+        ensure!(
+            self.state_root == trusted_state_root,
+            "The proofs were generated against a different state root"
+        );
+
+        ensure!(
+            self.account_proof.key[..] == account_key(self.address)[..],
+            "The account proof is not for account {}",
+            self.address
+        );
+        self.account_proof.verify(trusted_state_root)?;
+        let (balance, nonce, code_hash) = match &self.account_proof.value {
+            Some(value) => {
+                let record: AccountRecord = bcs::from_bytes(value)?;
+                (record.balance, record.nonce, record.code_hash)
+            }
+            None => (U256::ZERO, 0, KECCAK_EMPTY),
+        };
+        ensure!(
+            (balance, nonce, code_hash) == (self.balance, self.nonce, self.code_hash),
+            "The returned account differs from the proven one"
+        );
+
+        for storage in &self.storage_proof {
+            ensure!(
+                storage.proof.key[..] == storage_key(self.address, storage.key)[..],
+                "The proof of slot {} is not for account {}",
+                storage.key,
+                self.address
+            );
+            storage.proof.verify(trusted_state_root)?;
+            let value = match &storage.proof.value {
+                Some(value) => bcs::from_bytes(value)?,
+                None => U256::ZERO,
+            };
+            ensure!(
+                value == storage.value,
+                "The returned value of slot {} differs from the proven one",
+                storage.key
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The key of the account record with the given address in the rollup state.
+pub fn account_key(address: Address) -> Vec<u8> {
+    let mut key = EVM_ACCOUNTS_PREFIX.to_vec();
+    key.extend(bcs::to_bytes(&address).expect("Failed to serialize address"));
+    key
+}
+
+/// The key of a storage slot of the account with the given address in the rollup state.
+pub fn storage_key(address: Address, slot: U256) -> Vec<u8> {
+    let mut key = EVM_ACCOUNTS_PREFIX.to_vec();
+    key.extend_from_slice(&address.0);
+    key.extend(bcs::to_bytes(&slot).expect("Failed to serialize slot"));
+    key
+}
+
+/// The bcs layout of the account records of the EVM module: the account info, followed by the
+/// prefixes of the state containers holding its storage, which are derived from its address.
+#[derive(Deserialize)]
+struct AccountRecord {
+    balance: U256,
+    code_hash: H256,
+    nonce: u64,
+    _storage_prefixes: [Vec<u8>; 4],
+}
@@ -0,0 +1,122 @@
+//! A flat form of the JMT proofs, which only needs the `sha256` precompile to be checked
+//! in Solidity. See the README of the crate for the Solidity version of [`SolidityJmtProof::verify`].
+
+use anyhow::ensure;
+use jmt::proof::SparseMerkleProof;
+use reth_primitives::H256;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const LEAF_DOMAIN_SEPARATOR: &[u8] = b"JMT::LeafNode";
+const INTERNAL_DOMAIN_SEPARATOR: &[u8] = b"JMT::IntrnalNode";
+
+/// The hash of an empty subtree of the JMT.
+pub const PLACEHOLDER_HASH: [u8; 32] = *b"SPARSE_MERKLE_PLACEHOLDER_HASH__";
+
+/// A JMT proof made of 32-byte words, which maps to a Solidity struct.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolidityJmtProof {
+    /// `sha256(key)`.
+    pub key_hash: H256,
+    /// Whether the key is proven to exist.
+    pub exists: bool,
+    /// `sha256(value)` if the key exists, zero otherwise.
+    pub value_hash: H256,
+    /// Whether the path of the key ends at a leaf, rather than at an empty subtree.
+    pub has_leaf: bool,
+    /// The key hash of the leaf, which is another key's when the key is absent.
+    pub leaf_key_hash: H256,
+    /// The value hash of the leaf.
+    pub leaf_value_hash: H256,
+    /// The hashes of the siblings of the path, from the leaf up to the root.
+    pub siblings: Vec<H256>,
+}
+
+impl SolidityJmtProof {
+    pub(crate) fn new(key: &[u8], value: Option<&[u8]>, proof: &SparseMerkleProof<Sha256>) -> Self {
+        let leaf = proof.leaf();
+        Self {
+            key_hash: sha256(&[key]),
+            exists: value.is_some(),
+            value_hash: value.map(|value| sha256(&[value])).unwrap_or_default(),
+            has_leaf: leaf.is_some(),
+            leaf_key_hash: leaf.map(|leaf| H256(leaf.key_hash().0)).unwrap_or_default(),
+            leaf_value_hash: leaf
+                .map(|leaf| H256(leaf.value_hash().0))
+                .unwrap_or_default(),
+            siblings: proof
+                .siblings()
+                .iter()
+                .map(|sibling| H256(sibling.hash::<Sha256>()))
+                .collect(),
+        }
+    }
+
+    /// Checks the proof against the state root, the same way as the Solidity version does.
+    pub fn verify(&self, state_root: H256) -> anyhow::Result<()> {
+        let depth = self.siblings.len();
+        ensure!(depth <= 256, "The proof has more than 256 siblings");
+
+        let mut hash = if self.has_leaf {
+            sha256(&[
+                LEAF_DOMAIN_SEPARATOR,
+                self.leaf_key_hash.as_bytes(),
+                self.leaf_value_hash.as_bytes(),
+            ])
+        } else {
+            H256(PLACEHOLDER_HASH)
+        };
+        for (i, sibling) in self.siblings.iter().enumerate() {
+            // The bits of the key hash select the path from the root, so the sibling `i` levels
+            // above the leaf is selected by the bit at `depth - 1 - i`.
+            hash = if bit(&self.key_hash, depth - 1 - i) {
+                sha256(&[
+                    INTERNAL_DOMAIN_SEPARATOR,
+                    sibling.as_bytes(),
+                    hash.as_bytes(),
+                ])
+            } else {
+                sha256(&[
+                    INTERNAL_DOMAIN_SEPARATOR,
+                    hash.as_bytes(),
+                    sibling.as_bytes(),
+                ])
+            };
+        }
+        ensure!(hash == state_root, "The proof doesn't match the state root");
+
+        if self.exists {
+            ensure!(
+                self.has_leaf
+                    && self.leaf_key_hash == self.key_hash
+                    && self.leaf_value_hash == self.value_hash,
+                "The proof doesn't prove the value of the key"
+            );
+        } else {
+            // The path must end at an empty subtree, or at the leaf of another key sharing it.
+            ensure!(
+                !self.has_leaf
+                    || (self.leaf_key_hash != self.key_hash
+                        && (0..depth)
+                            .all(|i| bit(&self.leaf_key_hash, i) == bit(&self.key_hash, i))),
+                "The proof doesn't prove that the key is absent"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn sha256(chunks: &[&[u8]]) -> H256 {
+    let mut hasher = Sha256::new();
+    for chunk in chunks {
+        hasher.update(chunk);
+    }
+    H256(hasher.finalize().into())
+}
+
+/// The bit of the hash at the given index, counting from the most significant bit.
+fn bit(hash: &H256, index: usize) -> bool {
+    (hash.0[index / 8] >> (7 - index % 8)) & 1 == 1
+}
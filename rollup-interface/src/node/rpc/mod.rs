@@ -5,9 +5,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::maybestd::vec::Vec;
-#[cfg(feature = "native")]
-use crate::stf::Event;
-use crate::stf::EventKey;
+use crate::stf::{Event, EventKey};
 
 /// A struct containing enough information to uniquely specify single batch.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -111,6 +109,17 @@ pub enum SlotIdentifier {
     Number(u64),
 }
 
+/// An identifier that specifies a historical state of the rollup.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StateIdentifier {
+    /// The state after the slot with this number was processed.
+    /// Height 0 identifies the genesis state.
+    SlotHeight(u64),
+    /// The hex encoded state root reached at the end of a slot.
+    StateRoot(#[serde(with = "utils::rpc_hex")] Vec<u8>),
+}
+
 /// A QueryMode specifies how much information to return in response to an RPC query
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum QueryMode {
@@ -190,6 +199,117 @@ pub enum ItemOrHash<T> {
     Full(T),
 }
 
+/// A range of ledger items, used to restrict the events returned by a query
+/// to the ones emitted within the range. Both ends of the range are inclusive.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventRange {
+    /// Events emitted by the transactions of the slots with numbers `start..=end`.
+    Slots {
+        /// The number of the first slot in the range.
+        start: u64,
+        /// The number of the last slot in the range.
+        end: u64,
+    },
+    /// Events emitted by the transactions of the batches with numbers `start..=end`.
+    Batches {
+        /// The number of the first batch in the range.
+        start: u64,
+        /// The number of the last batch in the range.
+        end: u64,
+    },
+    /// Events emitted by the transactions with numbers `start..=end`.
+    Transactions {
+        /// The number of the first transaction in the range.
+        start: u64,
+        /// The number of the last transaction in the range.
+        end: u64,
+    },
+}
+
+/// A filter selecting the events returned by an event query or subscription.
+/// Events must satisfy all the criteria which are set.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// Only return events whose key starts with these bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<Vec<u8>>,
+    /// Only return events emitted by the module with this address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub module: Option<Vec<u8>>,
+    /// Only return events emitted within this range. Ignored by subscriptions,
+    /// which only ever see newly committed events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<EventRange>,
+}
+
+impl EventFilter {
+    /// Returns `true` if the event has the key prefix and emitting module required
+    /// by this filter. The range of the filter is not checked.
+    pub fn matches(&self, event: &Event) -> bool {
+        let key_matches = self
+            .key_prefix
+            .as_ref()
+            .map_or(true, |prefix| event.key().inner().starts_with(prefix));
+        let module_matches = self
+            .module
+            .as_ref()
+            .map_or(true, |module| event.module() == Some(module.as_slice()));
+        key_matches && module_matches
+    }
+}
+
+/// An event together with its number.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EventResponse {
+    /// The event number.
+    pub number: u64,
+    /// The event itself.
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// A page of events matching an [`EventFilter`], ordered by event number.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FilteredEventsResponse {
+    /// The matching events.
+    pub events: Vec<EventResponse>,
+    /// The cursor to pass to the next query to continue where this page ended,
+    /// or `None` if there are no more matching events.
+    pub next_cursor: Option<u64>,
+}
+
+/// The response to a JSON-RPC request for the proof of a slot.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ProofResponse {
+    /// The number of the first slot covered by the proof.
+    pub start_slot: u64,
+    /// The number of the last slot covered by the proof.
+    pub end_slot: u64,
+    /// The height of the DA block in which the proof was posted.
+    pub da_height: u64,
+    /// The public output of the proof, if the prover produced one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_transition: Option<StateTransitionResponse>,
+}
+
+/// The state transition proven by a proof. See [`crate::zk::StateTransition`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct StateTransitionResponse {
+    /// The hex encoded state root before the transition.
+    #[serde(with = "utils::rpc_hex")]
+    pub initial_state_root: Vec<u8>,
+    /// The hex encoded state root after the transition.
+    #[serde(with = "utils::rpc_hex")]
+    pub final_state_root: Vec<u8>,
+    /// The hex encoded hash of the last DA slot of the transition.
+    #[serde(with = "utils::rpc_hex")]
+    pub slot_hash: [u8; 32],
+    /// The hex encoded, borsh serialized validity condition of the transition.
+    #[serde(with = "utils::rpc_hex")]
+    pub validity_condition: Vec<u8>,
+}
+
 /// A LedgerRpcProvider provides a way to query the ledger for information about slots, batches, transactions, and events.
 #[cfg(feature = "native")]
 pub trait LedgerRpcProvider {
@@ -301,10 +421,37 @@ pub trait LedgerRpcProvider {
         query_mode: QueryMode,
     ) -> Result<Vec<Option<TxResponse<T>>>, anyhow::Error>;
 
+    /// Get a page of the events matching `filter`, ordered by event number.
+    ///
+    /// The page starts at event number `cursor`, if any, and contains at most `limit` events,
+    /// which must be at least 1.
+    /// Pass the `next_cursor` of the response to the next query to fetch the following page.
+    fn get_filtered_events(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<FilteredEventsResponse, anyhow::Error>;
+
+    /// Get the proof posted to the DA which covers the slot with the given id, if any.
+    fn get_proof_by_slot(
+        &self,
+        slot_id: &SlotIdentifier,
+    ) -> Result<Option<ProofResponse>, anyhow::Error>;
+
     /// Get a notification each time a slot is processed
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 }
 
+/// A StateVersionProvider maps [`StateIdentifier`]s to versions of the rollup state,
+/// so that historical state can be queried.
+#[cfg(feature = "native")]
+pub trait StateVersionProvider: Send + Sync {
+    /// Get the version of the state identified by `state_id`,
+    /// or `None` if the ledger doesn't know about this state.
+    fn get_state_version(&self, state_id: &StateIdentifier) -> Result<Option<u64>, anyhow::Error>;
+}
+
 /// JSON-RPC -related utilities. Occasionally useful but unimportant for most
 /// use cases.
 pub mod utils {
@@ -170,6 +170,8 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
 pub struct Event {
     key: EventKey,
     value: EventValue,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    module: Option<Vec<u8>>,
 }
 
 impl Event {
@@ -178,9 +180,25 @@ impl Event {
         Self {
             key: EventKey(key.as_bytes().to_vec()),
             value: EventValue(value.as_bytes().to_vec()),
+            module: None,
         }
     }
 
+    /// Create a new event from an existing key and value, not attributed to any module
+    pub fn from_parts(key: EventKey, value: EventValue) -> Self {
+        Self {
+            key,
+            value,
+            module: None,
+        }
+    }
+
+    /// Attributes the event to the module with the given address.
+    pub fn with_module(mut self, module_address: &[u8]) -> Self {
+        self.module = Some(module_address.to_vec());
+        self
+    }
+
     /// Get the event key
     pub fn key(&self) -> &EventKey {
         &self.key
@@ -190,6 +208,11 @@ impl Event {
     pub fn value(&self) -> &EventValue {
         &self.value
     }
+
+    /// Get the address of the module which emitted the event, if known.
+    pub fn module(&self) -> Option<&[u8]> {
+        self.module.as_deref()
+    }
 }
 
 /// The key of an event. This is a wrapper around a `Vec<u8>`.
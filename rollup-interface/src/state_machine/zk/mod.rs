@@ -6,8 +6,11 @@
 //! For a detailed example showing how to implement these traits, see the
 //! [risc0 adapter](https://github.com/Sovereign-Labs/sovereign-sdk/tree/main/adapters/risc0)
 //! maintained by the Sovereign Labs team.
+use alloc::collections::BTreeMap;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt::Debug;
+use core::time::Duration;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use digest::Digest;
@@ -17,7 +20,7 @@ use serde::{Deserialize, Serialize};
 use crate::da::DaSpec;
 
 /// The ZK proof generated by the [`ZkvmHost::run`] method.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub enum Proof {
     /// Only public input was generated.
     PublicInput(Vec<u8>),
@@ -25,6 +28,38 @@ pub enum Proof {
     Full(Vec<u8>),
 }
 
+/// A proof generated by the [`ZkvmHost::run`] method, along with the cost of generating it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofWithStats {
+    /// The generated proof.
+    pub proof: Proof,
+    /// Statistics about the execution of the guest program.
+    pub stats: ExecutionStats,
+}
+
+/// Statistics about an execution of a guest program by a zkVM.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    /// The number of cycles the execution took, as accounted for by the prover.
+    pub cycles: u64,
+    /// The number of segments the execution was split into.
+    pub segments: u64,
+    /// The time spent executing the guest program, and proving it if a proof was requested.
+    pub wall_time: Duration,
+    /// The cycles spent in the functions annotated with the `cycle_tracker` macro, by function
+    /// name. Only zkVMs which support cycle tracking fill it in.
+    pub cycles_by_function: BTreeMap<String, FunctionCycles>,
+}
+
+/// The cycles spent in a function during an execution of a guest program.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionCycles {
+    /// The number of times the function was called.
+    pub calls: u64,
+    /// The total number of cycles spent in the function.
+    pub cycles: u64,
+}
+
 /// A trait implemented by the prover ("host") of a zkVM program.
 pub trait ZkvmHost: Zkvm + Clone {
     /// The associated guest type
@@ -43,12 +78,18 @@ pub trait ZkvmHost: Zkvm + Clone {
     /// This runs the guest binary compiled for the zkVM target, optionally
     /// creating a SNARK of correct execution. Running the true guest binary comes
     /// with some mild performance overhead and is not as easy to debug as [`simulate_with_hints`](ZkvmHost::simulate_with_hints).
-    fn run(&mut self, with_proof: bool) -> Result<Proof, anyhow::Error>;
+    /// Returns the proof along with statistics about the execution.
+    fn run(&mut self, with_proof: bool) -> Result<ProofWithStats, anyhow::Error>;
 
     /// Extracts public input form the proof.
     fn extract_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
         proof: &Proof,
     ) -> Result<StateTransition<Da, Root>, Self::Error>;
+
+    /// Extracts public input from the proof of an aggregation program.
+    fn extract_aggregated_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
+        proof: &Proof,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error>;
 }
 
 /// A Zk proof system capable of proving and verifying arbitrary Rust code
@@ -59,7 +100,9 @@ pub trait Zkvm: Send + Sync {
         + Clone
         + Debug
         + Serialize
-        + DeserializeOwned;
+        + DeserializeOwned
+        + Send
+        + Sync;
 
     /// The error type which is returned when a proof fails to verify
     type Error: Debug;
@@ -78,6 +121,13 @@ pub trait Zkvm: Send + Sync {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<StateTransition<Da, Root>, Self::Error>;
+
+    /// Same as [`verify_and_extract_output`](Zkvm::verify_and_extract_output), for the proofs
+    /// of an aggregation program.
+    fn verify_and_extract_aggregated_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Self::CodeCommitment, Da, Root>, Self::Error>;
 }
 
 /// A trait which is accessible from within a zkVM program.
@@ -129,6 +179,21 @@ pub struct StateTransition<Da: DaSpec, Root> {
     pub validity_condition: Da::ValidityCondition,
 }
 
+/// The public output of an aggregated proof: the state transition made by consecutive slots,
+/// and the commitment to the program which proved each of them. The aggregation program
+/// takes this commitment from the host, so a verifier must check that it is the one of the
+/// rollup before trusting the state transition.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+// Prevent serde from generating spurious trait bounds, as for `StateTransitionData`.
+#[serde(bound = "CodeCommitment: Serialize + DeserializeOwned, Root: Serialize + DeserializeOwned")]
+pub struct AggregatedStateTransition<CodeCommitment, Da: DaSpec, Root> {
+    /// The state transition from the initial state of the first slot to the final state
+    /// of the last one
+    pub state_transition: StateTransition<Da, Root>,
+    /// The commitment to the program which proved each slot
+    pub slot_program: CodeCommitment,
+}
+
 /// This trait expresses that a type can check a validity condition.
 pub trait ValidityConditionChecker<Condition: ValidityCondition>:
     BorshDeserialize + BorshSerialize + Debug
@@ -167,3 +232,16 @@ pub struct StateTransitionData<StateRoot, Witness, Da: DaSpec> {
     /// The witness for the state transition
     pub state_transition_witness: Witness,
 }
+
+#[derive(Serialize, Deserialize)]
+// Prevent serde from generating spurious trait bounds, as for `StateTransitionData`.
+#[serde(bound = "CodeCommitment: Serialize + DeserializeOwned")]
+/// Data required to aggregate the proofs of consecutive slots into a single proof.
+pub struct AggregatedProofData<CodeCommitment, Da: DaSpec> {
+    /// The commitment to the program which proved each slot
+    pub slot_program: CodeCommitment,
+    /// The serialized proofs of the slots, in slot order
+    pub proofs: Vec<Vec<u8>>,
+    /// The headers of the da blocks processed by the slots, in slot order
+    pub da_block_headers: Vec<Da::BlockHeader>,
+}